@@ -15,6 +15,10 @@
 use core::intrinsics::{min_align_of_val, size_of_val};
 use core::ptr::{NonNull, Unique};
 use core::usize;
+#[cfg(target_has_atomic = "ptr")]
+use core::{mem, ptr};
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicPtr, Ordering};
 
 #[stable(feature = "alloc_module", since = "1.28.0")]
 #[doc(inline)]
@@ -28,13 +32,16 @@ extern "Rust" {
     #[allocator]
     #[rustc_allocator_nounwind]
     fn __rust_alloc(size: usize, align: usize) -> *mut u8;
+    #[cfg_attr(not(stage0), rustc_deallocator)]
     #[rustc_allocator_nounwind]
     fn __rust_dealloc(ptr: *mut u8, size: usize, align: usize);
+    #[cfg_attr(not(stage0), rustc_reallocator)]
     #[rustc_allocator_nounwind]
     fn __rust_realloc(ptr: *mut u8,
                       old_size: usize,
                       align: usize,
                       new_size: usize) -> *mut u8;
+    #[cfg_attr(not(stage0), rustc_allocator_zeroed)]
     #[rustc_allocator_nounwind]
     fn __rust_alloc_zeroed(size: usize, align: usize) -> *mut u8;
 }
@@ -234,6 +241,66 @@ pub fn handle_alloc_error(layout: Layout) -> ! {
     unsafe { oom_impl(layout) }
 }
 
+/// What an allocator call of code built with `-Z instrument-allocators` did.
+/// `realloc` is reported as a `Dealloc` of the old block followed by an
+/// `Alloc` of the new one.
+#[unstable(feature = "alloc_event_hook", issue = "0")]
+#[repr(u32)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AllocEvent {
+    /// A block was allocated, or failed to be if the pointer is null.
+    Alloc = 0,
+    /// A block was freed.
+    Dealloc = 1,
+}
+
+/// A function that allocator calls of code built with
+/// `-Z instrument-allocators` report to, see [`set_alloc_event_hook`].
+///
+/// It's passed the event, the block's pointer, size and alignment, and an ID
+/// of the call site the allocator was called from.
+///
+/// [`set_alloc_event_hook`]: fn.set_alloc_event_hook.html
+#[unstable(feature = "alloc_event_hook", issue = "0")]
+pub type AllocEventHook = extern "C" fn(AllocEvent, *mut u8, usize, usize, u64);
+
+// Read by the wrappers that codegen emits around the allocator calls, so the
+// name must match the one in `rustc_codegen_llvm::allocator`.
+#[cfg(target_has_atomic = "ptr")]
+#[allow(non_upper_case_globals)]
+#[doc(hidden)]
+#[no_mangle]
+#[unstable(feature = "alloc_internals", issue = "0")]
+pub static __rust_alloc_event_hook: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Registers a hook that allocator calls of code built with
+/// `-Z instrument-allocators` report to, replacing any that was previously
+/// registered. Without one, the allocations aren't reported anywhere.
+///
+/// The hook is called from within the allocator, so it must not allocate
+/// through the global allocator itself.
+#[cfg(target_has_atomic = "ptr")]
+#[unstable(feature = "alloc_event_hook", issue = "0")]
+pub fn set_alloc_event_hook(hook: AllocEventHook) {
+    __rust_alloc_event_hook.store(hook as *mut (), Ordering::SeqCst);
+}
+
+/// Unregisters the current allocation event hook, returning it.
+///
+/// *See also the function [`set_alloc_event_hook`].*
+///
+/// [`set_alloc_event_hook`]: fn.set_alloc_event_hook.html
+#[cfg(target_has_atomic = "ptr")]
+#[unstable(feature = "alloc_event_hook", issue = "0")]
+pub fn take_alloc_event_hook() -> Option<AllocEventHook> {
+    let hook = __rust_alloc_event_hook.swap(ptr::null_mut(), Ordering::SeqCst);
+    if hook.is_null() {
+        None
+    } else {
+        Some(unsafe { mem::transmute(hook) })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate test;
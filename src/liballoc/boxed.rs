@@ -621,8 +621,8 @@ impl<I: FusedIterator + ?Sized> FusedIterator for Box<I> {}
 /// `Box<dyn FnOnce()>` in a data structure, you should use
 /// `Box<dyn FnBox()>`. The two traits behave essentially the same, except
 /// that a `FnBox` closure can only be called if it is boxed. (Note
-/// that `FnBox` may be deprecated in the future if `Box<dyn FnOnce()>`
-/// closures become directly usable.)
+/// that `FnBox` may be deprecated in the future, now that `Box<dyn FnOnce()>`
+/// closures are directly usable.)
 ///
 /// # Examples
 ///
@@ -653,6 +653,7 @@ impl<I: FusedIterator + ?Sized> FusedIterator for Box<I> {}
 ///     }
 /// }
 /// ```
+#[cfg(stage0)]
 #[rustc_paren_sugar]
 #[unstable(feature = "fnbox",
            reason = "will be deprecated if and when `Box<FnOnce>` becomes usable", issue = "28796")]
@@ -662,6 +663,7 @@ pub trait FnBox<A> {
     fn call_box(self: Box<Self>, args: A) -> Self::Output;
 }
 
+#[cfg(stage0)]
 #[unstable(feature = "fnbox",
            reason = "will be deprecated if and when `Box<FnOnce>` becomes usable", issue = "28796")]
 impl<A, F> FnBox<A> for F
@@ -674,6 +676,7 @@ impl<A, F> FnBox<A> for F
     }
 }
 
+#[cfg(stage0)]
 #[unstable(feature = "fnbox",
            reason = "will be deprecated if and when `Box<FnOnce>` becomes usable", issue = "28796")]
 impl<'a, A, R> FnOnce<A> for Box<dyn FnBox<A, Output = R> + 'a> {
@@ -684,6 +687,7 @@ impl<'a, A, R> FnOnce<A> for Box<dyn FnBox<A, Output = R> + 'a> {
     }
 }
 
+#[cfg(stage0)]
 #[unstable(feature = "fnbox",
            reason = "will be deprecated if and when `Box<FnOnce>` becomes usable", issue = "28796")]
 impl<'a, A, R> FnOnce<A> for Box<dyn FnBox<A, Output = R> + Send + 'a> {
@@ -694,6 +698,57 @@ impl<'a, A, R> FnOnce<A> for Box<dyn FnBox<A, Output = R> + Send + 'a> {
     }
 }
 
+#[cfg(not(stage0))]
+#[rustc_paren_sugar]
+#[unstable(feature = "fnbox",
+           reason = "will be deprecated if and when `Box<FnOnce>` becomes usable", issue = "28796")]
+pub trait FnBox<A>: FnOnce<A> {
+    fn call_box(self: Box<Self>, args: A) -> Self::Output;
+}
+
+#[cfg(not(stage0))]
+#[unstable(feature = "fnbox",
+           reason = "will be deprecated if and when `Box<FnOnce>` becomes usable", issue = "28796")]
+impl<A, F> FnBox<A> for F
+    where F: FnOnce<A>
+{
+    fn call_box(self: Box<F>, args: A) -> F::Output {
+        (*self).call_once(args)
+    }
+}
+
+#[cfg(not(stage0))]
+#[unstable(feature = "boxed_closure_impls",
+           reason = "relies on unsized locals to move the closure out of the box",
+           issue = "48055")]
+impl<A, F: FnOnce<A> + ?Sized> FnOnce<A> for Box<F> {
+    type Output = <F as FnOnce<A>>::Output;
+
+    extern "rust-call" fn call_once(self, args: A) -> Self::Output {
+        <F as FnOnce<A>>::call_once(*self, args)
+    }
+}
+
+#[cfg(not(stage0))]
+#[unstable(feature = "boxed_closure_impls",
+           reason = "relies on unsized locals to move the closure out of the box",
+           issue = "48055")]
+impl<A, F: FnMut<A> + ?Sized> FnMut<A> for Box<F> {
+    extern "rust-call" fn call_mut(&mut self, args: A) -> Self::Output {
+        <F as FnMut<A>>::call_mut(self, args)
+    }
+}
+
+#[cfg(not(stage0))]
+#[unstable(feature = "boxed_closure_impls",
+           reason = "relies on unsized locals to move the closure out of the box",
+           issue = "48055")]
+impl<A, F: Fn<A> + ?Sized> Fn<A> for Box<F> {
+    extern "rust-call" fn call(&self, args: A) -> Self::Output {
+        <F as Fn<A>>::call(self, args)
+    }
+}
+
 #[unstable(feature = "coerce_unsized", issue = "27732")]
 impl<T: ?Sized + Unsize<U>, U: ?Sized> CoerceUnsized<Box<U>> for Box<T> {}
 
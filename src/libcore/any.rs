@@ -435,7 +435,10 @@ impl dyn Any+Send+Sync {
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct TypeId {
+    #[cfg(stage0)]
     t: u64,
+    #[cfg(not(stage0))]
+    t: u128,
 }
 
 impl TypeId {
@@ -458,6 +461,18 @@ impl TypeId {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_const_unstable(feature="const_type_id")]
+    #[cfg(not(stage0))]
+    pub const fn of<T: ?Sized + 'static>() -> TypeId {
+        TypeId {
+            t: unsafe { intrinsics::type_id128::<T>() },
+        }
+    }
+
+    /// Returns the `TypeId` of the type this generic function has been
+    /// instantiated with.
+    #[stable(feature = "rust1", since = "1.0.0")]
+    #[rustc_const_unstable(feature="const_type_id")]
+    #[cfg(stage0)]
     pub const fn of<T: ?Sized + 'static>() -> TypeId {
         TypeId {
             t: unsafe { intrinsics::type_id::<T>() },
@@ -59,3 +59,33 @@ use intrinsics;
 pub unsafe fn unreachable_unchecked() -> ! {
     intrinsics::unreachable()
 }
+
+/// Signals the processor that it is inside a busy-wait spin-loop ("spin lock").
+///
+/// Upon receiving the spin-loop signal the processor can optimize its behavior
+/// by, for example, saving power or switching hyper-threads.
+///
+/// A loop calling this function is never considered free of side effects, so
+/// the compiler will not remove it even if its body is otherwise empty.
+///
+/// On some platforms this function only has the latter effect.
+///
+/// # Example
+///
+/// ```
+/// #![feature(renamed_spin_loop)]
+///
+/// use std::hint;
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// let ready = AtomicBool::new(true);
+/// while !ready.load(Ordering::Acquire) {
+///     hint::spin_loop();
+/// }
+/// ```
+#[cfg(not(stage0))]
+#[inline]
+#[unstable(feature = "renamed_spin_loop", issue = "55002")]
+pub fn spin_loop() {
+    intrinsics::spin_loop()
+}
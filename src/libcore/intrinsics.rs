@@ -51,6 +51,28 @@
                    since = "1.18.0")]
 pub use ptr::drop_in_place;
 
+/// Allows the operands and result to be reassociated.
+#[cfg(not(stage0))]
+pub const FAST_MATH_REASSOC: u32 = 1 << 0;
+/// Allows assuming that neither the operands nor the result are NaN.
+#[cfg(not(stage0))]
+pub const FAST_MATH_NO_NANS: u32 = 1 << 1;
+/// Allows assuming that neither the operands nor the result are infinite.
+#[cfg(not(stage0))]
+pub const FAST_MATH_NO_INFS: u32 = 1 << 2;
+/// Allows treating the sign of a zero operand or result as insignificant.
+#[cfg(not(stage0))]
+pub const FAST_MATH_NO_SIGNED_ZEROS: u32 = 1 << 3;
+/// Allows replacing a division with multiplication by the reciprocal.
+#[cfg(not(stage0))]
+pub const FAST_MATH_ALLOW_RECIPROCAL: u32 = 1 << 4;
+/// Allows contracting the operation with others, e.g. into a fused multiply-add.
+#[cfg(not(stage0))]
+pub const FAST_MATH_CONTRACT: u32 = 1 << 5;
+/// Allows substituting approximations of the operation.
+#[cfg(not(stage0))]
+pub const FAST_MATH_APPROX_FUNC: u32 = 1 << 6;
+
 extern "rust-intrinsic" {
     // NB: These intrinsics take raw pointers because they mutate aliased
     // memory, which is not valid for either `&` or `&mut`.
@@ -597,6 +619,16 @@ extern "rust-intrinsic" {
     /// The `locality` argument must be a constant integer and is a temporal locality specifier
     /// ranging from (0) - no locality, to (3) - extremely local keep in cache
     pub fn prefetch_write_instruction<T>(data: *const T, locality: i32);
+    /// The `prefetch` intrinsic is a hint to the code generator to insert a prefetch instruction
+    /// if supported; otherwise, it is a noop.
+    /// Prefetches have no effect on the behavior of the program but can change its performance
+    /// characteristics.
+    ///
+    /// All arguments but `data` must be constant integers: `rw` is (0) for a read or (1) for a
+    /// write, `locality` ranges from (0) - no locality, i.e. non-temporal, to (3) - extremely
+    /// local keep in cache, and `cache_type` selects the (0) instruction or (1) data cache.
+    #[cfg(not(stage0))]
+    pub fn prefetch<T>(data: *const T, rw: i32, locality: i32, cache_type: i32);
 }
 
 extern "rust-intrinsic" {
@@ -659,6 +691,25 @@ extern "rust-intrinsic" {
     /// Any use other than with `if` statements will probably not have an effect.
     pub fn likely(b: bool) -> bool;
 
+    /// Hints to the compiler that the code leading to this call is unlikely
+    /// to be executed, e.g. because it prepares a panic.
+    ///
+    /// This only has an effect when the call is reached from a conditional
+    /// branch without passing through another branch first.
+    #[cfg(not(stage0))]
+    pub fn cold_path();
+
+    /// Returns whether the argument's value is known to the optimizer at
+    /// compile time, e.g. because it comes from a constant after inlining.
+    ///
+    /// This is meant for choosing between two implementations of the same
+    /// function, such as a table-driven one for constants and a loop for
+    /// everything else. The result may differ between compilations and
+    /// optimization levels, so both implementations must behave identically.
+    /// During const evaluation this always returns `true`.
+    #[cfg(not(stage0))]
+    pub fn is_val_statically_known<T: Copy>(arg: T) -> bool;
+
     /// Hints to the compiler that branch condition is likely to be false.
     /// Returns the value passed to it.
     ///
@@ -668,6 +719,62 @@ extern "rust-intrinsic" {
     /// Executes a breakpoint trap, for inspection by a debugger.
     pub fn breakpoint();
 
+    /// Hints that this is the body of a busy-wait loop, pausing or yielding
+    /// the hardware thread where the target supports it.
+    ///
+    /// This counts as a side effect, so a loop calling it isn't assumed to
+    /// terminate. The stabilized version is `core::hint::spin_loop`.
+    #[cfg(not(stage0))]
+    pub fn spin_loop();
+
+    /// Returns the current value of the stack pointer.
+    ///
+    /// The result only says where the stack currently is, for stack usage
+    /// measurements and overflow checks; nothing may be read or written
+    /// through it.
+    #[cfg(not(stage0))]
+    pub fn current_stack_pointer() -> *mut u8;
+
+    /// Returns the frame address of the calling function, i.e. its frame
+    /// pointer, or the stack pointer on entry when it doesn't keep one.
+    ///
+    /// Like `current_stack_pointer`, this only says where the frame is.
+    #[cfg(not(stage0))]
+    pub fn frame_address() -> *mut u8;
+
+    /// Switches from the current context to `restore`, saving the current
+    /// context in `save`. Returns once something switches back to `save`.
+    ///
+    /// A context holds the frame pointer in its first word, the address
+    /// execution resumes at in its second, and the stack pointer in its
+    /// third; the remaining words are scratch space. All registers are
+    /// treated as clobbered across the switch, so the optimizer never keeps
+    /// values in registers the other context may change.
+    ///
+    /// To start a new coroutine, `restore` can be built by hand from the
+    /// address of a function which never returns and the top of its stack.
+    ///
+    /// Only supported on targets with LLVM's `llvm.eh.sjlj` intrinsics,
+    /// e.g. x86, x86_64, ARM and PowerPC.
+    #[cfg(not(stage0))]
+    pub fn swap_context(save: *mut [*mut u8; 5], restore: *const [*mut u8; 5]);
+
+    /// Returns the current size of the given WebAssembly linear memory, in
+    /// units of 64KiB pages. `mem` must be a constant, and `0` until multiple
+    /// memories are supported.
+    ///
+    /// Only available when targeting wasm32.
+    #[cfg(not(stage0))]
+    pub fn wasm_memory_size(mem: u32) -> usize;
+    /// Grows the given WebAssembly linear memory by `delta` 64KiB pages,
+    /// returning its previous size in pages, or `usize::max_value()` if it
+    /// couldn't be grown. `mem` must be a constant, and `0` until multiple
+    /// memories are supported.
+    ///
+    /// Only available when targeting wasm32.
+    #[cfg(not(stage0))]
+    pub fn wasm_memory_grow(mem: u32, delta: usize) -> usize;
+
     /// The size of a type in bytes.
     ///
     /// More specifically, this is the offset in bytes between successive
@@ -700,6 +807,30 @@ extern "rust-intrinsic" {
     /// crate it is invoked in.
     pub fn type_id<T: ?Sized + 'static>() -> u64;
 
+    /// Gets a 128-bit identifier which is globally unique to the specified
+    /// type. The lower 64 bits are the same as the value returned by
+    /// `type_id`.
+    #[cfg(not(stage0))]
+    pub fn type_id128<T: ?Sized + 'static>() -> u128;
+
+    /// Allocates a block of memory during const evaluation.
+    ///
+    /// The memory lives on the const evaluator's heap and must be freed with
+    /// `const_deallocate` before evaluation finishes; a constant whose final
+    /// value still points into it is rejected.
+    ///
+    /// At runtime this always returns a null pointer.
+    #[cfg(not(stage0))]
+    pub fn const_allocate(size: usize, align: usize) -> *mut u8;
+
+    /// Deallocates memory which was allocated by `const_allocate`.
+    ///
+    /// `size` and `align` must match the values used for the allocation.
+    ///
+    /// At runtime this does nothing.
+    #[cfg(not(stage0))]
+    pub fn const_deallocate(ptr: *mut u8, size: usize, align: usize);
+
     /// Creates a value initialized to zero.
     ///
     /// `init` is unsafe because it returns a zeroed-out datum,
@@ -717,6 +848,17 @@ extern "rust-intrinsic" {
     /// initialize memory previous set to the result of `uninit`.
     pub fn uninit<T>() -> T;
 
+    /// Panics if `T` is uninhabited. This check happens during
+    /// monomorphization, so the intrinsic is a no-op for every other type.
+    #[cfg(not(stage0))]
+    pub fn assert_inhabited<T>();
+
+    /// Panics if `T` is uninhabited or if an all-zero bit pattern is known to
+    /// be an invalid value of `T`, e.g. because it contains a reference or a
+    /// `NonNull`. Like `assert_inhabited`, this is a no-op for every other type.
+    #[cfg(not(stage0))]
+    pub fn assert_zero_valid<T>();
+
     /// Moves a value out of scope without running drop glue.
     #[cfg(not(stage0))]
     pub fn forget<T: ?Sized>(_: T);
@@ -943,6 +1085,27 @@ extern "rust-intrinsic" {
     /// [`std::mem::needs_drop`](../../std/mem/fn.needs_drop.html).
     pub fn needs_drop<T>() -> bool;
 
+    /// Returns `true` if `T` has a niche: values its layout leaves invalid,
+    /// which enums like `Option<T>` use to store their discriminant.
+    #[cfg(not(stage0))]
+    pub fn has_niche<T>() -> bool;
+
+    /// Returns the offset in bytes of the niche of `T`, or 0 if it has none.
+    #[cfg(not(stage0))]
+    pub fn niche_offset<T>() -> usize;
+
+    /// Returns the first of the values that are valid for the niche of `T`,
+    /// read as an unsigned integer of its size, or 0 if it has none.
+    ///
+    /// The valid range wraps around if it starts after it ends.
+    #[cfg(not(stage0))]
+    pub fn niche_range_start<T>() -> u128;
+
+    /// Returns the last of the values that are valid for the niche of `T`,
+    /// read as an unsigned integer of its size, or 0 if it has none.
+    #[cfg(not(stage0))]
+    pub fn niche_range_end<T>() -> u128;
+
     /// Calculates the offset from a pointer.
     ///
     /// This is implemented as an intrinsic to avoid converting to and from an
@@ -969,6 +1132,65 @@ extern "rust-intrinsic" {
     /// value is not necessarily valid to be used to actually access memory.
     pub fn arith_offset<T>(dst: *const T, offset: isize) -> *const T;
 
+    /// Calculates the offset from a pointer, potentially wrapping, and whether
+    /// the calculation overflowed.
+    ///
+    /// The pointer is computed like `arith_offset` does. The flag is set if
+    /// the offset in bytes overflows an `isize`, if the address wraps around,
+    /// or if the result is past the end of the allocated object `dst` points
+    /// into, where the compiler can tell the size of that object. If the flag
+    /// isn't set, the pointer is not necessarily valid to use.
+    #[cfg(not(stage0))]
+    pub fn offset_with_overflow<T>(dst: *const T, offset: isize) -> (*const T, bool);
+
+    /// Returns the current rounding mode of floating-point operations: 0 for
+    /// to nearest, 1 for downward, 2 for upward and 3 for toward zero.
+    ///
+    /// In a function that calls any of the `fenv_*` intrinsics, the compiler
+    /// doesn't fold or move across those calls the arithmetic operators and
+    /// the `sqrt`, `fma`, `powi`, `pow`, `sin`, `cos`, `exp`, `exp2`, `log`,
+    /// `log10`, `log2`, `rint` and `nearbyint` intrinsics, on scalars or SIMD
+    /// vectors. Conversions and comparisons aren't covered. The function isn't
+    /// inlined, and the functions it calls aren't inlined into it unless they
+    /// are `#[inline(always)]`, but the operations of those functions aren't
+    /// covered either.
+    ///
+    /// Only supported on x86 with SSE, where it reads MXCSR and doesn't affect
+    /// the x87 unit, and on AArch64.
+    #[cfg(not(stage0))]
+    pub fn fenv_rounding_mode() -> u32;
+
+    /// Sets the rounding mode of floating-point operations, numbered as by
+    /// `fenv_rounding_mode`.
+    #[cfg(not(stage0))]
+    pub fn fenv_set_rounding_mode(mode: u32);
+
+    /// Returns the floating-point exception flags raised since they were last
+    /// cleared: 1 for invalid operation, 2 for division by zero, 4 for
+    /// overflow, 8 for underflow and 16 for inexact.
+    #[cfg(not(stage0))]
+    pub fn fenv_exceptions() -> u32;
+
+    /// Clears the floating-point exception flags set in `flags`, numbered as
+    /// by `fenv_exceptions`.
+    #[cfg(not(stage0))]
+    pub fn fenv_clear_exceptions(flags: u32);
+
+    /// Executes the x86 `cpuid` instruction for `leaf` and `subleaf`, returning
+    /// `eax`, `ebx`, `ecx` and `edx`.
+    ///
+    /// The CPU's answers are assumed not to change while the program runs, so
+    /// repeated queries are merged and hoisted out of loops.
+    #[cfg(not(stage0))]
+    pub fn cpuid(leaf: u32, subleaf: u32) -> (u32, u32, u32, u32);
+
+    /// Returns the `AT_HWCAP` entry of the auxiliary vector, the bitmask of
+    /// hardware capabilities on Linux and Android, e.g. `HWCAP_ASIMD` on
+    /// AArch64. Like `cpuid`, repeated queries are merged. On Android
+    /// versions before 4.3, which can't report them, it returns 0.
+    #[cfg(not(stage0))]
+    pub fn cpu_hwcap() -> usize;
+
     /// Copies `count * size_of::<T>()` bytes from `src` to `dst`. The source
     /// and destination must *not* overlap.
     ///
@@ -1207,6 +1429,27 @@ extern "rust-intrinsic" {
     /// unless size is equal to zero.
     pub fn volatile_set_memory<T>(dst: *mut T, val: u8, count: usize);
 
+    /// Equivalent to the appropriate `llvm.memcpy.element.unordered.atomic.*`
+    /// intrinsic, with an element size of `size_of::<T>()`: copies `count`
+    /// elements from `src` to `dst`, each with an unordered atomic load and
+    /// store, so the copy can only tear at element boundaries.
+    ///
+    /// `T` must have a power-of-two size no larger than the target's widest
+    /// atomic, and be aligned to its size.
+    #[cfg(not(stage0))]
+    pub fn copy_nonoverlapping_atomic_unordered<T>(src: *const T, dst: *mut T, count: usize);
+
+    /// Moves the value out of `src` without dropping it, leaving the memory
+    /// unchanged. The stabilized version of this intrinsic is
+    /// [`std::ptr::read`](../../std/ptr/fn.read.html).
+    #[cfg(not(stage0))]
+    pub fn read_via_copy<T>(src: *const T) -> T;
+    /// Moves `val` into `dst` without dropping the old value.
+    /// The stabilized version of this intrinsic is
+    /// [`std::ptr::write`](../../std/ptr/fn.write.html).
+    #[cfg(not(stage0))]
+    pub fn write_via_move<T>(dst: *mut T, val: T);
+
     /// Perform a volatile load from the `src` pointer.
     /// The stabilized version of this intrinsic is
     /// [`std::ptr::read_volatile`](../../std/ptr/fn.read_volatile.html).
@@ -1223,6 +1466,21 @@ extern "rust-intrinsic" {
     /// The pointer is not required to be aligned.
     pub fn unaligned_volatile_store<T>(dst: *mut T, val: T);
 
+    /// Perform a volatile load from the `src` pointer using exactly one
+    /// memory access of the size of `T`, as device registers require.
+    ///
+    /// `T` must have a power-of-two size no larger than a pointer and be
+    /// aligned to its size; other types are rejected at compile time. The
+    /// pointer must be aligned.
+    #[cfg(not(stage0))]
+    pub fn mmio_load<T>(src: *const T) -> T;
+    /// Perform a volatile store to the `dst` pointer using exactly one
+    /// memory access of the size of `T`, as device registers require.
+    ///
+    /// The same restrictions as for `mmio_load` apply.
+    #[cfg(not(stage0))]
+    pub fn mmio_store<T>(dst: *mut T, val: T);
+
     /// Returns the square root of an `f32`
     pub fn sqrtf32(x: f32) -> f32;
     /// Returns the square root of an `f64`
@@ -1320,6 +1578,34 @@ extern "rust-intrinsic" {
     /// Returns the nearest integer to an `f64`. Rounds half-way cases away from zero.
     pub fn roundf64(x: f64) -> f64;
 
+    /// Returns the platform's canonical encoding of an `f32`: signaling NaNs
+    /// are quieted, and denormals are flushed to zero if the floating-point
+    /// environment does that.
+    #[cfg(not(stage0))]
+    pub fn canonicalizef32(x: f32) -> f32;
+    /// Returns the platform's canonical encoding of an `f64`: signaling NaNs
+    /// are quieted, and denormals are flushed to zero if the floating-point
+    /// environment does that.
+    #[cfg(not(stage0))]
+    pub fn canonicalizef64(x: f64) -> f64;
+
+    /// Returns the minimum of two `f32` values, as defined by IEEE 754-2019:
+    /// NaN if either input is NaN, and -0.0 is considered less than +0.0.
+    #[cfg(not(stage0))]
+    pub fn minimumf32(x: f32, y: f32) -> f32;
+    /// Returns the minimum of two `f64` values, as defined by IEEE 754-2019:
+    /// NaN if either input is NaN, and -0.0 is considered less than +0.0.
+    #[cfg(not(stage0))]
+    pub fn minimumf64(x: f64, y: f64) -> f64;
+    /// Returns the maximum of two `f32` values, as defined by IEEE 754-2019:
+    /// NaN if either input is NaN, and +0.0 is considered greater than -0.0.
+    #[cfg(not(stage0))]
+    pub fn maximumf32(x: f32, y: f32) -> f32;
+    /// Returns the maximum of two `f64` values, as defined by IEEE 754-2019:
+    /// NaN if either input is NaN, and +0.0 is considered greater than -0.0.
+    #[cfg(not(stage0))]
+    pub fn maximumf64(x: f64, y: f64) -> f64;
+
     /// Float addition that allows optimizations based on algebraic rules.
     /// May assume inputs are finite.
     pub fn fadd_fast<T>(a: T, b: T) -> T;
@@ -1340,6 +1626,31 @@ extern "rust-intrinsic" {
     /// May assume inputs are finite.
     pub fn frem_fast<T>(a: T, b: T) -> T;
 
+    /// Float addition that allows the optimizations selected by `flags`, a
+    /// combination of the `FAST_MATH_*` constants. `flags` must be a constant.
+    #[cfg(not(stage0))]
+    pub fn fadd_flags<T>(a: T, b: T, flags: u32) -> T;
+
+    /// Float subtraction that allows the optimizations selected by `flags`, a
+    /// combination of the `FAST_MATH_*` constants. `flags` must be a constant.
+    #[cfg(not(stage0))]
+    pub fn fsub_flags<T>(a: T, b: T, flags: u32) -> T;
+
+    /// Float multiplication that allows the optimizations selected by `flags`, a
+    /// combination of the `FAST_MATH_*` constants. `flags` must be a constant.
+    #[cfg(not(stage0))]
+    pub fn fmul_flags<T>(a: T, b: T, flags: u32) -> T;
+
+    /// Float division that allows the optimizations selected by `flags`, a
+    /// combination of the `FAST_MATH_*` constants. `flags` must be a constant.
+    #[cfg(not(stage0))]
+    pub fn fdiv_flags<T>(a: T, b: T, flags: u32) -> T;
+
+    /// Float remainder that allows the optimizations selected by `flags`, a
+    /// combination of the `FAST_MATH_*` constants. `flags` must be a constant.
+    #[cfg(not(stage0))]
+    pub fn frem_flags<T>(a: T, b: T, flags: u32) -> T;
+
 
     /// Returns the number of bits set in an integer type `T`
     pub fn ctpop<T>(x: T) -> T;
@@ -1506,6 +1817,18 @@ extern "rust-intrinsic" {
     /// cast to a `u64`; if `T` has no discriminant, returns 0.
     pub fn discriminant_value<T>(v: &T) -> u64;
 
+    /// Returns the tag stored in 'v' as it is laid out in memory, zero-extended
+    /// to a `u128`. Unlike `discriminant_value`, niche-encoded tags are not
+    /// translated back to a discriminant; if `T` stores no tag, this returns
+    /// the same value as `discriminant_value`.
+    #[cfg(not(stage0))]
+    pub fn raw_discriminant<T>(v: &T) -> u128;
+    /// Stores `tag`, truncated to the size of the tag, as the tag of the value
+    /// at `dst`, leaving the rest of it untouched. This is the inverse of
+    /// `raw_discriminant`; if `T` stores no tag, it does nothing.
+    #[cfg(not(stage0))]
+    pub fn set_discriminant<T>(dst: *mut T, tag: u128);
+
     /// Rust's "try catch" construct which invokes the function pointer `f` with
     /// the data pointer `data`.
     ///
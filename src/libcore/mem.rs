@@ -413,6 +413,53 @@ pub fn align_of_val<T: ?Sized>(val: &T) -> usize {
     unsafe { intrinsics::min_align_of_val(val) }
 }
 
+/// Provides the size and alignment of values of an `extern type`.
+///
+/// The compiler knows nothing about the layout of an `extern type`, so
+/// [`size_of_val`] and [`align_of_val`] normally return `0` and `1` for them.
+/// If the `extern type` implements this trait, they call its methods instead.
+///
+/// [`size_of_val`]: fn.size_of_val.html
+/// [`align_of_val`]: fn.align_of_val.html
+///
+/// # Safety
+///
+/// The values returned must describe the memory `self` points to, as unsafe
+/// code relies on them to copy and deallocate such values.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(extern_types, extern_size)]
+///
+/// use std::mem::{self, ExternSize};
+///
+/// extern {
+///     type Handle;
+/// }
+///
+/// unsafe impl ExternSize for Handle {
+///     fn size_of_val(&self) -> usize { 16 }
+///     fn align_of_val(&self) -> usize { 8 }
+/// }
+///
+/// let h: &Handle = unsafe { &*(8usize as *const Handle) };
+/// assert_eq!(16, mem::size_of_val(h));
+/// assert_eq!(8, mem::align_of_val(h));
+/// ```
+#[cfg(not(stage0))]
+#[lang = "extern_size"]
+#[unstable(feature = "extern_size", issue = "43467")]
+pub unsafe trait ExternSize {
+    /// Returns the size of the pointed-to value in bytes.
+    fn size_of_val(&self) -> usize;
+
+    /// Returns the [ABI]-required minimum alignment of the pointed-to value.
+    ///
+    /// [ABI]: https://en.wikipedia.org/wiki/Application_binary_interface
+    fn align_of_val(&self) -> usize;
+}
+
 /// Returns whether dropping values of type `T` matters.
 ///
 /// This is purely an optimization hint, and may be implemented conservatively:
@@ -503,6 +550,8 @@ pub const fn needs_drop<T>() -> bool {
 #[rustc_deprecated(since = "2.0.0", reason = "use `mem::MaybeUninit::zeroed` instead")]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub unsafe fn zeroed<T>() -> T {
+    #[cfg(not(stage0))]
+    intrinsics::assert_zero_valid::<T>();
     intrinsics::init()
 }
 
@@ -596,6 +645,8 @@ pub unsafe fn zeroed<T>() -> T {
 #[rustc_deprecated(since = "2.0.0", reason = "use `mem::MaybeUninit::uninitialized` instead")]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub unsafe fn uninitialized<T>() -> T {
+    #[cfg(not(stage0))]
+    intrinsics::assert_inhabited::<T>();
     intrinsics::uninit()
 }
 
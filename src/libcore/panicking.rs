@@ -61,6 +61,7 @@ fn panic_bounds_check(file_line_col: &(&'static str, u32, u32),
 }
 
 #[cold] #[inline(never)]
+#[cfg_attr(not(stage0), lang = "panic_fmt")]
 pub fn panic_fmt(fmt: fmt::Arguments, file_line_col: &(&'static str, u32, u32)) -> ! {
     // NOTE This function never crosses the FFI boundary; it's a Rust-to-Rust call
     #[allow(improper_ctypes)] // PanicInfo contains a trait object which is not FFI safe
@@ -571,6 +571,15 @@ pub unsafe fn replace<T>(dst: *mut T, mut src: T) -> T {
 /// [`write`]: ./fn.write.html
 #[inline]
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(not(stage0))]
+pub unsafe fn read<T>(src: *const T) -> T {
+    intrinsics::read_via_copy(src)
+}
+
+/// Reads the value from `src` without moving it.
+#[inline]
+#[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(stage0)]
 pub unsafe fn read<T>(src: *const T) -> T {
     let mut tmp = MaybeUninit::<T>::uninitialized();
     copy_nonoverlapping(src, tmp.as_mut_ptr(), 1);
@@ -732,6 +741,16 @@ pub unsafe fn read_unaligned<T>(src: *const T) -> T {
 /// [`mem::swap`]: ../mem/fn.swap.html
 #[inline]
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(not(stage0))]
+pub unsafe fn write<T>(dst: *mut T, src: T) {
+    intrinsics::write_via_move(dst, src)
+}
+
+/// Overwrites a memory location with the given value without reading or
+/// dropping the old value.
+#[inline]
+#[stable(feature = "rust1", since = "1.0.0")]
+#[cfg(stage0)]
 pub unsafe fn write<T>(dst: *mut T, src: T) {
     intrinsics::move_val_init(&mut *dst, src)
 }
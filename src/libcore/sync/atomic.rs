@@ -106,12 +106,15 @@ use fmt;
 #[inline]
 #[stable(feature = "spin_loop_hint", since = "1.24.0")]
 pub fn spin_loop_hint() {
-    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[cfg(not(stage0))]
+    ::hint::spin_loop();
+
+    #[cfg(all(stage0, any(target_arch = "x86", target_arch = "x86_64")))]
     unsafe {
         asm!("pause" ::: "memory" : "volatile");
     }
 
-    #[cfg(target_arch = "aarch64")]
+    #[cfg(all(stage0, target_arch = "aarch64"))]
     unsafe {
         asm!("yield" ::: "memory" : "volatile");
     }
@@ -841,7 +844,12 @@ impl<T> AtomicPtr<T> {
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn load(&self, order: Ordering) -> *mut T {
-        unsafe { atomic_load(self.p.get() as *mut usize, order) as *mut T }
+        #[cfg(not(stage0))] {
+            unsafe { atomic_load(self.p.get(), order) }
+        }
+        #[cfg(stage0)] {
+            unsafe { atomic_load(self.p.get() as *mut usize, order) as *mut T }
+        }
     }
 
     /// Stores a value into the pointer.
@@ -875,8 +883,11 @@ impl<T> AtomicPtr<T> {
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn store(&self, ptr: *mut T, order: Ordering) {
-        unsafe {
-            atomic_store(self.p.get() as *mut usize, ptr as usize, order);
+        #[cfg(not(stage0))] {
+            unsafe { atomic_store(self.p.get(), ptr, order); }
+        }
+        #[cfg(stage0)] {
+            unsafe { atomic_store(self.p.get() as *mut usize, ptr as usize, order); }
         }
     }
 
@@ -908,7 +919,12 @@ impl<T> AtomicPtr<T> {
     #[stable(feature = "rust1", since = "1.0.0")]
     #[cfg(target_has_atomic = "cas")]
     pub fn swap(&self, ptr: *mut T, order: Ordering) -> *mut T {
-        unsafe { atomic_swap(self.p.get() as *mut usize, ptr as usize, order) as *mut T }
+        #[cfg(not(stage0))] {
+            unsafe { atomic_swap(self.p.get(), ptr, order) }
+        }
+        #[cfg(stage0)] {
+            unsafe { atomic_swap(self.p.get() as *mut usize, ptr as usize, order) as *mut T }
+        }
     }
 
     /// Stores a value into the pointer if the current value is the same as the `current` value.
@@ -993,15 +1009,22 @@ impl<T> AtomicPtr<T> {
                             success: Ordering,
                             failure: Ordering)
                             -> Result<*mut T, *mut T> {
-        unsafe {
-            let res = atomic_compare_exchange(self.p.get() as *mut usize,
-                                              current as usize,
-                                              new as usize,
-                                              success,
-                                              failure);
-            match res {
-                Ok(x) => Ok(x as *mut T),
-                Err(x) => Err(x as *mut T),
+        #[cfg(not(stage0))] {
+            unsafe {
+                atomic_compare_exchange(self.p.get(), current, new, success, failure)
+            }
+        }
+        #[cfg(stage0)] {
+            unsafe {
+                let res = atomic_compare_exchange(self.p.get() as *mut usize,
+                                                  current as usize,
+                                                  new as usize,
+                                                  success,
+                                                  failure);
+                match res {
+                    Ok(x) => Ok(x as *mut T),
+                    Err(x) => Err(x as *mut T),
+                }
             }
         }
     }
@@ -1053,15 +1076,22 @@ impl<T> AtomicPtr<T> {
                                  success: Ordering,
                                  failure: Ordering)
                                  -> Result<*mut T, *mut T> {
-        unsafe {
-            let res = atomic_compare_exchange_weak(self.p.get() as *mut usize,
-                                                   current as usize,
-                                                   new as usize,
-                                                   success,
-                                                   failure);
-            match res {
-                Ok(x) => Ok(x as *mut T),
-                Err(x) => Err(x as *mut T),
+        #[cfg(not(stage0))] {
+            unsafe {
+                atomic_compare_exchange_weak(self.p.get(), current, new, success, failure)
+            }
+        }
+        #[cfg(stage0)] {
+            unsafe {
+                let res = atomic_compare_exchange_weak(self.p.get() as *mut usize,
+                                                       current as usize,
+                                                       new as usize,
+                                                       success,
+                                                       failure);
+                match res {
+                    Ok(x) => Ok(x as *mut T),
+                    Err(x) => Err(x as *mut T),
+                }
             }
         }
     }
@@ -601,6 +601,7 @@ define_dep_nodes!( <'tcx>
     [input] CrateHash(CrateNum),
     [input] OriginalCrateName(CrateNum),
     [input] ExtraFileName(CrateNum),
+    [input] SymbolPrefix(CrateNum),
 
     [] ImplementationsOfTrait { krate: CrateNum, trait_id: DefId },
     [] AllTraitImplementations(CrateNum),
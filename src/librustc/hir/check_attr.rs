@@ -16,8 +16,12 @@
 
 use hir;
 use hir::intravisit::{self, Visitor, NestedVisitorMap};
+use mir::LoopHints;
+use session::config::OptLevel;
 use ty::TyCtxt;
 use std::fmt::{self, Display};
+use syntax::attr;
+use syntax::feature_gate::{emit_feature_err, GateIssue};
 use syntax_pos::Span;
 
 #[derive(Copy, Clone, PartialEq)]
@@ -114,6 +118,18 @@ impl<'a, 'tcx> CheckAttrVisitor<'a, 'tcx> {
                 self.check_non_exhaustive(attr, item, target)
             } else if attr.check_name("marker") {
                 self.check_marker(attr, item, target)
+            } else if attr.check_name("pointer_niche") {
+                self.check_pointer_niche(attr, item, target)
+            } else if attr.check_name("offload") {
+                self.check_offload(attr, item, target)
+            } else if attr.check_name("vectorize") {
+                self.check_vectorize(attr, item)
+            } else if attr.check_name("no_alloc") {
+                self.check_no_alloc(attr, item, target)
+            } else if attr.check_name("address_space") {
+                self.check_address_space(attr, item, target)
+            } else if hir::is_loop_hint(attr) {
+                self.emit_loop_hint_error(attr, item.span)
             }
         }
 
@@ -178,6 +194,97 @@ impl<'a, 'tcx> CheckAttrVisitor<'a, 'tcx> {
         }
     }
 
+    /// Check if the `#[pointer_niche]` attribute is applied to a struct.
+    fn check_pointer_niche(&self, attr: &hir::Attribute, item: &hir::Item, target: Target) {
+        if target != Target::Struct {
+            self.tcx.sess
+                .struct_span_err(attr.span, "attribute can only be applied to a struct")
+                .span_label(item.span, "not a struct")
+                .emit();
+        }
+    }
+
+    /// Check if an `#[address_space = "N"]` is applied to a static or a type
+    /// definition, with a number for `N`.
+    fn check_address_space(&self, attr: &hir::Attribute, item: &hir::Item, target: Target) {
+        match target {
+            Target::Static | Target::Struct | Target::Enum | Target::Union => {}
+            _ => {
+                self.tcx.sess
+                    .struct_span_err(attr.span,
+                                     "attribute should be applied to a static, struct, \
+                                      enum or union")
+                    .span_label(item.span, "not a static, struct, enum or union")
+                    .emit();
+                return;
+            }
+        }
+        if attr.value_str().and_then(|val| val.as_str().parse::<u32>().ok()).is_none() {
+            self.tcx.sess.span_err(
+                attr.span,
+                "#[address_space] attribute must be of the form #[address_space = \"N\"]",
+            );
+        }
+    }
+
+    /// Check if the `#[offload]` attribute is applied to a non-generic function
+    /// or a static.
+    fn check_offload(&self, attr: &hir::Attribute, item: &hir::Item, target: Target) {
+        match item.node {
+            hir::ItemKind::Fn(_, _, ref generics, _) => {
+                self.check_not_generic(attr, item, generics)
+            }
+            _ if target == Target::Static => { /* Valid */ }
+            _ => {
+                self.tcx.sess
+                    .struct_span_err(attr.span,
+                                     "attribute should be applied to a function or static")
+                    .span_label(item.span, "not a function or static")
+                    .emit();
+            }
+        }
+    }
+
+    /// Check if a `#[vectorize]` is applied to a non-generic function.
+    fn check_vectorize(&self, attr: &hir::Attribute, item: &hir::Item) {
+        match item.node {
+            hir::ItemKind::Fn(_, _, ref generics, _) => {
+                self.check_not_generic(attr, item, generics)
+            }
+            _ => {
+                self.tcx.sess
+                    .struct_span_err(attr.span, "attribute should be applied to a function")
+                    .span_label(item.span, "not a function")
+                    .emit();
+            }
+        }
+    }
+
+    /// Check that the function `item`, which `attr` is applied to, has no type
+    /// parameters, for attributes that need a single definition of it.
+    fn check_not_generic(&self, attr: &hir::Attribute, item: &hir::Item,
+                         generics: &hir::Generics) {
+        if generics.params.iter().any(|param| match param.kind {
+            hir::GenericParamKind::Type { .. } => true,
+            _ => false,
+        }) {
+            self.tcx.sess
+                .struct_span_err(attr.span, "attribute can't be applied to a generic function")
+                .span_label(item.span, "generic function")
+                .emit();
+        }
+    }
+
+    /// Check if a `#[no_alloc]` is applied to a function.
+    fn check_no_alloc(&self, attr: &hir::Attribute, item: &hir::Item, target: Target) {
+        if target != Target::Fn {
+            self.tcx.sess
+                .struct_span_err(attr.span, "attribute should be applied to a function")
+                .span_label(item.span, "not a function")
+                .emit();
+        }
+    }
+
     /// Check if the `#[repr]` attributes on `item` are valid.
     fn check_repr(&self, item: &hir::Item, target: Target) {
         // Extract the names of all repr hints, e.g., [foo, bar, align] for:
@@ -316,6 +423,9 @@ impl<'a, 'tcx> CheckAttrVisitor<'a, 'tcx> {
                         "not a struct, enum or union",
                     );
                 }
+                if hir::is_loop_hint(attr) {
+                    self.emit_loop_hint_error(attr, stmt.span);
+                }
             }
         }
     }
@@ -325,9 +435,12 @@ impl<'a, 'tcx> CheckAttrVisitor<'a, 'tcx> {
             hir::ExprKind::Closure(..) => Target::Closure,
             _ => Target::Expression,
         };
+        let mut hints = LoopHints::default();
         for attr in expr.attrs.iter() {
             if attr.check_name("inline") {
-                self.check_inline(attr, &expr.span, target);
+                if !self.check_call_site_inline(attr, expr) {
+                    self.check_inline(attr, &expr.span, target);
+                }
             }
             if attr.check_name("repr") {
                 self.emit_repr_error(
@@ -337,9 +450,71 @@ impl<'a, 'tcx> CheckAttrVisitor<'a, 'tcx> {
                     "not defining a struct, enum or union",
                 );
             }
+            if hir::is_loop_hint(attr) {
+                self.check_loop_hint(attr, expr, &mut hints);
+            }
+        }
+        if hints.unroll_count.is_some() && hints.no_unroll {
+            self.tcx.sess.span_err(expr.span, "conflicting `#[unroll]` and `#[no_unroll]` hints");
+        }
+    }
+
+    /// Check an `#[inline(always)]` or `#[inline(never)]` on a call expression.
+    /// Returns `false` if `attr` isn't one, so it's checked like any other `#[inline]`.
+    fn check_call_site_inline(&self, attr: &hir::Attribute, expr: &hir::Expr) -> bool {
+        match expr.node {
+            hir::ExprKind::Call(..) | hir::ExprKind::MethodCall(..) => {}
+            _ => return false,
+        }
+        let (is_override, is_always) = match attr.meta_item_list() {
+            Some(items) => {
+                let is_always = items.len() == 1 && attr::list_contains_name(&items, "always");
+                let is_never = items.len() == 1 && attr::list_contains_name(&items, "never");
+                (is_always || is_never, is_always)
+            }
+            None => (false, false),
+        };
+        if is_override && !self.tcx.features().call_site_inline {
+            emit_feature_err(&self.tcx.sess.parse_sess,
+                             "call_site_inline",
+                             attr.span,
+                             GateIssue::Language,
+                             "`#[inline]` on a call expression is experimental");
+        }
+        // The always-inliner LLVM runs without optimizations only looks at
+        // the callee's `alwaysinline`, not at the call site's.
+        if is_always && self.tcx.sess.opts.optimize == OptLevel::No {
+            self.tcx.sess.span_warn(
+                attr.span,
+                "`#[inline(always)]` on a call expression has no effect without optimizations",
+            );
+        }
+        is_override
+    }
+
+    /// Check if a `#[unroll]`, `#[no_unroll]` or `#[pipeline]` is a well-formed
+    /// hint on a loop, and add it to the loop's `hints`.
+    fn check_loop_hint(&self, attr: &hir::Attribute, expr: &hir::Expr, hints: &mut LoopHints) {
+        match expr.node {
+            hir::ExprKind::Loop(..) | hir::ExprKind::While(..) => {
+                if !hints.add_attr(attr) {
+                    self.tcx.sess.struct_span_err(attr.span, "malformed loop hint")
+                        .help("use `#[unroll(N)]`, `#[no_unroll]`, `#[pipeline(disable)]` or \
+                               `#[pipeline(initiation_interval = N)]` with a positive `N`")
+                        .emit();
+                }
+            }
+            _ => self.emit_loop_hint_error(attr, expr.span),
         }
     }
 
+    fn emit_loop_hint_error(&self, attr: &hir::Attribute, span: Span) {
+        self.tcx.sess
+            .struct_span_err(attr.span, "attribute should be applied to a loop")
+            .span_label(span, "not a loop")
+            .emit();
+    }
+
     fn check_used(&self, item: &hir::Item, target: Target) {
         for attr in &item.attrs {
             if attr.name() == "used" && target != Target::Static {
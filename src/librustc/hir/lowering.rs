@@ -4362,13 +4362,17 @@ impl<'a> LoweringContext<'a> {
                     self.lower_label(opt_label),
                     hir::LoopSource::ForLoop,
                 );
+                // The loop hints belong to the loop itself, the other attributes
+                // to the outer returned expr node.
+                let (hint_attrs, attrs): (Vec<_>, Vec<_>) =
+                    e.attrs.iter().cloned().partition(hir::is_loop_hint);
                 let LoweredNodeId { node_id, hir_id } = self.lower_node_id(e.id);
                 let loop_expr = P(hir::Expr {
                     id: node_id,
                     hir_id,
                     node: loop_expr,
                     span: e.span,
-                    attrs: ThinVec::new(),
+                    attrs: hint_attrs.into(),
                 });
 
                 // `mut iter => { ... }`
@@ -4398,7 +4402,7 @@ impl<'a> LoweringContext<'a> {
                 let result = P(self.expr_ident(e.span, result_ident, let_stmt_binding));
                 let block = P(self.block_all(e.span, hir_vec![let_stmt], Some(result)));
                 // add the attributes to the outer returned expr node
-                return self.expr_block(block, e.attrs.clone());
+                return self.expr_block(block, attrs.into());
             }
 
             // Desugar ExprKind::Try
@@ -1593,6 +1593,12 @@ pub enum LoopSource {
     ForLoop,
 }
 
+/// Whether `attr` is one of the optimization hints for a loop: `#[unroll]`,
+/// `#[no_unroll]` or `#[pipeline]`.
+pub fn is_loop_hint(attr: &Attribute) -> bool {
+    ["unroll", "no_unroll", "pipeline"].iter().any(|name| attr.check_name(name))
+}
+
 #[derive(Clone, RustcEncodable, RustcDecodable, Debug, Copy)]
 pub enum LoopIdError {
     OutsideLoopScope,
@@ -2417,11 +2423,18 @@ pub struct CodegenFnAttrs {
     /// The `#[target_feature(enable = "...")]` attribute and the enabled
     /// features (only enabled features are supported right now).
     pub target_features: Vec<Symbol>,
+    /// The `#[multiversion(targets(...))]` attribute, in order of preference.
+    /// When non-empty a copy of the function is generated for each target
+    /// and the function itself dispatches to the best one at runtime.
+    pub multiversion: Vec<Symbol>,
     /// The `#[linkage = "..."]` attribute and the value we found.
     pub linkage: Option<Linkage>,
     /// The `#[link_section = "..."]` attribute, or what executable section this
     /// should be placed in.
     pub link_section: Option<Symbol>,
+    /// The `#[address_space = "..."]` attribute, or which address space a
+    /// static should be placed in instead of the target's default.
+    pub address_space: Option<u32>,
 }
 
 bitflags! {
@@ -2458,6 +2471,24 @@ bitflags! {
         /// #[used], indicates that LLVM can't eliminate this function (but the
         /// linker can!)
         const USED                      = 1 << 9;
+        /// #[rustc_deallocator], the function frees memory returned by an
+        /// #[allocator] function, which LLVM may then remove in pairs
+        const DEALLOCATOR               = 1 << 10;
+        /// #[rustc_reallocator], the function resizes memory returned by an
+        /// #[allocator] function
+        const REALLOCATOR               = 1 << 11;
+        /// #[rustc_allocator_zeroed], like #[allocator] but the memory
+        /// returned is zeroed
+        const ALLOCATOR_ZEROED          = 1 << 12;
+        /// #[offload], the function is a device kernel or the static is
+        /// device data, mapped between host and device by an offload runtime
+        const OFFLOAD                   = 1 << 13;
+        /// #[vectorize(expect)]: loops in the function that the loop
+        /// vectorizer gives up on are reported as `autovectorization_failure`
+        const EXPECT_VECTORIZED         = 1 << 14;
+        /// #[no_alloc]: with `-Z verify-no-alloc`, it's an error if the
+        /// function may allocate or format a panic message
+        const NO_ALLOC                  = 1 << 15;
     }
 }
 
@@ -2469,8 +2500,10 @@ impl CodegenFnAttrs {
             export_name: None,
             link_name: None,
             target_features: vec![],
+            multiversion: vec![],
             linkage: None,
             link_section: None,
+            address_space: None,
         }
     }
 
@@ -2487,8 +2520,10 @@ impl CodegenFnAttrs {
     /// * `#[no_mangle]` is present
     /// * `#[export_name(...)]` is present
     /// * `#[linkage]` is present
+    /// * `#[offload]` is present
     pub fn contains_extern_indicator(&self) -> bool {
         self.flags.contains(CodegenFnAttrFlags::NO_MANGLE) ||
+            self.flags.contains(CodegenFnAttrFlags::OFFLOAD) ||
             self.export_name.is_some() ||
             match self.linkage {
                 // these are private, make sure we don't try to consider
@@ -24,7 +24,8 @@ impl_stable_hash_for!(enum middle::cstore::NativeLibraryKind {
     NativeStatic,
     NativeStaticNobundle,
     NativeFramework,
-    NativeUnknown
+    NativeUnknown,
+    NativeBitcode
 });
 
 impl_stable_hash_for!(struct middle::cstore::NativeLibrary {
@@ -1172,8 +1172,10 @@ impl_stable_hash_for!(struct hir::CodegenFnAttrs {
     export_name,
     link_name,
     target_features,
+    multiversion,
     linkage,
     link_section,
+    address_space,
 });
 
 impl<'hir> HashStable<StableHashingContext<'hir>> for hir::CodegenFnAttrFlags
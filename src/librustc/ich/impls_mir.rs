@@ -159,12 +159,14 @@ for mir::TerminatorKind<'gcx> {
                                         ref args,
                                         ref destination,
                                         cleanup,
-                                        from_hir_call, } => {
+                                        from_hir_call,
+                                        inline, } => {
                 func.hash_stable(hcx, hasher);
                 args.hash_stable(hcx, hasher);
                 destination.hash_stable(hcx, hasher);
                 cleanup.hash_stable(hcx, hasher);
                 from_hir_call.hash_stable(hcx, hasher);
+                inline.hash_stable(hcx, hasher);
             }
             mir::TerminatorKind::Assert { ref cond,
                                           expected,
@@ -229,6 +231,9 @@ for mir::StatementKind<'gcx> {
                 variance.hash_stable(hcx, hasher);
                 c_ty.hash_stable(hcx, hasher);
             }
+            mir::StatementKind::LoopHint(ref hints) => {
+                hints.hash_stable(hcx, hasher);
+            }
             mir::StatementKind::Nop => {}
             mir::StatementKind::InlineAsm { ref asm, ref outputs, ref inputs } => {
                 asm.hash_stable(hcx, hasher);
@@ -241,6 +246,13 @@ for mir::StatementKind<'gcx> {
 
 impl_stable_hash_for!(enum mir::FakeReadCause { ForMatchGuard, ForMatchedPlace, ForLet });
 
+impl_stable_hash_for!(struct mir::LoopHints {
+    unroll_count,
+    no_unroll,
+    pipeline_disable,
+    pipeline_initiation_interval
+});
+
 impl<'a, 'gcx> HashStable<StableHashingContext<'a>> for mir::Place<'gcx> {
     fn hash_stable<W: StableHasherResult>(&self,
                                           hcx: &mut StableHashingContext<'a>,
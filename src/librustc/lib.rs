@@ -133,6 +133,7 @@ pub mod lint;
 
 pub mod middle {
     pub mod allocator;
+    pub mod atomic_intrinsic;
     pub mod borrowck;
     pub mod expr_use_visitor;
     pub mod cstore;
@@ -356,6 +356,18 @@ declare_lint! {
     "outlives requirements can be inferred"
 }
 
+declare_lint! {
+    pub DYNAMIC_SIMD_INDEX,
+    Allow,
+    "detects `simd_extract` calls whose lane index is not a constant"
+}
+
+declare_lint! {
+    pub AUTOVECTORIZATION_FAILURE,
+    Allow,
+    "detects loops in `#[vectorize(expect)]` functions that LLVM failed to vectorize"
+}
+
 /// Some lints that are buffered from `libsyntax`. See `syntax::early_buffered_lints`.
 pub mod parser {
     declare_lint! {
@@ -426,6 +438,8 @@ impl LintPass for HardwiredLints {
             PROC_MACRO_DERIVE_RESOLUTION_FALLBACK,
             MACRO_USE_EXTERN_CRATE,
             MACRO_EXPANDED_MACRO_EXPORTS_ACCESSED_BY_ABSOLUTE_PATHS,
+            DYNAMIC_SIMD_INDEX,
+            AUTOVECTORIZATION_FAILURE,
             parser::QUESTION_MARK_MACRO_SEP,
         )
     }
@@ -0,0 +1,159 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The atomic intrinsics are named `atomic_<op>[_<ordering>]`. This parses
+//! those names into typed values once, so that type checking can reject the
+//! malformed ones with a proper error and codegen can match on enums.
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AtomicOp {
+    Cxchg,
+    CxchgWeak,
+    Load,
+    Store,
+    Xchg,
+    Xadd,
+    Xsub,
+    And,
+    Nand,
+    Or,
+    Xor,
+    Max,
+    Min,
+    Umax,
+    Umin,
+    Fence,
+    SingleThreadFence,
+}
+
+impl AtomicOp {
+    pub fn from_name(name: &str) -> Option<AtomicOp> {
+        Some(match name {
+            "cxchg" => AtomicOp::Cxchg,
+            "cxchgweak" => AtomicOp::CxchgWeak,
+            "load" => AtomicOp::Load,
+            "store" => AtomicOp::Store,
+            "xchg" => AtomicOp::Xchg,
+            "xadd" => AtomicOp::Xadd,
+            "xsub" => AtomicOp::Xsub,
+            "and" => AtomicOp::And,
+            "nand" => AtomicOp::Nand,
+            "or" => AtomicOp::Or,
+            "xor" => AtomicOp::Xor,
+            "max" => AtomicOp::Max,
+            "min" => AtomicOp::Min,
+            "umax" => AtomicOp::Umax,
+            "umin" => AtomicOp::Umin,
+            "fence" => AtomicOp::Fence,
+            "singlethreadfence" => AtomicOp::SingleThreadFence,
+            _ => return None,
+        })
+    }
+
+    pub fn is_cxchg(self) -> bool {
+        self == AtomicOp::Cxchg || self == AtomicOp::CxchgWeak
+    }
+
+    pub fn is_fence(self) -> bool {
+        self == AtomicOp::Fence || self == AtomicOp::SingleThreadFence
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AtomicOp::Cxchg => "cxchg",
+            AtomicOp::CxchgWeak => "cxchgweak",
+            AtomicOp::Load => "load",
+            AtomicOp::Store => "store",
+            AtomicOp::Xchg => "xchg",
+            AtomicOp::Xadd => "xadd",
+            AtomicOp::Xsub => "xsub",
+            AtomicOp::And => "and",
+            AtomicOp::Nand => "nand",
+            AtomicOp::Or => "or",
+            AtomicOp::Xor => "xor",
+            AtomicOp::Max => "max",
+            AtomicOp::Min => "min",
+            AtomicOp::Umax => "umax",
+            AtomicOp::Umin => "umin",
+            AtomicOp::Fence => "fence",
+            AtomicOp::SingleThreadFence => "singlethreadfence",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AtomicOrdering {
+    Unordered,
+    Relaxed,
+    Acquire,
+    Release,
+    AcqRel,
+    SeqCst,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AtomicIntrinsic {
+    pub op: AtomicOp,
+    pub ordering: AtomicOrdering,
+    /// The ordering of the load when a compare-exchange fails. For the other
+    /// operations it's derived from `ordering` the same way, and unused.
+    pub failure_ordering: AtomicOrdering,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AtomicIntrinsicError<'a> {
+    UnknownOp(&'a str),
+    UnknownOrdering(AtomicOp, &'a str),
+    /// An ordering the operation can't have, like a release load.
+    InvalidOrdering(AtomicOp, &'a str),
+}
+
+impl AtomicIntrinsic {
+    /// Parses the name of an atomic intrinsic, which starts with `atomic_`.
+    pub fn parse(name: &str) -> Result<AtomicIntrinsic, AtomicIntrinsicError> {
+        use self::AtomicOrdering::*;
+
+        let mut split = name.splitn(3, '_');
+        assert_eq!(split.next(), Some("atomic"));
+        let op_name = split.next().unwrap_or("");
+        let op = AtomicOp::from_name(op_name).ok_or(AtomicIntrinsicError::UnknownOp(op_name))?;
+
+        let is_cxchg = op.is_cxchg();
+        let ordering_name = split.next().unwrap_or("");
+        let (ordering, failure_ordering) = match ordering_name {
+            "" => (SeqCst, SeqCst),
+            "unordered" => (Unordered, Unordered),
+            "relaxed" => (Relaxed, Relaxed),
+            "acq" => (Acquire, Acquire),
+            "rel" => (Release, Relaxed),
+            "acqrel" => (AcqRel, Acquire),
+            "failrelaxed" if is_cxchg => (SeqCst, Relaxed),
+            "failacq" if is_cxchg => (SeqCst, Acquire),
+            "acq_failrelaxed" if is_cxchg => (Acquire, Relaxed),
+            "acqrel_failrelaxed" if is_cxchg => (AcqRel, Relaxed),
+            ordering => return Err(AtomicIntrinsicError::UnknownOrdering(op, ordering)),
+        };
+
+        // Loads can't release and stores can't acquire, fences have to do at
+        // least one of the two, and only loads and stores can be unordered.
+        let valid = match op {
+            AtomicOp::Load => ordering != Release && ordering != AcqRel,
+            AtomicOp::Store => ordering != Acquire && ordering != AcqRel,
+            AtomicOp::Fence |
+            AtomicOp::SingleThreadFence => ordering != Unordered && ordering != Relaxed,
+            _ => ordering != Unordered,
+        };
+        if !valid {
+            return Err(AtomicIntrinsicError::InvalidOrdering(op, ordering_name));
+        }
+
+        Ok(AtomicIntrinsic { op, ordering, failure_ordering })
+    }
+}
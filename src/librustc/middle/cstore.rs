@@ -115,6 +115,9 @@ pub enum NativeLibraryKind {
     NativeFramework,
     /// default way to specify a dynamic library
     NativeUnknown,
+    /// LLVM bitcode library (.bc file or archive of them), which gets merged
+    /// into the crate's own modules
+    NativeBitcode,
 }
 
 #[derive(Clone, RustcEncodable, RustcDecodable)]
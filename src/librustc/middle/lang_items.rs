@@ -265,6 +265,7 @@ language_item_table! {
     CloneTraitLangItem,          "clone",              clone_trait,             Target::Trait;
     SyncTraitLangItem,           "sync",               sync_trait,              Target::Trait;
     FreezeTraitLangItem,         "freeze",             freeze_trait,            Target::Trait;
+    ExternSizeTraitLangItem,     "extern_size",        extern_size_trait,       Target::Trait;
 
     DropTraitLangItem,           "drop",               drop_trait,              Target::Trait;
 
@@ -323,6 +324,7 @@ language_item_table! {
     // lang item, but do not have it defined.
     PanicFnLangItem,             "panic",              panic_fn,                Target::Fn;
     PanicBoundsCheckFnLangItem,  "panic_bounds_check", panic_bounds_check_fn,   Target::Fn;
+    PanicFmtLangItem,            "panic_fmt",          panic_fmt,               Target::Fn;
     PanicInfoLangItem,           "panic_info",         panic_info,              Target::Struct;
     PanicImplLangItem,           "panic_impl",         panic_impl,              Target::Fn;
     // Libstd panic entry point. Necessary for const eval to be able to catch it
@@ -335,6 +337,9 @@ language_item_table! {
     AllocLayoutLangItem,         "alloc_layout",       alloc_layout,            Target::Struct;
 
     StartFnLangItem,             "start",              start_fn,                Target::Fn;
+    MultiversionCpuSupportsFnLangItem, "multiversion_cpu_supports",
+                                 multiversion_cpu_supports_fn, Target::Fn;
+    CountVirtualCallFnLangItem,  "count_virtual_call", count_virtual_call_fn,  Target::Fn;
 
     EhPersonalityLangItem,       "eh_personality",     eh_personality,          Target::Fn;
     EhUnwindResumeLangItem,      "eh_unwind_resume",   eh_unwind_resume,        Target::Fn;
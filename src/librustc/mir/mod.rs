@@ -34,6 +34,7 @@ use std::slice;
 use std::vec::IntoIter;
 use std::{iter, mem, option, u32};
 use syntax::ast::{self, Name};
+use syntax::attr::InlineAttr;
 use syntax::symbol::InternedString;
 use syntax_pos::{Span, DUMMY_SP};
 use ty::fold::{TypeFoldable, TypeFolder, TypeVisitor};
@@ -1133,6 +1134,9 @@ pub enum TerminatorKind<'tcx> {
         /// Whether this is from a call in HIR, rather than from an overloaded
         /// operator. True for overloaded function call.
         from_hir_call: bool,
+        /// `#[inline(always)]` or `#[inline(never)]` on the call expression,
+        /// overriding the callee's own `#[inline]` at this call site only.
+        inline: InlineAttr,
     },
 
     /// Jump to the target if the condition has the expected value,
@@ -1802,10 +1806,95 @@ pub enum StatementKind<'tcx> {
     /// - `Bivariant` -- no effect
     AscribeUserType(Place<'tcx>, ty::Variance, Box<UserTypeProjection<'tcx>>),
 
+    /// Marks the block it's in as the header of a loop with the given
+    /// optimization hints, which codegen attaches to the branches back to it.
+    LoopHint(LoopHints),
+
     /// No-op. Useful for deleting instructions without affecting statement indices.
     Nop,
 }
 
+/// Optimization hints for a loop, given by its `#[unroll]`, `#[no_unroll]`
+/// and `#[pipeline]` attributes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, RustcEncodable, RustcDecodable)]
+pub struct LoopHints {
+    /// `#[unroll(N)]`: unroll the loop by a factor of `N`.
+    pub unroll_count: Option<u32>,
+    /// `#[no_unroll]`: don't unroll the loop.
+    pub no_unroll: bool,
+    /// `#[pipeline(disable)]`: don't software-pipeline the loop.
+    pub pipeline_disable: bool,
+    /// `#[pipeline(initiation_interval = N)]`: software-pipeline the loop,
+    /// starting a new iteration every `N` cycles.
+    pub pipeline_initiation_interval: Option<u32>,
+}
+
+impl LoopHints {
+    /// Collects the hints of a loop with the attributes `attrs`.
+    pub fn from_attrs(attrs: &[ast::Attribute]) -> LoopHints {
+        let mut hints = LoopHints::default();
+        for attr in attrs {
+            hints.add_attr(attr);
+        }
+        hints
+    }
+
+    /// Adds the hint given by `attr`. Returns `false`, and changes nothing,
+    /// if `attr` isn't a well-formed loop hint.
+    pub fn add_attr(&mut self, attr: &ast::Attribute) -> bool {
+        fn count(lit: Option<&ast::Lit>) -> Option<u32> {
+            match lit.map(|lit| &lit.node) {
+                Some(&ast::LitKind::Int(n, _)) if n > 0 && n <= u32::MAX as u128 => {
+                    Some(n as u32)
+                }
+                _ => None,
+            }
+        }
+
+        let list = attr.meta_item_list();
+        let item = match list {
+            Some(ref items) if items.len() == 1 => Some(&items[0]),
+            _ => None,
+        };
+        if attr.check_name("no_unroll") && attr.is_word() {
+            self.no_unroll = true;
+        } else if attr.check_name("unroll") {
+            match item.and_then(|item| count(item.literal())) {
+                Some(n) => self.unroll_count = Some(n),
+                None => return false,
+            }
+        } else if attr.check_name("pipeline") {
+            match item {
+                Some(item) if item.check_name("disable") && item.is_word() => {
+                    self.pipeline_disable = true;
+                }
+                Some(item) if item.check_name("initiation_interval") => {
+                    let lit = item.meta_item().and_then(|item| item.name_value_literal());
+                    match count(lit) {
+                        Some(n) => self.pipeline_initiation_interval = Some(n),
+                        None => return false,
+                    }
+                }
+                _ => return false,
+            }
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Whether there are no hints at all, e.g. because every attribute of the
+    /// loop was malformed.
+    pub fn is_empty(&self) -> bool {
+        *self == LoopHints::default()
+    }
+
+    /// Whether `#[pipeline]` gave any software-pipelining hint.
+    pub fn has_pipeline_hints(&self) -> bool {
+        self.pipeline_disable || self.pipeline_initiation_interval.is_some()
+    }
+}
+
 /// The `FakeReadCause` describes the type of pattern why a `FakeRead` statement exists.
 #[derive(Copy, Clone, RustcEncodable, RustcDecodable, Debug)]
 pub enum FakeReadCause {
@@ -1858,6 +1947,7 @@ impl<'tcx> Debug for Statement<'tcx> {
             AscribeUserType(ref place, ref variance, ref c_ty) => {
                 write!(fmt, "AscribeUserType({:?}, {:?}, {:?})", place, variance, c_ty)
             }
+            LoopHint(ref hints) => write!(fmt, "LoopHint({:?})", hints),
             Nop => write!(fmt, "nop"),
         }
     }
@@ -2953,6 +3043,7 @@ CloneTypeFoldableAndLiftImpls! {
     SourceInfo,
     UpvarDecl,
     FakeReadCause,
+    LoopHints,
     SourceScope,
     SourceScopeData,
     SourceScopeLocalData,
@@ -3022,6 +3113,7 @@ EnumTypeFoldableImpl! {
         (StatementKind::Retag) { fn_entry, place },
         (StatementKind::EscapeToRaw)(place),
         (StatementKind::AscribeUserType)(a, v, b),
+        (StatementKind::LoopHint)(hints),
         (StatementKind::Nop),
     }
 }
@@ -3085,6 +3177,7 @@ impl<'tcx> TypeFoldable<'tcx> for Terminator<'tcx> {
                 ref destination,
                 cleanup,
                 from_hir_call,
+                inline,
             } => {
                 let dest = destination
                     .as_ref()
@@ -3096,6 +3189,7 @@ impl<'tcx> TypeFoldable<'tcx> for Terminator<'tcx> {
                     destination: dest,
                     cleanup,
                     from_hir_call,
+                    inline,
                 }
             }
             Assert {
@@ -72,6 +72,9 @@ pub struct CodegenUnit<'tcx> {
     /// as well as the crate name and disambiguator.
     name: InternedString,
     items: FxHashMap<MonoItem<'tcx>, (Linkage, Visibility)>,
+    /// Items of this CGU which are emitted as an alias of another, identical
+    /// item of this CGU instead of being codegened themselves.
+    aliases: FxHashMap<MonoItem<'tcx>, MonoItem<'tcx>>,
     size_estimate: Option<usize>,
 }
 
@@ -122,6 +125,7 @@ impl<'tcx> CodegenUnit<'tcx> {
         CodegenUnit {
             name: name,
             items: Default::default(),
+            aliases: Default::default(),
             size_estimate: None,
         }
     }
@@ -144,6 +148,14 @@ impl<'tcx> CodegenUnit<'tcx> {
         &mut self.items
     }
 
+    pub fn aliases(&self) -> &FxHashMap<MonoItem<'tcx>, MonoItem<'tcx>> {
+        &self.aliases
+    }
+
+    pub fn aliases_mut(&mut self) -> &mut FxHashMap<MonoItem<'tcx>, MonoItem<'tcx>> {
+        &mut self.aliases
+    }
+
     pub fn mangle_name(human_readable_name: &str) -> String {
         // We generate a 80 bit hash from the name. This should be enough to
         // avoid collisions and is still reasonably short for filenames.
@@ -179,6 +191,7 @@ impl<'a, 'tcx> HashStable<StableHashingContext<'a>> for CodegenUnit<'tcx> {
                                            hasher: &mut StableHasher<W>) {
         let CodegenUnit {
             ref items,
+            ref aliases,
             name,
             // The size estimate is not relevant to the hash
             size_estimate: _,
@@ -195,6 +208,17 @@ impl<'a, 'tcx> HashStable<StableHashingContext<'a>> for CodegenUnit<'tcx> {
 
         items.sort_unstable_by_key(|i| i.0);
         items.hash_stable(hcx, hasher);
+
+        let mut aliases: Vec<(Fingerprint, Fingerprint)> = aliases.iter().map(|(alias, target)| {
+            let mut alias_hasher = StableHasher::new();
+            alias.hash_stable(hcx, &mut alias_hasher);
+            let mut target_hasher = StableHasher::new();
+            target.hash_stable(hcx, &mut target_hasher);
+            (alias_hasher.finish(), target_hasher.finish())
+        }).collect();
+
+        aliases.sort_unstable();
+        aliases.hash_stable(hcx, hasher);
     }
 }
 
@@ -427,6 +427,7 @@ macro_rules! make_mir_visitor {
                     ) => {
                         self.visit_ascribe_user_ty(place, variance, user_ty, location);
                     }
+                    StatementKind::LoopHint(_) |
                     StatementKind::Nop => {}
                 }
             }
@@ -514,7 +515,8 @@ macro_rules! make_mir_visitor {
                                            ref $($mutability)* args,
                                            ref $($mutability)* destination,
                                            cleanup,
-                                           from_hir_call: _, } => {
+                                           from_hir_call: _,
+                                           inline: _, } => {
                         self.visit_operand(func, source_location);
                         for arg in args {
                             self.visit_operand(arg, source_location);
@@ -103,6 +103,11 @@ pub enum LtoCli {
     Unspecified,
 }
 
+/// A file given on the command line whose contents, not just its path, affect
+/// the output, so that incremental compilation notices when they change.
+#[derive(Clone, PartialEq, Hash)]
+pub struct TrackedFile(pub PathBuf);
+
 #[derive(Clone, PartialEq, Hash)]
 pub enum CrossLangLto {
     LinkerPlugin(PathBuf),
@@ -431,6 +436,7 @@ pub enum PrintRequest {
     TargetList,
     TargetCPUs,
     TargetFeatures,
+    EnabledFeatures,
     RelocationModels,
     CodeModels,
     TlsModels,
@@ -649,9 +655,9 @@ impl Options {
                 match self.optimize {
                     OptLevel::No   |
                     OptLevel::Less |
+                    OptLevel::Default |
                     OptLevel::Size |
                     OptLevel::SizeMin => true,
-                    OptLevel::Default    |
                     OptLevel::Aggressive => false,
                 }
             }
@@ -794,6 +800,7 @@ macro_rules! options {
         pub const parse_pathbuf_push: Option<&'static str> = Some("a path");
         pub const parse_opt_string: Option<&'static str> = Some("a string");
         pub const parse_opt_pathbuf: Option<&'static str> = Some("a path");
+        pub const parse_opt_tracked_file: Option<&'static str> = Some("a path");
         pub const parse_list: Option<&'static str> = Some("a space-separated list of strings");
         pub const parse_opt_list: Option<&'static str> = Some("a space-separated list of strings");
         pub const parse_uint: Option<&'static str> = Some("a number");
@@ -873,6 +880,13 @@ macro_rules! options {
             }
         }
 
+        fn parse_opt_tracked_file(slot: &mut Option<TrackedFile>, v: Option<&str>) -> bool {
+            match v {
+                Some(s) => { *slot = Some(TrackedFile(PathBuf::from(s))); true },
+                None => false,
+            }
+        }
+
         fn parse_string(slot: &mut String, v: Option<&str>) -> bool {
             match v {
                 Some(s) => { *slot = s.to_string(); true },
@@ -1266,6 +1280,11 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "print layout information for each type encountered"),
     print_mono_items: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "print the result of the monomorphization collection pass"),
+    print_unused_monomorphizations: bool = (false, parse_bool, [UNTRACKED],
+        "print the monomorphizations that were codegened but removed by the linker \
+         (needs a final artifact with a symbol table)"),
+    explain_linker_errors: bool = (false, parse_bool, [UNTRACKED],
+        "point at the items behind the symbols a failed link complains about"),
     mir_opt_level: usize = (1, parse_uint, [TRACKED],
         "set the MIR optimization level (0-3, default: 1)"),
     mutable_noalias: Option<bool> = (None, parse_opt_bool, [TRACKED],
@@ -1385,6 +1404,70 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "whether to use the PLT when calling into shared libraries;
           only has effect for PIC code on systems with ELF binaries
           (default: PLT is disabled if full relro is enabled)"),
+    symbol_prefix: Option<String> = (None, parse_opt_string, [TRACKED],
+        "prefix the mangled names of all symbols defined in this crate with the given \
+         string, so that several copies of the crate can be linked into one binary"),
+    deterministic: bool = (false, parse_bool, [TRACKED],
+        "order codegen units by name, ignore host-dependent settings such as \
+         `-C target-cpu=native`, and check that object files are reproducible"),
+    write_import_lib: bool = (false, parse_bool, [UNTRACKED],
+        "write the import library of a windows-msvc `cdylib` from its exported symbols \
+         instead of having the linker produce it"),
+    ub_checks: bool = (false, parse_bool, [TRACKED],
+        "panic instead of causing undefined behavior when the preconditions of \
+         `offset`, `copy_nonoverlapping` and the unchecked arithmetic intrinsics \
+         don't hold"),
+    panic_abort_minimal: bool = (false, parse_bool, [TRACKED],
+        "with `-C panic=abort`, abort at failed assertions and calls to the `panic`, \
+         `panic_bounds_check` and `panic_fmt` lang items instead of calling into the panic \
+         machinery"),
+    atomics_critical_sections: bool = (false, parse_bool, [TRACKED],
+        "implement atomic operations up to the target's `max-atomic-width` by disabling \
+         interrupts around them, for single-core Cortex-M targets"),
+    merge_shims: bool = (false, parse_bool, [TRACKED],
+        "emit drop glue that is internal to a codegen unit and identical to other drop \
+         glue of it as an alias of that, instead of codegening it again"),
+    instrument_allocators: bool = (false, parse_bool, [TRACKED],
+        "route calls to `__rust_alloc` and the other allocator functions through wrappers \
+         that report each allocation, with a call-site ID, to the hook registered \
+         with `std::alloc::set_alloc_event_hook`"),
+    inline_threshold: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "pass `-inline-threshold` to LLVM, overriding the threshold of every inliner it runs, \
+         including the ones for LTO, which `-C inline-threshold` doesn't affect"),
+    count_virtual_calls: bool = (false, parse_bool, [TRACKED],
+        "count how often each call site calls a trait method through a vtable, and print \
+         the counts when the program exits"),
+    ffi_unwind_guards: bool = (false, parse_bool, [TRACKED],
+        "abort instead of unwinding out of functions with a non-Rust ABI, unless they're \
+         marked `#[unwind(allowed)]`"),
+    symbol_ordering_file: Option<TrackedFile> = (None, parse_opt_tracked_file, [TRACKED],
+        "lay out the functions whose symbol names are listed, one per line, in this file \
+         next to each other and, as far as the target's linker allows, in that order"),
+    relro_report: bool = (false, parse_bool, [UNTRACKED],
+        "print the number of dynamic relocations the initializer of each static needs \
+         when compiling position-independent code"),
+    codegen_hash_output: bool = (false, parse_bool, [UNTRACKED],
+        "write a manifest with a content hash of every codegen unit's bitcode before LTO \
+         and of its object file after LTO, next to the crate's other outputs"),
+    offload_device: bool = (false, parse_bool, [TRACKED],
+        "compile only the `#[offload]` items of the crate, and what they use, as device \
+         kernels and data for the current target"),
+    offload_image: Option<TrackedFile> = (None, parse_opt_tracked_file, [TRACKED],
+        "embed this device image, built with `-Z offload-device`, in the host binary next \
+         to the table of `#[offload]` entries"),
+    verify_no_alloc: bool = (false, parse_bool, [TRACKED],
+        "fail compilation if a `#[no_alloc]` function may allocate or format a panic message, \
+         or makes a call whose MIR isn't available to check"),
+    dump_fn_abi: bool = (false, parse_bool, [UNTRACKED],
+        "write how each function instance is called (how every argument and the return \
+         value are passed, their size and alignment) to `<crate>.fn-abi.json`"),
+    object_names: Option<String> = (None, parse_opt_string, [UNTRACKED],
+        "with `--emit=obj`, name the object of each codegen unit after this template, in which \
+         `{crate}`, `{target}`, `{cgu}` and `{index}` are replaced, and list the objects in \
+         `<crate>.objects.json`"),
+    merge_objects: bool = (false, parse_bool, [TRACKED],
+        "with `--emit=obj`, link the optimized codegen units into a single module and write \
+         that as the crate's one relocatable object"),
 }
 
 pub fn default_lib_output() -> CrateType {
@@ -1654,7 +1737,8 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
              print on stdout",
             "[crate-name|file-names|sysroot|cfg|target-list|\
              target-cpus|target-features|relocation-models|\
-             code-models|tls-models|target-spec-json|native-static-libs]",
+             code-models|tls-models|target-spec-json|native-static-libs|\
+             enabled-features]",
         ),
         opt::flagmulti_s("g", "", "Equivalent to -C debuginfo=2"),
         opt::flagmulti_s("O", "", "Equivalent to -C opt-level=2"),
@@ -1944,6 +2028,9 @@ pub fn build_session_options_and_crate_config(
         .iter()
         .map(|ot_path| ot_path.0)
         .filter(|ot| !ot.is_compatible_with_codegen_units_and_single_output_file())
+        // Objects can be merged into one, or named after their codegen unit.
+        .filter(|&&ot| ot != OutputType::Object ||
+                       !(debugging_opts.merge_objects || debugging_opts.object_names.is_some()))
         .map(|ot| ot.shorthand())
         .collect();
     if !incompatible.is_empty() {
@@ -1986,6 +2073,13 @@ pub fn build_session_options_and_crate_config(
         );
     }
 
+    if debugging_opts.merge_objects && debugging_opts.object_names.is_some() {
+        early_error(
+            error_format,
+            "-Z merge-objects and -Z object-names can't be used together",
+        );
+    }
+
     if codegen_units == Some(0) {
         early_error(
             error_format,
@@ -2131,6 +2225,7 @@ pub fn build_session_options_and_crate_config(
                 (Some(name), "framework") => (name, Some(cstore::NativeFramework)),
                 (Some(name), "static") => (name, Some(cstore::NativeStatic)),
                 (Some(name), "static-nobundle") => (name, Some(cstore::NativeStaticNobundle)),
+                (Some(name), "bitcode") => (name, Some(cstore::NativeBitcode)),
                 (_, s) => {
                     early_error(
                         error_format,
@@ -2151,6 +2246,12 @@ pub fn build_session_options_and_crate_config(
                     ),
                 );
             }
+            if kind == Some(cstore::NativeBitcode) && !nightly_options::is_nightly_build() {
+                early_error(
+                    error_format,
+                    "the library kind 'bitcode' is only accepted on the nightly compiler",
+                );
+            }
             let mut name_parts = name.splitn(2, ':');
             let name = name_parts.next().unwrap();
             let new_name = name_parts.next();
@@ -2175,6 +2276,17 @@ pub fn build_session_options_and_crate_config(
         "code-models" => PrintRequest::CodeModels,
         "tls-models" => PrintRequest::TlsModels,
         "native-static-libs" => PrintRequest::NativeStaticLibs,
+        "enabled-features" => {
+            if is_unstable_enabled {
+                PrintRequest::EnabledFeatures
+            } else {
+                early_error(
+                    error_format,
+                    "the `-Z unstable-options` flag must also be passed to \
+                     enable the enabled-features print option",
+                );
+            }
+        }
         "target-spec-json" => {
             if is_unstable_enabled {
                 PrintRequest::TargetSpec
@@ -2397,11 +2509,12 @@ mod dep_tracking {
     use lint;
     use middle::cstore;
     use std::collections::BTreeMap;
+    use std::fs;
     use std::hash::Hash;
     use std::path::PathBuf;
     use std::collections::hash_map::DefaultHasher;
     use super::{CrateType, DebugInfo, ErrorOutputType, OptLevel, OutputTypes,
-                Passes, Sanitizer, LtoCli, CrossLangLto};
+                Passes, Sanitizer, LtoCli, CrossLangLto, TrackedFile};
     use syntax::feature_gate::UnstableFeatures;
     use rustc_target::spec::{PanicStrategy, RelroLevel, TargetTriple};
     use syntax::edition::Edition;
@@ -2467,6 +2580,16 @@ mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(Edition);
     impl_dep_tracking_hash_via_hash!(CrossLangLto);
 
+    impl DepTrackingHash for Option<TrackedFile> {
+        fn hash(&self, hasher: &mut DefaultHasher, _: ErrorOutputType) {
+            Hash::hash(self, hasher);
+            // A file that can't be read is reported when it's used.
+            if let Some(TrackedFile(ref path)) = *self {
+                Hash::hash(&fs::read(path).ok(), hasher);
+            }
+        }
+    }
+
     impl_dep_tracking_hash_for_sortable_vec_of!(String);
     impl_dep_tracking_hash_for_sortable_vec_of!(PathBuf);
     impl_dep_tracking_hash_for_sortable_vec_of!(CrateType);
@@ -2530,7 +2653,7 @@ mod tests {
     use lint;
     use middle::cstore;
     use session::config::{build_configuration, build_session_options_and_crate_config};
-    use session::config::{LtoCli, CrossLangLto};
+    use session::config::{LtoCli, CrossLangLto, TrackedFile};
     use session::build_session;
     use std::collections::{BTreeMap, BTreeSet};
     use std::iter::FromIterator;
@@ -3134,6 +3257,14 @@ mod tests {
         assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
         opts.debugging_opts.dump_mir_graphviz = true;
         assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.codegen_hash_output = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.relro_report = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.object_names = Some(String::from("{cgu}.o"));
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
+        opts.debugging_opts.explain_linker_errors = true;
+        assert_eq!(reference.dep_tracking_hash(), opts.dep_tracking_hash());
 
         // Make sure changing a [TRACKED] option changes the hash
         opts = reference.clone();
@@ -3191,6 +3322,66 @@ mod tests {
         opts = reference.clone();
         opts.debugging_opts.cross_lang_lto = CrossLangLto::LinkerPluginAuto;
         assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.symbol_prefix = Some(String::from("abc"));
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.deterministic = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.ub_checks = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.panic_abort_minimal = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.atomics_critical_sections = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.merge_shims = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.instrument_allocators = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.inline_threshold = Some(10);
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.count_virtual_calls = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.ffi_unwind_guards = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.symbol_ordering_file = Some(TrackedFile(PathBuf::from("order.txt")));
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.offload_device = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.offload_image = Some(TrackedFile(PathBuf::from("device.o")));
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.verify_no_alloc = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
+
+        opts = reference.clone();
+        opts.debugging_opts.merge_objects = true;
+        assert!(reference.dep_tracking_hash() != opts.dep_tracking_hash());
     }
 
     #[test]
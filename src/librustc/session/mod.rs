@@ -47,6 +47,7 @@ use std;
 use std::cell::{self, Cell, RefCell};
 use std::env;
 use std::fmt;
+use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -164,6 +165,9 @@ pub struct Session {
 
     /// Cap lint level specified by a driver specifically.
     pub driver_lint_caps: FxHashMap<lint::LintId, lint::Level>,
+
+    /// The symbol names listed in `-Z symbol-ordering-file`, read on first use.
+    symbol_order: Once<Vec<String>>,
 }
 
 pub struct PerfStats {
@@ -177,6 +181,12 @@ pub struct PerfStats {
     pub normalize_ty_after_erasing_regions: AtomicUsize,
     /// Number of times this query is invoked.
     pub normalize_projection_ty: AtomicUsize,
+    /// Symbols of the upstream monomorphizations codegen linked to instead
+    /// of instantiating them again.
+    pub shared_generics: Lock<FxHashSet<String>>,
+    /// Symbols of the monomorphizations of upstream generic functions
+    /// instantiated in this crate.
+    pub duplicated_generics: Lock<FxHashSet<String>>,
 }
 
 /// Enum to support dispatch of one-time diagnostics (in Session.diag_once)
@@ -207,6 +217,32 @@ impl Session {
         *self.crate_disambiguator.get()
     }
 
+    /// The symbol names listed in `-Z symbol-ordering-file`, in the order the
+    /// functions they name should be laid out in. Blank lines and lines starting
+    /// with `#` are skipped. The file is only read once per session.
+    pub fn symbol_order(&self) -> &[String] {
+        self.symbol_order.init_locking(|| {
+            let path = match self.opts.debugging_opts.symbol_ordering_file {
+                Some(config::TrackedFile(ref path)) => path,
+                None => return Vec::new(),
+            };
+            match fs::read_to_string(path) {
+                Ok(contents) => {
+                    contents.lines()
+                        .map(|line| line.trim())
+                        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                        .map(|line| line.to_string())
+                        .collect()
+                }
+                Err(e) => {
+                    self.fatal(&format!("failed to read symbol ordering file `{}`: {}",
+                                        path.display(), e))
+                }
+            }
+        });
+        self.symbol_order.get()
+    }
+
     pub fn struct_span_warn<'a, S: Into<MultiSpan>>(
         &'a self,
         sp: S,
@@ -857,6 +893,10 @@ impl Session {
                  self.perf_stats.normalize_ty_after_erasing_regions.load(Ordering::Relaxed));
         println!("normalize_projection_ty:                       {}",
                  self.perf_stats.normalize_projection_ty.load(Ordering::Relaxed));
+        println!("Upstream monomorphizations shared:             {}",
+                 self.perf_stats.shared_generics.lock().len());
+        println!("Upstream monomorphizations duplicated:         {}",
+                 self.perf_stats.duplicated_generics.lock().len());
     }
 
     /// We want to know if we're allowed to do an optimization for crate foo from -z fuel=foo=n.
@@ -1176,6 +1216,8 @@ pub fn build_session_(
             queries_canonicalized: AtomicUsize::new(0),
             normalize_ty_after_erasing_regions: AtomicUsize::new(0),
             normalize_projection_ty: AtomicUsize::new(0),
+            shared_generics: Lock::new(FxHashSet::default()),
+            duplicated_generics: Lock::new(FxHashSet::default()),
         },
         code_stats: Default::default(),
         optimization_fuel_crate,
@@ -1212,6 +1254,7 @@ pub fn build_session_(
         has_global_allocator: Once::new(),
         has_panic_handler: Once::new(),
         driver_lint_caps: Default::default(),
+        symbol_order: Once::new(),
     };
 
     validate_commandline_args_with_session_available(&sess);
@@ -1249,6 +1292,15 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         sess.err("Linker plugin based LTO is not supported together with \
                   `-C prefer-dynamic` when targeting MSVC");
     }
+
+    // The CPU detected on the host running the compiler is the one piece of
+    // codegen configuration that differs between otherwise identical builds,
+    // so don't honor it when asked for reproducible output.
+    if sess.opts.debugging_opts.deterministic &&
+       sess.opts.cg.target_cpu.as_ref().map(|s| &s[..]) == Some("native") {
+        sess.warn("`-C target-cpu=native` is host-dependent and is ignored with \
+                   `-Z deterministic`; using the target's default CPU instead");
+    }
 }
 
 /// Hash value constructed out of all the `-C metadata` arguments passed to the
@@ -1994,7 +1994,18 @@ impl<'cx, 'gcx, 'tcx> SelectionContext<'cx, 'gcx, 'tcx> {
                     ..
                 } = self_ty.fn_sig(self.tcx()).skip_binder()
                 {
-                    candidates.vec.push(FnPointerCandidate);
+                    // Safe `#[target_feature]` functions can only be called
+                    // where their features are known to be available, which
+                    // the `Fn` traits can't express.
+                    let has_target_features = match self_ty.sty {
+                        ty::FnDef(def_id, _) => {
+                            !self.tcx().codegen_fn_attrs(def_id).target_features.is_empty()
+                        }
+                        _ => false,
+                    };
+                    if !has_target_features {
+                        candidates.vec.push(FnPointerCandidate);
+                    }
                 }
             }
             _ => {}
@@ -49,6 +49,10 @@ impl<'a, 'tcx> TyCtxt<'a, 'tcx, 'tcx> {
                     | "size_of"
                     | "min_align_of"
                     | "needs_drop"
+                    | "has_niche"
+                    | "niche_offset"
+                    | "niche_range_start"
+                    | "niche_range_end"
                     => return true,
                     _ => {},
                 }
@@ -10,6 +10,7 @@
 
 use hir::Unsafety;
 use hir::def_id::DefId;
+use mir::TerminatorKind;
 use ty::{self, Ty, PolyFnSig, TypeFoldable, Substs, TyCtxt};
 use traits;
 use rustc_target::spec::abi::Abi;
@@ -160,6 +161,40 @@ impl<'tcx> InstanceDef<'tcx> {
         }
     }
 
+    /// Whether this is drop glue that only drops a few fields one after the
+    /// other, possibly after calling `Drop::drop`, like the glue of a struct
+    /// of two `Vec`s. Such glue gets an inline hint so that code using a lot
+    /// of containers doesn't pay for an out-of-line call per type. Glue that
+    /// switches on enum variants or loops over elements stays out of line.
+    /// Unlike `is_inline` items, it is still only instantiated once.
+    pub fn is_small_drop_glue<'a>(
+        &self,
+        tcx: TyCtxt<'a, 'tcx, 'tcx>
+    ) -> bool {
+        const MAX_INLINE_DROP_GLUE_CALLS: usize = 4;
+
+        match *self {
+            ty::InstanceDef::DropGlue(_, Some(_)) => {}
+            _ => return false,
+        }
+        let mir = tcx.instance_mir(*self);
+        let mut calls = 0;
+        for block in mir.basic_blocks() {
+            // Unwinding only repeats the drops of the remaining fields.
+            if block.is_cleanup {
+                continue;
+            }
+            match block.terminator().kind {
+                TerminatorKind::Goto { .. } |
+                TerminatorKind::Return => {}
+                TerminatorKind::Drop { .. } |
+                TerminatorKind::Call { .. } => calls += 1,
+                _ => return false,
+            }
+        }
+        calls <= MAX_INLINE_DROP_GLUE_CALLS
+    }
+
     pub fn requires_local<'a>(
         &self,
         tcx: TyCtxt<'a, 'tcx, 'tcx>
@@ -234,7 +234,8 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                 variants: Variants::Single { index: VariantIdx::new(0) },
                 fields: FieldPlacement::Arbitrary {
                     offsets: vec![Size::ZERO, b_offset],
-                    memory_index: vec![0, 1]
+                    memory_index: vec![0, 1],
+                    bit_fields: vec![]
                 },
                 abi: Abi::ScalarPair(a, b),
                 align,
@@ -252,6 +253,24 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
             Prefixed(Size, Align),
         }
 
+        // Scalable vectors have a size that is only known at runtime, so they
+        // can't be given an offset inside of another type. Typeck rejects them
+        // in the fields of ADTs already; this catches the rest, like generic
+        // ADTs instantiated with one, and closures capturing one.
+        let reject_scalable_field = |field: &TyLayout<'_>| {
+            if field.ty.is_scalable_simd() {
+                let msg = format!("scalable vector type `{}` can't be stored in `{}`, \
+                                   only passed by value or used as a local",
+                                  field.ty, ty);
+                match ty.sty {
+                    ty::Adt(def, _) => tcx.sess.span_fatal(tcx.def_span(def.did), &msg),
+                    ty::Closure(def_id, _) |
+                    ty::Generator(def_id, ..) => tcx.sess.span_fatal(tcx.def_span(def_id), &msg),
+                    _ => tcx.sess.fatal(&msg),
+                }
+            }
+        };
+
         let univariant_uninterned = |fields: &[TyLayout<'_>], repr: &ReprOptions, kind| {
             let packed = repr.packed();
             if packed && repr.align > 0 {
@@ -321,6 +340,7 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
 
             for &i in &inverse_memory_index {
                 let field = fields[i as usize];
+                reject_scalable_field(&field);
                 if !sized {
                     bug!("univariant: field #{} of `{}` comes after unsized field",
                          offsets.len(), ty);
@@ -424,7 +444,8 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                             let pair_offsets = match pair.fields {
                                 FieldPlacement::Arbitrary {
                                     ref offsets,
-                                    ref memory_index
+                                    ref memory_index,
+                                    ..
                                 } => {
                                     assert_eq!(memory_index, &[0, 1]);
                                     offsets
@@ -454,7 +475,8 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                 variants: Variants::Single { index: VariantIdx::new(0) },
                 fields: FieldPlacement::Arbitrary {
                     offsets,
-                    memory_index
+                    memory_index,
+                    bit_fields: vec![]
                 },
                 abi,
                 align,
@@ -464,6 +486,96 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
         let univariant = |fields: &[TyLayout<'_>], repr: &ReprOptions, kind| {
             Ok(tcx.intern_layout(univariant_uninterned(fields, repr, kind)?))
         };
+
+        // `#[repr(C)]` structs with bit-fields are laid out the way the
+        // target's C compiler lays them out: with the Microsoft rules on MSVC
+        // targets, and with the System V ones everywhere else.
+        let bit_field_univariant = |fields: &[TyLayout<'_>],
+                                    widths: &[Option<u64>],
+                                    repr: &ReprOptions| {
+            let msvc = tcx.sess.target.target.options.is_like_msvc;
+            let overflow = || LayoutError::SizeOverflow(ty);
+
+            let mut align = dl.aggregate_align;
+            let mut offsets = Vec::with_capacity(fields.len());
+            let mut bit_fields = Vec::with_capacity(fields.len());
+            // The first bit not taken by the fields so far.
+            let mut next_bit = 0u64;
+            // On MSVC, where the storage unit of the bit-fields just before
+            // starts and how many bits it has. Bit-fields are placed in it
+            // while they're of the same size and fit, and it's taken in full.
+            let mut unit: Option<(u64, u64)> = None;
+
+            for (field, &width) in fields.iter().zip(widths) {
+                let end = unit.map_or(next_bit, |(start, bits)| start + bits);
+                let field_bits = field.size.bits();
+                let align_bits = field.align.abi.bits();
+                align = align.max(field.align);
+
+                let width = match width {
+                    Some(width) => width,
+                    None => {
+                        unit = None;
+                        let offset = Size::from_bits(end).align_to(field.align.abi);
+                        offsets.push(offset);
+                        bit_fields.push(None);
+                        next_bit = offset.checked_add(field.size, dl).ok_or_else(overflow)?.bits();
+                        continue;
+                    }
+                };
+
+                let start = if msvc {
+                    match unit {
+                        Some((start, bits)) if bits == field_bits &&
+                                               next_bit + width <= start + bits => next_bit,
+                        _ => {
+                            let start = Size::from_bits(end).align_to(field.align.abi).bits();
+                            unit = Some((start, field_bits));
+                            start
+                        }
+                    }
+                } else {
+                    // A bit-field never crosses the end of the aligned unit
+                    // the size of its type that it starts in.
+                    let unit_start = next_bit - next_bit % align_bits;
+                    if next_bit + width > unit_start + field_bits {
+                        Size::from_bits(next_bit).align_to(field.align.abi).bits()
+                    } else {
+                        next_bit
+                    }
+                };
+                next_bit = start.checked_add(width).ok_or_else(overflow)?;
+
+                let offset = Size::from_bytes(start / 8);
+                let storage = Size::from_bits(next_bit) - offset;
+                let first_bit = start - offset.bits();
+                let shift = match dl.endian {
+                    Endian::Little => first_bit,
+                    Endian::Big => storage.bits() - first_bit - width,
+                };
+                offsets.push(offset);
+                bit_fields.push(Some(BitField { storage, shift, width }));
+            }
+
+            if repr.align > 0 {
+                let repr_align = repr.align as u64;
+                align = align.max(AbiAndPrefAlign::new(Align::from_bytes(repr_align).unwrap()));
+            }
+            let end = unit.map_or(next_bit, |(start, bits)| start + bits);
+            let sized = fields.iter().all(|field| !field.is_unsized());
+
+            Ok(LayoutDetails {
+                variants: Variants::Single { index: VariantIdx::new(0) },
+                fields: FieldPlacement::Arbitrary {
+                    offsets,
+                    memory_index: (0..fields.len() as u32).collect(),
+                    bit_fields,
+                },
+                abi: Abi::Aggregate { sized },
+                align,
+                size: Size::from_bits(end).align_to(align.abi),
+            })
+        };
         debug_assert!(!ty.has_infer_types());
 
         Ok(match ty.sty {
@@ -547,6 +659,7 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                 }
 
                 let element = self.layout_of(element)?;
+                reject_scalable_field(&element);
                 let count = count.unwrap_usize(tcx);
                 let size = element.size.checked_mul(count, dl)
                     .ok_or(LayoutError::SizeOverflow(ty))?;
@@ -641,6 +754,9 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                                                 ty, element.ty));
                     }
                 };
+                // For scalable vectors `count` is the minimum number of lanes
+                // (`vscale == 1`); the size recorded here, which is also what
+                // `size_of` returns, is only a lower bound.
                 let size = element.size.checked_mul(count, dl)
                     .ok_or(LayoutError::SizeOverflow(ty))?;
                 let align = dl.vector_align(size);
@@ -696,6 +812,7 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                     let index = VariantIdx::new(0);
                     for field in &variants[index] {
                         assert!(!field.is_unsized());
+                        reject_scalable_field(field);
 
                         let field_align = if packed {
                             field.align.min(AbiAndPrefAlign::new(pack))
@@ -792,7 +909,18 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                         else { StructKind::AlwaysSized }
                     };
 
-                    let mut st = univariant_uninterned(&variants[v], &def.repr, kind)?;
+                    let bit_widths = if def.is_struct() && def.repr.c() {
+                        def.non_enum_variant().fields.iter()
+                            .map(|field| field.bit_width(tcx))
+                            .collect()
+                    } else {
+                        vec![]
+                    };
+                    let mut st = if bit_widths.iter().any(|width| width.is_some()) {
+                        bit_field_univariant(&variants[v], &bit_widths, &def.repr)?
+                    } else {
+                        univariant_uninterned(&variants[v], &def.repr, kind)?
+                    };
                     st.variants = Variants::Single { index: v };
                     let (start, end) = self.tcx.layout_scalar_valid_range(def.did);
                     match st.abi {
@@ -818,6 +946,27 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                             st,
                         ),
                     }
+                    if let Some((low_bits, high_bits)) = def.pointer_niche(tcx) {
+                        // Only ever narrow the valid range, and leave anything
+                        // that isn't a suitable scalar to be reported by typeck.
+                        if let Abi::Scalar(ref mut scalar) = st.abi {
+                            let bits = scalar.value.size(dl).bits() as u32;
+                            let (start, end) =
+                                (*scalar.valid_range.start(), *scalar.valid_range.end());
+                            if low_bits + high_bits < bits && start <= end {
+                                let max_value = !0u128 >> (128 - bits);
+                                let start = if low_bits > 0 {
+                                    cmp::max(start, 1 << low_bits)
+                                } else {
+                                    start
+                                };
+                                let end = cmp::min(end, max_value >> high_bits);
+                                if start <= end {
+                                    scalar.valid_range = start..=end;
+                                }
+                            }
+                        }
+                    }
                     return Ok(tcx.intern_layout(st));
                 }
 
@@ -920,7 +1069,8 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                                 },
                                 fields: FieldPlacement::Arbitrary {
                                     offsets: vec![offset],
-                                    memory_index: vec![0]
+                                    memory_index: vec![0],
+                                    bit_fields: vec![]
                                 },
                                 abi,
                                 size,
@@ -1112,7 +1262,8 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                         let pair_offsets = match pair.fields {
                             FieldPlacement::Arbitrary {
                                 ref offsets,
-                                ref memory_index
+                                ref memory_index,
+                                ..
                             } => {
                                 assert_eq!(memory_index, &[0, 1]);
                                 offsets
@@ -1141,7 +1292,8 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
                     },
                     fields: FieldPlacement::Arbitrary {
                         offsets: vec![Size::ZERO],
-                        memory_index: vec![0]
+                        memory_index: vec![0],
+                        bit_fields: vec![]
                     },
                     abi,
                     align,
@@ -1409,7 +1561,9 @@ impl<'a, 'tcx> SizeSkeleton<'tcx> {
                                 (Bound::Included(start), Bound::Included(end)) =>
                                     0 < start && start < end,
                                 _ => false,
-                            },
+                            } || def.pointer_niche(tcx).map_or(false, |(low_bits, _)| {
+                                low_bits > 0
+                            }),
                             tail,
                         });
                     } else {
@@ -1578,6 +1732,19 @@ impl TyCtxt<'a, 'tcx, '_> {
         };
         cx.layout_of(param_env_and_ty.value)
     }
+
+    /// Finds the niche that enum layout would use in a type, if it has one,
+    /// as its offset and the scalar there, whose `valid_range` is what the
+    /// type itself can hold.
+    pub fn layout_niche(self, param_env_and_ty: ty::ParamEnvAnd<'tcx, Ty<'tcx>>)
+                        -> Result<Option<(Size, Scalar)>, LayoutError<'tcx>> {
+        let cx = LayoutCx {
+            tcx: self.global_tcx(),
+            param_env: param_env_and_ty.param_env
+        };
+        let layout = cx.layout_of(param_env_and_ty.value)?;
+        Ok(cx.find_niche(layout)?.map(|niche| (niche.offset, niche.scalar)))
+    }
 }
 
 impl ty::query::TyCtxtAt<'a, 'tcx, '_> {
@@ -1850,6 +2017,11 @@ impl<'a, 'tcx> LayoutCx<'tcx, TyCtxt<'a, 'tcx, 'tcx>> {
         let mut niche = None;
         let mut available = 0;
         for i in 0..layout.fields.count() {
+            // The bytes of a bit-field are shared with its neighbours and only
+            // its low bits are valid, so they can't hold a niche.
+            if layout.fields.bit_field(i).is_some() {
+                continue;
+            }
             if let Some(mut c) = self.find_niche(layout.field(self, i)?)? {
                 if c.available > available {
                     available = c.available;
@@ -1913,9 +2085,10 @@ impl<'a> HashStable<StableHashingContext<'a>> for FieldPlacement {
                 count.hash_stable(hcx, hasher);
                 stride.hash_stable(hcx, hasher);
             }
-            Arbitrary { ref offsets, ref memory_index } => {
+            Arbitrary { ref offsets, ref memory_index, ref bit_fields } => {
                 offsets.hash_stable(hcx, hasher);
                 memory_index.hash_stable(hcx, hasher);
+                bit_fields.hash_stable(hcx, hasher);
             }
         }
     }
@@ -1996,6 +2169,12 @@ impl_stable_hash_for!(struct ::ty::layout::AbiAndPrefAlign {
     pref
 });
 
+impl_stable_hash_for!(struct ::ty::layout::BitField {
+    storage,
+    shift,
+    width
+});
+
 impl<'gcx> HashStable<StableHashingContext<'gcx>> for Align {
     fn hash_stable<W: StableHasherResult>(&self,
                                           hcx: &mut StableHashingContext<'gcx>,
@@ -1963,6 +1963,9 @@ bitflags! {
         const IS_TRANSPARENT     = 1 << 2;
         // Internal only for now. If true, don't reorder fields.
         const IS_LINEAR          = 1 << 3;
+        // Internal only for now. A `repr(simd)` type whose length is a
+        // multiple of a hardware-determined `vscale` (SVE, RISC-V V).
+        const IS_SCALABLE        = 1 << 4;
 
         // Any of these flags being set prevent field reordering optimisation.
         const IS_UNOPTIMISABLE   = ReprFlags::IS_C.bits |
@@ -2025,6 +2028,10 @@ impl ReprOptions {
             }
         }
 
+        if flags.contains(ReprFlags::IS_SIMD) && tcx.has_attr(did, "rustc_scalable_vector") {
+            flags.insert(ReprFlags::IS_SCALABLE);
+        }
+
         // This is here instead of layout because the choice must make it into metadata.
         if !tcx.consider_optimizing(|| format!("Reorder fields of {:?}", tcx.item_path_str(did))) {
             flags.insert(ReprFlags::IS_LINEAR);
@@ -2035,6 +2042,8 @@ impl ReprOptions {
     #[inline]
     pub fn simd(&self) -> bool { self.flags.contains(ReprFlags::IS_SIMD) }
     #[inline]
+    pub fn scalable(&self) -> bool { self.flags.contains(ReprFlags::IS_SCALABLE) }
+    #[inline]
     pub fn c(&self) -> bool { self.flags.contains(ReprFlags::IS_C) }
     #[inline]
     pub fn packed(&self) -> bool { self.pack > 0 }
@@ -2190,6 +2199,39 @@ impl<'a, 'gcx, 'tcx> AdtDef {
         self.destructor(tcx).is_some()
     }
 
+    /// The `(N, M)` of `#[pointer_niche(low_bits = N, high_bits = M)]`, which
+    /// promises that the `M` highest bits of the value of this struct are
+    /// always zero and, unless `N` is `0`, that the value is at least `1 << N`
+    /// (like a non-null pointer aligned to `1 << N`). Either defaults to `0`.
+    pub fn pointer_niche(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>) -> Option<(u32, u32)> {
+        let attr = attr::find_by_name(&tcx.get_attrs(self.did), "pointer_niche")?;
+        let (mut low_bits, mut high_bits) = (0, 0);
+        for item in attr.meta_item_list()? {
+            let meta = item.meta_item()?;
+            let bits = match meta.name_value_literal()?.node {
+                ast::LitKind::Int(bits, _) if bits <= 128 => bits as u32,
+                _ => return None,
+            };
+            if meta.name() == "low_bits" {
+                low_bits = bits;
+            } else if meta.name() == "high_bits" {
+                high_bits = bits;
+            } else {
+                return None;
+            }
+        }
+        Some((low_bits, high_bits))
+    }
+
+    /// The `N` of `#[address_space = "N"]`, the LLVM address space statics of
+    /// this type are placed in unless they have their own `#[address_space]`.
+    pub fn address_space(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>) -> Option<u32> {
+        attr::first_attr_value_str_by_name(&tcx.get_attrs(self.did), "address_space")?
+            .as_str()
+            .parse()
+            .ok()
+    }
+
     /// Asserts this is a struct or union and returns its unique variant.
     pub fn non_enum_variant(&self) -> &VariantDef {
         assert!(self.is_struct() || self.is_union());
@@ -2464,6 +2506,15 @@ impl<'a, 'gcx, 'tcx> FieldDef {
     pub fn ty(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>, subst: &Substs<'tcx>) -> Ty<'tcx> {
         tcx.type_of(self.did).subst(tcx, subst)
     }
+
+    /// The `N` of `#[bits(N)]`, which makes this a bit-field `N` bits wide.
+    pub fn bit_width(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>) -> Option<u64> {
+        let attr = attr::find_by_name(&tcx.get_attrs(self.did), "bits")?;
+        match attr.meta_item_list()?.first()?.literal()?.node {
+            ast::LitKind::Int(width, _) => Some(width as u64),
+            _ => None,
+        }
+    }
 }
 
 /// Represents the various closure traits in the Rust language. This
@@ -3154,6 +3205,12 @@ fn original_crate_name<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     tcx.crate_name.clone()
 }
 
+fn symbol_prefix<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                           crate_num: CrateNum) -> Option<Symbol> {
+    assert_eq!(crate_num, LOCAL_CRATE);
+    tcx.sess.opts.debugging_opts.symbol_prefix.as_ref().map(|prefix| Symbol::intern(prefix))
+}
+
 fn crate_hash<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                         crate_num: CrateNum)
                         -> Svh {
@@ -3190,6 +3247,7 @@ pub fn provide(providers: &mut ty::query::Providers<'_>) {
         trait_of_item,
         crate_disambiguator,
         original_crate_name,
+        symbol_prefix,
         crate_hash,
         trait_impls_of: trait_def::trait_impls_of_provider,
         instance_def_size_estimate,
@@ -621,6 +621,12 @@ impl<'tcx> QueryDescription<'tcx> for queries::extra_filename<'tcx> {
     }
 }
 
+impl<'tcx> QueryDescription<'tcx> for queries::symbol_prefix<'tcx> {
+    fn describe(_tcx: TyCtxt<'_, '_, '_>, _: CrateNum) -> Cow<'static, str> {
+        "looking up the symbol prefix for a crate".into()
+    }
+}
+
 impl<'tcx> QueryDescription<'tcx> for queries::implementations_of_trait<'tcx> {
     fn describe(_tcx: TyCtxt<'_, '_, '_>, _: (CrateNum, DefId)) -> Cow<'static, str> {
         "looking up implementations of a trait in a crate".into()
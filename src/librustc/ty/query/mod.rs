@@ -475,6 +475,7 @@ define_queries! { <'tcx>
         [] fn crate_hash: CrateHash(CrateNum) -> Svh,
         [] fn original_crate_name: OriginalCrateName(CrateNum) -> Symbol,
         [] fn extra_filename: ExtraFileName(CrateNum) -> String,
+        [] fn symbol_prefix: SymbolPrefix(CrateNum) -> Option<Symbol>,
     },
 
     TypeChecking {
@@ -1604,6 +1604,16 @@ impl<'a, 'gcx, 'tcx> TyS<'tcx> {
         }
     }
 
+    /// Returns `true` for `repr(simd)` types marked `#[rustc_scalable_vector]`,
+    /// whose actual length is only known at runtime. Their layout, and so
+    /// `size_of`, describes the minimum length.
+    pub fn is_scalable_simd(&self) -> bool {
+        match self.sty {
+            Adt(def, _) => def.repr.simd() && def.repr.scalable(),
+            _ => false,
+        }
+    }
+
     pub fn sequence_element_type(&self, tcx: TyCtxt<'a, 'gcx, 'tcx>) -> Ty<'tcx> {
         match self.sty {
             Array(ty, _) | Slice(ty) => ty,
@@ -235,9 +235,39 @@ impl<'tcx> ty::ParamEnv<'tcx> {
 }
 
 impl<'a, 'tcx> TyCtxt<'a, 'tcx, 'tcx> {
+    /// If `ty` is an `extern type` implementing the `ExternSize` lang item,
+    /// returns the instances of its `size_of_val` and `align_of_val` methods,
+    /// which the `size_of_val` and `min_align_of_val` intrinsics call.
+    pub fn extern_size_methods(self, ty: Ty<'tcx>)
+                               -> Option<(ty::Instance<'tcx>, ty::Instance<'tcx>)> {
+        match ty.sty {
+            Foreign(_) => {}
+            _ => return None,
+        }
+        let trait_def_id = self.lang_items().extern_size_trait()?;
+        let param_env = ty::ParamEnv::reveal_all();
+        let implemented = self.infer_ctxt().enter(|infcx| {
+            traits::type_known_to_meet_bound(&infcx, param_env, ty, trait_def_id, DUMMY_SP)
+        });
+        if !implemented {
+            return None;
+        }
+
+        let substs = self.mk_substs_trait(ty, &[]);
+        let method = |name: &str| {
+            let def_id = self.associated_items(trait_def_id)
+                .find(|item| item.kind == ty::AssociatedKind::Method && item.ident.name == name)
+                .unwrap_or_else(|| bug!("`extern_size` lang item has no method `{}`", name))
+                .def_id;
+            ty::Instance::resolve(self, param_env, def_id, substs).unwrap()
+        };
+        Some((method("size_of_val"), method("align_of_val")))
+    }
+
     /// Creates a hash of the type `Ty` which will be the same no matter what crate
-    /// context it's calculated within. This is used by the `type_id` intrinsic.
-    pub fn type_id_hash(self, ty: Ty<'tcx>) -> u64 {
+    /// context it's calculated within. This is used by the `type_id128` intrinsic,
+    /// while the `type_id` intrinsic only returns the lower 64 bits of it.
+    pub fn type_id_hash(self, ty: Ty<'tcx>) -> u128 {
         let mut hasher = StableHasher::new();
         let mut hcx = self.create_stable_hashing_context();
 
@@ -22,15 +22,36 @@ use rustc_target::abi::call::ArgType;
 use rustc_codegen_ssa::traits::*;
 
 use rustc_target::abi::{HasDataLayout, LayoutOf, Size, TyLayout, Abi as LayoutAbi};
+use rustc::hir::def_id::DefId;
 use rustc::ty::{self, Ty, Instance};
 use rustc::ty::layout;
 
 use libc::c_uint;
+use syntax::symbol::Symbol;
 
 pub use rustc_target::spec::abi::Abi;
 pub use rustc::ty::layout::{FAT_PTR_ADDR, FAT_PTR_EXTRA};
 pub use rustc_target::abi::call::*;
 
+/// Whether a vector of `size` fits in a single register of the baseline
+/// features of the target spec. Only the spec is consulted: `-C target-feature`
+/// and `-C target-cpu` may differ between crates linked together.
+fn fits_target_vector_register(cx: &CodegenCx<'ll, '_>, size: Size) -> bool {
+    let target = &cx.sess().target.target;
+    let has = |feature: &str| {
+        target.options.features.split(',').any(|f| f == &format!("+{}", feature)[..])
+    };
+    let max_bits = match &*target.arch {
+        "x86" | "x86_64" if has("avx512f") => 512,
+        "x86" | "x86_64" if has("avx") => 256,
+        "x86_64" => 128,
+        "x86" if has("sse2") => 128,
+        _ => return false,
+    };
+    // Smaller vectors may be `x86_mmx`, see `uncached_llvm_type`.
+    size.bits() >= 128 && size.bits() <= max_bits
+}
+
 macro_rules! for_each_kind {
     ($flags: ident, $f: ident, $($kind: ident),+) => ({
         $(if $flags.contains(ArgAttribute::$kind) { $f(llvm::Attribute::$kind) })+
@@ -303,6 +324,21 @@ impl ArgTypeMethods<'tcx> for Builder<'a, 'll, 'tcx> {
     }
 }
 
+/// The target features enabled for the whole crate and, if it's given, by
+/// `#[target_feature]` on the function `def_id`.
+fn enabled_target_features(cx: &CodegenCx<'ll, 'tcx>, def_id: Option<DefId>) -> Vec<String> {
+    let target_feature = Symbol::intern("target_feature");
+    let mut features: Vec<_> = cx.sess().parse_sess.config.iter()
+        .filter(|&&(name, _)| name == target_feature)
+        .filter_map(|&(_, value)| value.map(|feature| format!("+{}", feature)))
+        .collect();
+    if let Some(def_id) = def_id {
+        features.extend(cx.tcx.codegen_fn_attrs(def_id).target_features.iter()
+            .map(|feature| format!("+{}", feature)));
+    }
+    features
+}
+
 pub trait FnTypeExt<'tcx> {
     fn of_instance(cx: &CodegenCx<'ll, 'tcx>, instance: &ty::Instance<'tcx>) -> Self;
     fn new(cx: &CodegenCx<'ll, 'tcx>,
@@ -315,6 +351,7 @@ pub trait FnTypeExt<'tcx> {
         cx: &CodegenCx<'ll, 'tcx>,
         sig: ty::FnSig<'tcx>,
         extra_args: &[Ty<'tcx>],
+        target_features: Vec<String>,
         mk_arg_type: impl Fn(Ty<'tcx>, Option<usize>) -> ArgType<'tcx, Ty<'tcx>>,
     ) -> Self;
     fn adjust_for_abi(&mut self,
@@ -331,13 +368,17 @@ impl<'tcx> FnTypeExt<'tcx> for FnType<'tcx, Ty<'tcx>> {
     fn of_instance(cx: &CodegenCx<'ll, 'tcx>, instance: &ty::Instance<'tcx>) -> Self {
         let sig = instance.fn_sig(cx.tcx);
         let sig = cx.tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), &sig);
-        FnType::new(cx, sig, &[])
+        let target_features = enabled_target_features(cx, Some(instance.def_id()));
+        FnType::new_internal(cx, sig, &[], target_features, |ty, _| {
+            ArgType::new(cx.layout_of(ty))
+        })
     }
 
     fn new(cx: &CodegenCx<'ll, 'tcx>,
            sig: ty::FnSig<'tcx>,
            extra_args: &[Ty<'tcx>]) -> Self {
-        FnType::new_internal(cx, sig, extra_args, |ty, _| {
+        let target_features = enabled_target_features(cx, None);
+        FnType::new_internal(cx, sig, extra_args, target_features, |ty, _| {
             ArgType::new(cx.layout_of(ty))
         })
     }
@@ -345,7 +386,8 @@ impl<'tcx> FnTypeExt<'tcx> for FnType<'tcx, Ty<'tcx>> {
     fn new_vtable(cx: &CodegenCx<'ll, 'tcx>,
                   sig: ty::FnSig<'tcx>,
                   extra_args: &[Ty<'tcx>]) -> Self {
-        FnType::new_internal(cx, sig, extra_args, |ty, arg_idx| {
+        let target_features = enabled_target_features(cx, None);
+        FnType::new_internal(cx, sig, extra_args, target_features, |ty, arg_idx| {
             let mut layout = cx.layout_of(ty);
             // Don't pass the vtable, it's not an argument of the virtual fn.
             // Instead, pass just the data pointer, but give it the type `*const/mut dyn Trait`
@@ -400,6 +442,7 @@ impl<'tcx> FnTypeExt<'tcx> for FnType<'tcx, Ty<'tcx>> {
         cx: &CodegenCx<'ll, 'tcx>,
         sig: ty::FnSig<'tcx>,
         extra_args: &[Ty<'tcx>],
+        target_features: Vec<String>,
         mk_arg_type: impl Fn(Ty<'tcx>, Option<usize>) -> ArgType<'tcx, Ty<'tcx>>,
     ) -> Self {
         debug!("FnType::new_internal({:?}, {:?})", sig, extra_args);
@@ -572,6 +615,7 @@ impl<'tcx> FnTypeExt<'tcx> for FnType<'tcx, Ty<'tcx>> {
             }).collect(),
             variadic: sig.variadic,
             conv,
+            target_features,
         };
         fn_ty.adjust_for_abi(cx, sig.abi);
         fn_ty
@@ -587,6 +631,10 @@ impl<'tcx> FnTypeExt<'tcx> for FnType<'tcx, Ty<'tcx>> {
             let fixup = |arg: &mut ArgType<'tcx, Ty<'tcx>>| {
                 if arg.is_ignore() { return; }
 
+                // Scalable vectors have no size known at compile time, so they
+                // can't be spilled to memory and are always passed directly.
+                if arg.layout.ty.is_scalable_simd() { return; }
+
                 match arg.layout.abi {
                     layout::Abi::Aggregate { .. } => {}
 
@@ -609,9 +657,15 @@ impl<'tcx> FnTypeExt<'tcx> for FnType<'tcx, Ty<'tcx>> {
                     // Note that the platform intrinsic ABI is exempt here as
                     // that's how we connect up to LLVM and it's unstable
                     // anyway, we control all calls to it in libstd.
+                    //
+                    // Vectors that fit in the registers of the baseline
+                    // features of the target spec are exempt too: every crate
+                    // built for the target, including the prebuilt standard
+                    // library, enables at least those, so they all agree.
                     layout::Abi::Vector { .. }
                         if abi != Abi::PlatformIntrinsic &&
-                            cx.sess().target.target.options.simd_types_indirect =>
+                            cx.sess().target.target.options.simd_types_indirect &&
+                            !fits_target_vector_register(cx, arg.layout.size) =>
                     {
                         arg.make_indirect();
                         return
@@ -646,6 +700,14 @@ impl<'tcx> FnTypeExt<'tcx> for FnType<'tcx, Ty<'tcx>> {
         if let Err(msg) = self.adjust_for_cabi(cx, abi) {
             cx.sess().fatal(&msg);
         }
+
+        // The C ABIs only know about fixed-length vectors; scalable vectors
+        // are passed in (and returned from) their own registers.
+        for arg in Some(&mut self.ret).into_iter().chain(self.args.iter_mut()) {
+            if !arg.is_ignore() && arg.layout.ty.is_scalable_simd() {
+                arg.mode = PassMode::Direct(ArgAttributes::new());
+            }
+        }
     }
 
     fn llvm_type(&self, cx: &CodegenCx<'ll, 'tcx>) -> &'ll Type {
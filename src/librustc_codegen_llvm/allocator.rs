@@ -15,9 +15,22 @@ use libc::c_uint;
 use rustc::middle::allocator::AllocatorKind;
 use rustc::ty::TyCtxt;
 use rustc_allocator::{ALLOCATOR_METHODS, AllocatorTy};
+use rustc_codegen_ssa::common::{AtomicOrdering, IntPredicate, TypeKind};
+use rustc_codegen_ssa::traits::*;
 
 use ModuleLlvm;
+use builder::Builder;
+use context::CodegenCx;
 use llvm::{self, False, True};
+use value::Value;
+
+/// The static in liballoc holding the hook registered with
+/// `alloc::alloc::set_alloc_event_hook`, or null.
+const ALLOC_EVENT_HOOK: &str = "__rust_alloc_event_hook";
+/// The `alloc::alloc::AllocEvent` the hook is passed for a new allocation.
+const ALLOC_EVENT_ALLOC: u64 = 0;
+/// The `alloc::alloc::AllocEvent` the hook is passed for a freed allocation.
+const ALLOC_EVENT_DEALLOC: u64 = 1;
 
 pub(crate) unsafe fn codegen(tcx: TyCtxt, mods: &ModuleLlvm, kind: AllocatorKind) {
     let llcx = &*mods.llcx;
@@ -101,3 +114,88 @@ pub(crate) unsafe fn codegen(tcx: TyCtxt, mods: &ModuleLlvm, kind: AllocatorKind
         llvm::LLVMDisposeBuilder(llbuilder);
     }
 }
+
+/// Returns the wrapper `-Z instrument-allocators` routes calls to the allocator
+/// function `callee` through, defining it on first use.
+///
+/// The wrapper takes `callee`'s arguments followed by an `i64` call-site ID,
+/// calls `callee` and then, if a hook was registered with
+/// `alloc::alloc::set_alloc_event_hook`, reports to it. `realloc` is reported
+/// as freeing the old block and allocating the new one. Failed allocations are
+/// reported too, with a null `ptr`.
+pub(crate) fn instrumented_alloc_fn(
+    cx: &CodegenCx<'ll, '_>,
+    method: &str,
+    callee: &'ll Value,
+) -> &'ll Value {
+    let name = format!("__rust_{}_instrumented", method);
+    if let Some(llfn) = cx.get_defined_value(&name) {
+        return llfn;
+    }
+
+    // The hook is kept in an `AtomicPtr`, which liballoc doesn't have then.
+    let ptr_size = cx.tcx.data_layout.pointer_size;
+    if cx.sess().target.target.max_atomic_width() < ptr_size.bits() {
+        cx.sess().fatal("`-Z instrument-allocators` requires a target with pointer-sized atomics");
+    }
+
+    let callee_ty = cx.element_type(cx.val_ty(callee));
+    let mut params = cx.func_params_types(callee_ty);
+    let nargs = params.len();
+    params.push(cx.type_i64());
+    let ret_ty = unsafe { llvm::LLVMGetReturnType(callee_ty) };
+    let llfn = cx.declare_cfn(&name, cx.type_func(&params, ret_ty));
+    unsafe {
+        llvm::LLVMRustSetLinkage(llfn, llvm::Linkage::LinkOnceODRLinkage);
+        llvm::LLVMRustSetVisibility(llfn, llvm::Visibility::Hidden);
+    }
+
+    let hook_slot = cx.get_declared_value(ALLOC_EVENT_HOOK).unwrap_or_else(|| {
+        cx.declare_global(ALLOC_EVENT_HOOK, cx.type_isize())
+    });
+    let hook_ty = {
+        let usize = cx.type_isize();
+        cx.type_func(
+            &[cx.type_i32(), cx.type_i8p(), usize, usize, cx.type_i64()],
+            cx.type_void(),
+        )
+    };
+
+    let mut bx = Builder::new_block(cx, llfn, "start");
+    let args = (0..nargs).map(|i| cx.get_param(llfn, i as c_uint)).collect::<Vec<_>>();
+    let callsite = cx.get_param(llfn, nargs as c_uint);
+    let ret = bx.call(callee, &args, None);
+
+    let mut report_bx = bx.build_sibling_block("report");
+    let mut done_bx = bx.build_sibling_block("done");
+    let hook = bx.atomic_load(hook_slot, AtomicOrdering::Acquire, ptr_size);
+    let no_hook = bx.icmp(IntPredicate::IntEQ, hook, cx.const_usize(0));
+    bx.cond_br(no_hook, done_bx.llbb(), report_bx.llbb());
+
+    let hook = report_bx.inttoptr(hook, cx.type_ptr_to(hook_ty));
+    let report = |bx: &mut Builder<'_, 'll, '_>, kind: u64, ptr, size, align| {
+        let kind = cx.const_u32(kind as u32);
+        bx.call(hook, &[kind, ptr, size, align, callsite], None);
+    };
+    match method {
+        "alloc" | "alloc_zeroed" => {
+            report(&mut report_bx, ALLOC_EVENT_ALLOC, ret, args[0], args[1]);
+        }
+        "dealloc" => {
+            report(&mut report_bx, ALLOC_EVENT_DEALLOC, args[0], args[1], args[2]);
+        }
+        "realloc" => {
+            report(&mut report_bx, ALLOC_EVENT_DEALLOC, args[0], args[1], args[2]);
+            report(&mut report_bx, ALLOC_EVENT_ALLOC, ret, args[3], args[2]);
+        }
+        _ => bug!("unknown allocator method `{}`", method),
+    }
+    report_bx.br(done_bx.llbb());
+
+    if cx.type_kind(ret_ty) == TypeKind::Void {
+        done_bx.ret_void();
+    } else {
+        done_bx.ret(ret);
+    }
+    llfn
+}
@@ -77,6 +77,17 @@ pub fn naked(val: &'ll Value, is_naked: bool) {
     Attribute::Naked.toggle_llfn(Function, val, is_naked);
 }
 
+/// Tell LLVM that this function accesses the floating-point environment, so
+/// its floating-point operations can't be moved across those accesses. It's
+/// also kept from being inlined into callers, whose operations aren't
+/// constrained.
+#[inline]
+pub fn strict_fp(cx: &CodegenCx<'ll, '_>, val: &'ll Value) {
+    Attribute::StrictFP.apply_llfn(Function, val);
+    inline(cx, val, InlineAttr::None);
+    inline(cx, val, InlineAttr::Never);
+}
+
 pub fn set_frame_pointer_elimination(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
     if cx.sess().must_not_eliminate_frame_pointers() {
         llvm::AddFunctionAttrStringValue(
@@ -116,6 +127,20 @@ pub fn set_probestack(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
         const_cstr!("probe-stack"), const_cstr!("__rust_probestack"));
 }
 
+/// Signs and authenticates return addresses on targets using the pointer
+/// authentication ABI.
+pub fn set_ptrauth_returns(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
+    if !cx.sess().target.target.options.ptrauth_returns {
+        return
+    }
+    llvm::AddFunctionAttrStringValue(
+        llfn, llvm::AttributePlace::Function,
+        const_cstr!("ptrauth-returns"), const_cstr!(""));
+    llvm::AddFunctionAttrStringValue(
+        llfn, llvm::AttributePlace::Function,
+        const_cstr!("ptrauth-auth-traps"), const_cstr!(""));
+}
+
 pub fn llvm_target_features(sess: &Session) -> impl Iterator<Item = &str> {
     const RUSTC_SPECIFIC_FEATURES: &[&str] = &[
         "crt-static",
@@ -147,6 +172,50 @@ pub fn non_lazy_bind(sess: &Session, llfn: &'ll Value) {
     }
 }
 
+/// Describes the functions of the global allocator to LLVM, so that it can
+/// remove or shrink the allocations they make which don't escape, like it
+/// does for `malloc` and `free`.
+///
+/// Only LLVM 15 and later understand `allockind`, `allocalign` and
+/// `allocptr`, so older versions just get `allocsize`. Our LLVM fork knows
+/// the `__rust_*` functions by name instead.
+fn allocator_family(llfn: &'ll Value, flags: CodegenFnAttrFlags) {
+    use llvm::AllocKind;
+
+    // (kind, size argument, alignment argument, pointer argument)
+    let (kind, size, align, ptr) = if flags.contains(CodegenFnAttrFlags::ALLOCATOR) {
+        (AllocKind::ALLOC | AllocKind::UNINITIALIZED | AllocKind::ALIGNED, Some(0), Some(1), None)
+    } else if flags.contains(CodegenFnAttrFlags::ALLOCATOR_ZEROED) {
+        (AllocKind::ALLOC | AllocKind::ZEROED | AllocKind::ALIGNED, Some(0), Some(1), None)
+    } else if flags.contains(CodegenFnAttrFlags::REALLOCATOR) {
+        (AllocKind::REALLOC | AllocKind::ALIGNED, Some(3), Some(2), Some(0))
+    } else if flags.contains(CodegenFnAttrFlags::DEALLOCATOR) {
+        (AllocKind::FREE, None, None, Some(0))
+    } else {
+        return
+    };
+
+    // Only functions of the same family may be paired up.
+    llvm::AddFunctionAttrStringValue(
+        llfn, Function,
+        const_cstr!("alloc-family"), const_cstr!("__rust_alloc"));
+    unsafe {
+        if let Some(size) = size {
+            llvm::LLVMRustAddAllocSizeAttr(llfn, size);
+        }
+        if llvm_util::get_major_version() < 15 {
+            return
+        }
+        llvm::LLVMRustAddAllocKindAttr(llfn, kind.bits());
+        if let Some(align) = align {
+            llvm::LLVMRustAddAllocAlignAttr(llfn, align);
+        }
+        if let Some(ptr) = ptr {
+            llvm::LLVMRustAddAllocPtrAttr(llfn, ptr);
+        }
+    }
+}
+
 /// Composite function which sets LLVM attributes for function depending on its AST (#[attribute])
 /// attributes.
 pub fn from_fn_attrs(
@@ -182,6 +251,7 @@ pub fn from_fn_attrs(
 
     set_frame_pointer_elimination(cx, llfn);
     set_probestack(cx, llfn);
+    set_ptrauth_returns(cx, llfn);
 
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::COLD) {
         Attribute::Cold.apply_llfn(Function, llfn);
@@ -189,10 +259,13 @@ pub fn from_fn_attrs(
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NAKED) {
         naked(llfn, true);
     }
-    if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::ALLOCATOR) {
+    if codegen_fn_attrs.flags.intersects(CodegenFnAttrFlags::ALLOCATOR |
+                                         CodegenFnAttrFlags::ALLOCATOR_ZEROED |
+                                         CodegenFnAttrFlags::REALLOCATOR) {
         Attribute::NoAlias.apply_llfn(
             llvm::AttributePlace::ReturnValue, llfn);
     }
+    allocator_family(llfn, codegen_fn_attrs.flags);
 
     let can_unwind = if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::UNWIND) {
         Some(true)
@@ -226,25 +299,10 @@ pub fn from_fn_attrs(
         apply_target_cpu_attr(cx, llfn);
     }
 
-    let features = llvm_target_features(cx.tcx.sess)
-        .map(|s| s.to_string())
-        .chain(
-            codegen_fn_attrs.target_features
-                .iter()
-                .map(|f| {
-                    let feature = &*f.as_str();
-                    format!("+{}", llvm_util::to_llvm_feature(cx.tcx.sess, feature))
-                })
-        )
-        .collect::<Vec<String>>()
-        .join(",");
-
-    if !features.is_empty() {
-        let val = CString::new(features).unwrap();
-        llvm::AddFunctionAttrStringValue(
-            llfn, llvm::AttributePlace::Function,
-            const_cstr!("target-features"), &val);
-    }
+    let target_features = codegen_fn_attrs.target_features.iter()
+        .map(|f| f.as_str().to_string())
+        .collect::<Vec<_>>();
+    apply_target_features(cx, llfn, &target_features);
 
     // Note that currently the `wasm-import-module` doesn't do anything, but
     // eventually LLVM 7 should read this and ferry the appropriate import
@@ -263,6 +321,33 @@ pub fn from_fn_attrs(
     }
 }
 
+/// Sets the `target-features` attribute of `llfn` to the crate-wide features
+/// plus `target_features`, which use the Rust names of the features.
+pub fn apply_target_features(
+    cx: &CodegenCx<'ll, '_>,
+    llfn: &'ll Value,
+    target_features: &[String],
+) {
+    let features = llvm_target_features(cx.tcx.sess)
+        .map(|s| s.to_string())
+        .chain(
+            target_features
+                .iter()
+                .map(|feature| {
+                    format!("+{}", llvm_util::to_llvm_feature(cx.tcx.sess, feature))
+                })
+        )
+        .collect::<Vec<String>>()
+        .join(",");
+
+    if !features.is_empty() {
+        let val = CString::new(features).unwrap();
+        llvm::AddFunctionAttrStringValue(
+            llfn, llvm::AttributePlace::Function,
+            const_cstr!("target-features"), &val);
+    }
+}
+
 pub fn provide(providers: &mut Providers) {
     providers.target_features_whitelist = |tcx, cnum| {
         assert_eq!(cnum, LOCAL_CRATE);
@@ -27,23 +27,29 @@ use rustc::middle::cstore::{NativeLibrary, NativeLibraryKind};
 use rustc::middle::dependency_format::Linkage;
 use rustc_codegen_ssa::CodegenResults;
 use rustc::util::common::time;
-use rustc_fs_util::fix_windows_verbatim_for_gcc;
-use rustc::hir::def_id::CrateNum;
+use rustc_fs_util::{fix_windows_verbatim_for_gcc, path2cstr};
+use rustc::hir::def_id::{CrateNum, LOCAL_CRATE};
+use errors::DiagnosticBuilder;
+use rustc_demangle;
 use tempfile::{Builder as TempFileBuilder, TempDir};
 use rustc_target::spec::{PanicStrategy, RelroLevel, LinkerFlavor};
 use rustc_data_structures::fx::FxHashSet;
 use context::get_reloc_model;
 use llvm;
 
+use libc::{c_char, c_void, size_t};
 use std::ascii;
 use std::char;
 use std::env;
+use std::ffi::CString;
 use std::fmt;
+use std::fmt::Write;
 use std::fs;
 use std::io;
 use std::iter;
 use std::path::{Path, PathBuf};
 use std::process::{Output, Stdio};
+use std::slice;
 use std::str;
 use syntax::attr;
 
@@ -199,6 +205,14 @@ fn link_binary_output(sess: &Session,
             }
             _ => {
                 link_natively(sess, crate_type, &out_filename, codegen_results, tmpdir.path());
+                if sess.opts.debugging_opts.print_unused_monomorphizations {
+                    print_unused_monomorphizations(sess, codegen_results, &out_filename);
+                }
+                if crate_type == config::CrateType::Cdylib &&
+                   sess.target.target.options.is_like_msvc &&
+                   sess.opts.debugging_opts.write_import_lib {
+                    write_import_library(sess, codegen_results, &out_filename);
+                }
             }
         }
         out_filenames.push(out_filename);
@@ -295,7 +309,8 @@ fn link_rlib<'a>(sess: &'a Session,
             NativeLibraryKind::NativeStatic => {}
             NativeLibraryKind::NativeStaticNobundle |
             NativeLibraryKind::NativeFramework |
-            NativeLibraryKind::NativeUnknown => continue,
+            NativeLibraryKind::NativeUnknown |
+            NativeLibraryKind::NativeBitcode => continue,
         }
         if let Some(name) = lib.name {
             ab.add_native_library(&name.as_str());
@@ -449,7 +464,8 @@ fn print_native_static_libs(sess: &Session, all_native_libs: &[NativeLibrary]) {
                     Some(format!("-framework {}", name))
                 },
                 // These are included, no need to print them
-                NativeLibraryKind::NativeStatic => None,
+                NativeLibraryKind::NativeStatic |
+                NativeLibraryKind::NativeBitcode => None,
             }
         })
         .collect();
@@ -462,6 +478,233 @@ fn print_native_static_libs(sess: &Session, all_native_libs: &[NativeLibrary]) {
     }
 }
 
+// Writes `<name>.dll.lib` for a windows-msvc cdylib straight from its list of
+// exported symbols, so that no `lib.exe` (or MSVC linker) is needed to produce
+// a complete distributable artifact, e.g. when cross-compiling from Linux.
+fn write_import_library(sess: &Session,
+                        codegen_results: &CodegenResults,
+                        out_filename: &Path) {
+    // Values of `IMAGE_FILE_MACHINE_*` from the PE/COFF specification
+    let machine: u16 = match &sess.target.target.arch[..] {
+        "x86" => 0x14c,
+        "x86_64" => 0x8664,
+        "arm" => 0x1c4,
+        "aarch64" => 0xaa64,
+        arch => {
+            sess.err(&format!("cannot write an import library for architecture `{}`", arch));
+            return
+        }
+    };
+
+    let import_name = out_filename.file_name().unwrap().to_str().unwrap();
+    let lib_path = out_filename.with_extension("dll.lib");
+    let exports = codegen_results.linker_info.exports(config::CrateType::Cdylib)
+        .iter()
+        .map(|name| CString::new(&name[..]).unwrap())
+        .collect::<Vec<_>>();
+    let export_ptrs = exports.iter().map(|name| name.as_ptr()).collect::<Vec<_>>();
+    let import_name = CString::new(import_name).unwrap();
+    let path = CString::new(lib_path.to_str().unwrap()).unwrap();
+
+    let result = unsafe {
+        llvm::LLVMRustWriteImportLibrary(import_name.as_ptr(),
+                                         path.as_ptr(),
+                                         export_ptrs.as_ptr(),
+                                         export_ptrs.len(),
+                                         machine,
+                                         false /* MinGW */)
+    };
+    if result.into_result().is_err() {
+        let msg = llvm::last_error().unwrap_or_else(|| "unknown error".to_string());
+        sess.err(&format!("failed to write import library `{}`: {}",
+                          lib_path.display(), msg));
+    }
+}
+
+// Collects the names of the symbols defined by an object file, executable or
+// shared library.
+fn defined_symbols(path: &Path) -> Result<FxHashSet<String>, String> {
+    unsafe extern "C" fn add_symbol(state: *mut c_void, name: *const c_char, len: size_t) {
+        let symbols = &mut *(state as *mut FxHashSet<String>);
+        let name = slice::from_raw_parts(name as *const u8, len);
+        symbols.insert(String::from_utf8_lossy(name).into_owned());
+    }
+
+    let mut symbols = FxHashSet::default();
+    let path = path2cstr(path);
+    let result = unsafe {
+        llvm::LLVMRustForEachDefinedSymbol(path.as_ptr(),
+                                           &mut symbols as *mut _ as *mut c_void,
+                                           add_symbol)
+    };
+    match result.into_result() {
+        Ok(()) => Ok(symbols),
+        Err(()) => Err(llvm::last_error().unwrap_or_else(|| "unknown error".to_string())),
+    }
+}
+
+// Prints the monomorphizations of this crate that made it into an object file
+// but not into the linked artifact, i.e. the ones the linker garbage collected.
+fn print_unused_monomorphizations(sess: &Session,
+                                  codegen_results: &CodegenResults,
+                                  out_filename: &Path) {
+    let linked = match defined_symbols(out_filename) {
+        Ok(symbols) => symbols,
+        Err(e) => {
+            sess.warn(&format!("failed to read the symbols of `{}`: {}",
+                               out_filename.display(), e));
+            return
+        }
+    };
+
+    let symbol_items = &codegen_results.crate_info.symbol_items;
+    let is_local_item = |symbol: &str| {
+        // Mach-O symbols carry an extra leading underscore.
+        let symbol = if sess.target.target.options.is_like_osx && symbol.starts_with('_') {
+            &symbol[1..]
+        } else {
+            symbol
+        };
+        symbol_items.get(symbol).map_or(false, |&(cnum, _)| cnum == LOCAL_CRATE)
+    };
+
+    let mut unused = vec![];
+    for object in codegen_results.modules.iter().filter_map(|m| m.object.as_ref()) {
+        let symbols = match defined_symbols(object) {
+            Ok(symbols) => symbols,
+            Err(e) => {
+                sess.warn(&format!("failed to read the symbols of `{}`: {}",
+                                   object.display(), e));
+                continue
+            }
+        };
+        unused.extend(symbols.into_iter().filter(|symbol| {
+            !linked.contains(symbol) && is_local_item(symbol)
+        }));
+    }
+
+    let mut unused = unused.into_iter().map(|symbol| {
+        match rustc_demangle::try_demangle(&symbol) {
+            Ok(demangled) => format!("{:#}", demangled),
+            Err(_) => symbol,
+        }
+    }).collect::<Vec<_>>();
+    unused.sort();
+    unused.dedup();
+    for item in unused {
+        println!("UNUSED_MONO_ITEM {}", item);
+    }
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$' || c == '.'
+}
+
+// Splits linker output into the words that may be symbol names, together with
+// what the line they appear on (or the closest line before it that says) is
+// complaining about.
+fn linker_output_symbols(output: &str) -> Vec<(LinkerSymbolError, &str)> {
+    let mut symbols = vec![];
+    let mut kind = None;
+    for line in output.lines() {
+        if line.contains("undefined reference") ||
+           line.contains("undefined symbol") ||
+           line.contains("Undefined symbols") ||
+           line.contains("unresolved external symbol") {
+            kind = Some(LinkerSymbolError::Undefined);
+        } else if line.contains("multiple definition") ||
+                  line.contains("duplicate symbol") ||
+                  line.contains("already defined") {
+            kind = Some(LinkerSymbolError::Duplicate);
+        }
+        if let Some(kind) = kind {
+            symbols.extend(line.split(|c| !is_symbol_char(c))
+                               .map(|word| word.trim_right_matches('.'))
+                               .filter(|word| !word.is_empty())
+                               .map(|word| (kind, word)));
+        }
+    }
+    symbols
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum LinkerSymbolError {
+    Undefined,
+    Duplicate,
+}
+
+// Replaces every mangled Rust symbol in the linker output by its demangled
+// form (hash included, so that distinct instances stay distinguishable).
+fn demangle_linker_output(output: &str) -> String {
+    let mut demangled = String::with_capacity(output.len());
+    let mut rest = output;
+    while let Some(start) = rest.find(is_symbol_char) {
+        let end = rest[start..].find(|c| !is_symbol_char(c))
+            .map_or(rest.len(), |len| start + len);
+        demangled.push_str(&rest[..start]);
+        let word = &rest[start..end];
+        match rustc_demangle::try_demangle(word) {
+            Ok(symbol) => write!(demangled, "{}", symbol).unwrap(),
+            Err(_) => demangled.push_str(word),
+        }
+        rest = &rest[end..];
+    }
+    demangled.push_str(rest);
+    demangled
+}
+
+// Points at the items behind the symbols the linker complained about, as far
+// as we know them.
+fn add_linker_symbol_notes(err: &mut DiagnosticBuilder,
+                           codegen_results: &CodegenResults,
+                           output: &str) {
+    const MAX_NOTES: usize = 10;
+
+    let crate_info = &codegen_results.crate_info;
+    let mut seen = FxHashSet::default();
+    for (kind, word) in linker_output_symbols(output) {
+        if seen.len() == MAX_NOTES {
+            break
+        }
+        // Windows import thunks and the extra underscore of Mach-O and
+        // 32-bit Windows symbols aren't part of the symbol name proper.
+        let word = if word.starts_with("__imp_") { &word[6..] } else { word };
+        let unprefixed = if word.starts_with('_') { &word[1..] } else { word };
+        let symbol = [word, unprefixed]
+            .iter()
+            .cloned()
+            .find(|symbol| crate_info.symbol_items.contains_key(*symbol));
+        let symbol = match symbol {
+            Some(symbol) => symbol,
+            None => continue,
+        };
+        if !seen.insert(symbol) {
+            continue
+        }
+        let (cnum, span) = crate_info.symbol_items[symbol];
+        let crate_name = if cnum == LOCAL_CRATE {
+            codegen_results.crate_name.to_string()
+        } else {
+            crate_info.crate_name[&cnum].clone()
+        };
+        let name = match rustc_demangle::try_demangle(symbol) {
+            Ok(demangled) => format!("{:#}", demangled),
+            Err(_) => symbol.to_string(),
+        };
+        let msg = match kind {
+            LinkerSymbolError::Undefined => {
+                format!("undefined symbol `{}` refers to this item in crate `{}`",
+                        name, crate_name)
+            }
+            LinkerSymbolError::Duplicate => {
+                format!("duplicate symbol `{}` is defined by this item in crate `{}`",
+                        name, crate_name)
+            }
+        };
+        err.span_note(span, &msg);
+    }
+}
+
 // Create a dynamic library or executable
 //
 // This will invoke the system linker/cc to create the resulting file. This
@@ -645,12 +888,19 @@ fn link_natively(sess: &Session,
             if !prog.status.success() {
                 let mut output = prog.stderr.clone();
                 output.extend_from_slice(&prog.stdout);
-                sess.struct_err(&format!("linking with `{}` failed: {}",
-                                         pname.display(),
-                                         prog.status))
-                    .note(&format!("{:?}", &cmd))
-                    .note(&escape_string(&output))
-                    .emit();
+                let output = escape_string(&output);
+                let mut err = sess.struct_err(&format!("linking with `{}` failed: {}",
+                                                       pname.display(),
+                                                       prog.status));
+                err.note(&format!("{:?}", &cmd));
+                err.note(&demangle_linker_output(&output));
+                if sess.opts.debugging_opts.explain_linker_errors {
+                    add_linker_symbol_notes(&mut err, codegen_results, &output);
+                } else if !linker_output_symbols(&output).is_empty() {
+                    err.note("run with `-Z explain-linker-errors` to point at the items \
+                              behind these symbols");
+                }
+                err.emit();
                 sess.abort_if_errors();
             }
             info!("linker stderr:\n{}", escape_string(&prog.stderr));
@@ -887,6 +1137,10 @@ fn link_args(cmd: &mut dyn Linker,
         cmd.export_symbols(tmpdir, crate_type);
     }
 
+    if sess.opts.debugging_opts.symbol_ordering_file.is_some() {
+        cmd.order_symbols(tmpdir, sess.symbol_order());
+    }
+
     // When linking a dynamic library, we put the metadata into a section of the
     // executable. This metadata is in a separate object file from the main
     // object file, so we link that in here.
@@ -1010,7 +1264,7 @@ fn link_args(cmd: &mut dyn Linker,
 
     // Tell the linker what we're doing.
     if crate_type != config::CrateType::Executable {
-        cmd.build_dylib(out_filename);
+        cmd.build_dylib(out_filename, crate_type);
     }
     if crate_type == config::CrateType::Executable && sess.crt_static() {
         cmd.build_static_executable();
@@ -1090,7 +1344,9 @@ fn add_local_native_libraries(cmd: &mut dyn Linker,
             NativeLibraryKind::NativeFramework => cmd.link_framework(&name.as_str()),
             NativeLibraryKind::NativeStaticNobundle => cmd.link_staticlib(&name.as_str()),
             NativeLibraryKind::NativeStatic => cmd.link_whole_staticlib(&name.as_str(),
-                                                                        &search_path)
+                                                                        &search_path),
+            // Already merged into the crate's modules
+            NativeLibraryKind::NativeBitcode => {}
         }
     }
 }
@@ -1473,7 +1729,8 @@ fn add_upstream_native_libraries(cmd: &mut dyn Linker,
                 // ignore statically included native libraries here as we've
                 // already included them when we included the rust library
                 // previously
-                NativeLibraryKind::NativeStatic => {}
+                NativeLibraryKind::NativeStatic |
+                NativeLibraryKind::NativeBitcode => {}
             }
         }
     }
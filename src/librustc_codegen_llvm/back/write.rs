@@ -10,18 +10,21 @@
 
 use attributes;
 use back::bytecode::{self, RLIB_BYTECODE_EXTENSION};
-use back::lto::ThinBuffer;
-use rustc_codegen_ssa::back::write::{CodegenContext, ModuleConfig, run_assembler};
+use back::lto::{Linker, ModuleBuffer, ThinBuffer};
+use rustc_codegen_ssa::back::write::{CodegenContext, ModuleConfig, Message, run_assembler,
+                                     content_hash};
 use rustc_codegen_ssa::traits::*;
 use base;
 use consts;
-use rustc::session::config::{self, OutputType, Passes, Lto};
+use rustc::lint;
+use rustc::session::config::{self, OutputFilenames, OutputType, Passes, Lto};
 use rustc::session::Session;
 use time_graph::Timeline;
 use llvm::{self, DiagnosticInfo, PassManager, SMDiagnostic};
+use llvm::archive_ro::ArchiveRO;
 use llvm_util;
 use ModuleLlvm;
-use rustc_codegen_ssa::{ModuleCodegen, CompiledModule};
+use rustc_codegen_ssa::{ModuleCodegen, ModuleKind, CompiledModule, CodegenResults};
 use rustc::util::common::time_ext;
 use rustc_fs_util::{path2cstr, link_or_copy};
 use rustc_data_structures::small_c_str::SmallCStr;
@@ -32,6 +35,9 @@ use common;
 use LlvmCodegenBackend;
 use rustc_demangle;
 
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::BTreeMap;
 use std::ffi::{CString, CStr};
 use std::fs;
 use std::io::{self, Write};
@@ -217,8 +223,16 @@ pub(crate) fn save_temp_bitcode(
     }
 }
 
+/// What the loop vectorizer said about each loop of the `#[vectorize(expect)]`
+/// functions in a module, by symbol name and location of the loop: `None` if
+/// it vectorized the loop, otherwise the first reason it gave for not doing so.
+type LoopVectorizeRemarks = RefCell<BTreeMap<(String, String, c_uint, c_uint), Option<String>>>;
+
+type DiagnosticHandlerData<'a> =
+    (&'a CodegenContext<LlvmCodegenBackend>, &'a Handler, LoopVectorizeRemarks);
+
 pub struct DiagnosticHandlers<'a> {
-    data: *mut (&'a CodegenContext<LlvmCodegenBackend>, &'a Handler),
+    data: *mut DiagnosticHandlerData<'a>,
     llcx: &'a llvm::Context,
 }
 
@@ -226,10 +240,19 @@ impl<'a> DiagnosticHandlers<'a> {
     pub fn new(cgcx: &'a CodegenContext<LlvmCodegenBackend>,
                handler: &'a Handler,
                llcx: &'a llvm::Context) -> Self {
-        let data = Box::into_raw(Box::new((cgcx, handler)));
+        let data = Box::into_raw(Box::new((cgcx, handler, Default::default())));
         unsafe {
             llvm::LLVMRustSetInlineAsmDiagnosticHandler(llcx, inline_asm_handler, data as *mut _);
-            llvm::LLVMContextSetDiagnosticHandler(llcx, diagnostic_handler, data as *mut _);
+            if cgcx.expect_vectorized.is_empty() {
+                llvm::LLVMContextSetDiagnosticHandler(llcx, diagnostic_handler, data as *mut _);
+            } else {
+                // The vectorizer's remarks are needed to report loops in
+                // `#[vectorize(expect)]` functions that it gave up on.
+                llvm::LLVMRustContextSetDiagnosticHandler(llcx,
+                                                          diagnostic_handler,
+                                                          data as *mut _,
+                                                          "loop-vectorize\0".as_ptr() as *const _);
+            }
         }
         DiagnosticHandlers { data, llcx }
     }
@@ -239,6 +262,9 @@ impl<'a> Drop for DiagnosticHandlers<'a> {
     fn drop(&mut self) {
         use std::ptr::null_mut;
         unsafe {
+            let (cgcx, _, ref remarks) = *self.data;
+            report_expect_vectorized(cgcx, &remarks.borrow());
+
             llvm::LLVMRustSetInlineAsmDiagnosticHandler(self.llcx, inline_asm_handler, null_mut());
             llvm::LLVMContextSetDiagnosticHandler(self.llcx, diagnostic_handler, null_mut());
             drop(Box::from_raw(self.data));
@@ -258,7 +284,7 @@ unsafe extern "C" fn inline_asm_handler(diag: &SMDiagnostic,
     if user.is_null() {
         return
     }
-    let (cgcx, _) = *(user as *const (&CodegenContext<LlvmCodegenBackend>, &Handler));
+    let (cgcx, _, _) = *(user as *const DiagnosticHandlerData);
 
     let msg = llvm::build_string(|s| llvm::LLVMRustWriteSMDiagnosticToString(diag, s))
         .expect("non-UTF8 SMDiagnostic");
@@ -270,7 +296,7 @@ unsafe extern "C" fn diagnostic_handler(info: &DiagnosticInfo, user: *mut c_void
     if user.is_null() {
         return
     }
-    let (cgcx, diag_handler) = *(user as *const (&CodegenContext<LlvmCodegenBackend>, &Handler));
+    let (cgcx, diag_handler, ref remarks) = *(user as *const DiagnosticHandlerData);
 
     match llvm::diagnostic::Diagnostic::unpack(info) {
         llvm::diagnostic::InlineAsm(inline) => {
@@ -280,6 +306,8 @@ unsafe extern "C" fn diagnostic_handler(info: &DiagnosticInfo, user: *mut c_void
         }
 
         llvm::diagnostic::Optimization(opt) => {
+            record_loop_vectorize_remark(cgcx, remarks, &opt);
+
             let enabled = match cgcx.remark {
                 Passes::All => true,
                 Passes::Some(ref v) => v.iter().any(|s| *s == opt.pass_name),
@@ -306,6 +334,99 @@ unsafe extern "C" fn diagnostic_handler(info: &DiagnosticInfo, user: *mut c_void
     }
 }
 
+unsafe fn record_loop_vectorize_remark(cgcx: &CodegenContext<LlvmCodegenBackend>,
+                                       remarks: &LoopVectorizeRemarks,
+                                       opt: &llvm::diagnostic::OptimizationDiagnostic) {
+    use llvm::diagnostic::OptimizationDiagnosticKind::*;
+
+    if opt.pass_name != "loop-vectorize" {
+        return
+    }
+    let symbol = CStr::from_ptr(llvm::LLVMGetValueName(opt.function));
+    let symbol = symbol.to_string_lossy().into_owned();
+    if !cgcx.expect_vectorized.contains_key(&symbol) {
+        return
+    }
+
+    // Without debuginfo every loop of a function has the same location, so a
+    // vectorized loop hides the others rather than risking a false report.
+    let key = (symbol, opt.filename.clone(), opt.line, opt.column);
+    let mut remarks = remarks.borrow_mut();
+    match opt.kind {
+        // A loop that is only interleaved gets an "interleaved loop" remark.
+        OptimizationRemark if opt.message.starts_with("vectorized loop") => {
+            remarks.insert(key, None);
+        }
+        OptimizationMissed | OptimizationAnalysis | OptimizationFailure => {
+            remarks.entry(key).or_insert_with(|| Some(opt.message.clone()));
+        }
+        _ => {}
+    }
+}
+
+/// Reports each loop of a `#[vectorize(expect)]` function that the loop
+/// vectorizer gave up on, once, at the function.
+fn report_expect_vectorized(cgcx: &CodegenContext<LlvmCodegenBackend>,
+                            remarks: &BTreeMap<(String, String, c_uint, c_uint), Option<String>>) {
+    for (&(ref symbol, ref filename, line, column), reason) in remarks {
+        let reason = match *reason {
+            Some(ref reason) => reason,
+            None => continue,
+        };
+        let expect = &cgcx.expect_vectorized[symbol];
+        let lvl = match expect.level {
+            lint::Forbid | lint::Deny => errors::Level::Error,
+            _ => errors::Level::Warning,
+        };
+        let mut notes = vec![];
+        if line != 0 {
+            notes.push(format!("the loop is at {}:{}:{}", filename, line, column));
+        }
+        notes.push(format!("the loop vectorizer says: {}", reason));
+        cgcx.diag_emitter.span_diagnostic(expect.span,
+                                          lvl,
+                                          format!("loop in `{}` was not vectorized", expect.name),
+                                          notes.as_slice());
+    }
+}
+
+/// Links the bitcode library `name` at `path`, a bitcode file or an archive of
+/// them, into `llmod`. Unless `define`, its definitions are only made
+/// available for inlining, so that a single module defines them.
+unsafe fn link_bitcode_lib(diag_handler: &Handler,
+                           llmod: &llvm::Module,
+                           name: &str,
+                           path: &Path,
+                           define: bool)
+    -> Result<(), FatalError>
+{
+    let link = |bc: &[u8]| {
+        let bc_ptr = bc.as_ptr() as *const c_char;
+        if llvm::LLVMRustLinkBitcodeLibrary(llmod, bc_ptr, bc.len(), define) {
+            Ok(())
+        } else {
+            Err(llvm_err(diag_handler, &format!("failed to link bitcode library `{}`", name)))
+        }
+    };
+
+    let bc = fs::read(path).map_err(|e| {
+        diag_handler.fatal(&format!("failed to read bitcode library `{}`: {}", name, e))
+    })?;
+    if !bc.starts_with(b"!<arch>\n") {
+        return link(&bc);
+    }
+    let archive = ArchiveRO::open(path).map_err(|e| {
+        diag_handler.fatal(&format!("failed to read bitcode library `{}`: {}", name, e))
+    })?;
+    for child in archive.iter() {
+        let child = child.map_err(|e| {
+            diag_handler.fatal(&format!("failed to read bitcode library `{}`: {}", name, e))
+        })?;
+        link(child.data())?;
+    }
+    Ok(())
+}
+
 // Unsafe due to LLVM calls.
 pub(crate) unsafe fn optimize(cgcx: &CodegenContext<LlvmCodegenBackend>,
                    diag_handler: &Handler,
@@ -322,6 +443,15 @@ pub(crate) unsafe fn optimize(cgcx: &CodegenContext<LlvmCodegenBackend>,
     let module_name = module.name.clone();
     let module_name = Some(&module_name[..]);
 
+    if module.kind == ModuleKind::Regular {
+        if let Some(ref bitcode_libs) = cgcx.bitcode_libs {
+            let define = module.name == bitcode_libs.home_cgu;
+            for &(ref name, ref path) in &bitcode_libs.libs {
+                link_bitcode_lib(diag_handler, llmod, name, path, define)?;
+            }
+        }
+    }
+
     if config.emit_no_opt_bc {
         let out = cgcx.output_filenames.temp_path_ext("no-opt.bc", module_name);
         let out = path2cstr(&out);
@@ -437,6 +567,14 @@ pub(crate) unsafe fn optimize(cgcx: &CodegenContext<LlvmCodegenBackend>,
         llvm::LLVMDisposePassManager(fpm);
         llvm::LLVMDisposePassManager(mpm);
     }
+
+    if cgcx.opts.debugging_opts.codegen_hash_output {
+        let buffer = ModuleBuffer::new(llmod);
+        drop(cgcx.coordinator_send.send(Box::new(Message::PreLtoHash::<LlvmCodegenBackend> {
+            module_name: module.name.clone(),
+            hash: content_hash(buffer.data()),
+        })));
+    }
     Ok(())
 }
 
@@ -570,6 +708,13 @@ pub(crate) unsafe fn codegen(cgcx: &CodegenContext<LlvmCodegenBackend>,
                 timeline.record("ir");
             }
 
+            // Machine code is emitted in module order, which follows the
+            // order in which things were codegened. Make it independent of
+            // that, so object files are reproducible.
+            if config.emit_asm || asm_to_obj || write_obj {
+                llvm::LLVMRustSortModuleSymbols(llmod);
+            }
+
             if config.emit_asm || asm_to_obj {
                 let path = cgcx.output_filenames.temp_path(OutputType::Assembly, module_name);
 
@@ -577,7 +722,7 @@ pub(crate) unsafe fn codegen(cgcx: &CodegenContext<LlvmCodegenBackend>,
                 // various errors like invalid IR or broken binaries, so we might have to clone the
                 // module to produce the asm output
                 let llmod = if config.emit_obj {
-                    llvm::LLVMCloneModule(llmod)
+                    &*llvm::LLVMCloneModule(llmod)
                 } else {
                     llmod
                 };
@@ -589,11 +734,34 @@ pub(crate) unsafe fn codegen(cgcx: &CodegenContext<LlvmCodegenBackend>,
             }
 
             if write_obj {
+                // For `-Z deterministic` we first emit a copy of the module to
+                // a second object file, so the two results can be compared.
+                let reference_obj = if config.verify_deterministic_obj {
+                    let path = obj_out.with_extension("repro.o");
+                    let llmod = llvm::LLVMCloneModule(llmod);
+                    let result = with_codegen(tm, llmod, config.no_builtins, |cpm| {
+                        write_output_file(diag_handler, tm, cpm, llmod, &path,
+                                          llvm::FileType::ObjectFile)
+                    });
+                    llvm::LLVMDisposeModule(llmod);
+                    result?;
+                    Some(path)
+                } else {
+                    None
+                };
                 with_codegen(tm, llmod, config.no_builtins, |cpm| {
                     write_output_file(diag_handler, tm, cpm, llmod, &obj_out,
                                       llvm::FileType::ObjectFile)
                 })?;
                 timeline.record("obj");
+
+                if let Some(path) = reference_obj {
+                    verify_deterministic_obj(diag_handler, &module.name, &obj_out, &path);
+                    if !cgcx.save_temps {
+                        drop(fs::remove_file(&path));
+                    }
+                    timeline.record("verify-obj");
+                }
             } else if asm_to_obj {
                 let assembly = cgcx.output_filenames.temp_path(OutputType::Assembly, module_name);
                 run_assembler(cgcx, diag_handler, &assembly, &obj_out);
@@ -629,6 +797,82 @@ pub(crate) unsafe fn codegen(cgcx: &CodegenContext<LlvmCodegenBackend>,
                                    &cgcx.output_filenames))
 }
 
+/// Compares two object files produced from the same LLVM module, reporting an
+/// error if they are not byte-identical.
+fn verify_deterministic_obj(handler: &Handler, cgu_name: &str, obj: &Path, reference: &Path) {
+    let (a, b) = match (fs::read(obj), fs::read(reference)) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(e), _) | (_, Err(e)) => {
+            handler.err(&format!("failed to read object file for codegen unit `{}`: {}",
+                                 cgu_name, e));
+            return
+        }
+    };
+    if a == b {
+        return
+    }
+
+    let offset = a.iter().zip(&b).position(|(x, y)| x != y)
+        .unwrap_or(cmp::min(a.len(), b.len()));
+    let mut err = handler.struct_err(&format!(
+        "object file for codegen unit `{}` is not reproducible", cgu_name));
+    err.note(&format!("emitting the same LLVM module twice produced different output, \
+                       first differing at byte offset {:#x}", offset));
+    err.note("the nondeterminism comes from LLVM code generation, \
+              not from the module rustc generated");
+    err.emit();
+}
+
+/// Links the optimized bitcode of every codegen unit into a single module and
+/// writes it as the crate's object file, for `-Z merge-objects`. This is the
+/// equivalent of `ld -r` on the objects the codegen units would have produced,
+/// except that LLVM sees the whole crate again while generating code.
+pub(crate) fn merge_objects(sess: &Session,
+                            codegen_results: &CodegenResults,
+                            outputs: &OutputFilenames) -> Result<(), FatalError> {
+    let handler = sess.diagnostic();
+    let mut modules: Vec<_> = codegen_results.modules.iter()
+        .filter(|module| module.kind == ModuleKind::Regular)
+        .collect();
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let merged = ModuleLlvm::new(sess, &codegen_results.crate_name.as_str());
+    let (llmod, tm) = (merged.llmod(), &*merged.tm);
+    let mut linker = Linker::new(llmod);
+    for module in &modules {
+        let path = module.bytecode.as_ref().ok_or_else(|| {
+            handler.fatal(&format!("no bitcode was kept for codegen unit `{}` to merge",
+                                   module.name))
+        })?;
+        let data = fs::read(path).map_err(|e| {
+            handler.fatal(&format!("failed to read `{}`: {}", path.display(), e))
+        })?;
+        linker.add(&data).map_err(|()| {
+            llvm_err(handler, &format!("failed to link the bitcode of {:?}", module.name))
+        })?;
+    }
+    drop(linker);
+
+    let out = outputs.path(OutputType::Object);
+    unsafe {
+        let cpm = llvm::LLVMCreatePassManager();
+        llvm::LLVMRustAddAnalysisPasses(tm, cpm, llmod);
+        llvm::LLVMRustAddLibraryInfo(cpm, llmod, sess.target.target.options.no_builtins);
+        write_output_file(handler, tm, cpm, llmod, &out, llvm::FileType::ObjectFile)?;
+    }
+
+    if !sess.opts.cg.save_temps && !sess.opts.output_types.contains_key(&OutputType::Bitcode) {
+        for module in modules {
+            if let Some(ref path) = module.bytecode {
+                if let Err(e) = fs::remove_file(path) {
+                    handler.err(&format!("failed to remove bitcode: {}", e));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Embed the bitcode of an LLVM module in the LLVM module itself.
 ///
 /// This is done primarily for iOS where it appears to be standard to compile C
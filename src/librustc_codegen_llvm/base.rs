@@ -28,27 +28,33 @@ use rustc_codegen_ssa::{ModuleCodegen, ModuleKind};
 use rustc_codegen_ssa::base::maybe_create_entry_wrapper;
 use super::LlvmCodegenBackend;
 
+use abi::{ArgAttribute, ArgAttributes, ArgType, Conv, FnType, FnTypeExt, LlvmType, PassMode};
 use llvm;
 use metadata;
-use rustc::mir::mono::{Linkage, Visibility, Stats};
+use rustc::mir::mono::{Linkage, MonoItem, Visibility, Stats};
 use rustc::middle::cstore::{EncodedMetadata};
-use rustc::ty::TyCtxt;
+use rustc::ty::{Ty, TyCtxt};
 use rustc::middle::exported_symbols;
-use rustc::session::config::{self, DebugInfo};
+use rustc::session::config::{self, DebugInfo, TrackedFile};
 use builder::Builder;
 use common;
 use context::CodegenCx;
 use monomorphize::partitioning::CodegenUnitExt;
-use rustc_codegen_ssa::mono_item::MonoItemExt;
+use offload;
+use rustc_codegen_ssa::mono_item::{BaseMonoItemExt, MonoItemExt};
+use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::small_c_str::SmallCStr;
 
 use rustc_codegen_ssa::traits::*;
 use rustc_codegen_ssa::back::write::submit_codegened_module_to_llvm;
 
+use serialize::json;
 use std::ffi::CString;
+use std::fs;
 use std::time::Instant;
 use syntax_pos::symbol::InternedString;
 use rustc::hir::CodegenFnAttrs;
+use rustc::hir::def_id::LOCAL_CRATE;
 
 use value::Value;
 
@@ -177,21 +183,59 @@ pub fn compile_codegen_unit<'ll, 'tcx>(tcx: TyCtxt<'ll, 'tcx, 'tcx>,
         let llvm_module = backend.new_metadata(tcx.sess, &cgu_name.as_str());
         let stats = {
             let cx = CodegenCx::new(tcx, cgu, &llvm_module);
-            let mono_items = cx.codegen_unit
-                               .items_in_deterministic_order(cx.tcx);
+            let mut mono_items = cx.codegen_unit
+                                   .items_in_deterministic_order(cx.tcx);
+
+            // Define the functions listed in `-Z symbol-ordering-file` first,
+            // in the listed order, so that they're next to each other.
+            let symbol_order: FxHashMap<&str, usize> = cx.sess().symbol_order()
+                .iter()
+                .enumerate()
+                .map(|(i, symbol)| (&symbol[..], i))
+                .collect();
+            if !symbol_order.is_empty() {
+                mono_items.sort_by_key(|&(mono_item, _)| {
+                    let symbol_name = mono_item.symbol_name(cx.tcx);
+                    symbol_order.get(&*symbol_name.as_str()).cloned().unwrap_or(usize::MAX)
+                });
+            }
+
+            let aliases = cx.codegen_unit.aliases();
             for &(mono_item, (linkage, visibility)) in &mono_items {
-                mono_item.predefine::<Builder>(&cx, linkage, visibility);
+                if !aliases.contains_key(&mono_item) {
+                    mono_item.predefine::<Builder>(&cx, linkage, visibility);
+                }
+            }
+            for &(mono_item, (linkage, _)) in &mono_items {
+                if let Some(&target) = aliases.get(&mono_item) {
+                    define_alias(&cx, mono_item, target, linkage);
+                }
+            }
+
+            if !symbol_order.is_empty() {
+                place_hot_functions(&cx, &mono_items, &symbol_order);
             }
 
             // ... and now that we have everything pre-defined, fill out those definitions.
             for &(mono_item, _) in &mono_items {
-                mono_item.define::<Builder>(&cx);
+                if !aliases.contains_key(&mono_item) {
+                    mono_item.define::<Builder>(&cx);
+                }
             }
 
             // If this codegen unit contains the main function, also create the
             // wrapper here
             maybe_create_entry_wrapper::<Builder>(&cx);
 
+            // The device image goes into a single codegen unit, the first one by
+            // name, so that it's only embedded once.
+            if let Some(TrackedFile(ref image)) = cx.sess().opts.debugging_opts.offload_image {
+                let cgus = tcx.collect_and_partition_mono_items(LOCAL_CRATE).1;
+                if cgus.iter().map(|cgu| cgu.name()).min() == Some(&cgu_name) {
+                    offload::embed_image(&cx, image);
+                }
+            }
+
             // Run replace-all-uses-with for statics that need it
             for &(old_g, new_g) in cx.statics_to_rauw().borrow().iter() {
                 unsafe {
@@ -221,6 +265,195 @@ pub fn compile_codegen_unit<'ll, 'tcx>(tcx: TyCtxt<'ll, 'tcx, 'tcx>,
     }
 }
 
+/// Writes how each function instance of the crate is called, as computed by
+/// `FnType`, to `<crate>.fn-abi.json` for `-Z dump-fn-abi`. The functions are
+/// sorted by symbol name, so the file only changes when an ABI does.
+pub fn dump_fn_abis<'ll, 'tcx>(tcx: TyCtxt<'ll, 'tcx, 'tcx>) {
+    let cgus = tcx.collect_and_partition_mono_items(LOCAL_CRATE).1;
+    let cgu = match cgus.first() {
+        Some(cgu) => cgu.clone(),
+        None => return,
+    };
+    let mut instances: Vec<_> = cgus.iter()
+        .flat_map(|cgu| cgu.items().keys())
+        .filter_map(|mono_item| match *mono_item {
+            MonoItem::Fn(instance) => Some((mono_item.symbol_name(tcx), instance)),
+            _ => None,
+        })
+        .collect();
+    instances.sort_by_key(|&(symbol_name, _)| symbol_name);
+    instances.dedup_by_key(|&mut (symbol_name, _)| symbol_name);
+
+    // Computing an ABI needs a context, but nothing is codegened into it.
+    let llvm_module = ModuleLlvm::new(tcx.sess, "fn_abi");
+    let cx = CodegenCx::new(tcx, cgu, &llvm_module);
+    let functions = instances.iter().map(|&(symbol_name, instance)| {
+        let fn_ty = FnType::of_instance(&cx, &instance);
+        FnAbiDump {
+            symbol: symbol_name.as_str().to_string(),
+            instance: instance.to_string(),
+            conv: conv_name(fn_ty.conv),
+            variadic: fn_ty.variadic,
+            ret: ArgAbiDump::new(&cx, &fn_ty.ret),
+            args: fn_ty.args.iter().map(|arg| ArgAbiDump::new(&cx, arg)).collect(),
+        }
+    }).collect();
+
+    let dump = format!("{}\n", json::as_pretty_json(&FnAbisDump { functions }));
+    let path = tcx.output_filenames(LOCAL_CRATE).with_extension("fn-abi.json");
+    if let Err(e) = fs::write(&path, dump) {
+        tcx.sess.err(&format!("failed to write `{}`: {}", path.display(), e));
+    }
+}
+
+#[derive(RustcEncodable)]
+struct FnAbisDump {
+    functions: Vec<FnAbiDump>,
+}
+
+#[derive(RustcEncodable)]
+struct FnAbiDump {
+    symbol: String,
+    instance: String,
+    conv: &'static str,
+    variadic: bool,
+    ret: ArgAbiDump,
+    args: Vec<ArgAbiDump>,
+}
+
+/// How a single argument or the return value is passed. `attrs` has one list
+/// of attributes for each LLVM argument it is passed as, so it is empty for
+/// `ignore` and `cast`, and has two entries for `pair` and for unsized
+/// `indirect` arguments. `cast` is only set for `cast`.
+#[derive(RustcEncodable)]
+struct ArgAbiDump {
+    ty: String,
+    size: u64,
+    align: u64,
+    mode: &'static str,
+    attrs: Vec<Vec<&'static str>>,
+    cast: Option<String>,
+}
+
+/// The name of the `extern` ABI using this calling convention.
+fn conv_name(conv: Conv) -> &'static str {
+    match conv {
+        Conv::C => "C",
+        Conv::ArmAapcs => "aapcs",
+        Conv::Msp430Intr => "msp430-interrupt",
+        Conv::PtxKernel => "ptx-kernel",
+        Conv::X86Fastcall => "fastcall",
+        Conv::X86Intr => "x86-interrupt",
+        Conv::X86Stdcall => "stdcall",
+        Conv::X86ThisCall => "thiscall",
+        Conv::X86VectorCall => "vectorcall",
+        Conv::X86_64SysV => "sysv64",
+        Conv::X86_64Win64 => "win64",
+        Conv::AmdGpuKernel => "amdgpu-kernel",
+    }
+}
+
+impl ArgAbiDump {
+    fn new(cx: &CodegenCx<'ll, 'tcx>, arg: &ArgType<'tcx, Ty<'tcx>>) -> ArgAbiDump {
+        let (mode, attrs, cast) = match arg.mode {
+            PassMode::Ignore => ("ignore", vec![], None),
+            PassMode::Direct(ref attrs) => ("direct", vec![attr_names(attrs)], None),
+            PassMode::Pair(ref a, ref b) => ("pair", vec![attr_names(a), attr_names(b)], None),
+            PassMode::Cast(cast) => {
+                ("cast", vec![], Some(format!("{:?}", cast.llvm_type(cx))))
+            }
+            PassMode::Indirect(ref attrs, None) => ("indirect", vec![attr_names(attrs)], None),
+            PassMode::Indirect(ref attrs, Some(ref extra_attrs)) => {
+                ("indirect", vec![attr_names(attrs), attr_names(extra_attrs)], None)
+            }
+        };
+        ArgAbiDump {
+            ty: arg.layout.ty.to_string(),
+            size: arg.layout.size.bytes(),
+            align: arg.layout.align.abi.bytes(),
+            mode,
+            attrs,
+            cast,
+        }
+    }
+}
+
+fn attr_names(attrs: &ArgAttributes) -> Vec<&'static str> {
+    let names = [
+        (ArgAttribute::ByVal, "byval"),
+        (ArgAttribute::NoAlias, "noalias"),
+        (ArgAttribute::NoCapture, "nocapture"),
+        (ArgAttribute::NonNull, "nonnull"),
+        (ArgAttribute::ReadOnly, "readonly"),
+        (ArgAttribute::SExt, "signext"),
+        (ArgAttribute::StructRet, "sret"),
+        (ArgAttribute::ZExt, "zeroext"),
+        (ArgAttribute::InReg, "inreg"),
+    ];
+    names.iter()
+        .filter(|&&(attr, _)| attrs.contains(attr))
+        .map(|&(_, name)| name)
+        .collect()
+}
+
+/// Emits `alias` as an alias of the already predefined function `target`.
+fn define_alias(cx: &CodegenCx<'ll, 'tcx>,
+                alias: MonoItem<'tcx>,
+                target: MonoItem<'tcx>,
+                linkage: Linkage) {
+    let (alias, target) = match (alias, target) {
+        (MonoItem::Fn(alias), MonoItem::Fn(target)) => (alias, target),
+        _ => bug!("alias {:?} of {:?} isn't between functions", alias, target),
+    };
+    let llfn = cx.instances.borrow()[&target];
+    let symbol_name = SmallCStr::new(&alias.symbol_name(cx.tcx).as_str());
+    let llalias = unsafe {
+        let llalias = llvm::LLVMAddAlias(cx.llmod, cx.val_ty(llfn), llfn, symbol_name.as_ptr());
+        llvm::LLVMRustSetLinkage(llalias, linkage_to_llvm(linkage));
+        llalias
+    };
+    cx.instances.borrow_mut().insert(alias, llalias);
+}
+
+/// Puts the functions listed in `-Z symbol-ordering-file` in `.text.hot.*`
+/// sections, which the default linker scripts for ELF targets place next to
+/// each other. That only groups them: the listed order is kept within a
+/// codegen unit, but not across codegen units unless the linker is told the
+/// order as well (see `Linker::order_symbols`). Other targets order them
+/// through their linker instead.
+fn place_hot_functions(cx: &CodegenCx<'ll, 'tcx>,
+                       mono_items: &[(MonoItem<'tcx>, (Linkage, Visibility))],
+                       symbol_order: &FxHashMap<&str, usize>) {
+    let target = &cx.sess().target.target;
+    if target.options.is_like_osx || target.options.is_like_windows || target.arch == "wasm32" {
+        return;
+    }
+    for &(mono_item, _) in mono_items {
+        let instance = match mono_item {
+            MonoItem::Fn(instance) => instance,
+            _ => continue,
+        };
+        // Aliases have no body of their own to place.
+        if cx.codegen_unit.aliases().contains_key(&mono_item) {
+            continue;
+        }
+        let symbol_name = mono_item.symbol_name(cx.tcx);
+        if !symbol_order.contains_key(&*symbol_name.as_str()) {
+            continue;
+        }
+        // An explicit `#[link_section]` wins.
+        if cx.tcx.codegen_fn_attrs(instance.def_id()).link_section.is_some() {
+            continue;
+        }
+        if let Some(&llfn) = cx.instances.borrow().get(&instance) {
+            let section = SmallCStr::new(&format!(".text.hot.{}", symbol_name));
+            unsafe {
+                llvm::LLVMSetSection(llfn, section.as_ptr());
+            }
+        }
+    }
+}
+
 pub fn set_link_section(llval: &Value, attrs: &CodegenFnAttrs) {
     let sect = match attrs.link_section {
         Some(name) => name,
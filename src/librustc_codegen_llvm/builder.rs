@@ -10,6 +10,7 @@
 
 use llvm::{AtomicRmwBinOp, AtomicOrdering, SynchronizationScope, AsmDialect};
 use llvm::{self, False, BasicBlock};
+use llvm_util;
 use rustc_codegen_ssa::common::{IntPredicate, TypeKind, RealPredicate};
 use rustc_codegen_ssa::{self, MemFlags};
 use common::Funclet;
@@ -18,6 +19,7 @@ use type_::Type;
 use type_of::LayoutLlvmExt;
 use value::Value;
 use libc::{c_uint, c_char};
+use rustc::mir::LoopHints;
 use rustc::ty::{self, Ty, TyCtxt};
 use rustc::ty::layout::{self, Align, Size, TyLayout};
 use rustc::session::config;
@@ -31,6 +33,7 @@ use std::borrow::Cow;
 use std::ffi::CStr;
 use std::ops::Range;
 use std::ptr;
+use syntax_pos::Span;
 
 // All Builders must have an llfn associated with them
 #[must_use]
@@ -201,6 +204,35 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         }
     }
 
+    fn cond_br_with_expect(
+        &mut self,
+        cond: &'ll Value,
+        then_llbb: &'ll BasicBlock,
+        else_llbb: &'ll BasicBlock,
+        expect: Option<bool>,
+    ) {
+        self.count_insn("condbr");
+        unsafe {
+            let br = llvm::LLVMBuildCondBr(self.llbuilder, cond, then_llbb, else_llbb);
+            if let Some(expect) = expect {
+                // The same weights clang uses for `__builtin_expect`.
+                let (likely, unlikely) = (self.cx.const_u32(2000), self.cx.const_u32(1));
+                let name = "branch_weights";
+                let v = [
+                    llvm::LLVMMDStringInContext(self.cx.llcx,
+                                                name.as_ptr() as *const c_char,
+                                                name.len() as c_uint),
+                    if expect { likely } else { unlikely },
+                    if expect { unlikely } else { likely },
+                ];
+                llvm::LLVMSetMetadata(br, llvm::MD_prof as c_uint,
+                                      llvm::LLVMMDNodeInContext(self.cx.llcx,
+                                                                v.as_ptr(),
+                                                                v.len() as c_uint));
+            }
+        }
+    }
+
     fn switch(
         &mut self,
         v: &'ll Value,
@@ -230,7 +262,7 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         let bundle = funclet.map(|funclet| funclet.bundle());
         let bundle = bundle.as_ref().map(|b| &*b.raw);
 
-        unsafe {
+        let invoke = unsafe {
             llvm::LLVMRustBuildInvoke(self.llbuilder,
                                       llfn,
                                       args.as_ptr(),
@@ -239,7 +271,9 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
                                       catch,
                                       bundle,
                                       noname())
-        }
+        };
+        self.forbid_strict_fp_inlining(invoke);
+        invoke
     }
 
     fn unreachable(&mut self) {
@@ -259,6 +293,9 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
 
     fn fadd(&mut self, lhs: &'ll Value, rhs: &'ll Value) -> &'ll Value {
         self.count_insn("fadd");
+        if let Some(val) = self.constrained_fp_op("fadd", &[lhs, rhs]) {
+            return val;
+        }
         unsafe {
             llvm::LLVMBuildFAdd(self.llbuilder, lhs, rhs, noname())
         }
@@ -282,6 +319,9 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
 
     fn fsub(&mut self, lhs: &'ll Value, rhs: &'ll Value) -> &'ll Value {
         self.count_insn("fsub");
+        if let Some(val) = self.constrained_fp_op("fsub", &[lhs, rhs]) {
+            return val;
+        }
         unsafe {
             llvm::LLVMBuildFSub(self.llbuilder, lhs, rhs, noname())
         }
@@ -305,6 +345,9 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
 
     fn fmul(&mut self, lhs: &'ll Value, rhs: &'ll Value) -> &'ll Value {
         self.count_insn("fmul");
+        if let Some(val) = self.constrained_fp_op("fmul", &[lhs, rhs]) {
+            return val;
+        }
         unsafe {
             llvm::LLVMBuildFMul(self.llbuilder, lhs, rhs, noname())
         }
@@ -350,6 +393,9 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
 
     fn fdiv(&mut self, lhs: &'ll Value, rhs: &'ll Value) -> &'ll Value {
         self.count_insn("fdiv");
+        if let Some(val) = self.constrained_fp_op("fdiv", &[lhs, rhs]) {
+            return val;
+        }
         unsafe {
             llvm::LLVMBuildFDiv(self.llbuilder, lhs, rhs, noname())
         }
@@ -380,6 +426,9 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
 
     fn frem(&mut self, lhs: &'ll Value, rhs: &'ll Value) -> &'ll Value {
         self.count_insn("frem");
+        if let Some(val) = self.constrained_fp_op("frem", &[lhs, rhs]) {
+            return val;
+        }
         unsafe {
             llvm::LLVMBuildFRem(self.llbuilder, lhs, rhs, noname())
         }
@@ -549,6 +598,14 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
             return OperandRef::new_zst(self.cx(), place.layout);
         }
 
+        if let Some(bit_field) = place.bit_field {
+            let llval = place.load_bit_field(self, bit_field);
+            return OperandRef {
+                val: OperandValue::Immediate(llval),
+                layout: place.layout
+            };
+        }
+
         fn scalar_load_metadata<'a, 'll, 'tcx>(
             bx: &mut Builder<'a, 'll, 'tcx>,
             load: &'ll Value,
@@ -1004,12 +1061,17 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         }
     }
 
+    fn vector_reduce_fadd(&mut self, acc: &'ll Value, src: &'ll Value) -> &'ll Value {
+        self.count_insn("vector.reduce.fadd");
+        unsafe { llvm::LLVMRustBuildVectorReduceFAdd(self.llbuilder, acc, src) }
+    }
+    fn vector_reduce_fmul(&mut self, acc: &'ll Value, src: &'ll Value) -> &'ll Value {
+        self.count_insn("vector.reduce.fmul");
+        unsafe { llvm::LLVMRustBuildVectorReduceFMul(self.llbuilder, acc, src) }
+    }
     fn vector_reduce_fadd_fast(&mut self, acc: &'ll Value, src: &'ll Value) -> &'ll Value {
         self.count_insn("vector.reduce.fadd_fast");
         unsafe {
-            // FIXME: add a non-fast math version once
-            // https://bugs.llvm.org/show_bug.cgi?id=36732
-            // is fixed.
             let instr = llvm::LLVMRustBuildVectorReduceFAdd(self.llbuilder, acc, src);
             llvm::LLVMRustSetHasUnsafeAlgebra(instr);
             instr
@@ -1018,9 +1080,6 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
     fn vector_reduce_fmul_fast(&mut self, acc: &'ll Value, src: &'ll Value) -> &'ll Value {
         self.count_insn("vector.reduce.fmul_fast");
         unsafe {
-            // FIXME: add a non-fast math version once
-            // https://bugs.llvm.org/show_bug.cgi?id=36732
-            // is fixed.
             let instr = llvm::LLVMRustBuildVectorReduceFMul(self.llbuilder, acc, src);
             llvm::LLVMRustSetHasUnsafeAlgebra(instr);
             instr
@@ -1377,7 +1436,7 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         let bundle = funclet.map(|funclet| funclet.bundle());
         let bundle = bundle.as_ref().map(|b| &*b.raw);
 
-        unsafe {
+        let call = unsafe {
             llvm::LLVMRustBuildCall(
                 self.llbuilder,
                 llfn,
@@ -1385,7 +1444,9 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
                 args.len() as c_uint,
                 bundle, noname()
             )
-        }
+        };
+        self.forbid_strict_fp_inlining(call);
+        call
     }
 
     fn zext(&mut self, val: &'ll Value, dest_ty: &'ll Type) -> &'ll Value {
@@ -1411,7 +1472,92 @@ impl BuilderMethods<'a, 'tcx> for Builder<'a, 'll, 'tcx> {
         llvm::LLVMDeleteBasicBlock(bb);
     }
 
+    fn set_loop_hints(&mut self, header: &'ll BasicBlock, hints: &LoopHints, span: Span) {
+        let mut hints = *hints;
+        // LLVM only knows `llvm.loop.pipeline.*` since version 9.
+        if hints.has_pipeline_hints() && llvm_util::get_major_version() < 9 {
+            self.cx().sess().span_warn(span, "`#[pipeline]` needs LLVM 9 or later and is ignored");
+            hints.pipeline_disable = false;
+            hints.pipeline_initiation_interval = None;
+            if hints.is_empty() {
+                return;
+            }
+        }
+        unsafe {
+            llvm::LLVMRustSetLoopHints(header,
+                                       hints.unroll_count.unwrap_or(0),
+                                       hints.no_unroll,
+                                       hints.pipeline_disable,
+                                       hints.pipeline_initiation_interval.unwrap_or(0));
+        }
+    }
+
     fn do_not_inline(&mut self, llret: &'ll Value) {
         llvm::Attribute::NoInline.apply_callsite(llvm::AttributePlace::Function, llret);
     }
+
+    fn always_inline(&mut self, llret: &'ll Value) {
+        llvm::Attribute::AlwaysInline.apply_callsite(llvm::AttributePlace::Function, llret);
+    }
+}
+
+impl Builder<'a, 'll, 'tcx> {
+    /// Whether the function being built accesses the floating-point
+    /// environment, see `attributes::strict_fp`.
+    fn in_strict_fp_fn(&self) -> bool {
+        let strict_fp_fns = self.cx.strict_fp_fns.borrow();
+        !strict_fp_fns.is_empty() && strict_fp_fns.contains(self.llfn())
+    }
+
+    /// Keeps calls in functions that access the floating-point environment
+    /// from being inlined, which would bring in unconstrained operations.
+    /// `#[inline(always)]` functions are still inlined.
+    fn forbid_strict_fp_inlining(&self, call: &'ll Value) {
+        if self.in_strict_fp_fn() {
+            llvm::Attribute::NoInline.apply_callsite(llvm::AttributePlace::Function, call);
+        }
+    }
+
+    /// In functions that access the floating-point environment, emits the
+    /// floating-point operation `op` on `args`, scalars or vectors of `f32`
+    /// or `f64`, as a constrained intrinsic which respects the dynamic
+    /// rounding mode and may raise exceptions, so that LLVM neither folds it
+    /// nor moves it across those accesses.
+    ///
+    /// LLVM 8 has no constrained conversions or comparisons, so those stay
+    /// unconstrained.
+    pub fn constrained_fp_op(&mut self, op: &str, args: &[&'ll Value]) -> Option<&'ll Value> {
+        if !self.in_strict_fp_fn() {
+            return None;
+        }
+        let ty = self.cx.val_ty(args[0]);
+        let (elem_ty, prefix) = match self.cx.type_kind(ty) {
+            TypeKind::Vector => {
+                (self.cx.element_type(ty), format!("v{}", self.cx.vector_length(ty)))
+            }
+            _ => (ty, String::new()),
+        };
+        let width = match self.cx.type_kind(elem_ty) {
+            TypeKind::Float => 32,
+            TypeKind::Double => 64,
+            _ => return None,
+        };
+        let t_md = self.cx.type_metadata();
+        let mut arg_tys: Vec<_> = args.iter().map(|&arg| self.cx.val_ty(arg)).collect();
+        arg_tys.extend(&[t_md, t_md]);
+        let llfn = self.cx.declare_cfn(
+            &format!("llvm.experimental.constrained.{}.{}f{}", op, prefix, width),
+            self.cx.type_func(&arg_tys, ty),
+        );
+        let llcx = self.cx.llcx;
+        let metadata = |s: &str| unsafe {
+            llvm::LLVMMDStringInContext(llcx, s.as_ptr() as *const c_char, s.len() as c_uint)
+        };
+        let mut args = args.to_vec();
+        args.push(metadata("round.dynamic"));
+        args.push(metadata("fpexcept.strict"));
+        let val = self.call(llfn, &args, None);
+        llvm::Attribute::StrictFP.apply_callsite(llvm::AttributePlace::Function, val);
+        Some(val)
+    }
 }
@@ -14,15 +14,17 @@
 //! and methods are represented as just a fn ptr and not a full
 //! closure.
 
+use abi::{FnType, FnTypeExt};
 use attributes;
 use llvm;
 use monomorphize::Instance;
 use context::CodegenCx;
+use declare;
 use value::Value;
 use rustc_codegen_ssa::traits::*;
 
 use rustc::ty::TypeFoldable;
-use rustc::ty::layout::{LayoutOf, HasTyCtxt};
+use rustc::ty::layout::HasTyCtxt;
 
 /// Codegens a reference to a fn/method item, monomorphizing and
 /// inlining as it goes.
@@ -51,9 +53,9 @@ pub fn get_fn(
     let sym = tcx.symbol_name(instance).as_str();
     debug!("get_fn({:?}: {:?}) => {}", instance, sig, sym);
 
-    // Create a fn pointer with the substituted signature.
-    let fn_ptr_ty = tcx.mk_fn_ptr(sig);
-    let llptrty = cx.backend_type(cx.layout_of(fn_ptr_ty));
+    // Create a fn pointer with the substituted signature. Its ABI may depend on
+    // the target features the function enables, unlike that of fn pointers.
+    let llptrty = cx.fn_ptr_backend_type(&FnType::of_instance(cx, &instance));
 
     let llfn = if let Some(llfn) = cx.get_declared_value(&sym) {
         // This is subtle and surprising, but sometimes we have to bitcast
@@ -87,9 +89,7 @@ pub fn get_fn(
             llfn
         }
     } else {
-        let llfn = cx.declare_fn(&sym, sig);
-        assert_eq!(cx.val_ty(llfn), llptrty);
-        debug!("get_fn: not casting pointer!");
+        let llfn = declare::declare_instance_fn(cx, &sym, instance);
 
         if instance.def.is_inline(tcx) {
             attributes::inline(cx, llfn, attributes::InlineAttr::Hint);
@@ -150,6 +150,10 @@ pub fn get_fn(
                                  .unwrap_or(false) {
                             // This is instantiated in another crate. It cannot
                             // be `hidden`.
+                            if tcx.sess.opts.debugging_opts.perf_stats {
+                                tcx.sess.perf_stats.shared_generics.lock()
+                                    .insert(sym.to_string());
+                            }
                         } else {
                             // This is a local instantiation of an upstream definition.
                             // If the current crate does not re-export it
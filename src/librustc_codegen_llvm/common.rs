@@ -136,6 +136,12 @@ impl ConstMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         }
     }
 
+    fn const_real(&self, t: &'ll Type, val: f64) -> &'ll Value {
+        unsafe {
+            llvm::LLVMConstReal(t, val)
+        }
+    }
+
     fn const_bool(&self, val: bool) -> &'ll Value {
         self.const_uint(self.type_i1(), val as u64)
     }
@@ -172,7 +178,8 @@ impl ConstMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         null_terminated: bool,
     ) -> &'ll Value {
         unsafe {
-            if let Some(&llval) = self.const_cstr_cache.borrow().get(&s) {
+            let key = (s.clone(), null_terminated);
+            if let Some(&llval) = self.const_cstr_cache.borrow().get(&key) {
                 return llval;
             }
 
@@ -187,8 +194,13 @@ impl ConstMethods<'tcx> for CodegenCx<'ll, 'tcx> {
             llvm::LLVMSetInitializer(g, sc);
             llvm::LLVMSetGlobalConstant(g, True);
             llvm::LLVMRustSetLinkage(g, llvm::Linkage::InternalLinkage);
+            if null_terminated {
+                // Lets LLVM put the string into a mergeable section, where
+                // the linker deduplicates it with other copies.
+                llvm::SetUnnamedAddr(g, true);
+            }
 
-            self.const_cstr_cache.borrow_mut().insert(s, g);
+            self.const_cstr_cache.borrow_mut().insert(key, g);
             g
         }
     }
@@ -332,7 +344,8 @@ impl ConstMethods<'tcx> for CodegenCx<'ll, 'tcx> {
                     }
                     Some(AllocType::Static(def_id)) => {
                         assert!(self.tcx.is_static(def_id).is_some());
-                        self.get_static(def_id)
+                        // The static may be in another address space.
+                        self.static_ptrcast(self.get_static(def_id), self.type_i8p())
                     }
                     None => bug!("missing allocation {:?}", ptr.alloc_id),
                 };
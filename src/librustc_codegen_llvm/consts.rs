@@ -11,21 +11,26 @@
 use libc::c_uint;
 use llvm::{self, SetUnnamedAddr, True};
 use rustc::hir::def_id::DefId;
-use rustc::mir::interpret::{ConstValue, Allocation, read_target_uint,
+use rustc::mir::interpret::{ConstValue, Allocation, AllocType, read_target_uint,
     Pointer, ErrorHandled, GlobalId};
 use rustc::hir::Node;
 use debuginfo;
 use monomorphize::MonoItem;
 use common::CodegenCx;
+use context::{get_reloc_model, is_pie_binary};
 use monomorphize::Instance;
 use syntax_pos::Span;
 use rustc_target::abi::HasDataLayout;
 use syntax_pos::symbol::LocalInternedString;
 use base;
+use declare;
 use type_::Type;
 use type_of::LayoutLlvmExt;
 use value::Value;
 use rustc::ty::{self, Ty};
+use rustc::util::nodemap::FxHashSet;
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_codegen_ssa::traits::*;
 
 use rustc::ty::layout::{self, Size, Align, LayoutOf};
@@ -33,6 +38,51 @@ use rustc::ty::layout::{self, Size, Align, LayoutOf};
 use rustc::hir::{self, CodegenFnAttrs, CodegenFnAttrFlags};
 
 use std::ffi::{CStr, CString};
+use std::hash::Hash;
+
+/// Whether pointers to the static `def_id` in the initializers of other
+/// statics can go through a private alias of it, see `local_static_alias`.
+fn can_alias_static_locally(cx: &CodegenCx<'ll, '_>, def_id: DefId) -> bool {
+    let tcx = cx.tcx;
+    let target = &tcx.sess.target.target.options;
+    // Mach-O and COFF don't interpose symbols of the same image anyway.
+    if target.is_like_osx || target.is_like_windows {
+        return false;
+    }
+    get_reloc_model(tcx.sess) == llvm::RelocMode::PIC &&
+        !is_pie_binary(tcx.sess) &&
+        def_id.is_local() &&
+        !tcx.is_foreign_item(def_id) &&
+        tcx.is_reachable_non_generic(def_id) &&
+        !tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::THREAD_LOCAL) &&
+        cx.codegen_unit.items().contains_key(&MonoItem::Static(def_id))
+}
+
+/// In a dynamic library, a pointer to an exported static needs a symbol
+/// lookup when the library is loaded, since an executable could interpose
+/// its own definition. Rust doesn't support interposing statics, so if
+/// `def_id` is defined in this codegen unit the initializers of other statics
+/// point to a private alias of it instead, which only needs a relative
+/// relocation. Exported statics of other codegen units and functions still
+/// need the lookup.
+fn local_static_alias(cx: &CodegenCx<'ll, '_>, def_id: DefId) -> Option<&'ll Value> {
+    if !can_alias_static_locally(cx, def_id) {
+        return None;
+    }
+    if let Some(&alias) = cx.local_static_aliases.borrow().get(&def_id) {
+        return Some(alias);
+    }
+
+    let g = cx.get_static(def_id);
+    let name = CString::new(cx.generate_local_symbol_name("static_alias")).unwrap();
+    let alias = unsafe {
+        let alias = llvm::LLVMAddAlias(cx.llmod, cx.val_ty(g), g, name.as_ptr());
+        llvm::LLVMRustSetLinkage(alias, llvm::Linkage::PrivateLinkage);
+        alias
+    };
+    cx.local_static_aliases.borrow_mut().insert(def_id, alias);
+    Some(alias)
+}
 
 pub fn const_alloc_to_llvm(cx: &CodegenCx<'ll, '_>, alloc: &Allocation) -> &'ll Value {
     let mut llvals = Vec::with_capacity(alloc.relocations.len() + 1);
@@ -51,14 +101,28 @@ pub fn const_alloc_to_llvm(cx: &CodegenCx<'ll, '_>, alloc: &Allocation) -> &'ll
             dl.endian,
             &alloc.bytes[offset..(offset + pointer_size)],
         ).expect("const_alloc_to_llvm: could not read relocation pointer") as u64;
-        llvals.push(cx.scalar_to_backend(
-            Pointer::new(alloc_id, Size::from_bytes(ptr_offset)).into(),
-            &layout::Scalar {
-                value: layout::Primitive::Pointer,
-                valid_range: 0..=!0
+        let alloc_type = cx.tcx.alloc_map.lock().get(alloc_id);
+        let alias = match alloc_type {
+            Some(AllocType::Static(def_id)) => local_static_alias(cx, def_id),
+            _ => None,
+        };
+        llvals.push(match alias {
+            Some(alias) => unsafe {
+                llvm::LLVMConstInBoundsGEP(
+                    cx.static_ptrcast(alias, cx.type_i8p()),
+                    &cx.const_usize(ptr_offset),
+                    1,
+                )
             },
-            cx.type_i8p()
-        ));
+            None => cx.scalar_to_backend(
+                Pointer::new(alloc_id, Size::from_bytes(ptr_offset)).into(),
+                &layout::Scalar {
+                    value: layout::Primitive::Pointer,
+                    valid_range: 0..=!0
+                },
+                cx.type_i8p()
+            ),
+        });
         next_offset = offset + pointer_size;
     }
     if alloc.bytes.len() >= next_offset {
@@ -87,6 +151,62 @@ pub fn codegen_static_initializer(
     Ok((const_alloc_to_llvm(cx, alloc), alloc))
 }
 
+/// Prints the `-Z relro-report` line of the static `def_id`, if its
+/// initializer contains pointers. The dynamic loader has to relocate each of
+/// them, which dirties the page it's on even if the static is read-only
+/// afterwards. Pointers into this crate that aren't exported from a dynamic
+/// library, or that point to a static of the same codegen unit, get cheap
+/// relative relocations, the others may need a symbol lookup. Pointers in
+/// anonymous memory the static refers to, e.g. the elements of a `&[&str]`,
+/// are counted too.
+fn report_relocations(cx: &CodegenCx<'ll, 'tcx>,
+                      def_id: DefId,
+                      alloc: &'tcx Allocation,
+                      writable: bool) {
+    let tcx = cx.tcx;
+    let is_relative = |target: DefId, has_substs: bool| {
+        let local_copy = target.is_local() || (has_substs && !tcx.sess.opts.share_generics());
+        local_copy && (is_pie_binary(tcx.sess) || !tcx.is_reachable_non_generic(target))
+    };
+
+    let mut relative = 0;
+    let mut symbolic = 0;
+    let mut seen = FxHashSet::default();
+    let mut todo = vec![alloc];
+    while let Some(alloc) = todo.pop() {
+        for &(_, ((), alloc_id)) in alloc.relocations.iter() {
+            let relative_reloc = match tcx.alloc_map.lock().get(alloc_id) {
+                Some(AllocType::Memory(nested)) => {
+                    if seen.insert(alloc_id) {
+                        todo.push(nested);
+                    }
+                    true
+                }
+                Some(AllocType::Function(instance)) => {
+                    is_relative(instance.def_id(), instance.substs.types().next().is_some())
+                }
+                Some(AllocType::Static(target)) => {
+                    is_relative(target, false) || can_alias_static_locally(cx, target)
+                }
+                None => bug!("missing allocation {:?}", alloc_id),
+            };
+            if relative_reloc {
+                relative += 1;
+            } else {
+                symbolic += 1;
+            }
+        }
+    }
+
+    if relative + symbolic > 0 {
+        println!("relro-report static `{}`: {} relative and {} symbolic relocations, {}",
+                 tcx.item_path_str(def_id),
+                 relative,
+                 symbolic,
+                 if writable { "in writable memory" } else { "read-only after relocation" });
+    }
+}
+
 fn set_global_alignment(cx: &CodegenCx<'ll, '_>,
                         gv: &'ll Value,
                         mut align: Align) {
@@ -106,6 +226,18 @@ fn set_global_alignment(cx: &CodegenCx<'ll, '_>,
     }
 }
 
+/// The address space a static of type `ty` is placed in: the one given by
+/// its `#[address_space]` attribute, or that of its type, or the target's
+/// default for globals.
+fn static_address_space(cx: &CodegenCx<'ll, 'tcx>, attrs: &CodegenFnAttrs, ty: Ty<'tcx>) -> u32 {
+    attrs.address_space
+        .or_else(|| match ty.sty {
+            ty::Adt(def, _) => def.address_space(cx.tcx),
+            _ => None,
+        })
+        .unwrap_or(cx.data_layout().globals_address_space)
+}
+
 fn check_and_apply_linkage(
     cx: &CodegenCx<'ll, 'tcx>,
     attrs: &CodegenFnAttrs,
@@ -114,6 +246,7 @@ fn check_and_apply_linkage(
     span: Option<Span>
 ) -> &'ll Value {
     let llty = cx.layout_of(ty).llvm_type(cx);
+    let address_space = static_address_space(cx, attrs, ty);
     if let Some(linkage) = attrs.linkage {
         debug!("get_static: sym={} linkage={:?}", sym, linkage);
 
@@ -133,7 +266,7 @@ fn check_and_apply_linkage(
         };
         unsafe {
             // Declare a symbol `foo` with the desired linkage.
-            let g1 = cx.declare_global(&sym, llty2);
+            let g1 = declare::declare_global_in_address_space(cx, &sym, llty2, address_space);
             llvm::LLVMRustSetLinkage(g1, base::linkage_to_llvm(linkage));
 
             // Declare an internal global `extern_with_linkage_foo` which
@@ -155,13 +288,13 @@ fn check_and_apply_linkage(
                 }
             });
             llvm::LLVMRustSetLinkage(g2, llvm::Linkage::InternalLinkage);
-            llvm::LLVMSetInitializer(g2, g1);
+            llvm::LLVMSetInitializer(g2, ptrcast(g1, llty));
             g2
         }
     } else {
         // Generate an external declaration.
         // FIXME(nagisa): investigate whether it can be changed into define_global
-        cx.declare_global(&sym, llty)
+        declare::declare_global_in_address_space(cx, &sym, llty, address_space)
     }
 }
 
@@ -234,6 +367,68 @@ impl StaticMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         gv
     }
 
+    fn panic_location(
+        &self,
+        msg: Option<LocalInternedString>,
+        file: LocalInternedString,
+        line: u32,
+        col: u32,
+    ) -> &'ll Value {
+        let key = (msg.clone(), file.clone(), line, col);
+        if let Some(&gv) = self.panic_locations.borrow().get(&key) {
+            return gv;
+        }
+
+        // The strings are NUL-terminated so that they go into mergeable
+        // sections, which lets the linker share them between all the panics
+        // in the program with the same message or in the same file.
+        let str_slice = |s: LocalInternedString| {
+            let len = s.len();
+            let cs = ptrcast(self.const_cstr(s, true),
+                             self.type_ptr_to(self.layout_of(self.tcx.mk_str()).llvm_type(self)));
+            self.const_fat_ptr(cs, self.const_usize(len as u64))
+        };
+        let mut fields = Vec::with_capacity(4);
+        if let Some(msg) = msg.clone() {
+            fields.push(str_slice(msg));
+        }
+        fields.push(str_slice(file.clone()));
+        fields.push(self.const_u32(line));
+        fields.push(self.const_u32(col));
+        let cv = self.const_struct(&fields, false);
+
+        // The locations themselves contain pointers, so they can't go into
+        // mergeable sections. Instead, each one gets a name derived from its
+        // contents and is emitted into a COMDAT of its own, so the linker
+        // keeps a single copy for all codegen units and crates.
+        let mut hasher = StableHasher::<Fingerprint>::new();
+        msg.as_ref().map(|msg| &msg[..]).hash(&mut hasher);
+        file[..].hash(&mut hasher);
+        line.hash(&mut hasher);
+        col.hash(&mut hasher);
+        let hash: Fingerprint = hasher.finish();
+        let name = format!("__rust_panic_loc.{}", hash.to_hex());
+
+        let align = self.tcx.data_layout.aggregate_align.abi
+            .max(self.tcx.data_layout.i32_align.abi)
+            .max(self.tcx.data_layout.pointer_align.abi);
+        let gv = self.define_global(&name, self.val_ty(cv)).unwrap_or_else(|| {
+            bug!("symbol `{}` is already defined", name);
+        });
+        unsafe {
+            llvm::LLVMSetInitializer(gv, cv);
+            llvm::LLVMSetGlobalConstant(gv, True);
+            llvm::LLVMRustSetLinkage(gv, llvm::Linkage::LinkOnceODRLinkage);
+            llvm::LLVMRustSetVisibility(gv, llvm::Visibility::Hidden);
+            set_global_alignment(&self, gv, align);
+            SetUnnamedAddr(gv, true);
+        }
+        llvm::SetUniqueComdat(self.llmod, gv);
+
+        self.panic_locations.borrow_mut().insert(key, gv);
+        gv
+    }
+
     fn get_static(&self, def_id: DefId) -> &'ll Value {
         let instance = Instance::mono(self.tcx, def_id);
         if let Some(&g) = self.instances.borrow().get(&instance) {
@@ -264,7 +459,13 @@ impl StaticMethods<'tcx> for CodegenCx<'ll, 'tcx> {
                         span_bug!(span, "Conflicting symbol names for static?");
                     }
 
-                    let g = self.define_global(&sym[..], llty).unwrap();
+                    let fn_attrs = self.tcx.codegen_fn_attrs(def_id);
+                    let g = declare::declare_global_in_address_space(
+                        self,
+                        &sym[..],
+                        llty,
+                        static_address_space(self, &fn_attrs, ty),
+                    );
 
                     if !self.tcx.is_reachable_non_generic(def_id) {
                         unsafe {
@@ -397,8 +598,9 @@ impl StaticMethods<'tcx> for CodegenCx<'ll, 'tcx> {
                 let linkage = llvm::LLVMRustGetLinkage(g);
                 let visibility = llvm::LLVMRustGetVisibility(g);
 
-                let new_g = llvm::LLVMRustGetOrInsertGlobal(
-                    self.llmod, name_string.as_ptr(), val_llty);
+                let address_space = llvm::LLVMGetPointerAddressSpace(self.val_ty(g));
+                let new_g = declare::declare_global_in_address_space(
+                    self, name_string.to_str().unwrap(), val_llty, address_space);
 
                 llvm::LLVMRustSetLinkage(new_g, linkage);
                 llvm::LLVMRustSetVisibility(new_g, visibility);
@@ -420,6 +622,11 @@ impl StaticMethods<'tcx> for CodegenCx<'ll, 'tcx> {
                 }
             }
 
+            if self.tcx.sess.opts.debugging_opts.relro_report &&
+               get_reloc_model(self.tcx.sess) == llvm::RelocMode::PIC {
+                report_relocations(self, def_id, alloc, is_mutable || !self.type_is_freeze(ty));
+            }
+
             debuginfo::create_global_var_metadata(&self, def_id, g);
 
             if attrs.flags.contains(CodegenFnAttrFlags::THREAD_LOCAL) {
@@ -8,11 +8,13 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use allocator;
 use attributes;
 use llvm;
 use llvm_util;
 use rustc::dep_graph::DepGraphSafe;
 use rustc::hir;
+use rustc::hir::def_id::DefId;
 use debuginfo;
 use monomorphize::Instance;
 use value::Value;
@@ -30,8 +32,8 @@ use rustc::session::config::{self, DebugInfo};
 use rustc::session::Session;
 use rustc::ty::layout::{LayoutError, LayoutOf, Size, TyLayout, VariantIdx};
 use rustc::ty::{self, Ty, TyCtxt};
-use rustc::util::nodemap::FxHashMap;
-use rustc_target::spec::{HasTargetSpec, Target};
+use rustc::util::nodemap::{FxHashMap, FxHashSet};
+use rustc_target::spec::{AbortStrategy, HasTargetSpec, Target};
 use rustc_codegen_ssa::callee::resolve_and_get_fn;
 use rustc_codegen_ssa::base::wants_msvc_seh;
 use callee::get_fn;
@@ -62,8 +64,11 @@ pub struct CodegenCx<'ll, 'tcx: 'll> {
     pub instances: RefCell<FxHashMap<Instance<'tcx>, &'ll Value>>,
     /// Cache generated vtables
     pub vtables: RefCell<FxHashMap<(Ty<'tcx>, ty::PolyExistentialTraitRef<'tcx>), &'ll Value>>,
-    /// Cache of constant strings,
-    pub const_cstr_cache: RefCell<FxHashMap<LocalInternedString, &'ll Value>>,
+    /// Cache of constant strings, with or without a trailing NUL
+    pub const_cstr_cache: RefCell<FxHashMap<(LocalInternedString, bool), &'ll Value>>,
+    /// Cache of panic locations, see `StaticMethods::panic_location`
+    pub panic_locations: RefCell<FxHashMap<(Option<LocalInternedString>, LocalInternedString,
+                                            u32, u32), &'ll Value>>,
 
     /// Reverse-direction for const ptrs cast from globals.
     /// Key is a Value holding a *T,
@@ -84,10 +89,22 @@ pub struct CodegenCx<'ll, 'tcx: 'll> {
     /// to constants.)
     pub statics_to_rauw: RefCell<Vec<(&'ll Value, &'ll Value)>>,
 
+    /// Private aliases of exported statics of this codegen unit, that the
+    /// initializers of other statics point to, see `consts::local_static_alias`
+    pub local_static_aliases: RefCell<FxHashMap<DefId, &'ll Value>>,
+
     /// Statics that will be placed in the llvm.used variable
     /// See http://llvm.org/docs/LangRef.html#the-llvm-used-global-variable for details
     pub used_statics: RefCell<Vec<&'ll Value>>,
 
+    /// Functions that access the floating-point environment, whose
+    /// floating-point operations are emitted as constrained intrinsics
+    pub strict_fp_fns: RefCell<FxHashSet<&'ll Value>>,
+
+    /// Stack slot per vector type that `simd_extract`s with a run-time lane
+    /// index go through, for the function they were last allocated in
+    pub simd_index_slots: RefCell<(Option<&'ll Value>, FxHashMap<Ty<'tcx>, &'ll Value>)>,
+
     pub lltypes: RefCell<FxHashMap<(Ty<'tcx>, Option<VariantIdx>), &'ll Type>>,
     pub scalar_lltypes: RefCell<FxHashMap<Ty<'tcx>, &'ll Type>>,
     pub pointee_infos: RefCell<FxHashMap<(Ty<'tcx>, Size), Option<PointeeInfo>>>,
@@ -161,6 +178,8 @@ pub unsafe fn create_module(
     let mod_name = SmallCStr::new(mod_name);
     let llmod = llvm::LLVMModuleCreateWithNameInContext(mod_name.as_ptr(), llcx);
 
+    let mut target_data_layout = sess.target.target.data_layout.clone();
+
     // Ensure the data-layout values hardcoded remain the defaults.
     if sess.target.target.options.is_builtin {
         let tm = ::back::write::create_target_machine(sess, false);
@@ -189,16 +208,26 @@ pub unsafe fn create_module(
         let cfg_llvm_root = option_env!("CFG_LLVM_ROOT").unwrap_or("");
         let custom_llvm_used = cfg_llvm_root.trim() != "";
 
-        if !custom_llvm_used && sess.target.target.data_layout != data_layout {
+        // Our x86_64 layouts align `i128` to 16 bytes like Clang's `__int128`,
+        // which the default layout of the LLVM we're using might not do yet.
+        // The module then keeps LLVM's layout, so that it agrees with the
+        // target machine. Nothing we emit relies on LLVM's alignment of
+        // `i128`: accesses and allocas are explicitly aligned, and
+        // `abi::call::x86_64` aligns `i128` arguments passed on the stack.
+        if sess.target.target.arch == "x86_64" && !data_layout.contains("-i128:128") {
+            target_data_layout = target_data_layout.replace("-i128:128", "");
+        }
+
+        if !custom_llvm_used && target_data_layout != data_layout {
             bug!("data-layout for builtin `{}` target, `{}`, \
                   differs from LLVM default, `{}`",
                  sess.target.target.llvm_target,
-                 sess.target.target.data_layout,
+                 target_data_layout,
                  data_layout);
         }
     }
 
-    let data_layout = SmallCStr::new(&sess.target.target.data_layout);
+    let data_layout = SmallCStr::new(&target_data_layout);
     llvm::LLVMSetDataLayout(llmod, data_layout.as_ptr());
 
     let llvm_target = SmallCStr::new(&sess.target.target.llvm_target);
@@ -298,10 +327,14 @@ impl<'ll, 'tcx> CodegenCx<'ll, 'tcx> {
             instances: Default::default(),
             vtables: Default::default(),
             const_cstr_cache: Default::default(),
+            panic_locations: Default::default(),
             const_unsized: Default::default(),
             const_globals: Default::default(),
             statics_to_rauw: RefCell::new(Vec::new()),
+            local_static_aliases: Default::default(),
             used_statics: RefCell::new(Vec::new()),
+            strict_fp_fns: Default::default(),
+            simd_index_slots: Default::default(),
             lltypes: Default::default(),
             scalar_lltypes: Default::default(),
             pointee_infos: Default::default(),
@@ -411,6 +444,28 @@ impl MiscMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         llfn
     }
 
+    fn abort_fn(&self) -> &'ll Value {
+        match self.sess().target.target.options.abort_strategy {
+            AbortStrategy::Trap => self.get_intrinsic("llvm.trap"),
+            AbortStrategy::DebugTrap => self.get_intrinsic("llvm.debugtrap"),
+            AbortStrategy::Call => {
+                let tcx = self.tcx;
+                let sig = ty::Binder::bind(tcx.mk_fn_sig(
+                    iter::empty(),
+                    tcx.types.never,
+                    false,
+                    hir::Unsafety::Unsafe,
+                    Abi::C
+                ));
+                self.declare_fn("abort", sig)
+            }
+        }
+    }
+
+    fn instrumented_alloc_fn(&self, method: &str, callee: &'ll Value) -> &'ll Value {
+        allocator::instrumented_alloc_fn(self, method, callee)
+    }
+
     fn sess(&self) -> &Session {
         &self.tcx.sess
     }
@@ -537,9 +592,21 @@ impl IntrinsicDeclarationMethods<'tcx> for CodegenCx<'b, 'tcx> {
         ifn!("llvm.memset.p0i8.i32", fn(i8p, t_i8, t_i32, t_i32, i1) -> void);
         ifn!("llvm.memset.p0i8.i64", fn(i8p, t_i8, t_i64, t_i32, i1) -> void);
 
+        ifn!("llvm.memcpy.element.unordered.atomic.p0i8.p0i8.i32",
+             fn(i8p, i8p, t_i32, t_i32) -> void);
+        ifn!("llvm.memcpy.element.unordered.atomic.p0i8.p0i8.i64",
+             fn(i8p, i8p, t_i64, t_i32) -> void);
+
         ifn!("llvm.trap", fn() -> void);
         ifn!("llvm.debugtrap", fn() -> void);
+        ifn!("llvm.sideeffect", fn() -> void);
         ifn!("llvm.frameaddress", fn(t_i32) -> i8p);
+        ifn!("llvm.stacksave", fn() -> i8p);
+        ifn!("llvm.eh.sjlj.setjmp", fn(i8p) -> t_i32);
+        ifn!("llvm.eh.sjlj.longjmp", fn(i8p) -> void);
+
+        ifn!("llvm.wasm.memory.size.i32", fn(t_i32) -> t_i32);
+        ifn!("llvm.wasm.memory.grow.i32", fn(t_i32, t_i32) -> t_i32);
 
         ifn!("llvm.powi.f32", fn(t_f32, t_i32) -> t_f32);
         ifn!("llvm.powi.v2f32", fn(t_v2f32, t_i32) -> t_v2f32);
@@ -682,17 +749,53 @@ impl IntrinsicDeclarationMethods<'tcx> for CodegenCx<'b, 'tcx> {
         ifn!("llvm.ceil.v8f64", fn(t_v8f64) -> t_v8f64);
 
         ifn!("llvm.trunc.f32", fn(t_f32) -> t_f32);
+        ifn!("llvm.trunc.v2f32", fn(t_v2f32) -> t_v2f32);
+        ifn!("llvm.trunc.v4f32", fn(t_v4f32) -> t_v4f32);
+        ifn!("llvm.trunc.v8f32", fn(t_v8f32) -> t_v8f32);
+        ifn!("llvm.trunc.v16f32", fn(t_v16f32) -> t_v16f32);
         ifn!("llvm.trunc.f64", fn(t_f64) -> t_f64);
+        ifn!("llvm.trunc.v2f64", fn(t_v2f64) -> t_v2f64);
+        ifn!("llvm.trunc.v4f64", fn(t_v4f64) -> t_v4f64);
+        ifn!("llvm.trunc.v8f64", fn(t_v8f64) -> t_v8f64);
 
         ifn!("llvm.copysign.f32", fn(t_f32, t_f32) -> t_f32);
         ifn!("llvm.copysign.f64", fn(t_f64, t_f64) -> t_f64);
+
+        ifn!("llvm.minimum.f32", fn(t_f32, t_f32) -> t_f32);
+        ifn!("llvm.minimum.f64", fn(t_f64, t_f64) -> t_f64);
+        ifn!("llvm.maximum.f32", fn(t_f32, t_f32) -> t_f32);
+        ifn!("llvm.maximum.f64", fn(t_f64, t_f64) -> t_f64);
         ifn!("llvm.round.f32", fn(t_f32) -> t_f32);
+        ifn!("llvm.round.v2f32", fn(t_v2f32) -> t_v2f32);
+        ifn!("llvm.round.v4f32", fn(t_v4f32) -> t_v4f32);
+        ifn!("llvm.round.v8f32", fn(t_v8f32) -> t_v8f32);
+        ifn!("llvm.round.v16f32", fn(t_v16f32) -> t_v16f32);
         ifn!("llvm.round.f64", fn(t_f64) -> t_f64);
+        ifn!("llvm.round.v2f64", fn(t_v2f64) -> t_v2f64);
+        ifn!("llvm.round.v4f64", fn(t_v4f64) -> t_v4f64);
+        ifn!("llvm.round.v8f64", fn(t_v8f64) -> t_v8f64);
 
         ifn!("llvm.rint.f32", fn(t_f32) -> t_f32);
+        ifn!("llvm.rint.v2f32", fn(t_v2f32) -> t_v2f32);
+        ifn!("llvm.rint.v4f32", fn(t_v4f32) -> t_v4f32);
+        ifn!("llvm.rint.v8f32", fn(t_v8f32) -> t_v8f32);
+        ifn!("llvm.rint.v16f32", fn(t_v16f32) -> t_v16f32);
         ifn!("llvm.rint.f64", fn(t_f64) -> t_f64);
+        ifn!("llvm.rint.v2f64", fn(t_v2f64) -> t_v2f64);
+        ifn!("llvm.rint.v4f64", fn(t_v4f64) -> t_v4f64);
+        ifn!("llvm.rint.v8f64", fn(t_v8f64) -> t_v8f64);
         ifn!("llvm.nearbyint.f32", fn(t_f32) -> t_f32);
+        ifn!("llvm.nearbyint.v2f32", fn(t_v2f32) -> t_v2f32);
+        ifn!("llvm.nearbyint.v4f32", fn(t_v4f32) -> t_v4f32);
+        ifn!("llvm.nearbyint.v8f32", fn(t_v8f32) -> t_v8f32);
+        ifn!("llvm.nearbyint.v16f32", fn(t_v16f32) -> t_v16f32);
         ifn!("llvm.nearbyint.f64", fn(t_f64) -> t_f64);
+        ifn!("llvm.nearbyint.v2f64", fn(t_v2f64) -> t_v2f64);
+        ifn!("llvm.nearbyint.v4f64", fn(t_v4f64) -> t_v4f64);
+        ifn!("llvm.nearbyint.v8f64", fn(t_v8f64) -> t_v8f64);
+
+        ifn!("llvm.canonicalize.f32", fn(t_f32) -> t_f32);
+        ifn!("llvm.canonicalize.f64", fn(t_f64) -> t_f64);
 
         ifn!("llvm.ctpop.i8", fn(t_i8) -> t_i8);
         ifn!("llvm.ctpop.i16", fn(t_i16) -> t_i16);
@@ -775,11 +878,34 @@ impl IntrinsicDeclarationMethods<'tcx> for CodegenCx<'b, 'tcx> {
         ifn!("llvm.lifetime.end", fn(t_i64, i8p) -> void);
 
         ifn!("llvm.expect.i1", fn(i1, i1) -> i1);
+
+        ifn!("llvm.is.constant.i1", fn(i1) -> i1);
+        ifn!("llvm.is.constant.i8", fn(t_i8) -> i1);
+        ifn!("llvm.is.constant.i16", fn(t_i16) -> i1);
+        ifn!("llvm.is.constant.i32", fn(t_i32) -> i1);
+        ifn!("llvm.is.constant.i64", fn(t_i64) -> i1);
+        ifn!("llvm.is.constant.i128", fn(t_i128) -> i1);
+        ifn!("llvm.is.constant.f32", fn(t_f32) -> i1);
+        ifn!("llvm.is.constant.f64", fn(t_f64) -> i1);
+        ifn!("llvm.is.constant.p0i8", fn(i8p) -> i1);
+
         ifn!("llvm.eh.typeid.for", fn(i8p) -> t_i32);
         ifn!("llvm.localescape", fn(...) -> void);
         ifn!("llvm.localrecover", fn(i8p, i8p, t_i32) -> i8p);
         ifn!("llvm.x86.seh.recoverfp", fn(i8p, i8p) -> i8p);
 
+        // LLVM 9 added the `dynamic` flag to `llvm.objectsize`
+        if llvm_util::get_major_version() >= 9 {
+            ifn!("llvm.objectsize.i32.p0i8", fn(i8p, i1, i1, i1) -> t_i32);
+            ifn!("llvm.objectsize.i64.p0i8", fn(i8p, i1, i1, i1) -> t_i64);
+        } else {
+            ifn!("llvm.objectsize.i32.p0i8", fn(i8p, i1, i1) -> t_i32);
+            ifn!("llvm.objectsize.i64.p0i8", fn(i8p, i1, i1) -> t_i64);
+        }
+
+        ifn!("llvm.x86.sse.stmxcsr", fn(i8p) -> void);
+        ifn!("llvm.x86.sse.ldmxcsr", fn(i8p) -> void);
+
         ifn!("llvm.assume", fn(i1) -> void);
         ifn!("llvm.prefetch", fn(i8p, t_i32, t_i32, t_i32) -> void);
 
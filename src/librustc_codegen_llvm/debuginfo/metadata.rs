@@ -35,8 +35,8 @@ use rustc_data_structures::fingerprint::Fingerprint;
 use rustc::ty::Instance;
 use common::CodegenCx;
 use rustc::ty::{self, AdtKind, ParamEnv, Ty, TyCtxt};
-use rustc::ty::layout::{self, Align, HasDataLayout, Integer, IntegerExt, LayoutOf,
-                        PrimitiveExt, Size, TyLayout};
+use rustc::ty::layout::{self, Align, BitField, Endian, HasDataLayout, Integer, IntegerExt,
+                        LayoutOf, PrimitiveExt, Size, TyLayout};
 use rustc::session::config;
 use rustc::util::nodemap::FxHashMap;
 use rustc_fs_util::path2cstr;
@@ -358,6 +358,7 @@ fn vec_slice_metadata(
             align: pointer_align,
             flags: DIFlags::FlagZero,
             discriminant: None,
+            bit_field: None,
         },
         MemberDescription {
             name: "length".to_owned(),
@@ -367,6 +368,7 @@ fn vec_slice_metadata(
             align: usize_align,
             flags: DIFlags::FlagZero,
             discriminant: None,
+            bit_field: None,
         },
     ];
 
@@ -468,6 +470,7 @@ fn trait_pointer_metadata(
             align: data_ptr_field.align.abi,
             flags: DIFlags::FlagArtificial,
             discriminant: None,
+            bit_field: None,
         },
         MemberDescription {
             name: "vtable".to_owned(),
@@ -477,6 +480,7 @@ fn trait_pointer_metadata(
             align: vtable_field.align.abi,
             flags: DIFlags::FlagArtificial,
             discriminant: None,
+            bit_field: None,
         },
     ];
 
@@ -926,6 +930,8 @@ struct MemberDescription<'ll> {
     align: Align,
     flags: DIFlags,
     discriminant: Option<u64>,
+    /// Where the bits are if this member is a bit-field.
+    bit_field: Option<BitField>,
 }
 
 // A factory for MemberDescriptions. It produces a list of member descriptions
@@ -993,6 +999,7 @@ impl<'tcx> StructMemberDescriptionFactory<'tcx> {
                 align: field.align.abi,
                 flags: DIFlags::FlagZero,
                 discriminant: None,
+                bit_field: layout.fields.bit_field(i),
             }
         }).collect()
     }
@@ -1059,6 +1066,7 @@ impl<'tcx> TupleMemberDescriptionFactory<'tcx> {
                 align,
                 flags: DIFlags::FlagZero,
                 discriminant: None,
+                bit_field: None,
             }
         }).collect()
     }
@@ -1116,6 +1124,7 @@ impl<'tcx> UnionMemberDescriptionFactory<'tcx> {
                 align: field.align.abi,
                 flags: DIFlags::FlagZero,
                 discriminant: None,
+                bit_field: None,
             }
         }).collect()
     }
@@ -1229,6 +1238,7 @@ impl EnumMemberDescriptionFactory<'ll, 'tcx> {
                         align: self.layout.align.abi,
                         flags: DIFlags::FlagZero,
                         discriminant: None,
+                        bit_field: None,
                     }
                 ]
             }
@@ -1270,6 +1280,7 @@ impl EnumMemberDescriptionFactory<'ll, 'tcx> {
                         discriminant: Some(self.layout.ty.ty_adt_def().unwrap()
                                            .discriminant_for_variant(cx.tcx, i)
                                            .val as u64),
+                        bit_field: None,
                     }
                 }).collect()
             }
@@ -1337,6 +1348,7 @@ impl EnumMemberDescriptionFactory<'ll, 'tcx> {
                             align: variant.align.abi,
                             flags: DIFlags::FlagZero,
                             discriminant: None,
+                            bit_field: None,
                         }
                     ]
                 } else {
@@ -1375,6 +1387,7 @@ impl EnumMemberDescriptionFactory<'ll, 'tcx> {
                             align: self.layout.align.abi,
                             flags: DIFlags::FlagZero,
                             discriminant: niche_value,
+                            bit_field: None,
                         }
                     }).collect()
                 }
@@ -1412,6 +1425,7 @@ impl VariantMemberDescriptionFactory<'ll, 'tcx> {
                 align,
                 flags: DIFlags::FlagZero,
                 discriminant: None,
+                bit_field: None,
             }
         }).collect()
     }
@@ -1795,6 +1809,27 @@ fn set_members_of_composite_type(cx: &CodegenCx<'ll, '_>,
         .into_iter()
         .map(|member_description| {
             let member_name = CString::new(member_description.name).unwrap();
+            if let Some(bit_field) = member_description.bit_field {
+                // The offset of a bit-field is that of its first bit in
+                // memory order, which is its highest on big-endian targets.
+                let first_bit = match cx.data_layout().endian {
+                    Endian::Little => bit_field.shift,
+                    Endian::Big => bit_field.storage.bits() - bit_field.shift - bit_field.width,
+                };
+                return unsafe {
+                    Some(llvm::LLVMRustDIBuilderCreateBitFieldMemberType(
+                        DIB(cx),
+                        composite_type_metadata,
+                        member_name.as_ptr(),
+                        unknown_file_metadata(cx),
+                        UNKNOWN_LINE_NUMBER,
+                        bit_field.width,
+                        member_description.offset.bits() + first_bit,
+                        member_description.offset.bits(),
+                        member_description.flags | DIFlags::FlagBitField,
+                        member_description.type_metadata))
+                };
+            }
             unsafe {
                 Some(llvm::LLVMRustDIBuilderCreateVariantMemberType(
                     DIB(cx),
@@ -22,7 +22,7 @@
 
 use llvm;
 use llvm::AttributePlace::Function;
-use rustc::ty::{self, PolyFnSig};
+use rustc::ty::{self, Instance, PolyFnSig, Ty};
 use rustc::ty::layout::LayoutOf;
 use rustc::session::config::Sanitizer;
 use rustc_data_structures::small_c_str::SmallCStr;
@@ -95,6 +95,66 @@ fn declare_raw_fn(
     llfn
 }
 
+/// Declare a Rust function of signature `sig` and ABI `fty`.
+fn declare_rust_fn(
+    cx: &CodegenCx<'ll, 'tcx>,
+    name: &str,
+    sig: ty::FnSig<'tcx>,
+    fty: FnType<'tcx, Ty<'tcx>>,
+) -> &'ll Value {
+    let llfn = declare_raw_fn(cx, name, fty.llvm_cconv(), fty.llvm_type(cx));
+
+    if cx.layout_of(sig.output()).abi.is_uninhabited() {
+        llvm::Attribute::NoReturn.apply_llfn(Function, llfn);
+    }
+
+    if sig.abi != Abi::Rust && sig.abi != Abi::RustCall {
+        attributes::unwind(llfn, false);
+    }
+
+    fty.apply_attrs_llfn(llfn);
+
+    llfn
+}
+
+/// Declare the function of `instance`. Unlike `declare_fn`, the ABI takes
+/// the target features the function enables into account.
+pub fn declare_instance_fn(
+    cx: &CodegenCx<'ll, 'tcx>,
+    name: &str,
+    instance: Instance<'tcx>,
+) -> &'ll Value {
+    debug!("declare_instance_fn(name={:?}, instance={:?})", name, instance);
+    let sig = instance.fn_sig(cx.tcx);
+    let sig = cx.tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), &sig);
+    declare_rust_fn(cx, name, sig, FnType::of_instance(cx, &instance))
+}
+
+/// Declare a global in the given address space.
+///
+/// If there’s a global with the same name already declared, it is returned
+/// as is, whichever address space it is in.
+pub fn declare_global_in_address_space(
+    cx: &CodegenCx<'ll, '_>,
+    name: &str,
+    ty: &'ll Type,
+    address_space: u32,
+) -> &'ll Value {
+    if address_space == 0 {
+        return cx.declare_global(name, ty);
+    }
+    debug!("declare_global_in_address_space(name={:?}, address_space={})",
+           name, address_space);
+    let namebuf = SmallCStr::new(name);
+    unsafe {
+        match llvm::LLVMGetNamedGlobal(cx.llmod, namebuf.as_ptr()) {
+            Some(g) => g,
+            None => llvm::LLVMAddGlobalInAddressSpace(cx.llmod, ty, namebuf.as_ptr(),
+                                                      address_space),
+        }
+    }
+}
+
 impl DeclareMethods<'tcx> for CodegenCx<'ll, 'tcx> {
 
     fn declare_global(
@@ -125,20 +185,7 @@ impl DeclareMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         let sig = self.tcx.normalize_erasing_late_bound_regions(ty::ParamEnv::reveal_all(), &sig);
         debug!("declare_rust_fn (after region erasure) sig={:?}", sig);
 
-        let fty = FnType::new(self, sig, &[]);
-        let llfn = declare_raw_fn(self, name, fty.llvm_cconv(), fty.llvm_type(self));
-
-        if self.layout_of(sig.output()).abi.is_uninhabited() {
-            llvm::Attribute::NoReturn.apply_llfn(Function, llfn);
-        }
-
-        if sig.abi != Abi::Rust && sig.abi != Abi::RustCall {
-            attributes::unwind(llfn, false);
-        }
-
-        fty.apply_attrs_llfn(llfn);
-
-        llfn
+        declare_rust_fn(self, name, sig, FnType::new(self, sig, &[]))
     }
 
     fn define_global(
@@ -19,14 +19,18 @@ use rustc_codegen_ssa::MemFlags;
 use rustc_codegen_ssa::mir::place::PlaceRef;
 use rustc_codegen_ssa::mir::operand::{OperandRef, OperandValue};
 use rustc_codegen_ssa::glue;
+use rustc_codegen_ssa::mir::rvalue;
 use rustc_codegen_ssa::base::{to_immediate, wants_msvc_seh, compare_simd_types};
 use context::CodegenCx;
 use type_::Type;
 use type_of::LayoutLlvmExt;
 use rustc::ty::{self, Ty};
-use rustc::ty::layout::{LayoutOf, HasTyCtxt};
-use rustc_codegen_ssa::common::TypeKind;
+use rustc::ty::layout::{self, Endian, HasDataLayout, LayoutOf, HasTyCtxt};
+use rustc_codegen_ssa::common::{langcall, AtomicOrdering, IntPredicate, TypeKind};
 use rustc::hir;
+use rustc::middle::atomic_intrinsic::{self, AtomicIntrinsic, AtomicOp};
+use rustc::middle::lang_items::PanicFnLangItem;
+use rustc_mir::interpret;
 use syntax::ast;
 use syntax::symbol::Symbol;
 use builder::Builder;
@@ -35,11 +39,19 @@ use value::Value;
 use rustc_codegen_ssa::traits::*;
 
 use rustc::session::Session;
+use rustc_data_structures::small_c_str::SmallCStr;
 use syntax_pos::Span;
 
 use std::cmp::Ordering;
+use std::ffi::CString;
 use std::iter;
 
+use libc::c_uint;
+
+/// Every flag understood by the `f*_flags` intrinsics, i.e. the `FAST_MATH_*`
+/// constants in libcore.
+const FAST_MATH_FLAGS_ALL: u128 = (1 << 7) - 1;
+
 fn get_simple_intrinsic(cx: &CodegenCx<'ll, '_>, name: &str) -> Option<&'ll Value> {
     let llvm_name = match name {
         "sqrtf32" => "llvm.sqrt.f32",
@@ -80,13 +92,36 @@ fn get_simple_intrinsic(cx: &CodegenCx<'ll, '_>, name: &str) -> Option<&'ll Valu
         "nearbyintf64" => "llvm.nearbyint.f64",
         "roundf32" => "llvm.round.f32",
         "roundf64" => "llvm.round.f64",
+        "canonicalizef32" => "llvm.canonicalize.f32",
+        "canonicalizef64" => "llvm.canonicalize.f64",
         "assume" => "llvm.assume",
-        "abort" => "llvm.trap",
+        "abort" => return Some(cx.abort_fn()),
         _ => return None
     };
     Some(cx.get_intrinsic(&llvm_name))
 }
 
+/// The operation of the constrained intrinsic that replaces the simple
+/// intrinsic `name` in functions accessing the floating-point environment.
+fn constrained_fp_intrinsic(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "sqrtf32" | "sqrtf64" => "sqrt",
+        "powif32" | "powif64" => "powi",
+        "sinf32" | "sinf64" => "sin",
+        "cosf32" | "cosf64" => "cos",
+        "powf32" | "powf64" => "pow",
+        "expf32" | "expf64" => "exp",
+        "exp2f32" | "exp2f64" => "exp2",
+        "logf32" | "logf64" => "log",
+        "log10f32" | "log10f64" => "log10",
+        "log2f32" | "log2f64" => "log2",
+        "fmaf32" | "fmaf64" => "fma",
+        "rintf32" | "rintf64" => "rint",
+        "nearbyintf32" | "nearbyintf64" => "nearbyint",
+        _ => return None,
+    })
+}
+
 impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
     fn codegen_intrinsic_call(
         &mut self,
@@ -113,11 +148,27 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
         let result = PlaceRef::new_sized(llresult, fn_ty.ret.layout, fn_ty.ret.layout.align.abi);
 
         let simple = get_simple_intrinsic(self.cx(), name);
+        let nullary = interpret::eval_nullary_intrinsic(tcx, ty::ParamEnv::reveal_all(),
+                                                        name, substs);
         let llval = match name {
+            // Shared with const evaluation so that both always agree.
+            _ if nullary.is_some() => {
+                match nullary.unwrap() {
+                    Ok(val) => {
+                        let llty = self.cx().immediate_backend_type(fn_ty.ret.layout);
+                        self.cx().const_uint_big(llty, val)
+                    }
+                    Err(err) => tcx.sess.span_fatal(span, &err.to_string()),
+                }
+            }
             _ if simple.is_some() => {
-                self.call(simple.unwrap(),
-                        &args.iter().map(|arg| arg.immediate()).collect::<Vec<_>>(),
-                        None)
+                let args = args.iter().map(|arg| arg.immediate()).collect::<Vec<_>>();
+                let constrained = constrained_fp_intrinsic(name)
+                    .and_then(|op| self.constrained_fp_op(op, &args));
+                match constrained {
+                    Some(llval) => llval,
+                    None => self.call(simple.unwrap(), &args, None),
+                }
             }
             "unreachable" => {
                 return;
@@ -130,6 +181,41 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                 let expect = self.cx().get_intrinsic(&("llvm.expect.i1"));
                 self.call(expect, &[args[0].immediate(), self.cx().const_bool(false)], None)
             }
+            // The hint is consumed when the branch leading here is generated.
+            "cold_path" => {
+                return;
+            }
+            "is_val_statically_known" => {
+                // `llvm.is.constant` only exists since LLVM 8 and is only
+                // overloaded for first-class types; for anything else we can
+                // always answer "not known".
+                let layout = self.cx().layout_of(substs.type_at(0));
+                let scalar = match layout.abi {
+                    layout::Abi::Scalar(ref scalar) if llvm_util::get_major_version() >= 8 => {
+                        Some(scalar.value)
+                    }
+                    _ => None,
+                };
+                let mut val = args[0].immediate();
+                let suffix = match scalar {
+                    Some(layout::Int(_, _)) if layout.ty.is_bool() => Some("i1".to_string()),
+                    Some(layout::Int(i, _)) => Some(format!("i{}", i.size().bits())),
+                    Some(layout::Float(layout::FloatTy::F32)) => Some("f32".to_string()),
+                    Some(layout::Float(layout::FloatTy::F64)) => Some("f64".to_string()),
+                    Some(layout::Pointer) => {
+                        val = self.pointercast(val, self.cx().type_i8p());
+                        Some("p0i8".to_string())
+                    }
+                    None => None,
+                };
+                match suffix {
+                    Some(suffix) => {
+                        let llfn = self.cx().get_intrinsic(&format!("llvm.is.constant.{}", suffix));
+                        self.call(llfn, &[val], None)
+                    }
+                    None => self.cx().const_bool(false),
+                }
+            }
             "try" => {
                 try_intrinsic(self,
                               args[0].immediate(),
@@ -142,9 +228,68 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                 let llfn = self.cx().get_intrinsic(&("llvm.debugtrap"));
                 self.call(llfn, &[], None)
             }
-            "size_of" => {
-                let tp_ty = substs.type_at(0);
-                self.cx().const_usize(self.cx().size_of(tp_ty).bytes())
+            "current_stack_pointer" => {
+                let llfn = self.cx().get_intrinsic(&("llvm.stacksave"));
+                self.call(llfn, &[], None)
+            }
+            "spin_loop" => {
+                // An empty loop without side effects may be assumed to
+                // terminate, and removed along with the code following it.
+                if llvm_util::get_major_version() >= 8 {
+                    let llfn = self.cx().get_intrinsic(&("llvm.sideeffect"));
+                    self.call(llfn, &[], None);
+                }
+                let hint = match &*self.cx().sess().target.target.arch {
+                    "x86" | "x86_64" => Some("pause"),
+                    "aarch64" => Some("yield"),
+                    _ => None,
+                };
+                if let Some(hint) = hint {
+                    let void = self.cx().type_void();
+                    self.inline_asm_call(&CString::new(hint).unwrap(),
+                                         &CString::new("~{memory}").unwrap(), &[],
+                                         void, true, false, ast::AsmDialect::Att)
+                        .unwrap_or_else(|| bug!("invalid constraints for `{}`", hint));
+                }
+                return;
+            }
+            "frame_address" => {
+                let llfn = self.cx().get_intrinsic(&("llvm.frameaddress"));
+                self.call(llfn, &[self.cx().const_i32(0)], None)
+            }
+            "swap_context" => {
+                // Saves the current context with the returns-twice `setjmp`, so
+                // LLVM treats every register as clobbered across the switch, then
+                // resumes the other context. It comes back here once something
+                // switches to the saved context, with `setjmp` returning 1.
+                let setjmp = self.cx().get_intrinsic(&("llvm.eh.sjlj.setjmp"));
+                let longjmp = self.cx().get_intrinsic(&("llvm.eh.sjlj.longjmp"));
+                let save = self.pointercast(args[0].immediate(), self.cx().type_i8p());
+                let restore = self.pointercast(args[1].immediate(), self.cx().type_i8p());
+
+                // `setjmp` only stores the resume address, in the second word;
+                // the frame and stack pointers are up to us.
+                let words = self.pointercast(save, self.cx().type_ptr_to(self.cx().type_i8p()));
+                let ptr_align = self.tcx().data_layout.pointer_align.abi;
+                let frameaddress = self.cx().get_intrinsic(&("llvm.frameaddress"));
+                let frame = self.call(frameaddress, &[self.cx().const_i32(0)], None);
+                self.store(frame, words, ptr_align);
+                let stacksave = self.cx().get_intrinsic(&("llvm.stacksave"));
+                let stack = self.call(stacksave, &[], None);
+                let stack_slot = self.inbounds_gep(words, &[self.cx().const_usize(2)]);
+                self.store(stack, stack_slot, ptr_align);
+
+                let saved = self.call(setjmp, &[save], None);
+                let is_saved = self.icmp(IntPredicate::IntEQ, saved, self.cx().const_i32(0));
+                let mut switch = self.build_sibling_block("swap_context_switch");
+                let resumed = self.build_sibling_block("swap_context_resumed");
+                self.cond_br(is_saved, switch.llbb(), resumed.llbb());
+
+                switch.call(longjmp, &[restore], None);
+                switch.unreachable();
+
+                self.position_at_end(resumed.llbb());
+                return;
             }
             "size_of_val" => {
                 let tp_ty = substs.type_at(0);
@@ -152,36 +297,31 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                     let (llsize, _) =
                         glue::size_and_align_of_dst(self, tp_ty, Some(meta));
                     llsize
+                } else if let Some((size_fn, _)) = self.tcx().extern_size_methods(tp_ty) {
+                    let llfn = self.cx().get_fn(size_fn);
+                    self.call(llfn, &[args[0].immediate()], None)
                 } else {
                     self.cx().const_usize(self.cx().size_of(tp_ty).bytes())
                 }
             }
-            "min_align_of" => {
-                let tp_ty = substs.type_at(0);
-                self.cx().const_usize(self.cx().align_of(tp_ty).bytes())
-            }
             "min_align_of_val" => {
                 let tp_ty = substs.type_at(0);
                 if let OperandValue::Pair(_, meta) = args[0].val {
                     let (_, llalign) =
                         glue::size_and_align_of_dst(self, tp_ty, Some(meta));
                     llalign
+                } else if let Some((_, align_fn)) = self.tcx().extern_size_methods(tp_ty) {
+                    let llfn = self.cx().get_fn(align_fn);
+                    self.call(llfn, &[args[0].immediate()], None)
                 } else {
                     self.cx().const_usize(self.cx().align_of(tp_ty).bytes())
                 }
             }
-            "pref_align_of" => {
-                let tp_ty = substs.type_at(0);
-                self.cx().const_usize(self.cx().layout_of(tp_ty).align.pref.bytes())
-            }
             "type_name" => {
                 let tp_ty = substs.type_at(0);
                 let ty_name = Symbol::intern(&tp_ty.to_string()).as_str();
                 self.cx().const_str_slice(ty_name)
             }
-            "type_id" => {
-                self.cx().const_u64(self.cx().tcx.type_id_hash(substs.type_at(0)))
-            }
             "init" => {
                 let ty = substs.type_at(0);
                 if !self.cx().layout_of(ty).is_zst() {
@@ -204,14 +344,24 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
             "uninit" | "forget" => {
                 return;
             }
-            "needs_drop" => {
-                let tp_ty = substs.type_at(0);
-
-                self.cx().const_bool(self.cx().type_needs_drop(tp_ty))
+            // The failing case of these is turned into a panic before we get here.
+            "assert_inhabited" | "assert_zero_valid" => {
+                return;
+            }
+            // The const heap only exists during const evaluation; at runtime
+            // allocation always fails and deallocation does nothing.
+            "const_allocate" => {
+                self.cx().const_null(self.cx().type_i8p())
+            }
+            "const_deallocate" => {
+                return;
             }
             "offset" => {
                 let ptr = args[0].immediate();
                 let offset = args[1].immediate();
+                if tcx.sess.opts.debugging_opts.ub_checks {
+                    check_offset(self, span, substs.type_at(0), ptr, offset);
+                }
                 self.inbounds_gep(ptr, &[offset])
             }
             "arith_offset" => {
@@ -219,8 +369,42 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                 let offset = args[1].immediate();
                 self.gep(ptr, &[offset])
             }
+            "offset_with_overflow" => {
+                let ptr = args[0].immediate();
+                let offset = args[1].immediate();
+                let overflow = offset_overflow(self, substs.type_at(0), ptr, offset);
+                let overflow = self.zext(overflow, self.cx().type_bool());
+                let new_ptr = self.gep(ptr, &[offset]);
+
+                let dest = result.project_field(self, 0);
+                self.store(new_ptr, dest.llval, dest.align);
+                let dest = result.project_field(self, 1);
+                self.store(overflow, dest.llval, dest.align);
+
+                return;
+            }
+
+            "fenv_rounding_mode" | "fenv_set_rounding_mode" |
+            "fenv_exceptions" | "fenv_clear_exceptions" => {
+                match float_env(self, span, name, args) {
+                    Some(llval) => llval,
+                    None => return,
+                }
+            }
+
+            "cpuid" | "cpu_hwcap" => {
+                match cpu_query(self, span, name, args, result) {
+                    Some(llval) => llval,
+                    None => return,
+                }
+            }
 
             "copy_nonoverlapping" => {
+                if tcx.sess.opts.debugging_opts.ub_checks {
+                    check_copy_nonoverlapping(self, span, substs.type_at(0),
+                                              args[1].immediate(), args[0].immediate(),
+                                              args[2].immediate());
+                }
                 copy_intrinsic(self, false, false, substs.type_at(0),
                                args[1].immediate(), args[0].immediate(), args[2].immediate());
                 return;
@@ -230,6 +414,26 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                                args[1].immediate(), args[0].immediate(), args[2].immediate());
                 return;
             }
+            "copy_nonoverlapping_atomic_unordered" => {
+                let ty = substs.type_at(0);
+                let (size, align) = self.cx().size_and_align_of(ty);
+                let max_atomic_width = tcx.sess.target.target.options.max_atomic_width();
+                if !size.bytes().is_power_of_two() || size.bits() > max_atomic_width ||
+                   align.bytes() < size.bytes() {
+                    span_invalid_monomorphization_error(
+                        tcx.sess, span,
+                        &format!("invalid monomorphization of `{}` intrinsic: \
+                                  `{}` is not suitable for atomic element copies \
+                                  (size {}, align {})",
+                                 name, ty, size.bytes(), align.bytes()));
+                    return;
+                }
+                atomic_element_copy_intrinsic(self, ty,
+                                              args[1].immediate(),
+                                              args[0].immediate(),
+                                              args[2].immediate());
+                return;
+            }
             "write_bytes" => {
                 memset_intrinsic(self, false, substs.type_at(0),
                                  args[0].immediate(), args[1].immediate(), args[2].immediate());
@@ -251,6 +455,16 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                                  args[0].immediate(), args[1].immediate(), args[2].immediate());
                 return;
             }
+            "read_via_copy" => {
+                let src = args[0].deref(self.cx());
+                self.load_operand(src).val.store(self, result);
+                return;
+            }
+            "write_via_move" => {
+                let dst = args[0].deref(self.cx());
+                args[1].val.store(self, dst);
+                return;
+            }
             "volatile_load" | "unaligned_volatile_load" => {
                 let tp_ty = substs.type_at(0);
                 let mut ptr = args[0].immediate();
@@ -278,29 +492,116 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                 args[1].val.unaligned_volatile_store(self, dst);
                 return;
             },
+            "mmio_load" | "mmio_store" => {
+                let tp_ty = substs.type_at(0);
+                let (llty, align) = match mmio_access_type(self.cx(), name, span, tp_ty) {
+                    Some(access) => access,
+                    None => return,
+                };
+                let llptr_ty = self.cx().type_ptr_to(llty);
+                if name == "mmio_load" {
+                    let src = self.pointercast(args[0].immediate(), llptr_ty);
+                    let val = self.volatile_load(src);
+                    unsafe {
+                        llvm::LLVMSetAlignment(val, align.bytes() as u32);
+                    }
+                    let dst = self.pointercast(result.llval, llptr_ty);
+                    self.store(val, dst, result.align);
+                } else {
+                    // Go through memory so that any `T` becomes a single
+                    // integer of the right width.
+                    let tmp = PlaceRef::alloca(self, self.cx().layout_of(tp_ty), "mmio_val");
+                    args[1].val.store(self, tmp);
+                    let tmp_ptr = self.pointercast(tmp.llval, llptr_ty);
+                    let val = self.load(tmp_ptr, tmp.align);
+                    let dst = self.pointercast(args[0].immediate(), llptr_ty);
+                    self.store_with_flags(val, dst, align, MemFlags::VOLATILE);
+                }
+                return;
+            }
             "prefetch_read_data" | "prefetch_write_data" |
-            "prefetch_read_instruction" | "prefetch_write_instruction" => {
-                let expect = self.cx().get_intrinsic(&("llvm.prefetch"));
-                let (rw, cache_type) = match name {
-                    "prefetch_read_data" => (0, 1),
-                    "prefetch_write_data" => (1, 1),
-                    "prefetch_read_instruction" => (0, 0),
-                    "prefetch_write_instruction" => (1, 0),
+            "prefetch_read_instruction" | "prefetch_write_instruction" | "prefetch" => {
+                // `llvm.prefetch` only accepts constants in range for all but
+                // the address, so check them here rather than emit invalid IR.
+                let const_arg = |idx: usize, what: &str, max: u128| {
+                    match self.cx().const_to_opt_u128(args[idx].immediate(), false) {
+                        Some(val) if val <= max => Some(val as i32),
+                        Some(_) => {
+                            span_invalid_monomorphization_error(
+                                tcx.sess, span,
+                                &format!("invalid argument to `{}` intrinsic: \
+                                          {} must be between 0 and {}", name, what, max));
+                            None
+                        }
+                        None => {
+                            span_invalid_monomorphization_error(
+                                tcx.sess, span,
+                                &format!("invalid argument to `{}` intrinsic: \
+                                          {} must be a constant", name, what));
+                            None
+                        }
+                    }
+                };
+                let (rw, locality, cache_type) = match name {
+                    "prefetch_read_data" => (Some(0), const_arg(1, "locality", 3), Some(1)),
+                    "prefetch_write_data" => (Some(1), const_arg(1, "locality", 3), Some(1)),
+                    "prefetch_read_instruction" => (Some(0), const_arg(1, "locality", 3), Some(0)),
+                    "prefetch_write_instruction" => (Some(1), const_arg(1, "locality", 3), Some(0)),
+                    "prefetch" => (const_arg(1, "rw", 1),
+                                   const_arg(2, "locality", 3),
+                                   const_arg(3, "cache_type", 1)),
                     _ => bug!()
                 };
+                let (rw, locality, cache_type) = match (rw, locality, cache_type) {
+                    (Some(rw), Some(locality), Some(cache_type)) => (rw, locality, cache_type),
+                    _ => return,
+                };
+                let expect = self.cx().get_intrinsic(&("llvm.prefetch"));
                 self.call(expect, &[
                     args[0].immediate(),
                     self.cx().const_i32(rw),
-                    args[1].immediate(),
+                    self.cx().const_i32(locality),
                     self.cx().const_i32(cache_type)
                 ], None)
             },
+            "wasm_memory_size" | "wasm_memory_grow" => {
+                if tcx.sess.target.target.arch != "wasm32" {
+                    return span_invalid_monomorphization_error(
+                        tcx.sess, span,
+                        &format!("the `{}` intrinsic is only available on wasm32 targets",
+                                 name));
+                }
+                match self.cx().const_to_opt_u128(args[0].immediate(), false) {
+                    Some(0) => {}
+                    Some(_) => {
+                        return span_invalid_monomorphization_error(
+                            tcx.sess, span,
+                            &format!("invalid argument to `{}` intrinsic: \
+                                      only memory 0 is supported", name));
+                    }
+                    None => {
+                        return span_invalid_monomorphization_error(
+                            tcx.sess, span,
+                            &format!("invalid argument to `{}` intrinsic: \
+                                      the memory index must be a constant", name));
+                    }
+                }
+                let llfn = self.cx().get_intrinsic(
+                    &format!("llvm.wasm.memory.{}.i32", &name["wasm_memory_".len()..]));
+                let llargs = args.iter().map(|arg| arg.immediate()).collect::<Vec<_>>();
+                self.call(llfn, &llargs, None)
+            }
             "ctlz" | "ctlz_nonzero" | "cttz" | "cttz_nonzero" | "ctpop" | "bswap" |
             "bitreverse" | "add_with_overflow" | "sub_with_overflow" |
             "mul_with_overflow" | "overflowing_add" | "overflowing_sub" | "overflowing_mul" |
             "unchecked_div" | "unchecked_rem" | "unchecked_shl" | "unchecked_shr" | "exact_div" |
             "rotate_left" | "rotate_right" => {
                 let ty = arg_tys[0];
+                if tcx.sess.opts.debugging_opts.ub_checks {
+                    if let Some((width, signed)) = int_type_width_signed(ty, self.cx()) {
+                        check_int_preconditions(self, span, name, width, signed, args);
+                    }
+                }
                 match int_type_width_signed(ty, self.cx()) {
                     Some((width, signed)) =>
                         match name {
@@ -317,6 +618,10 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                                 let llfn = self.cx().get_intrinsic(llvm_name);
                                 self.call(llfn, &[args[0].immediate(), y], None)
                             }
+                            "ctpop" | "bswap" | "bitreverse" if width == 128 &&
+                                tcx.data_layout.pointer_size.bits() < 64 => {
+                                split_i128_bit_intrinsic(self, name, args[0].immediate())
+                            }
                             "ctpop" => self.call(
                                 self.cx().get_intrinsic(&format!("llvm.ctpop.i{}", width)),
                                 &[args[0].immediate()],
@@ -459,10 +764,68 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                 }
 
             },
+            "fadd_flags" | "fsub_flags" | "fmul_flags" | "fdiv_flags" | "frem_flags" => {
+                let sty = &arg_tys[0].sty;
+                if float_type_width(sty).is_none() {
+                    span_invalid_monomorphization_error(
+                        tcx.sess, span,
+                        &format!("invalid monomorphization of `{}` intrinsic: \
+                                  expected basic float type, found `{}`", name, sty));
+                    return;
+                }
+                let flags = match self.cx().const_to_opt_u128(args[2].immediate(), false) {
+                    Some(flags) if flags <= FAST_MATH_FLAGS_ALL => flags as c_uint,
+                    Some(flags) => {
+                        span_invalid_monomorphization_error(
+                            tcx.sess, span,
+                            &format!("invalid argument to `{}` intrinsic: \
+                                      unknown fast-math flags `{:#x}`", name, flags));
+                        return;
+                    }
+                    None => {
+                        span_invalid_monomorphization_error(
+                            tcx.sess, span,
+                            &format!("invalid argument to `{}` intrinsic: \
+                                      fast-math flags must be a constant", name));
+                        return;
+                    }
+                };
+                let (lhs, rhs) = (args[0].immediate(), args[1].immediate());
+                let instr = match name {
+                    "fadd_flags" => self.fadd(lhs, rhs),
+                    "fsub_flags" => self.fsub(lhs, rhs),
+                    "fmul_flags" => self.fmul(lhs, rhs),
+                    "fdiv_flags" => self.fdiv(lhs, rhs),
+                    "frem_flags" => self.frem(lhs, rhs),
+                    _ => bug!(),
+                };
+                unsafe {
+                    llvm::LLVMRustSetFastMathFlags(instr, flags);
+                }
+                instr
+            }
+            "minimumf32" | "minimumf64" | "maximumf32" | "maximumf64" => {
+                let (lhs, rhs) = (args[0].immediate(), args[1].immediate());
+                if llvm_util::get_major_version() >= 8 {
+                    let llfn = self.cx().get_intrinsic(&format!("llvm.{}.{}",
+                                                                &name[..7], &name[7..]));
+                    self.call(llfn, &[lhs, rhs], None)
+                } else {
+                    float_minimum_maximum(self, name.starts_with("min"), lhs, rhs)
+                }
+            }
 
             "discriminant_value" => {
                 args[0].deref(self.cx()).codegen_get_discr(self, ret_ty)
             }
+            "raw_discriminant" => {
+                args[0].deref(self.cx()).codegen_get_raw_discr(self, ret_ty)
+            }
+            "set_discriminant" => {
+                let tag = args[1].immediate();
+                args[0].deref(self.cx()).codegen_set_raw_discr(self, tag);
+                return;
+            }
 
             name if name.starts_with("simd_") => {
                 match generic_simd_intrinsic(self, name,
@@ -474,51 +837,65 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                     Err(()) => return
                 }
             }
-            // This requires that atomic intrinsics follow a specific naming pattern:
-            // "atomic_<operation>[_<ordering>]", and no ordering means SeqCst
+            // The name of the intrinsic was validated when it was type-checked.
             name if name.starts_with("atomic_") => {
-                use rustc_codegen_ssa::common::AtomicOrdering::*;
-                use rustc_codegen_ssa::common::
-                    {SynchronizationScope, AtomicRmwBinOp};
-
-                let split: Vec<&str> = name.split('_').collect();
-
-                let is_cxchg = split[1] == "cxchg" || split[1] == "cxchgweak";
-                let (order, failorder) = match split.len() {
-                    2 => (SequentiallyConsistent, SequentiallyConsistent),
-                    3 => match split[2] {
-                        "unordered" => (Unordered, Unordered),
-                        "relaxed" => (Monotonic, Monotonic),
-                        "acq"     => (Acquire, Acquire),
-                        "rel"     => (Release, Monotonic),
-                        "acqrel"  => (AcquireRelease, Acquire),
-                        "failrelaxed" if is_cxchg =>
-                            (SequentiallyConsistent, Monotonic),
-                        "failacq" if is_cxchg =>
-                            (SequentiallyConsistent, Acquire),
-                        _ => self.cx().sess().fatal("unknown ordering in atomic intrinsic")
-                    },
-                    4 => match (split[2], split[3]) {
-                        ("acq", "failrelaxed") if is_cxchg =>
-                            (Acquire, Monotonic),
-                        ("acqrel", "failrelaxed") if is_cxchg =>
-                            (AcquireRelease, Monotonic),
-                        _ => self.cx().sess().fatal("unknown ordering in atomic intrinsic")
-                    },
-                    _ => self.cx().sess().fatal("Atomic intrinsic not in correct format"),
+                use rustc_codegen_ssa::common::{SynchronizationScope, AtomicRmwBinOp};
+
+                let atomic = AtomicIntrinsic::parse(name).unwrap_or_else(|_| {
+                    bug!("malformed atomic intrinsic `{}`", name)
+                });
+                let order = atomic_ordering(atomic.ordering);
+                let failorder = atomic_ordering(atomic.failure_ordering);
+
+                // Loads, stores and exchanges also take thin raw pointers, which
+                // stay pointers instead of round-tripping through `usize`, so that
+                // LLVM keeps track of what they point to.
+                let ptr_op = match atomic.op {
+                    AtomicOp::Load | AtomicOp::Store | AtomicOp::Cxchg |
+                    AtomicOp::CxchgWeak | AtomicOp::Xchg => true,
+                    _ => false,
                 };
-
                 let invalid_monomorphization = |ty| {
+                    let expected = if ptr_op {
+                        "basic integer or raw pointer type"
+                    } else {
+                        "basic integer type"
+                    };
                     span_invalid_monomorphization_error(tcx.sess, span,
                         &format!("invalid monomorphization of `{}` intrinsic: \
-                                  expected basic integer type, found `{}`", name, ty));
+                                  expected {}, found `{}`", name, expected, ty));
                 };
 
-                match split[1] {
-                    "cxchg" | "cxchgweak" => {
+                if tcx.sess.opts.debugging_opts.atomics_critical_sections &&
+                   !atomic.op.is_fence() {
+                    let ty = substs.type_at(0);
+                    if int_type_width_signed(ty, self.cx()).is_none() &&
+                       !(ptr_op && is_thin_raw_ptr(ty, self.cx())) {
+                        return invalid_monomorphization(ty);
+                    }
+                    let max_width = tcx.sess.target.target.max_atomic_width();
+                    if self.cx().size_of(ty).bits() > max_width {
+                        return span_invalid_monomorphization_error(tcx.sess, span,
+                            &format!("invalid monomorphization of `{}` intrinsic: `{}` is \
+                                      wider than the target's {}-bit atomics", name, ty,
+                                     max_width));
+                    }
+                    atomic_in_critical_section(self, atomic.op, ty, args, result);
+                    return;
+                }
+
+                // 128-bit integers are lowered by LLVM like the others, based on
+                // the target features of the function using them: to `cmpxchg16b`
+                // on x86_64 where that feature is enabled, be it for the crate or
+                // with `#[target_feature]`, to `ldxp`/`stxp` on AArch64, and to
+                // libatomic's `__atomic_*` functions everywhere else. The address
+                // must be 16-byte aligned either way.
+                match atomic.op {
+                    AtomicOp::Cxchg | AtomicOp::CxchgWeak => {
                         let ty = substs.type_at(0);
-                        if int_type_width_signed(ty, self.cx()).is_some() {
-                            let weak = split[1] == "cxchgweak";
+                        if int_type_width_signed(ty, self.cx()).is_some() ||
+                           is_thin_raw_ptr(ty, self.cx()) {
+                            let weak = atomic.op == AtomicOp::CxchgWeak;
                             let pair = self.atomic_cmpxchg(
                                 args[0].immediate(),
                                 args[1].immediate(),
@@ -540,9 +917,10 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                         }
                     }
 
-                    "load" => {
+                    AtomicOp::Load => {
                         let ty = substs.type_at(0);
-                        if int_type_width_signed(ty, self.cx()).is_some() {
+                        if int_type_width_signed(ty, self.cx()).is_some() ||
+                           is_thin_raw_ptr(ty, self.cx()) {
                             let size = self.cx().size_of(ty);
                             self.atomic_load(args[0].immediate(), order, size)
                         } else {
@@ -550,9 +928,10 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                         }
                     }
 
-                    "store" => {
+                    AtomicOp::Store => {
                         let ty = substs.type_at(0);
-                        if int_type_width_signed(ty, self.cx()).is_some() {
+                        if int_type_width_signed(ty, self.cx()).is_some() ||
+                           is_thin_raw_ptr(ty, self.cx()) {
                             let size = self.cx().size_of(ty);
                             self.atomic_store(
                                 args[1].immediate(),
@@ -566,12 +945,12 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                         }
                     }
 
-                    "fence" => {
+                    AtomicOp::Fence => {
                         self.atomic_fence(order, SynchronizationScope::CrossThread);
                         return;
                     }
 
-                    "singlethreadfence" => {
+                    AtomicOp::SingleThreadFence => {
                         self.atomic_fence(order, SynchronizationScope::SingleThread);
                         return;
                     }
@@ -579,22 +958,30 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                     // These are all AtomicRMW ops
                     op => {
                         let atom_op = match op {
-                            "xchg"  => AtomicRmwBinOp::AtomicXchg,
-                            "xadd"  => AtomicRmwBinOp::AtomicAdd,
-                            "xsub"  => AtomicRmwBinOp::AtomicSub,
-                            "and"   => AtomicRmwBinOp::AtomicAnd,
-                            "nand"  => AtomicRmwBinOp::AtomicNand,
-                            "or"    => AtomicRmwBinOp::AtomicOr,
-                            "xor"   => AtomicRmwBinOp::AtomicXor,
-                            "max"   => AtomicRmwBinOp::AtomicMax,
-                            "min"   => AtomicRmwBinOp::AtomicMin,
-                            "umax"  => AtomicRmwBinOp::AtomicUMax,
-                            "umin"  => AtomicRmwBinOp::AtomicUMin,
-                            _ => self.cx().sess().fatal("unknown atomic operation")
+                            AtomicOp::Xchg => AtomicRmwBinOp::AtomicXchg,
+                            AtomicOp::Xadd => AtomicRmwBinOp::AtomicAdd,
+                            AtomicOp::Xsub => AtomicRmwBinOp::AtomicSub,
+                            AtomicOp::And  => AtomicRmwBinOp::AtomicAnd,
+                            AtomicOp::Nand => AtomicRmwBinOp::AtomicNand,
+                            AtomicOp::Or   => AtomicRmwBinOp::AtomicOr,
+                            AtomicOp::Xor  => AtomicRmwBinOp::AtomicXor,
+                            AtomicOp::Max  => AtomicRmwBinOp::AtomicMax,
+                            AtomicOp::Min  => AtomicRmwBinOp::AtomicMin,
+                            AtomicOp::Umax => AtomicRmwBinOp::AtomicUMax,
+                            AtomicOp::Umin => AtomicRmwBinOp::AtomicUMin,
+                            _ => bug!("`{}` is not a read-modify-write operation", op.name()),
                         };
 
                         let ty = substs.type_at(0);
-                        if int_type_width_signed(ty, self.cx()).is_some() {
+                        if op == AtomicOp::Xchg && is_thin_raw_ptr(ty, self.cx()) {
+                            // `atomicrmw` only takes integers, so exchange the address.
+                            let isize_ty = self.cx().type_isize();
+                            let dst = self.pointercast(args[0].immediate(),
+                                                       self.cx().type_ptr_to(isize_ty));
+                            let val = self.ptrtoint(args[1].immediate(), isize_ty);
+                            let old = self.atomic_rmw(atom_op, dst, val, order);
+                            self.inttoptr(old, llret_ty)
+                        } else if int_type_width_signed(ty, self.cx()).is_some() {
                             self.atomic_rmw(
                                 atom_op,
                                 args[0].immediate(),
@@ -726,6 +1113,23 @@ impl IntrinsicCallMethods<'tcx> for Builder<'a, 'll, 'tcx> {
                 assert_eq!(inputs.len(), llargs.len());
 
                 let val = match intr.definition {
+                    // The HVX intrinsics take and return vectors of `i32`
+                    // (or of `i1` for predicates) whatever their lanes, so
+                    // declare them as LLVM does and bitcast around the call.
+                    intrinsics::IntrinsicDef::Named(name) if name.starts_with("llvm.hexagon.") => {
+                        let cname = SmallCStr::new(name);
+                        let f = unsafe {
+                            llvm::LLVMRustGetOrInsertIntrinsic(self.cx().llmod, cname.as_ptr())
+                        }.unwrap_or_else(|| {
+                            self.cx().declare_cfn(name, self.cx().type_func(&inputs, outputs))
+                        });
+                        let val = self.call(f, &llargs, None);
+                        if self.cx().val_ty(val) != outputs {
+                            self.bitcast(val, outputs)
+                        } else {
+                            val
+                        }
+                    }
                     intrinsics::IntrinsicDef::Named(name) => {
                         let f = self.cx().declare_cfn(
                             name,
@@ -788,6 +1192,209 @@ fn copy_intrinsic(
     }
 }
 
+fn atomic_element_copy_intrinsic(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    ty: Ty<'tcx>,
+    dst: &'ll Value,
+    src: &'ll Value,
+    count: &'ll Value,
+) {
+    let (size, align) = bx.cx().size_and_align_of(ty);
+    let len = bx.mul(bx.cx().const_usize(size.bytes()), count);
+    let len = bx.intcast(len, bx.cx().type_isize(), false);
+    let dst = bx.pointercast(dst, bx.cx().type_i8p());
+    let src = bx.pointercast(src, bx.cx().type_i8p());
+    let ptr_bits = bx.cx().tcx.data_layout.pointer_size.bits();
+    let llfn = bx.cx().get_intrinsic(
+        &format!("llvm.memcpy.element.unordered.atomic.p0i8.p0i8.i{}", ptr_bits));
+    let element_size = bx.cx().const_u32(size.bytes() as u32);
+    let call = bx.call(llfn, &[dst, src, len, element_size], None);
+    // The intrinsic requires both pointers to be at least element-aligned.
+    for &idx in &[0, 1] {
+        unsafe {
+            llvm::LLVMRustAddAlignmentCallSiteAttr(call,
+                                                   llvm::AttributePlace::Argument(idx).as_uint(),
+                                                   align.bytes() as u32);
+        }
+    }
+}
+
+/// Continues only if `ok` holds, panicking with `msg` otherwise. Used by
+/// `-Z ub-checks` in front of operations that would be undefined behavior
+/// when their preconditions are violated. The panic isn't expected to return,
+/// but aborts if it does.
+fn ub_check(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    span: Span,
+    ok: &'ll Value,
+    msg: &str,
+) {
+    let cx = bx.cx;
+    let tcx = cx.tcx;
+    let expect = cx.get_intrinsic(&"llvm.expect.i1");
+    let ok = bx.call(expect, &[ok, cx.const_bool(true)], None);
+    let mut failed = bx.build_sibling_block("ub_check_failed");
+    let next = bx.build_sibling_block("ub_check_ok");
+    bx.cond_br(ok, next.llbb(), failed.llbb());
+
+    let loc = tcx.sess.source_map().lookup_char_pos(span.lo());
+    let filename = Symbol::intern(&loc.file.name.to_string()).as_str();
+    let msg = Symbol::intern(&format!("unsafe precondition violated: {}", msg)).as_str();
+    let msg_file_line_col = cx.panic_location(Some(msg),
+                                              filename,
+                                              loc.line as u32,
+                                              loc.col.to_usize() as u32 + 1);
+    let def_id = langcall(tcx, Some(span), "", PanicFnLangItem);
+    let panic_fn = cx.get_fn(ty::Instance::mono(tcx, def_id));
+    failed.call(panic_fn, &[msg_file_line_col], None);
+    failed.call(cx.abort_fn(), &[], None);
+    failed.unreachable();
+
+    bx.position_at_end(next.llbb());
+}
+
+fn check_int_preconditions(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    span: Span,
+    name: &str,
+    width: u64,
+    signed: bool,
+    args: &[OperandRef<'tcx, &'ll Value>],
+) {
+    let cx = bx.cx;
+    let ty = cx.type_ix(width);
+    match name {
+        "unchecked_shl" | "unchecked_shr" => {
+            let ok = bx.icmp(IntPredicate::IntULT, args[1].immediate(), cx.const_uint(ty, width));
+            ub_check(bx, span, ok,
+                     &format!("`{}` by {} or more bits", name, width));
+        }
+        "unchecked_div" | "unchecked_rem" | "exact_div" => {
+            let (lhs, rhs) = (args[0].immediate(), args[1].immediate());
+            let zero = cx.const_uint(ty, 0);
+            let nonzero = bx.icmp(IntPredicate::IntNE, rhs, zero);
+            ub_check(bx, span, nonzero, &format!("`{}` by zero", name));
+            if signed {
+                let min = cx.const_uint_big(ty, 1 << (width - 1));
+                let is_min = bx.icmp(IntPredicate::IntEQ, lhs, min);
+                let is_neg_one = bx.icmp(IntPredicate::IntEQ, rhs, cx.const_int(ty, -1));
+                let overflow = bx.and(is_min, is_neg_one);
+                let ok = bx.not(overflow);
+                ub_check(bx, span, ok, &format!("`{}` overflowed", name));
+            }
+            if name == "exact_div" {
+                let rem = if signed { bx.srem(lhs, rhs) } else { bx.urem(lhs, rhs) };
+                let exact = bx.icmp(IntPredicate::IntEQ, rem, zero);
+                ub_check(bx, span, exact, "`exact_div` with a remainder");
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_offset(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    span: Span,
+    ty: Ty<'tcx>,
+    ptr: &'ll Value,
+    offset: &'ll Value,
+) {
+    let (_, _, bad) = offset_wraps(bx, ty, ptr, offset);
+    let ok = bx.not(bad);
+    ub_check(bx, span, ok, "`offset` overflowed the address space");
+}
+
+/// Computes the offset of `ptr` by `offset` elements of type `ty` in bytes,
+/// whether it's forward and whether it overflows or makes the address wrap
+/// around.
+fn offset_wraps(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    ty: Ty<'tcx>,
+    ptr: &'ll Value,
+    offset: &'ll Value,
+) -> (&'ll Value, &'ll Value, &'ll Value) {
+    let cx = bx.cx;
+    let size = cx.size_of(ty).bytes();
+    let bits = cx.tcx.data_layout.pointer_size.bits();
+    let mul = cx.get_intrinsic(&format!("llvm.smul.with.overflow.i{}", bits));
+    let pair = bx.call(mul, &[offset, cx.const_usize(size)], None);
+    let bytes = bx.extract_value(pair, 0);
+    let overflow = bx.extract_value(pair, 1);
+
+    // The address may not wrap around in either direction.
+    let addr = bx.ptrtoint(ptr, cx.type_isize());
+    let new_addr = bx.add(addr, bytes);
+    let forward = bx.icmp(IntPredicate::IntSGE, bytes, cx.const_usize(0));
+    let wrapped_forward = bx.icmp(IntPredicate::IntULT, new_addr, addr);
+    let wrapped_backward = bx.icmp(IntPredicate::IntUGT, new_addr, addr);
+    let wrapped = bx.select(forward, wrapped_forward, wrapped_backward);
+
+    (bytes, forward, bx.or(overflow, wrapped))
+}
+
+/// Like `offset_wraps`, but also true if the offset goes past the end of the
+/// object `ptr` points into. `llvm.objectsize` folds to the number of bytes
+/// left in the object where the optimizer can see it, and to -1 otherwise, so
+/// this only catches what's known statically.
+fn offset_overflow(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    ty: Ty<'tcx>,
+    ptr: &'ll Value,
+    offset: &'ll Value,
+) -> &'ll Value {
+    let cx = bx.cx;
+    let (bytes, forward, wraps) = offset_wraps(bx, ty, ptr, offset);
+
+    let bits = cx.tcx.data_layout.pointer_size.bits();
+    let objectsize = cx.get_intrinsic(&format!("llvm.objectsize.i{}.p0i8", bits));
+    let ptr = bx.pointercast(ptr, cx.type_i8p());
+    let min = cx.const_bool(false);
+    let null_unknown = cx.const_bool(true);
+    let remaining = if llvm_util::get_major_version() >= 9 {
+        let dynamic = cx.const_bool(false);
+        bx.call(objectsize, &[ptr, min, null_unknown, dynamic], None)
+    } else {
+        bx.call(objectsize, &[ptr, min, null_unknown], None)
+    };
+    let past_end = bx.icmp(IntPredicate::IntUGT, bytes, remaining);
+    let past_end = bx.and(forward, past_end);
+
+    bx.or(wraps, past_end)
+}
+
+fn check_copy_nonoverlapping(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    span: Span,
+    ty: Ty<'tcx>,
+    dst: &'ll Value,
+    src: &'ll Value,
+    count: &'ll Value,
+) {
+    let cx = bx.cx;
+    let (size, align) = cx.size_and_align_of(ty);
+    let bits = cx.tcx.data_layout.pointer_size.bits();
+    let mul = cx.get_intrinsic(&format!("llvm.umul.with.overflow.i{}", bits));
+    let pair = bx.call(mul, &[count, cx.const_usize(size.bytes())], None);
+    let bytes = bx.extract_value(pair, 0);
+    let overflow = bx.extract_value(pair, 1);
+    let ok = bx.not(overflow);
+    ub_check(bx, span, ok, "`copy_nonoverlapping` size overflowed");
+
+    let src = bx.ptrtoint(src, cx.type_isize());
+    let dst = bx.ptrtoint(dst, cx.type_isize());
+    let addrs = bx.or(src, dst);
+    let misalignment = bx.and(addrs, cx.const_usize(align.bytes() - 1));
+    let aligned = bx.icmp(IntPredicate::IntEQ, misalignment, cx.const_usize(0));
+    ub_check(bx, span, aligned, "`copy_nonoverlapping` with a misaligned pointer");
+
+    let src_end = bx.add(src, bytes);
+    let dst_end = bx.add(dst, bytes);
+    let src_before = bx.icmp(IntPredicate::IntULE, src_end, dst);
+    let dst_before = bx.icmp(IntPredicate::IntULE, dst_end, src);
+    let disjoint = bx.or(src_before, dst_before);
+    ub_check(bx, span, disjoint, "`copy_nonoverlapping` with overlapping ranges");
+}
+
 fn memset_intrinsic(
     bx: &mut Builder<'a, 'll, 'tcx>,
     volatile: bool,
@@ -1108,6 +1715,28 @@ fn generic_simd_intrinsic(
     let in_elem = arg_tys[0].simd_type(tcx);
     let in_len = arg_tys[0].simd_size(tcx);
 
+    // Scalable vectors only have a minimum length at compile time, so only the
+    // lane-wise operations that never need to know the actual number of lanes
+    // can be lowered for them.
+    let scalable = in_ty.is_scalable_simd();
+    if scalable || ret_ty.is_scalable_simd() {
+        match name {
+            "simd_add" | "simd_sub" | "simd_mul" | "simd_div" | "simd_rem" |
+            "simd_shl" | "simd_shr" | "simd_and" | "simd_or" | "simd_xor" |
+            "simd_fmax" | "simd_fmin" |
+            "simd_eq" | "simd_ne" | "simd_lt" | "simd_le" | "simd_gt" | "simd_ge" |
+            "simd_select" | "simd_cast" => {}
+            _ => return_error!("`{}` is not supported on scalable vector type `{}`",
+                               name, if scalable { in_ty } else { ret_ty }),
+        }
+        for &ty in arg_tys.iter().chain(Some(&ret_ty)) {
+            require!(!ty.is_simd() || ty.is_scalable_simd() == scalable,
+                     "cannot mix scalable vector type `{}` and fixed-length vector type `{}`",
+                     if scalable { in_ty } else { ret_ty },
+                     if scalable { ty } else { in_ty });
+        }
+    }
+
     let comparison = match name {
         "simd_eq" => Some(hir::BinOpKind::Eq),
         "simd_ne" => Some(hir::BinOpKind::Ne),
@@ -1132,6 +1761,9 @@ fn generic_simd_intrinsic(
                  ret_ty,
                  ret_ty.simd_type(tcx));
 
+        // Comparisons returning a mask of `bool`s give LLVM's `<N x i1>`
+        // directly, everything else sign-extends it to the integer lanes.
+        let llret_ty = bx.cx().layout_of(ret_ty).immediate_llvm_type(bx.cx());
         return Ok(compare_simd_types(bx,
                                      args[0].immediate(),
                                      args[1].immediate(),
@@ -1141,8 +1773,18 @@ fn generic_simd_intrinsic(
     }
 
     if name.starts_with("simd_shuffle") {
-        let n: usize = name["simd_shuffle".len()..].parse().unwrap_or_else(|_|
-            span_bug!(span, "bad `simd_shuffle` instruction only caught in codegen?"));
+        // The number of indices is either part of the name (`simd_shuffle{N}`),
+        // or the length of the index array (`simd_shuffle`).
+        let n: usize = if name == "simd_shuffle" {
+            match arg_tys[2].sty {
+                ty::Array(elem, len) if elem == tcx.types.u32 => len.unwrap_usize(tcx) as usize,
+                _ => return_error!("simd_shuffle index must be an array of `u32`, got `{}`",
+                                   arg_tys[2]),
+            }
+        } else {
+            name["simd_shuffle".len()..].parse().unwrap_or_else(|_|
+                span_bug!(span, "bad `simd_shuffle` instruction only caught in codegen?"))
+        };
 
         require_simd!(ret_ty, "return");
 
@@ -1192,15 +1834,45 @@ fn generic_simd_intrinsic(
         require!(in_elem == arg_tys[2],
                  "expected inserted type `{}` (element of input `{}`), found `{}`",
                  in_elem, in_ty, arg_tys[2]);
-        return Ok(bx.insert_element(args[0].immediate(),
-                                    args[2].immediate(),
-                                    args[1].immediate()))
+        let (vector, index, elem) = (args[0].immediate(),
+                                     args[1].immediate(),
+                                     args[2].immediate());
+        if bx.cx().const_to_opt_u128(index, false).is_some() {
+            return Ok(bx.insert_element(vector, elem, index));
+        }
+        // LLVM spills the vector to the stack for a variable `insertelement`;
+        // blending in a splat of the element under a lane mask keeps it in
+        // registers instead.
+        let lanes = (0..in_len).map(|i| bx.cx().const_u32(i as u32)).collect::<Vec<_>>();
+        let lanes = bx.cx().const_vector(&lanes);
+        let index = bx.vector_splat(in_len, index);
+        let mask = bx.icmp(IntPredicate::IntEQ, index, lanes);
+        let elem = bx.vector_splat(in_len, elem);
+        return Ok(bx.select(mask, elem, vector));
     }
     if name == "simd_extract" {
         require!(ret_ty == in_elem,
                  "expected return type `{}` (element of input `{}`), found `{}`",
                  in_elem, in_ty, ret_ty);
-        return Ok(bx.extract_element(args[0].immediate(), args[1].immediate()))
+        let (vector, index) = (args[0].immediate(), args[1].immediate());
+        if bx.cx().const_to_opt_u128(index, false).is_some() {
+            return Ok(bx.extract_element(vector, index));
+        }
+        // A variable lane has to be read back from memory. Rather than a new
+        // stack temporary for every extract, all of them in a function share
+        // one slot per vector type. An out-of-bounds index, for which
+        // `extractelement` would return poison, reads lane 0 rather than past
+        // the slot.
+        let len = bx.cx().const_u32(in_len as u32);
+        let in_bounds = bx.icmp(IntPredicate::IntULT, index, len);
+        let index = bx.select(in_bounds, index, bx.cx().const_u32(0));
+        let vector_align = bx.cx().layout_of(in_ty).align.abi;
+        let slot = simd_index_slot(bx, in_ty, vector_align);
+        bx.store(vector, slot, vector_align);
+        let elem_ptr_ty = bx.cx().type_ptr_to(llret_ty);
+        let elem_ptr = bx.pointercast(slot, elem_ptr_ty);
+        let elem_ptr = bx.inbounds_gep(elem_ptr, &[index]);
+        return Ok(bx.load(elem_ptr, bx.cx().layout_of(in_elem).align.abi));
     }
 
     if name == "simd_select" {
@@ -1212,16 +1884,69 @@ fn generic_simd_intrinsic(
                  m_len, v_len
         );
         match m_elem_ty.sty {
-            ty::Int(_) => {},
-            _ => return_error!("mask element type is `{}`, expected `i_`", m_elem_ty)
+            // `bool` masks already are vectors of i1s.
+            ty::Int(_) | ty::Uint(_) | ty::Bool => {},
+            _ => return_error!("mask element type is `{}`, expected `i_`, `u_` or `bool`",
+                               m_elem_ty)
         }
         // truncate the mask to a vector of i1s
         let i1 = bx.cx().type_i1();
-        let i1xn = bx.cx().type_vector(i1, m_len as u64);
+        let i1xn = if scalable {
+            bx.cx().type_scalable_vector(i1, m_len as u64)
+        } else {
+            bx.cx().type_vector(i1, m_len as u64)
+        };
         let m_i1s = bx.trunc(args[0].immediate(), i1xn);
         return Ok(bx.select(m_i1s, args[1].immediate(), args[2].immediate()));
     }
 
+    if name == "simd_bitmask" {
+        // Packs the most significant bit of each lane of a mask into an
+        // integer, lane 0 in the least significant bit. Vectors of fewer than
+        // 8 lanes give a `u8` with the unused high bits cleared.
+        if in_len > 64 {
+            return_error!("mask of length {} doesn't fit in a `u64`", in_len);
+        }
+        let bitmask_bits = in_len.max(8).next_power_of_two();
+        match ret_ty.sty {
+            ty::Uint(i) if i.bit_width() == Some(bitmask_bits) => {}
+            _ => return_error!("bitmask `{}`, expected `u{}`", ret_ty, bitmask_bits)
+        }
+        let i1xn = bx.cx().type_vector(bx.cx().type_i1(), in_len as u64);
+        let msbs = match in_elem.sty {
+            // `bool` masks already are vectors of i1s.
+            ty::Bool => args[0].immediate(),
+            ty::Int(_) | ty::Uint(_) => {
+                // Shift the sign bits down into bit 0, then truncate to a
+                // vector of i1s, which has the same bits as an iN.
+                let elem_bits = bx.cx().layout_of(in_elem).size.bits();
+                let elem_llty = bx.cx().element_type(bx.cx().val_ty(args[0].immediate()));
+                let shift = bx.cx().const_uint_big(elem_llty, (elem_bits - 1) as u128);
+                let shifts = bx.cx().const_vector(&vec![shift; in_len]);
+                let msbs = bx.lshr(args[0].immediate(), shifts);
+                bx.trunc(msbs, i1xn)
+            }
+            _ => return_error!("mask element type is `{}`, expected `i_`, `u_` or `bool`",
+                               in_elem)
+        };
+        // Bitcasting a vector to an integer follows the memory layout, so on
+        // big-endian targets lane 0 would end up in the most significant bit.
+        // Reverse the lanes first to keep lane 0 in bit 0 everywhere.
+        let msbs = if bx.cx().data_layout().endian == Endian::Big {
+            let indices: Vec<_> = (0..in_len as u64).rev()
+                .map(|i| bx.cx().const_i32(i as i32))
+                .collect();
+            let undef = bx.cx().const_undef(i1xn);
+            let mask = bx.cx().const_vector(&indices);
+            bx.shuffle_vector(msbs, undef, mask)
+        } else {
+            msbs
+        };
+        let in_int = bx.cx().type_ix(in_len as u64);
+        let bits = bx.bitcast(msbs, in_int);
+        return Ok(bx.zext(bits, llret_ty));
+    }
+
     fn simd_simple_float_intrinsic(
         name: &str,
         in_elem: &::rustc::ty::TyS,
@@ -1305,6 +2030,18 @@ fn generic_simd_intrinsic(
         "simd_ceil" => {
             return simd_simple_float_intrinsic("ceil", in_elem, in_ty, in_len, bx, span, args);
         }
+        "simd_round" => {
+            return simd_simple_float_intrinsic("round", in_elem, in_ty, in_len, bx, span, args);
+        }
+        "simd_trunc" => {
+            return simd_simple_float_intrinsic("trunc", in_elem, in_ty, in_len, bx, span, args);
+        }
+        "simd_rint" => {
+            return simd_simple_float_intrinsic("rint", in_elem, in_ty, in_len, bx, span, args);
+        }
+        "simd_nearbyint" => {
+            return simd_simple_float_intrinsic("nearbyint", in_elem, in_ty, in_len, bx, span, args);
+        }
         "simd_fexp" => {
             return simd_simple_float_intrinsic("exp", in_elem, in_ty, in_len, bx, span, args);
         }
@@ -1332,6 +2069,27 @@ fn generic_simd_intrinsic(
         _ => { /* fallthrough */ }
     }
 
+    if name == "simd_abs" {
+        // simd_abs(x: <N x T>) -> <N x T>
+        // * T: any signed integer or float type
+        // For integers, `T::min_value()` stays as it is, like with `wrapping_abs`.
+        // LLVM matches the compare-negate-select sequence to instructions like
+        // `PABSB` and `ABS`.
+        match in_elem.sty {
+            ty::Float(_) => {
+                return simd_simple_float_intrinsic("fabs", in_elem, in_ty, in_len, bx, span, args);
+            }
+            ty::Int(_) => {
+                let x = args[0].immediate();
+                let zero = bx.cx().const_null(llret_ty);
+                let neg = bx.sub(zero, x);
+                let is_neg = bx.icmp(IntPredicate::IntSLT, x, zero);
+                return Ok(bx.select(is_neg, neg, x));
+            }
+            _ => return_error!("unsupported operation on `{}` with element `{}`", in_ty, in_elem),
+        }
+    }
+
     // FIXME: use:
     //  https://github.com/llvm-mirror/llvm/blob/master/include/llvm/IR/Function.h#L182
     //  https://github.com/llvm-mirror/llvm/blob/master/include/llvm/IR/Intrinsics.h#L81
@@ -1361,10 +2119,43 @@ fn generic_simd_intrinsic(
         cx.type_vector(elem_ty, vec_len as u64)
     }
 
+    // Pointers needing metadata cannot be built from, or reduced to, a single value.
+    fn is_thin_ptr<'tcx>(tcx: ty::TyCtxt<'_, 'tcx, 'tcx>, span: Span, t: Ty<'tcx>) -> bool {
+        match t.sty {
+            ty::RawPtr(p) => {
+                let pointee = tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), p.ty);
+                pointee.is_sized(tcx.at(span), ty::ParamEnv::reveal_all())
+            }
+            _ => false,
+        }
+    }
 
-    if name == "simd_gather" {
+    // simd_{gather,scatter}_with_align take the alignment of the elements as
+    // a fourth argument instead of assuming `align_of::<T>()`, e.g. to access
+    // packed structures. It has to be a constant power of two, which
+    // const-qualification already made sure of for the former.
+    let explicit_align = if name == "simd_gather_with_align" ||
+                            name == "simd_scatter_with_align" {
+        match bx.cx().const_to_opt_u128(args[3].immediate(), false) {
+            Some(align) if !align.is_power_of_two() => {
+                return_error!("alignment `{}` is not a power of two", align)
+            }
+            // LLVM's maximum alignment.
+            Some(align) if align > 1 << 29 => {
+                return_error!("alignment `{}` is larger than the maximum of `{}`", align, 1 << 29)
+            }
+            Some(align) => Some(align as u64),
+            None => span_bug!(span, "alignment of `{}` is not a constant", name),
+        }
+    } else {
+        None
+    };
+
+    if name == "simd_gather" || name == "simd_gather_with_align" {
         // simd_gather(values: <N x T>, pointers: <N x *_ T>,
         //             mask: <N x i{M}>) -> <N x T>
+        // simd_gather_with_align(values: <N x T>, pointers: <N x *_ T>,
+        //                        mask: <N x i{M}>, align: u32) -> <N x T>
         // * N: number of elements in the input vectors
         // * T: type of the element to load
         // * M: any integer width is supported, will be truncated to i1
@@ -1424,19 +2215,23 @@ fn generic_simd_intrinsic(
         assert_eq!(pointer_count - 1, ptr_count(arg_tys[0].simd_type(tcx)));
         assert_eq!(underlying_ty, non_ptr(arg_tys[0].simd_type(tcx)));
 
-        // The element type of the third argument must be a signed integer type of any width:
+        // The element type of the third argument must be an integer type of any width:
         match arg_tys[2].simd_type(tcx).sty {
-            ty::Int(_) => (),
+            ty::Int(_) | ty::Uint(_) => (),
             _ => {
                 require!(false, "expected element type `{}` of third argument `{}` \
-                                 to be a signed integer type",
+                                 to be an integer type",
                          arg_tys[2].simd_type(tcx).sty, arg_tys[2]);
             }
         }
 
-        // Alignment of T, must be a constant integer value:
+        // Alignment of T, or the one given explicitly, must be a constant integer value:
         let alignment_ty = bx.cx().type_i32();
-        let alignment = bx.cx().const_i32(bx.cx().align_of(in_elem).bytes() as i32);
+        let alignment = match explicit_align {
+            Some(align) => align,
+            None => bx.cx().align_of(in_elem).bytes(),
+        };
+        let alignment = bx.cx().const_i32(alignment as i32);
 
         // Truncate the mask vector to a vector of i1s:
         let (mask, mask_ty) = {
@@ -1467,9 +2262,11 @@ fn generic_simd_intrinsic(
         return Ok(v);
     }
 
-    if name == "simd_scatter" {
+    if name == "simd_scatter" || name == "simd_scatter_with_align" {
         // simd_scatter(values: <N x T>, pointers: <N x *mut T>,
         //             mask: <N x i{M}>) -> ()
+        // simd_scatter_with_align(values: <N x T>, pointers: <N x *mut T>,
+        //                         mask: <N x i{M}>, align: u32) -> ()
         // * N: number of elements in the input vectors
         // * T: type of the element to load
         // * M: any integer width is supported, will be truncated to i1
@@ -1524,19 +2321,23 @@ fn generic_simd_intrinsic(
         assert_eq!(pointer_count - 1, ptr_count(arg_tys[0].simd_type(tcx)));
         assert_eq!(underlying_ty, non_ptr(arg_tys[0].simd_type(tcx)));
 
-        // The element type of the third argument must be a signed integer type of any width:
+        // The element type of the third argument must be an integer type of any width:
         match arg_tys[2].simd_type(tcx).sty {
-            ty::Int(_) => (),
+            ty::Int(_) | ty::Uint(_) => (),
             _ => {
                 require!(false, "expected element type `{}` of third argument `{}` \
-                                 to be a signed integer type",
+                                 to be an integer type",
                          arg_tys[2].simd_type(tcx).sty, arg_tys[2]);
             }
         }
 
-        // Alignment of T, must be a constant integer value:
+        // Alignment of T, or the one given explicitly, must be a constant integer value:
         let alignment_ty = bx.cx().type_i32();
-        let alignment = bx.cx().const_i32(bx.cx().align_of(in_elem).bytes() as i32);
+        let alignment = match explicit_align {
+            Some(align) => align,
+            None => bx.cx().align_of(in_elem).bytes(),
+        };
+        let alignment = bx.cx().const_i32(alignment as i32);
 
         // Truncate the mask vector to a vector of i1s:
         let (mask, mask_ty) = {
@@ -1568,6 +2369,194 @@ fn generic_simd_intrinsic(
         return Ok(v);
     }
 
+    if name == "simd_masked_load" || name == "simd_expand_load" {
+        // simd_masked_load(mask: <N x i{M}>, pointer: *_ T, values: <N x T>) -> <N x T>
+        // simd_expand_load(mask: <N x i{M}>, pointer: *_ T, values: <N x T>) -> <N x T>
+        // * N: number of elements in the input vectors
+        // * T: type of the element to load
+        // * M: any integer width is supported, will be truncated to i1
+        // simd_masked_load loads the elements whose mask is set from the
+        // elements at the same index at `pointer`, and simd_expand_load from
+        // consecutive elements at `pointer`, one per element whose mask is
+        // set. Both take the other elements from `values`.
+
+        require_simd!(arg_tys[2], "third");
+        require_simd!(ret_ty, "return");
+
+        let values_len = arg_tys[2].simd_size(tcx);
+        let values_elem = arg_tys[2].simd_type(tcx);
+        require!(in_len == values_len,
+                 "expected {} argument with length {} (same as input type `{}`), \
+                  found `{}` with length {}", "third", in_len, in_ty, arg_tys[2], values_len);
+
+        // The return type must match the third argument type
+        require!(ret_ty == arg_tys[2],
+                 "expected return type `{}`, found `{}`",
+                 arg_tys[2], ret_ty);
+
+        // The second argument must be a pointer to the element type of the third
+        match arg_tys[1].sty {
+            ty::RawPtr(p) if p.ty == values_elem => {}
+            _ => {
+                return_error!("expected second argument `{}` to be a pointer to the \
+                               element type `{}` of the third argument `{}`",
+                              arg_tys[1], values_elem, arg_tys[2]);
+            }
+        }
+        match values_elem.sty {
+            ty::Int(_) | ty::Uint(_) | ty::Float(_) => {}
+            _ => {
+                return_error!("unsupported element type `{}` of third argument `{}`",
+                              values_elem, arg_tys[2]);
+            }
+        }
+
+        // The element type of the mask must be an integer type of any width:
+        match in_elem.sty {
+            ty::Int(_) | ty::Uint(_) => (),
+            _ => {
+                return_error!("expected element type `{}` of first argument `{}` \
+                               to be an integer type",
+                              in_elem, in_ty);
+            }
+        }
+
+        // Alignment of T, must be a constant integer value:
+        let alignment_ty = bx.cx().type_i32();
+        let alignment = bx.cx().const_i32(bx.cx().align_of(values_elem).bytes() as i32);
+
+        // Truncate the mask vector to a vector of i1s:
+        let (mask, mask_ty) = {
+            let i1 = bx.cx().type_i1();
+            let i1xn = bx.cx().type_vector(i1, in_len as u64);
+            (bx.trunc(args[0].immediate(), i1xn), i1xn)
+        };
+
+        let llvm_elem_vec_ty = llvm_vector_ty(bx.cx(), values_elem, in_len, 0);
+        let llvm_elem_vec_str = llvm_vector_str(values_elem, in_len, 0);
+
+        if name == "simd_expand_load" {
+            // Takes a pointer to the element type and no alignment:
+            let llvm_intrinsic = format!("llvm.masked.expandload.{}", llvm_elem_vec_str);
+            let llvm_pointer_ty = bx.cx().type_ptr_to(bx.cx().element_type(llvm_elem_vec_ty));
+            let f = bx.cx().declare_cfn(&llvm_intrinsic,
+                                         bx.cx().type_func(&[
+                                             llvm_pointer_ty,
+                                             mask_ty,
+                                             llvm_elem_vec_ty], llvm_elem_vec_ty));
+            llvm::SetUnnamedAddr(f, false);
+            let pointer = bx.pointercast(args[1].immediate(), llvm_pointer_ty);
+            let v = bx.call(f, &[pointer, mask, args[2].immediate()], None);
+            return Ok(v);
+        }
+
+        let llvm_pointer_ty = bx.cx().type_ptr_to(llvm_elem_vec_ty);
+        let pointer = bx.pointercast(args[1].immediate(), llvm_pointer_ty);
+
+        let llvm_intrinsic = format!("llvm.masked.load.{}.p0{}",
+                                     llvm_elem_vec_str, llvm_elem_vec_str);
+        let f = bx.cx().declare_cfn(&llvm_intrinsic,
+                                     bx.cx().type_func(&[
+                                         llvm_pointer_ty,
+                                         alignment_ty,
+                                         mask_ty,
+                                         llvm_elem_vec_ty], llvm_elem_vec_ty));
+        llvm::SetUnnamedAddr(f, false);
+        let v = bx.call(f, &[pointer, alignment, mask, args[2].immediate()], None);
+        return Ok(v);
+    }
+
+    if name == "simd_masked_store" || name == "simd_compress_store" {
+        // simd_masked_store(mask: <N x i{M}>, pointer: *mut T, values: <N x T>) -> ()
+        // simd_compress_store(mask: <N x i{M}>, pointer: *mut T, values: <N x T>) -> ()
+        // * N: number of elements in the input vectors
+        // * T: type of the element to store
+        // * M: any integer width is supported, will be truncated to i1
+        // simd_masked_store stores the elements of `values` whose mask is set
+        // to the elements at the same index at `pointer`, and
+        // simd_compress_store to consecutive elements at `pointer`. Both leave
+        // the rest of the memory untouched.
+
+        require_simd!(arg_tys[2], "third");
+
+        let values_len = arg_tys[2].simd_size(tcx);
+        let values_elem = arg_tys[2].simd_type(tcx);
+        require!(in_len == values_len,
+                 "expected {} argument with length {} (same as input type `{}`), \
+                  found `{}` with length {}", "third", in_len, in_ty, arg_tys[2], values_len);
+
+        // The second argument must be a mutable pointer to the element type of the third
+        match arg_tys[1].sty {
+            ty::RawPtr(p) if p.ty == values_elem && p.mutbl == hir::MutMutable => {}
+            _ => {
+                return_error!("expected second argument `{}` to be a mutable pointer to the \
+                               element type `{}` of the third argument `{}`",
+                              arg_tys[1], values_elem, arg_tys[2]);
+            }
+        }
+        match values_elem.sty {
+            ty::Int(_) | ty::Uint(_) | ty::Float(_) => {}
+            _ => {
+                return_error!("unsupported element type `{}` of third argument `{}`",
+                              values_elem, arg_tys[2]);
+            }
+        }
+
+        // The element type of the mask must be an integer type of any width:
+        match in_elem.sty {
+            ty::Int(_) | ty::Uint(_) => (),
+            _ => {
+                return_error!("expected element type `{}` of first argument `{}` \
+                               to be an integer type",
+                              in_elem, in_ty);
+            }
+        }
+
+        // Alignment of T, must be a constant integer value:
+        let alignment_ty = bx.cx().type_i32();
+        let alignment = bx.cx().const_i32(bx.cx().align_of(values_elem).bytes() as i32);
+
+        // Truncate the mask vector to a vector of i1s:
+        let (mask, mask_ty) = {
+            let i1 = bx.cx().type_i1();
+            let i1xn = bx.cx().type_vector(i1, in_len as u64);
+            (bx.trunc(args[0].immediate(), i1xn), i1xn)
+        };
+
+        let ret_t = bx.cx().type_void();
+
+        let llvm_elem_vec_ty = llvm_vector_ty(bx.cx(), values_elem, in_len, 0);
+        let llvm_elem_vec_str = llvm_vector_str(values_elem, in_len, 0);
+
+        if name == "simd_compress_store" {
+            // Takes a pointer to the element type and no alignment:
+            let llvm_intrinsic = format!("llvm.masked.compressstore.{}", llvm_elem_vec_str);
+            let llvm_pointer_ty = bx.cx().type_ptr_to(bx.cx().element_type(llvm_elem_vec_ty));
+            let f = bx.cx().declare_cfn(&llvm_intrinsic,
+                                         bx.cx().type_func(&[llvm_elem_vec_ty,
+                                                      llvm_pointer_ty,
+                                                      mask_ty], ret_t));
+            llvm::SetUnnamedAddr(f, false);
+            let pointer = bx.pointercast(args[1].immediate(), llvm_pointer_ty);
+            let v = bx.call(f, &[args[2].immediate(), pointer, mask], None);
+            return Ok(v);
+        }
+
+        let llvm_pointer_ty = bx.cx().type_ptr_to(llvm_elem_vec_ty);
+        let pointer = bx.pointercast(args[1].immediate(), llvm_pointer_ty);
+
+        let llvm_intrinsic = format!("llvm.masked.store.{}.p0{}",
+                                     llvm_elem_vec_str, llvm_elem_vec_str);
+        let f = bx.cx().declare_cfn(&llvm_intrinsic,
+                                     bx.cx().type_func(&[llvm_elem_vec_ty,
+                                                  llvm_pointer_ty,
+                                                  alignment_ty,
+                                                  mask_ty], ret_t));
+        llvm::SetUnnamedAddr(f, false);
+        let v = bx.call(f, &[args[2].immediate(), pointer, alignment, mask], None);
+        return Ok(v);
+    }
+
     macro_rules! arith_red {
         ($name:tt : $integer_reduce:ident, $float_reduce:ident, $ordered:expr) => {
             if name == $name {
@@ -1590,41 +2579,45 @@ fn generic_simd_intrinsic(
                         }
                     },
                     ty::Float(f) => {
-                        // ordered arithmetic reductions take an accumulator
-                        let acc = if $ordered {
-                            let acc = args[1].immediate();
-                            // FIXME: https://bugs.llvm.org/show_bug.cgi?id=36734
-                            // * if the accumulator of the fadd isn't 0, incorrect
-                            //   code is generated
-                            // * if the accumulator of the fmul isn't 1, incorrect
-                            //   code is generated
-                            match bx.cx().const_get_real(acc) {
-                                None => return_error!("accumulator of {} is not a constant", $name),
-                                Some((v, loses_info)) => {
-                                    if $name.contains("mul") && v != 1.0_f64 {
-                                        return_error!("accumulator of {} is not 1.0", $name);
-                                    } else if $name.contains("add") && v != 0.0_f64 {
-                                        return_error!("accumulator of {} is not 0.0", $name);
-                                    } else if loses_info {
-                                        return_error!("accumulator of {} loses information", $name);
-                                    }
-                                }
-                            }
-                            acc
-                        } else {
-                            // unordered arithmetic reductions do not:
-                            match f.bit_width() {
-                                32 => bx.cx().const_undef(bx.cx().type_f32()),
-                                64 => bx.cx().const_undef(bx.cx().type_f64()),
-                                v => {
-                                    return_error!(r#"
+                        let fty = match f.bit_width() {
+                            32 => bx.cx().type_f32(),
+                            64 => bx.cx().type_f64(),
+                            v => {
+                                return_error!(r#"
 unsupported {} from `{}` with element `{}` of size `{}` to `{}`"#,
-                                        $name, in_ty, in_elem, v, ret_ty
-                                    )
-                                }
+                                    $name, in_ty, in_elem, v, ret_ty
+                                )
                             }
                         };
-                        Ok(bx.$float_reduce(acc, args[0].immediate()))
+                        if !$ordered {
+                            // unordered arithmetic reductions don't take an accumulator
+                            let acc = bx.cx().const_undef(fty);
+                            return Ok(bx.$float_reduce(acc, args[0].immediate()));
+                        }
+
+                        // ordered arithmetic reductions do, but before LLVM 9 the
+                        // result is wrong unless the accumulator is the identity
+                        // (https://bugs.llvm.org/show_bug.cgi?id=36734), so there
+                        // the reduction starts from the identity and any other
+                        // accumulator is applied afterwards
+                        let acc = args[1].immediate();
+                        let is_mul = $name.contains("mul");
+                        let identity = if is_mul { 1.0 } else { 0.0 };
+                        let acc_is_identity = match bx.cx().const_get_real(acc) {
+                            Some((v, loses_info)) => v == identity && !loses_info,
+                            None => false,
+                        };
+                        if llvm_util::get_major_version() >= 9 || acc_is_identity {
+                            Ok(bx.$float_reduce(acc, args[0].immediate()))
+                        } else {
+                            let start = bx.cx().const_real(fty, identity);
+                            let r = bx.$float_reduce(start, args[0].immediate());
+                            if is_mul {
+                                Ok(bx.fmul(acc, r))
+                            } else {
+                                Ok(bx.fadd(acc, r))
+                            }
+                        }
                     }
                     _ => {
                         return_error!(
@@ -1637,11 +2630,48 @@ unsupported {} from `{}` with element `{}` of size `{}` to `{}`"#,
         }
     }
 
-    arith_red!("simd_reduce_add_ordered": vector_reduce_add, vector_reduce_fadd_fast, true);
-    arith_red!("simd_reduce_mul_ordered": vector_reduce_mul, vector_reduce_fmul_fast, true);
+    arith_red!("simd_reduce_add_ordered": vector_reduce_add, vector_reduce_fadd, true);
+    arith_red!("simd_reduce_mul_ordered": vector_reduce_mul, vector_reduce_fmul, true);
     arith_red!("simd_reduce_add_unordered": vector_reduce_add, vector_reduce_fadd_fast, false);
     arith_red!("simd_reduce_mul_unordered": vector_reduce_mul, vector_reduce_fmul_fast, false);
 
+    // Pairwise reductions combine adjacent lanes in log2(N) steps of
+    // shuffles, whatever `llvm.experimental.vector.reduce` lowers to, which
+    // also fixes the order of floating-point operations.
+    if name == "simd_reduce_add_pairwise" || name == "simd_reduce_mul_pairwise" {
+        require!(ret_ty == in_elem,
+                 "expected return type `{}` (element of input `{}`), found `{}`",
+                 in_elem, in_ty, ret_ty);
+        let float = match in_elem.sty {
+            ty::Int(_) | ty::Uint(_) => false,
+            ty::Float(_) => true,
+            _ => return_error!("unsupported {} from `{}` with element `{}` to `{}`",
+                               name, in_ty, in_elem, ret_ty),
+        };
+        require!(in_len.is_power_of_two(),
+                 "expected a power-of-two number of lanes, found `{}` with length {}",
+                 in_ty, in_len);
+        let is_mul = name.contains("mul");
+
+        let mut v = args[0].immediate();
+        let mut len = in_len;
+        while len > 1 {
+            len /= 2;
+            let undef = bx.cx().const_undef(bx.cx().val_ty(v));
+            let evens: Vec<_> = (0..len).map(|i| bx.cx().const_i32(2 * i as i32)).collect();
+            let odds: Vec<_> = (0..len).map(|i| bx.cx().const_i32(2 * i as i32 + 1)).collect();
+            let lo = bx.shuffle_vector(v, undef, bx.cx().const_vector(&evens));
+            let hi = bx.shuffle_vector(v, undef, bx.cx().const_vector(&odds));
+            v = match (float, is_mul) {
+                (false, false) => bx.add(lo, hi),
+                (false, true) => bx.mul(lo, hi),
+                (true, false) => bx.fadd(lo, hi),
+                (true, true) => bx.fmul(lo, hi),
+            };
+        }
+        return Ok(bx.extract_element(v, bx.cx().const_i32(0)));
+    }
+
     macro_rules! minmax_red {
         ($name:tt: $int_red:ident, $float_red:ident) => {
             if name == $name {
@@ -1684,20 +2714,21 @@ unsupported {} from `{}` with element `{}` of size `{}` to `{}`"#,
                     args[0].immediate()
                 } else {
                     match in_elem.sty {
-                        ty::Int(_) | ty::Uint(_) => {},
+                        ty::Int(_) | ty::Uint(_) | ty::Bool => {},
                         _ => {
                             return_error!("unsupported {} from `{}` with element `{}` to `{}`",
                                           $name, in_ty, in_elem, ret_ty)
                         }
                     }
 
-                    // boolean reductions operate on vectors of i1s:
+                    // boolean reductions operate on vectors of i1s, which
+                    // `bool` masks already are:
                     let i1 = bx.cx().type_i1();
                     let i1xn = bx.cx().type_vector(i1, in_len as u64);
                     bx.trunc(args[0].immediate(), i1xn)
                 };
                 return match in_elem.sty {
-                    ty::Int(_) | ty::Uint(_) => {
+                    ty::Int(_) | ty::Uint(_) | ty::Bool => {
                         let r = bx.$red(input);
                         Ok(
                             if !$boolean {
@@ -1722,7 +2753,90 @@ unsupported {} from `{}` with element `{}` of size `{}` to `{}`"#,
     bitwise_red!("simd_reduce_all": vector_reduce_and, true);
     bitwise_red!("simd_reduce_any": vector_reduce_or, true);
 
-    if name == "simd_cast" {
+    if name == "simd_cast_ptr" || name == "simd_expose_addr" || name == "simd_from_exposed_addr" {
+        // simd_cast_ptr(values: <N x *_ T>) -> <N x *_ U>
+        // simd_expose_addr(values: <N x *_ T>) -> <N x usize>
+        // simd_from_exposed_addr(values: <N x usize>) -> <N x *_ T>
+        // * N: number of elements in the input vectors
+        // * T, U: the pointee types, which must not need pointer metadata
+        require_simd!(ret_ty, "return");
+        let out_len = ret_ty.simd_size(tcx);
+        require!(in_len == out_len,
+                 "expected return type with length {} (same as input type `{}`), \
+                  found `{}` with length {}",
+                 in_len, in_ty,
+                 ret_ty, out_len);
+        let out_elem = ret_ty.simd_type(tcx);
+
+        fn is_usize(t: Ty) -> bool {
+            match t.sty {
+                ty::Uint(ast::UintTy::Usize) => true,
+                _ => false,
+            }
+        }
+
+        if name == "simd_from_exposed_addr" {
+            require!(is_usize(in_elem),
+                     "expected input element type `{}` to be `usize`", in_elem);
+        } else {
+            require!(is_thin_ptr(tcx, span, in_elem),
+                     "expected input element type `{}` to be a thin pointer", in_elem);
+        }
+        if name == "simd_expose_addr" {
+            require!(is_usize(out_elem),
+                     "expected return element type `{}` to be `usize`", out_elem);
+        } else {
+            require!(is_thin_ptr(tcx, span, out_elem),
+                     "expected return element type `{}` to be a thin pointer", out_elem);
+        }
+
+        return Ok(match name {
+            "simd_cast_ptr" => bx.pointercast(args[0].immediate(), llret_ty),
+            "simd_expose_addr" => bx.ptrtoint(args[0].immediate(), llret_ty),
+            _ => bx.inttoptr(args[0].immediate(), llret_ty),
+        });
+    }
+
+    if name == "simd_arith_offset" {
+        // simd_arith_offset(pointers: <N x *_ T>, offsets: <N x isize>) -> <N x *_ T>
+        // * N: number of elements in the input vectors
+        // * T: the pointee type, which must not need pointer metadata
+        // Like `arith_offset`, each lane is offset by a multiple of the size of T
+        // and may wrap around the address space.
+        require!(is_thin_ptr(tcx, span, in_elem),
+                 "expected input element type `{}` to be a thin pointer", in_elem);
+        require_simd!(arg_tys[1], "second");
+        require!(in_len == arg_tys[1].simd_size(tcx),
+                 "expected {} argument with length {} (same as input type `{}`), \
+                  found `{}` with length {}", "second", in_len, in_ty, arg_tys[1],
+                 arg_tys[1].simd_size(tcx));
+        match arg_tys[1].simd_type(tcx).sty {
+            ty::Int(ast::IntTy::Isize) => (),
+            _ => {
+                require!(false, "expected element type `{}` of second argument `{}` \
+                                 to be `isize`",
+                         arg_tys[1].simd_type(tcx).sty, arg_tys[1]);
+            }
+        }
+        require!(ret_ty == in_ty,
+                 "expected return type `{}`, found `{}`",
+                 in_ty, ret_ty);
+
+        // The GEP is strided by the pointee, so make sure the lanes point to T.
+        let pointee = match in_elem.sty {
+            ty::RawPtr(p) => p.ty,
+            _ => unreachable!(),
+        };
+        let llpointee = bx.cx().layout_of(pointee).llvm_type(bx.cx());
+        let llptrs_ty = bx.cx().type_vector(bx.cx().type_ptr_to(llpointee), in_len as u64);
+        let ptrs = bx.pointercast(args[0].immediate(), llptrs_ty);
+        let v = bx.gep(ptrs, &[args[1].immediate()]);
+        return Ok(bx.pointercast(v, llret_ty));
+    }
+
+    if name == "simd_cast" || name == "simd_as" {
+        // simd_cast converts the elements like LLVM's casts, while simd_as
+        // has the semantics of Rust's `as`, i.e. float to int casts saturate.
         require_simd!(ret_ty, "return");
         let out_len = ret_ty.simd_size(tcx);
         require!(in_len == out_len,
@@ -1737,20 +2851,15 @@ unsupported {} from `{}` with element `{}` of size `{}` to `{}`"#,
 
         enum Style { Float, Int(/* is signed? */ bool), Unsupported }
 
-        let (in_style, in_width) = match in_elem.sty {
-            // vectors of pointer-sized integers should've been
-            // disallowed before here, so this unwrap is safe.
-            ty::Int(i) => (Style::Int(true), i.bit_width().unwrap()),
-            ty::Uint(u) => (Style::Int(false), u.bit_width().unwrap()),
-            ty::Float(f) => (Style::Float, f.bit_width()),
-            _ => (Style::Unsupported, 0)
-        };
-        let (out_style, out_width) = match out_elem.sty {
-            ty::Int(i) => (Style::Int(true), i.bit_width().unwrap()),
-            ty::Uint(u) => (Style::Int(false), u.bit_width().unwrap()),
-            ty::Float(f) => (Style::Float, f.bit_width()),
-            _ => (Style::Unsupported, 0)
+        // The sizes of the layouts also cover pointer-sized integers.
+        let style = |elem: Ty| match elem.sty {
+            ty::Int(_) => Style::Int(true),
+            ty::Uint(_) => Style::Int(false),
+            ty::Float(_) => Style::Float,
+            _ => Style::Unsupported
         };
+        let (in_style, in_width) = (style(in_elem), bx.cx().layout_of(in_elem).size.bits());
+        let (out_style, out_width) = (style(out_elem), bx.cx().layout_of(out_elem).size.bits());
 
         match (in_style, out_style) {
             (Style::Int(in_is_signed), Style::Int(_)) => {
@@ -1772,11 +2881,18 @@ unsupported {} from `{}` with element `{}` of size `{}` to `{}`"#,
                 })
             }
             (Style::Float, Style::Int(out_is_signed)) => {
-                return Ok(if out_is_signed {
-                    bx.fptosi(args[0].immediate(), llret_ty)
+                let x = args[0].immediate();
+                let fptosui_result = if out_is_signed {
+                    bx.fptosi(x, llret_ty)
                 } else {
-                    bx.fptoui(args[0].immediate(), llret_ty)
-                })
+                    bx.fptoui(x, llret_ty)
+                };
+                if name == "simd_as" {
+                    let llarg_ty = bx.cx().val_ty(x);
+                    return Ok(rvalue::saturate_float_to_int(bx, out_is_signed, x, fptosui_result,
+                                                            llarg_ty, llret_ty));
+                }
+                return Ok(fptosui_result);
             }
             (Style::Float, Style::Float) => {
                 return Ok(match in_width.cmp(&out_width) {
@@ -1792,6 +2908,172 @@ unsupported {} from `{}` with element `{}` of size `{}` to `{}`"#,
                  in_ty, in_elem,
                  ret_ty, out_elem);
     }
+
+    if name == "simd_rotate_left" || name == "simd_rotate_right" ||
+       name == "simd_funnel_shl" || name == "simd_funnel_shr" {
+        // simd_rotate_{left,right}(values: <N x T>, shifts: <N x T>) -> <N x T>
+        // simd_funnel_sh{l,r}(hi: <N x T>, lo: <N x T>, shifts: <N x T>) -> <N x T>
+        // * T: any integer type, each element is shifted by the shift at the
+        //      same index modulo its bit width
+        // A funnel shift shifts the concatenation of the elements of `hi` and
+        // `lo` and keeps the high (shl) or low (shr) half, and a rotate is a
+        // funnel shift of an element with itself.
+        match in_elem.sty {
+            ty::Int(_) | ty::Uint(_) => {}
+            _ => return_error!("unsupported operation on `{}` with element `{}`", in_ty, in_elem),
+        }
+        let is_left = name == "simd_rotate_left" || name == "simd_funnel_shl";
+        let width = bx.cx().size_of(in_elem).bits();
+        let (hi, lo, raw_shift) = if name.starts_with("simd_rotate") {
+            (args[0].immediate(), args[0].immediate(), args[1].immediate())
+        } else {
+            (args[0].immediate(), args[1].immediate(), args[2].immediate())
+        };
+        if llvm_util::get_major_version() >= 7 {
+            let llvm_intrinsic = format!("llvm.fsh{}.v{}i{}",
+                                         if is_left { 'l' } else { 'r' }, in_len, width);
+            let f = bx.cx().declare_cfn(&llvm_intrinsic,
+                                         bx.cx().type_func(&[llret_ty, llret_ty, llret_ty],
+                                                           llret_ty));
+            llvm::SetUnnamedAddr(f, false);
+            return Ok(bx.call(f, &[hi, lo, raw_shift], None));
+        }
+        // fshl: (HI << (S % BW)) | (LO >> ((BW - S) % BW)), or HI if S % BW == 0
+        // fshr: (HI << ((BW - S) % BW)) | (LO >> (S % BW)), or LO if S % BW == 0
+        let llelem_ty = bx.cx().type_ix(width);
+        let width = bx.cx().const_uint(llelem_ty, width);
+        let width = bx.cx().const_vector(&vec![width; in_len]);
+        let zero = bx.cx().const_uint(llelem_ty, 0);
+        let zero = bx.cx().const_vector(&vec![zero; in_len]);
+        let shift = bx.urem(raw_shift, width);
+        let width_minus_shift = bx.sub(width, shift);
+        let inv_shift = bx.urem(width_minus_shift, width);
+        let shift1 = bx.shl(hi, if is_left { shift } else { inv_shift });
+        let shift2 = bx.lshr(lo, if !is_left { shift } else { inv_shift });
+        let shifted = bx.or(shift1, shift2);
+        let no_shift = bx.icmp(IntPredicate::IntEQ, shift, zero);
+        return Ok(bx.select(no_shift, if is_left { hi } else { lo }, shifted));
+    }
+
+    if name == "simd_mul_high" {
+        // simd_mul_high(a: <N x T>, b: <N x T>) -> <N x T>
+        // * T: any integer type
+        // Returns the high half of each full-width product; together with
+        // `simd_mul`, which gives the low half, this is a widening multiply.
+        // LLVM matches the extend-multiply-shift-truncate sequence to
+        // instructions like `PMULHW` and `SQDMULH`.
+        let signed = match in_elem.sty {
+            ty::Int(_) => true,
+            ty::Uint(_) => false,
+            _ => return_error!("unsupported operation on `{}` with element `{}`", in_ty, in_elem),
+        };
+        let width = bx.cx().size_of(in_elem).bits();
+        let llwide_elem_ty = bx.cx().type_ix(width * 2);
+        let llwide_ty = bx.cx().type_vector(llwide_elem_ty, in_len as u64);
+        let (a, b) = if signed {
+            (bx.sext(args[0].immediate(), llwide_ty), bx.sext(args[1].immediate(), llwide_ty))
+        } else {
+            (bx.zext(args[0].immediate(), llwide_ty), bx.zext(args[1].immediate(), llwide_ty))
+        };
+        let product = bx.mul(a, b);
+        let shift = bx.cx().const_uint(llwide_elem_ty, width);
+        let shift = bx.cx().const_vector(&vec![shift; in_len]);
+        let high = if signed { bx.ashr(product, shift) } else { bx.lshr(product, shift) };
+        return Ok(bx.trunc(high, llret_ty));
+    }
+
+    if name == "simd_avg_round" || name == "simd_avg_floor" {
+        // simd_avg_round(a: <N x T>, b: <N x T>) -> <N x T>
+        // simd_avg_floor(a: <N x T>, b: <N x T>) -> <N x T>
+        // * T: any integer type
+        // Returns `(a + b + 1) >> 1` respectively `(a + b) >> 1`, computed
+        // without overflow. LLVM matches the extend-add-shift-truncate
+        // sequence to instructions like `PAVGB` and `URHADD`.
+        let signed = match in_elem.sty {
+            ty::Int(_) => true,
+            ty::Uint(_) => false,
+            _ => return_error!("unsupported operation on `{}` with element `{}`", in_ty, in_elem),
+        };
+        let width = bx.cx().size_of(in_elem).bits();
+        let llwide_elem_ty = bx.cx().type_ix(width * 2);
+        let llwide_ty = bx.cx().type_vector(llwide_elem_ty, in_len as u64);
+        let (a, b) = if signed {
+            (bx.sext(args[0].immediate(), llwide_ty), bx.sext(args[1].immediate(), llwide_ty))
+        } else {
+            (bx.zext(args[0].immediate(), llwide_ty), bx.zext(args[1].immediate(), llwide_ty))
+        };
+        let one = bx.cx().const_uint(llwide_elem_ty, 1);
+        let one = bx.cx().const_vector(&vec![one; in_len]);
+        let mut sum = bx.add(a, b);
+        if name == "simd_avg_round" {
+            sum = bx.add(sum, one);
+        }
+        let avg = if signed { bx.ashr(sum, one) } else { bx.lshr(sum, one) };
+        return Ok(bx.trunc(avg, llret_ty));
+    }
+
+    if name == "simd_pack_sat" {
+        // simd_pack_sat(x: <N x T>) -> <N x U>
+        // * T, U: any integer types, with U narrower than T
+        // Narrows each lane after clamping it to the range of U. LLVM matches
+        // the compare-select-truncate sequence to instructions like `PACKSSWB`,
+        // `PACKUSWB` and `SQXTN`.
+        require_simd!(ret_ty, "return");
+        let out_len = ret_ty.simd_size(tcx);
+        require!(in_len == out_len,
+                 "expected return type with length {} (same as input type `{}`), \
+                  found `{}` with length {}",
+                 in_len, in_ty,
+                 ret_ty, out_len);
+        let out_elem = ret_ty.simd_type(tcx);
+        let (in_signed, out_signed) = match (&in_elem.sty, &out_elem.sty) {
+            (ty::Int(_), ty::Int(_)) => (true, true),
+            (ty::Int(_), ty::Uint(_)) => (true, false),
+            (ty::Uint(_), ty::Int(_)) => (false, true),
+            (ty::Uint(_), ty::Uint(_)) => (false, false),
+            _ => return_error!("expected integer element types, found `{}` and `{}`",
+                               in_elem, out_elem),
+        };
+        let in_width = bx.cx().size_of(in_elem).bits();
+        let out_width = bx.cx().size_of(out_elem).bits();
+        require!(out_width < in_width,
+                 "expected return element type `{}` to be narrower than input element type `{}`",
+                 out_elem, in_elem);
+
+        fn splat(cx: &CodegenCx<'ll, '_>, llelem_ty: &'ll Type, len: usize, v: u128)
+                 -> &'ll Value {
+            cx.const_vector(&vec![cx.const_uint_big(llelem_ty, v); len])
+        }
+        let llin_elem_ty = bx.cx().type_ix(in_width);
+        let mut x = args[0].immediate();
+
+        // The largest value of U, which is positive in T as well.
+        let max = if out_signed {
+            (1u128 << (out_width - 1)) - 1
+        } else {
+            (1u128 << out_width) - 1
+        };
+        let max = splat(bx.cx(), llin_elem_ty, in_len, max);
+        let pred = if in_signed { IntPredicate::IntSGT } else { IntPredicate::IntUGT };
+        let too_big = bx.icmp(pred, x, max);
+        x = bx.select(too_big, max, x);
+
+        // Only signed lanes can be below the smallest value of U.
+        if in_signed {
+            let min = if out_signed {
+                // Sign-extended to the width of T.
+                let in_mask = !0u128 >> (128 - in_width);
+                (!0u128 << (out_width - 1)) & in_mask
+            } else {
+                0
+            };
+            let min = splat(bx.cx(), llin_elem_ty, in_len, min);
+            let too_small = bx.icmp(IntPredicate::IntSLT, x, min);
+            x = bx.select(too_small, min, x);
+        }
+        return Ok(bx.trunc(x, llret_ty));
+    }
+
     macro_rules! arith {
         ($($name: ident: $($($p: ident),* => $call: ident),*;)*) => {
             $(if name == stringify!($name) {
@@ -1825,6 +3107,263 @@ unsupported {} from `{}` with element `{}` of size `{}` to `{}`"#,
     span_bug!(span, "unknown SIMD intrinsic");
 }
 
+/// The register holding the rounding mode or the one holding the exception
+/// flags, which on x86 are both MXCSR.
+#[derive(Copy, Clone, PartialEq)]
+enum FloatEnvReg {
+    Control,
+    Status,
+}
+
+fn read_float_env(bx: &mut Builder<'a, 'll, 'tcx>, reg: FloatEnvReg) -> &'ll Value {
+    let cx = bx.cx;
+    if cx.sess().target.target.arch == "aarch64" {
+        let asm = match reg {
+            FloatEnvReg::Control => "mrs $0, fpcr",
+            FloatEnvReg::Status => "mrs $0, fpsr",
+        };
+        let val = bx.inline_asm_call(&CString::new(asm).unwrap(), &CString::new("=r").unwrap(),
+                                     &[], cx.type_i64(), true, false, ast::AsmDialect::Att)
+            .unwrap_or_else(|| bug!("invalid constraints for `{}`", asm));
+        bx.trunc(val, cx.type_i32())
+    } else {
+        let i32_align = cx.data_layout().i32_align.abi;
+        let slot = bx.alloca(cx.type_i32(), "mxcsr", i32_align);
+        let ptr = bx.pointercast(slot, cx.type_i8p());
+        bx.call(cx.get_intrinsic("llvm.x86.sse.stmxcsr"), &[ptr], None);
+        bx.load(slot, i32_align)
+    }
+}
+
+fn write_float_env(bx: &mut Builder<'a, 'll, 'tcx>, reg: FloatEnvReg, val: &'ll Value) {
+    let cx = bx.cx;
+    if cx.sess().target.target.arch == "aarch64" {
+        let asm = match reg {
+            FloatEnvReg::Control => "msr fpcr, $0",
+            FloatEnvReg::Status => "msr fpsr, $0",
+        };
+        let val = bx.zext(val, cx.type_i64());
+        bx.inline_asm_call(&CString::new(asm).unwrap(), &CString::new("r").unwrap(),
+                           &[val], cx.type_void(), true, false, ast::AsmDialect::Att)
+            .unwrap_or_else(|| bug!("invalid constraints for `{}`", asm));
+    } else {
+        let i32_align = cx.data_layout().i32_align.abi;
+        let slot = bx.alloca(cx.type_i32(), "mxcsr", i32_align);
+        bx.store(val, slot, i32_align);
+        let ptr = bx.pointercast(slot, cx.type_i8p());
+        bx.call(cx.get_intrinsic("llvm.x86.sse.ldmxcsr"), &[ptr], None);
+    }
+}
+
+/// Codegens the `fenv_*` intrinsics, returning the value of the ones that
+/// read the floating-point environment.
+///
+/// Rounding modes are numbered like the RC field of x86's MXCSR: to nearest,
+/// downward, upward and toward zero. Exception flags are numbered like the
+/// low bits of AArch64's FPSR: invalid, division by zero, overflow, underflow
+/// and inexact. The other encoding is converted to and from these.
+fn float_env(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    span: Span,
+    name: &str,
+    args: &[OperandRef<'tcx, &'ll Value>],
+) -> Option<&'ll Value> {
+    let cx = bx.cx;
+    let has_sse = cx.sess().parse_sess.config.contains(
+        &(Symbol::intern("target_feature"), Some(Symbol::intern("sse"))));
+    let (rmode_shift, aarch64) = match &cx.sess().target.target.arch[..] {
+        // Without SSE there's no MXCSR, and the x87 unit isn't supported.
+        "x86" if !has_sse => {
+            cx.sess().span_err(span, &format!("`{}` requires the `sse` target feature on `x86`",
+                                              name));
+            return None;
+        }
+        "x86" | "x86_64" => (13, false),
+        "aarch64" => (22, true),
+        arch => {
+            cx.sess().span_err(span, &format!("`{}` isn't supported on `{}` targets",
+                                              name, arch));
+            return None;
+        }
+    };
+    let int = |v: u32| cx.const_u32(v);
+
+    // AArch64 numbers the upward and downward modes the other way around, so
+    // swap them when the two bits differ.
+    let swap_up_down = |bx: &mut Builder<'a, 'll, 'tcx>, mode: &'ll Value| {
+        if !aarch64 {
+            return mode;
+        }
+        let high = bx.lshr(mode, int(1));
+        let differ = bx.xor(high, mode);
+        let differ = bx.and(differ, int(1));
+        let mask = bx.mul(differ, int(3));
+        bx.xor(mode, mask)
+    };
+
+    match name {
+        "fenv_rounding_mode" => {
+            let control = read_float_env(bx, FloatEnvReg::Control);
+            let mode = bx.lshr(control, int(rmode_shift));
+            let mode = bx.and(mode, int(3));
+            Some(swap_up_down(bx, mode))
+        }
+        "fenv_set_rounding_mode" => {
+            let mode = bx.and(args[0].immediate(), int(3));
+            let mode = swap_up_down(bx, mode);
+            let mode = bx.shl(mode, int(rmode_shift));
+            let control = read_float_env(bx, FloatEnvReg::Control);
+            let control = bx.and(control, int(!(3 << rmode_shift)));
+            let control = bx.or(control, mode);
+            write_float_env(bx, FloatEnvReg::Control, control);
+            None
+        }
+        "fenv_exceptions" => {
+            let status = read_float_env(bx, FloatEnvReg::Status);
+            if aarch64 {
+                Some(bx.and(status, int(0x1f)))
+            } else {
+                // MXCSR has the denormal flag between invalid and division
+                // by zero.
+                let invalid = bx.and(status, int(1));
+                let rest = bx.lshr(status, int(1));
+                let rest = bx.and(rest, int(0x1e));
+                Some(bx.or(invalid, rest))
+            }
+        }
+        "fenv_clear_exceptions" => {
+            let flags = bx.and(args[0].immediate(), int(0x1f));
+            let flags = if aarch64 {
+                flags
+            } else {
+                let invalid = bx.and(flags, int(1));
+                let rest = bx.and(flags, int(0x1e));
+                let rest = bx.shl(rest, int(1));
+                bx.or(invalid, rest)
+            };
+            let status = read_float_env(bx, FloatEnvReg::Status);
+            let flags = bx.not(flags);
+            let status = bx.and(status, flags);
+            write_float_env(bx, FloatEnvReg::Status, status);
+            None
+        }
+        _ => bug!("unknown floating-point environment intrinsic `{}`", name),
+    }
+}
+
+/// Codegen for the `cpuid` and `cpu_hwcap` intrinsics; `cpuid` stores its
+/// registers to `result`. What the CPU supports can't change while the
+/// program runs, so LLVM may merge repeated queries and hoist them out of
+/// loops: `cpuid` is inline assembly without side effects or memory clobbers,
+/// and `getauxval` only reads memory the program can't otherwise access.
+fn cpu_query(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    span: Span,
+    name: &str,
+    args: &[OperandRef<'tcx, &'ll Value>],
+    result: PlaceRef<'tcx, &'ll Value>,
+) -> Option<&'ll Value> {
+    let cx = bx.cx;
+    let target = &cx.sess().target.target;
+    match name {
+        "cpuid" => {
+            // LLVM may reserve `ebx` (as the PIC base on x86) or `rbx` (as the
+            // base pointer), so it's kept in `esi`/`rsi` around `cpuid`.
+            let asm = match &target.arch[..] {
+                "x86" => "movl %ebx, %esi\n\tcpuid\n\txchgl %ebx, %esi",
+                "x86_64" => "movq %rbx, %rsi\n\tcpuid\n\txchgq %rbx, %rsi",
+                arch => {
+                    cx.sess().span_err(span, &format!("`cpuid` isn't supported on `{}` targets",
+                                                      arch));
+                    return None;
+                }
+            };
+            let reg = cx.type_i32();
+            let output = cx.type_struct(&[reg, reg, reg, reg], false);
+            let constraints = "={ax},={si},={cx},={dx},{ax},{cx}";
+            let regs = bx.inline_asm_call(&CString::new(asm).unwrap(),
+                                          &CString::new(constraints).unwrap(),
+                                          &[args[0].immediate(), args[1].immediate()],
+                                          output, false, false, ast::AsmDialect::Att)
+                .unwrap_or_else(|| bug!("invalid constraints for `cpuid`"));
+            llvm::Attribute::NoUnwind.apply_callsite(llvm::AttributePlace::Function, regs);
+            for i in 0..4 {
+                let reg = bx.extract_value(regs, i as u64);
+                let dest = result.project_field(bx, i);
+                bx.store(reg, dest.llval, dest.align);
+            }
+            None
+        }
+        "cpu_hwcap" => {
+            if target.target_os != "linux" && target.target_os != "android" {
+                cx.sess().span_err(span, &format!("`cpu_hwcap` isn't supported on `{}` targets",
+                                                  target.target_os));
+                return None;
+            }
+            const AT_HWCAP: u64 = 16;
+            let ulong = cx.type_isize();
+            let getauxval = cx.declare_cfn("getauxval", cx.type_func(&[ulong], ulong));
+            let getauxval_args = [cx.const_usize(AT_HWCAP)];
+            let call_getauxval = |bx: &mut Builder<'a, 'll, 'tcx>| {
+                let hwcap = bx.call(getauxval, &getauxval_args, None);
+                for &attr in &[llvm::Attribute::InaccessibleMemOnly,
+                               llvm::Attribute::ReadOnly,
+                               llvm::Attribute::NoUnwind] {
+                    attr.apply_callsite(llvm::AttributePlace::Function, hwcap);
+                }
+                hwcap
+            };
+            if target.target_os != "android" {
+                return Some(call_getauxval(bx));
+            }
+
+            // `getauxval` only exists since Android 4.3 (API level 18), so it's
+            // linked weakly, and without it no capabilities are reported.
+            unsafe {
+                llvm::LLVMRustSetLinkage(getauxval, llvm::Linkage::ExternalWeakLinkage);
+            }
+            let addr = bx.ptrtoint(getauxval, ulong);
+            let found = bx.icmp(IntPredicate::IntNE, addr, cx.const_usize(0));
+            let mut call = bx.build_sibling_block("cpu_hwcap_call");
+            let next = bx.build_sibling_block("cpu_hwcap_next");
+            let entry = bx.llbb();
+            bx.cond_br(found, call.llbb(), next.llbb());
+
+            let hwcap = call_getauxval(&mut call);
+            call.br(next.llbb());
+
+            bx.position_at_end(next.llbb());
+            Some(bx.phi(ulong, &[cx.const_usize(0), hwcap], &[entry, call.llbb()]))
+        }
+        _ => bug!("unknown CPU query intrinsic `{}`", name),
+    }
+}
+
+/// The current function's stack slot for vectors of type `ty`, allocated
+/// in the entry block the first time it is asked for. The slots of the
+/// previous function are dropped once another one asks for a slot.
+fn simd_index_slot(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    ty: Ty<'tcx>,
+    align: layout::Align,
+) -> &'ll Value {
+    let cx = bx.cx;
+    let llfn = bx.llfn();
+    let mut state = cx.simd_index_slots.borrow_mut();
+    let (ref mut slots_llfn, ref mut slots) = *state;
+    if *slots_llfn != Some(llfn) {
+        *slots_llfn = Some(llfn);
+        slots.clear();
+    }
+    if let Some(&slot) = slots.get(&ty) {
+        return slot;
+    }
+    let llty = cx.layout_of(ty).llvm_type(cx);
+    let slot = bx.alloca(llty, "simd_index_slot", align);
+    slots.insert(ty, slot);
+    slot
+}
+
 // Returns the width of an int Ty, and if it's signed or not
 // Returns None if the type is not an integer
 // FIXME: there’s multiple of this functions, investigate using some of the already existing
@@ -1851,6 +3390,210 @@ fn int_type_width_signed(ty: Ty, cx: &CodegenCx) -> Option<(u64, bool)> {
     }
 }
 
+fn atomic_ordering(ordering: atomic_intrinsic::AtomicOrdering) -> AtomicOrdering {
+    match ordering {
+        atomic_intrinsic::AtomicOrdering::Unordered => AtomicOrdering::Unordered,
+        atomic_intrinsic::AtomicOrdering::Relaxed => AtomicOrdering::Monotonic,
+        atomic_intrinsic::AtomicOrdering::Acquire => AtomicOrdering::Acquire,
+        atomic_intrinsic::AtomicOrdering::Release => AtomicOrdering::Release,
+        atomic_intrinsic::AtomicOrdering::AcqRel => AtomicOrdering::AcquireRelease,
+        atomic_intrinsic::AtomicOrdering::SeqCst => AtomicOrdering::SequentiallyConsistent,
+    }
+}
+
+/// Whether `ty` is a raw pointer to a sized type, which is a single scalar.
+fn is_thin_raw_ptr(ty: Ty<'tcx>, cx: &CodegenCx<'ll, 'tcx>) -> bool {
+    match ty.sty {
+        ty::RawPtr(ty::TypeAndMut { ty: pointee, .. }) => cx.type_is_sized(pointee),
+        _ => false,
+    }
+}
+
+/// Performs the atomic operation `op` (a load, compare-exchange, add, ...) on `ty`
+/// with interrupts disabled, for `-Z atomics-critical-sections`, and stores
+/// its result. This is only atomic on single-core targets.
+fn atomic_in_critical_section(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    op: AtomicOp,
+    ty: Ty<'tcx>,
+    args: &[OperandRef<'tcx, &'ll Value>],
+    result: PlaceRef<'tcx, &'ll Value>,
+) {
+    let cx = bx.cx;
+    let target = &cx.sess().target.target;
+    let section = match target.critical_section() {
+        Some(section) => section,
+        None => cx.sess().fatal(&format!("-Z atomics-critical-sections is not supported \
+                                          for the `{}` target", target.llvm_target)),
+    };
+    let state_ty = cx.type_ix(section.state_bits);
+    let enter = CString::new(section.enter).unwrap();
+    let exit = CString::new(section.exit).unwrap();
+    let state = bx.inline_asm_call(&enter, &CString::new("=r,~{memory}").unwrap(), &[],
+                                   state_ty, true, false, ast::AsmDialect::Att)
+        .unwrap_or_else(|| bug!("invalid constraints for entering a critical section"));
+
+    let align = cx.align_of(ty);
+    let ptr = args[0].immediate();
+    let load = |bx: &mut Builder<'a, 'll, 'tcx>| {
+        let val = bx.volatile_load(ptr);
+        unsafe {
+            llvm::LLVMSetAlignment(val, align.bytes() as u32);
+        }
+        val
+    };
+    let store = |bx: &mut Builder<'a, 'll, 'tcx>, val| {
+        bx.store_with_flags(val, ptr, align, MemFlags::VOLATILE);
+    };
+    let llval = match op {
+        AtomicOp::Load => Some(load(bx)),
+        AtomicOp::Store => {
+            store(bx, args[1].immediate());
+            None
+        }
+        AtomicOp::Cxchg | AtomicOp::CxchgWeak => {
+            let old = load(bx);
+            let success = bx.icmp(IntPredicate::IntEQ, old, args[1].immediate());
+            let new = bx.select(success, args[2].immediate(), old);
+            store(bx, new);
+            let dest = result.project_field(bx, 0);
+            bx.store(old, dest.llval, dest.align);
+            let success = bx.zext(success, cx.type_bool());
+            let dest = result.project_field(bx, 1);
+            bx.store(success, dest.llval, dest.align);
+            None
+        }
+        op => {
+            let old = load(bx);
+            let val = args[1].immediate();
+            let pick = |bx: &mut Builder<'a, 'll, 'tcx>, pred: IntPredicate| {
+                let cmp = bx.icmp(pred, old, val);
+                bx.select(cmp, old, val)
+            };
+            let new = match op {
+                AtomicOp::Xchg => val,
+                AtomicOp::Xadd => bx.add(old, val),
+                AtomicOp::Xsub => bx.sub(old, val),
+                AtomicOp::And => bx.and(old, val),
+                AtomicOp::Nand => {
+                    let and = bx.and(old, val);
+                    bx.not(and)
+                }
+                AtomicOp::Or => bx.or(old, val),
+                AtomicOp::Xor => bx.xor(old, val),
+                AtomicOp::Max => pick(bx, IntPredicate::IntSGT),
+                AtomicOp::Min => pick(bx, IntPredicate::IntSLT),
+                AtomicOp::Umax => pick(bx, IntPredicate::IntUGT),
+                AtomicOp::Umin => pick(bx, IntPredicate::IntULT),
+                _ => bug!("`{}` is not a read-modify-write operation", op.name()),
+            };
+            store(bx, new);
+            Some(old)
+        }
+    };
+
+    bx.inline_asm_call(&exit, &CString::new("r,~{memory}").unwrap(), &[state],
+                       cx.type_void(), true, false, ast::AsmDialect::Att)
+        .unwrap_or_else(|| bug!("invalid constraints for leaving a critical section"));
+
+    if let Some(llval) = llval {
+        OperandValue::Immediate(llval).store(bx, result);
+    }
+}
+
+/// The integer type `mmio_load`/`mmio_store` access `ty` as, and its
+/// alignment. Only types a single load or store instruction can access on
+/// every target qualify: power-of-two sizes up to the pointer size, aligned
+/// to their size.
+fn mmio_access_type(
+    cx: &CodegenCx<'ll, 'tcx>,
+    name: &str,
+    span: Span,
+    ty: Ty<'tcx>,
+) -> Option<(&'ll Type, layout::Align)> {
+    let layout = cx.layout_of(ty);
+    let size = layout.size;
+    let error = if !size.bytes().is_power_of_two() || size > cx.tcx.data_layout.pointer_size {
+        format!("`{}` has a size of {} bytes, which is not a power of two \
+                 no larger than a pointer", ty, size.bytes())
+    } else if layout.align.abi.bytes() < size.bytes() {
+        format!("`{}` is only aligned to {} bytes, less than its size",
+                ty, layout.align.abi.bytes())
+    } else {
+        return Some((cx.type_ix(size.bits()), layout.align.abi));
+    };
+    span_invalid_monomorphization_error(
+        cx.tcx.sess, span,
+        &format!("invalid monomorphization of `{}` intrinsic: {}", name, error));
+    None
+}
+
+/// Performs a 128-bit `ctpop`, `bswap` or `bitreverse` as two 64-bit ones.
+/// LLVM fails to select, or miscompiles, the 128-bit versions on some 16-bit
+/// and 32-bit targets, while the 64-bit ones are legalized reliably.
+fn split_i128_bit_intrinsic(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    name: &str,
+    val: &'ll Value,
+) -> &'ll Value {
+    let cx = bx.cx;
+    let (i64_ty, i128_ty) = (cx.type_i64(), cx.type_i128());
+    let shift = cx.const_uint(i128_ty, 64);
+    let lo = bx.trunc(val, i64_ty);
+    let hi = bx.lshr(val, shift);
+    let hi = bx.trunc(hi, i64_ty);
+
+    let llfn = cx.get_intrinsic(&format!("llvm.{}.i64", name));
+    let lo = bx.call(llfn, &[lo], None);
+    let hi = bx.call(llfn, &[hi], None);
+
+    if name == "ctpop" {
+        let count = bx.add(lo, hi);
+        bx.zext(count, i128_ty)
+    } else {
+        // Reversing the bytes or bits of the whole also swaps the halves.
+        let lo = bx.zext(lo, i128_ty);
+        let lo = bx.shl(lo, shift);
+        let hi = bx.zext(hi, i128_ty);
+        bx.or(lo, hi)
+    }
+}
+
+// `llvm.minimum`/`llvm.maximum` for LLVM versions that don't have them: NaN
+// if either operand is NaN, otherwise the smaller (larger) operand, treating
+// -0.0 as less than +0.0.
+fn float_minimum_maximum(
+    bx: &mut Builder<'a, 'll, 'tcx>,
+    is_min: bool,
+    lhs: &'ll Value,
+    rhs: &'ll Value,
+) -> &'ll Value {
+    use rustc_codegen_ssa::common::RealPredicate::*;
+
+    let float_ty = bx.cx().val_ty(lhs);
+    let int_ty = bx.cx().type_ix(bx.cx().float_width(float_ty) as u64);
+    let (pick_lhs, pick_rhs) = if is_min {
+        (bx.fcmp(RealOLT, lhs, rhs), bx.fcmp(RealOGT, lhs, rhs))
+    } else {
+        (bx.fcmp(RealOGT, lhs, rhs), bx.fcmp(RealOLT, lhs, rhs))
+    };
+    // The operands compare equal, so they only differ if they are zeros of
+    // opposite sign; combining the sign bits picks the right one.
+    let lhs_bits = bx.bitcast(lhs, int_ty);
+    let rhs_bits = bx.bitcast(rhs, int_ty);
+    let equal_bits = if is_min {
+        bx.or(lhs_bits, rhs_bits)
+    } else {
+        bx.and(lhs_bits, rhs_bits)
+    };
+    let equal = bx.bitcast(equal_bits, float_ty);
+    let ordered = bx.select(pick_rhs, rhs, equal);
+    let ordered = bx.select(pick_lhs, lhs, ordered);
+    let unordered = bx.fcmp(RealUNO, lhs, rhs);
+    let nan = bx.fadd(lhs, rhs);
+    bx.select(unordered, nan, ordered)
+}
+
 // Returns the width of a float TypeVariant
 // Returns None if the type is not a float
 fn float_type_width<'tcx>(sty: &ty::TyKind<'tcx>) -> Option<u64> {
@@ -63,6 +63,7 @@ extern crate rustc_fs_util;
 extern crate syntax_pos;
 extern crate rustc_errors as errors;
 extern crate serialize;
+extern crate serialize as rustc_serialize; // used by deriving
 extern crate cc; // Used to locate MSVC
 extern crate tempfile;
 extern crate memmap;
@@ -124,6 +125,7 @@ mod intrinsic;
 mod llvm_util;
 mod metadata;
 mod mono_item;
+mod offload;
 mod type_;
 mod type_of;
 mod value;
@@ -275,6 +277,10 @@ impl CodegenBackend for LlvmCodegenBackend {
         target_features(sess)
     }
 
+    fn known_target_features(&self) -> Vec<Symbol> {
+        llvm_util::all_known_features().map(|(feature, _)| Symbol::intern(feature)).collect()
+    }
+
     fn metadata_loader(&self) -> Box<dyn MetadataLoader + Sync> {
         box metadata::LlvmMetadataLoader
     }
@@ -297,6 +303,9 @@ impl CodegenBackend for LlvmCodegenBackend {
         tcx: TyCtxt<'b, 'tcx, 'tcx>,
         rx: mpsc::Receiver<Box<dyn Any + Send>>
     ) -> Box<dyn Any> {
+        if tcx.sess.opts.debugging_opts.dump_fn_abi {
+            base::dump_fn_abis(tcx);
+        }
         box rustc_codegen_ssa::base::codegen_crate(LlvmCodegenBackend(()), tcx, rx)
     }
 
@@ -323,6 +332,13 @@ impl CodegenBackend for LlvmCodegenBackend {
 
         sess.compile_status()?;
 
+        if sess.opts.debugging_opts.merge_objects &&
+           sess.opts.output_types.contains_key(&OutputType::Object) {
+            time(sess, "merging objects", || {
+                back::write::merge_objects(sess, &codegen_results, outputs)
+            }).unwrap_or_else(|err| err.raise());
+        }
+
         if !sess.opts.output_types.keys().any(|&i| i == OutputType::Exe ||
                                                    i == OutputType::Metadata) {
             return Ok(());
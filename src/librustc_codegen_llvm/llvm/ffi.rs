@@ -125,6 +125,22 @@ pub enum Attribute {
     SanitizeAddress = 21,
     SanitizeMemory  = 22,
     NonLazyBind     = 23,
+    StrictFP        = 24,
+    InaccessibleMemOnly = 25,
+}
+
+// These values **must** match with llvm::AllocFnKind!!
+bitflags! {
+    #[repr(C)]
+    pub struct AllocKind: u64 {
+        const UNKNOWN       = 0;
+        const ALLOC         = 1 << 0;
+        const REALLOC       = 1 << 1;
+        const FREE          = 1 << 2;
+        const UNINITIALIZED = 1 << 3;
+        const ZEROED        = 1 << 4;
+        const ALIGNED       = 1 << 5;
+    }
 }
 
 /// LLVMIntPredicate
@@ -482,6 +498,9 @@ extern { pub type ThinLTOData; }
 /// LLVMRustThinLTOBuffer
 extern { pub type ThinLTOBuffer; }
 
+// LLVMRustSymbolCallback
+pub type SymbolCallback = unsafe extern "C" fn(*mut c_void, *const c_char, size_t);
+
 // LLVMRustModuleNameCallback
 pub type ThinLTOModuleNameCallback =
     unsafe extern "C" fn(*mut c_void, *const c_char, *const c_char);
@@ -616,7 +635,8 @@ extern "C" {
     // Create modules.
     pub fn LLVMModuleCreateWithNameInContext(ModuleID: *const c_char, C: &Context) -> &Module;
     pub fn LLVMGetModuleContext(M: &Module) -> &Context;
-    pub fn LLVMCloneModule(M: &Module) -> &Module;
+    pub fn LLVMCloneModule(M: &'a Module) -> &'a mut Module;
+    pub fn LLVMDisposeModule(M: &'a mut Module);
 
     /// Data layout. See Module::getDataLayout.
     pub fn LLVMGetDataLayout(M: &Module) -> *const c_char;
@@ -649,6 +669,7 @@ extern "C" {
                             ParamCount: c_uint,
                             IsVarArg: Bool)
                             -> &'a Type;
+    pub fn LLVMGetReturnType(FunctionTy: &Type) -> &Type;
     pub fn LLVMCountParamTypes(FunctionTy: &Type) -> c_uint;
     pub fn LLVMGetParamTypes(FunctionTy: &'a Type, Dest: *mut &'a Type);
 
@@ -662,7 +683,9 @@ extern "C" {
     // Operations on array, pointer, and vector types (sequence types)
     pub fn LLVMRustArrayType(ElementType: &Type, ElementCount: u64) -> &Type;
     pub fn LLVMPointerType(ElementType: &Type, AddressSpace: c_uint) -> &Type;
+    pub fn LLVMGetPointerAddressSpace(PointerTy: &Type) -> c_uint;
     pub fn LLVMVectorType(ElementType: &Type, ElementCount: c_uint) -> &Type;
+    pub fn LLVMRustScalableVectorType(ElementType: &Type, ElementCount: c_uint) -> &Type;
 
     pub fn LLVMGetElementType(Ty: &Type) -> &Type;
     pub fn LLVMGetVectorSize(VectorTy: &Type) -> c_uint;
@@ -691,6 +714,7 @@ extern "C" {
     // Operations on scalar constants
     pub fn LLVMConstInt(IntTy: &Type, N: c_ulonglong, SignExtend: Bool) -> &Value;
     pub fn LLVMConstIntOfArbitraryPrecision(IntTy: &Type, Wn: c_uint, Ws: *const u64) -> &Value;
+    pub fn LLVMConstReal(RealTy: &Type, N: f64) -> &Value;
     pub fn LLVMConstIntGetZExtValue(ConstantVal: &Value) -> c_ulonglong;
     pub fn LLVMRustConstInt128Get(ConstantVal: &Value, SExt: bool,
                                   high: &mut u64, low: &mut u64) -> bool;
@@ -746,6 +770,16 @@ extern "C" {
     // Operations on global variables
     pub fn LLVMIsAGlobalVariable(GlobalVar: &Value) -> Option<&Value>;
     pub fn LLVMAddGlobal(M: &'a Module, Ty: &'a Type, Name: *const c_char) -> &'a Value;
+    pub fn LLVMAddAlias(M: &'a Module,
+                        Ty: &'a Type,
+                        Aliasee: &'a Value,
+                        Name: *const c_char)
+                        -> &'a Value;
+    pub fn LLVMAddGlobalInAddressSpace(M: &'a Module,
+                                       Ty: &'a Type,
+                                       Name: *const c_char,
+                                       AddressSpace: c_uint)
+                                       -> &'a Value;
     pub fn LLVMGetNamedGlobal(M: &Module, Name: *const c_char) -> Option<&Value>;
     pub fn LLVMRustGetOrInsertGlobal(M: &'a Module, Name: *const c_char, T: &'a Type) -> &'a Value;
     pub fn LLVMRustInsertPrivateGlobal(M: &'a Module, T: &'a Type) -> &'a Value;
@@ -766,11 +800,16 @@ extern "C" {
                                        Name: *const c_char,
                                        FunctionTy: &'a Type)
                                        -> &'a Value;
+    pub fn LLVMRustGetOrInsertIntrinsic(M: &'a Module, Name: *const c_char) -> Option<&'a Value>;
     pub fn LLVMSetFunctionCallConv(Fn: &Value, CC: c_uint);
     pub fn LLVMRustAddAlignmentAttr(Fn: &Value, index: c_uint, bytes: u32);
     pub fn LLVMRustAddDereferenceableAttr(Fn: &Value, index: c_uint, bytes: u64);
     pub fn LLVMRustAddDereferenceableOrNullAttr(Fn: &Value, index: c_uint, bytes: u64);
     pub fn LLVMRustAddFunctionAttribute(Fn: &Value, index: c_uint, attr: Attribute);
+    pub fn LLVMRustAddAllocKindAttr(Fn: &Value, kind: u64);
+    pub fn LLVMRustAddAllocSizeAttr(Fn: &Value, size_arg: c_uint);
+    pub fn LLVMRustAddAllocAlignAttr(Fn: &Value, arg: c_uint);
+    pub fn LLVMRustAddAllocPtrAttr(Fn: &Value, arg: c_uint);
     pub fn LLVMRustAddFunctionAttrStringValue(Fn: &Value,
                                               index: c_uint,
                                               Name: *const c_char,
@@ -997,6 +1036,7 @@ extern "C" {
     pub fn LLVMBuildFNeg(B: &Builder<'a>, V: &'a Value, Name: *const c_char) -> &'a Value;
     pub fn LLVMBuildNot(B: &Builder<'a>, V: &'a Value, Name: *const c_char) -> &'a Value;
     pub fn LLVMRustSetHasUnsafeAlgebra(Instr: &Value);
+    pub fn LLVMRustSetFastMathFlags(Instr: &Value, Flags: c_uint);
 
     // Memory
     pub fn LLVMBuildAlloca(B: &Builder<'a>, Ty: &'a Type, Name: *const c_char) -> &'a Value;
@@ -1229,6 +1269,12 @@ extern "C" {
         LHS: &'a Value,
     ) -> Option<&'a Value>;
 
+    pub fn LLVMRustSetLoopHints(Header: &BasicBlock,
+                                UnrollCount: c_uint,
+                                NoUnroll: bool,
+                                PipelineDisable: bool,
+                                PipelineII: c_uint);
+
     // Atomic Operations
     pub fn LLVMRustBuildAtomicLoad(B: &Builder<'a>,
                                    PointerVal: &'a Value,
@@ -1447,6 +1493,18 @@ extern "C" {
                                              Ty: &'a DIType)
                                              -> &'a DIDerivedType;
 
+    pub fn LLVMRustDIBuilderCreateBitFieldMemberType(Builder: &DIBuilder<'a>,
+                                                     Scope: &'a DIScope,
+                                                     Name: *const c_char,
+                                                     File: &'a DIFile,
+                                                     LineNo: c_uint,
+                                                     SizeInBits: u64,
+                                                     OffsetInBits: u64,
+                                                     StorageOffsetInBits: u64,
+                                                     Flags: DIFlags,
+                                                     Ty: &'a DIType)
+                                                     -> &'a DIType;
+
     pub fn LLVMRustDIBuilderCreateVariantMemberType(Builder: &DIBuilder<'a>,
                                                     Scope: &'a DIScope,
                                                     Name: *const c_char,
@@ -1727,6 +1785,11 @@ extern "C" {
     pub fn LLVMRustSetInlineAsmDiagnosticHandler(C: &Context,
                                                  H: InlineAsmDiagHandler,
                                                  CX: *mut c_void);
+
+    pub fn LLVMRustContextSetDiagnosticHandler(C: &Context,
+                                               H: DiagnosticHandler,
+                                               CX: *mut c_void,
+                                               RemarkPass: *const c_char);
 }
 
 #[allow(improper_ctypes)] // FIXME(#52456) needed for RustString.
@@ -1746,6 +1809,17 @@ extern "C" {
                                     Child: Option<&ArchiveChild<'a>>)
                                     -> &'a mut RustArchiveMember<'a>;
     pub fn LLVMRustArchiveMemberFree(Member: &'a mut RustArchiveMember<'a>);
+    pub fn LLVMRustForEachDefinedSymbol(Path: *const c_char,
+                                        State: *mut c_void,
+                                        Callback: SymbolCallback)
+                                        -> LLVMRustResult;
+    pub fn LLVMRustWriteImportLibrary(ImportName: *const c_char,
+                                      Path: *const c_char,
+                                      Exports: *const *const c_char,
+                                      NumExports: size_t,
+                                      Machine: u16,
+                                      MinGW: bool)
+                                      -> LLVMRustResult;
 
     pub fn LLVMRustSetDataLayoutFromTargetMachine(M: &'a Module, TM: &'a TargetMachine);
 
@@ -1760,6 +1834,7 @@ extern "C" {
     pub fn LLVMRustSetComdat(M: &'a Module, V: &'a Value, Name: *const c_char);
     pub fn LLVMRustUnsetComdat(V: &Value);
     pub fn LLVMRustSetModulePIELevel(M: &Module);
+    pub fn LLVMRustSortModuleSymbols(M: &Module);
     pub fn LLVMRustModuleBufferCreate(M: &Module) -> &'static mut ModuleBuffer;
     pub fn LLVMRustModuleBufferPtr(p: &ModuleBuffer) -> *const u8;
     pub fn LLVMRustModuleBufferLen(p: &ModuleBuffer) -> usize;
@@ -1814,4 +1889,8 @@ extern "C" {
                              bytecode: *const c_char,
                              bytecode_len: usize) -> bool;
     pub fn LLVMRustLinkerFree(linker: &'a mut Linker<'a>);
+    pub fn LLVMRustLinkBitcodeLibrary(M: &Module,
+                                      bitcode: *const c_char,
+                                      bitcode_len: usize,
+                                      define: bool) -> bool;
 }
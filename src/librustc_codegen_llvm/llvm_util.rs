@@ -70,6 +70,9 @@ unsafe fn configure_llvm(sess: &Session) {
         if sess.opts.debugging_opts.disable_instrumentation_preinliner {
             add("-disable-preinline");
         }
+        if let Some(threshold) = sess.opts.debugging_opts.inline_threshold {
+            add(&format!("-inline-threshold={}", threshold));
+        }
 
         for arg in &sess.opts.cg.llvm_args {
             add(&(*arg));
@@ -136,6 +139,7 @@ const X86_WHITELIST: &[(&str, Option<&str>)] = &[
     ("avx512vpopcntdq", Some("avx512_target_feature")),
     ("bmi1", None),
     ("bmi2", None),
+    ("cmpxchg16b", Some("cmpxchg16b_target_feature")),
     ("fma", None),
     ("fxsr", None),
     ("lzcnt", None),
@@ -162,6 +166,11 @@ const X86_WHITELIST: &[(&str, Option<&str>)] = &[
 const HEXAGON_WHITELIST: &[(&str, Option<&str>)] = &[
     ("hvx", Some("hexagon_target_feature")),
     ("hvx-double", Some("hexagon_target_feature")),
+    ("hvx-length64b", Some("hexagon_target_feature")),
+    ("hvx-length128b", Some("hexagon_target_feature")),
+    ("hvxv60", Some("hexagon_target_feature")),
+    ("hvxv62", Some("hexagon_target_feature")),
+    ("hvxv65", Some("hexagon_target_feature")),
 ];
 
 const POWERPC_WHITELIST: &[(&str, Option<&str>)] = &[
@@ -210,6 +219,8 @@ pub fn to_llvm_feature<'a>(sess: &Session, s: &'a str) -> &'a str {
         ("x86", "bmi1") => "bmi",
         ("aarch64", "fp") => "fp-armv8",
         ("aarch64", "fp16") => "fullfp16",
+        // LLVM replaced `hvx-double` with an explicit vector length.
+        ("hexagon", "hvx-double") => "hvx-length128b",
         (_, s) => s,
     }
 }
@@ -280,6 +291,10 @@ pub(crate) fn print(req: PrintRequest, sess: &Session) {
 
 pub fn target_cpu(sess: &Session) -> &str {
     let name = match sess.opts.cg.target_cpu {
+        // Host CPU detection makes the output depend on the build machine.
+        Some(ref s) if s == "native" && sess.opts.debugging_opts.deterministic => {
+            &*sess.target.target.options.cpu
+        }
         Some(ref s) => &**s,
         None => &*sess.target.target.options.cpu
     };
@@ -8,17 +8,31 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use abi::{FnType, FnTypeExt, PassMode};
 use attributes;
 use base;
+use builder::Builder;
+use consts;
 use context::CodegenCx;
+use declare;
 use llvm;
 use monomorphize::Instance;
+use offload;
 use type_of::LayoutLlvmExt;
+use value::Value;
+use rustc::hir::CodegenFnAttrFlags;
 use rustc::hir::def_id::{DefId, LOCAL_CRATE};
+use rustc::middle::lang_items::MultiversionCpuSupportsFnLangItem;
+use rustc::mir;
 use rustc::mir::mono::{Linkage, Visibility};
-use rustc::ty::TypeFoldable;
+use rustc::ty::{self, TyCtxt, TypeFoldable};
 use rustc::ty::layout::{LayoutOf, HasTyCtxt};
+use rustc_codegen_ssa::callee::resolve_and_get_fn;
+use rustc_codegen_ssa::common::{langcall, AtomicOrdering, IntPredicate};
 use rustc_codegen_ssa::traits::*;
+use rustc_target::spec::abi::Abi;
+use rustc_target::target_features;
+use syntax::symbol::Symbol;
 
 pub use rustc::mir::mono::MonoItem;
 
@@ -30,7 +44,8 @@ impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
                                   symbol_name: &str) {
         let instance = Instance::mono(self.tcx, def_id);
         let ty = instance.ty(self.tcx);
-        let llty = self.layout_of(ty).llvm_type(self);
+        let layout = self.layout_of(ty);
+        let llty = layout.llvm_type(self);
 
         let g = self.define_global(symbol_name, llty).unwrap_or_else(|| {
             self.sess().span_fatal(self.tcx.def_span(def_id),
@@ -42,6 +57,11 @@ impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
             llvm::LLVMRustSetVisibility(g, base::visibility_to_llvm(visibility));
         }
 
+        if !self.sess().opts.debugging_opts.offload_device &&
+           self.tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::OFFLOAD) {
+            offload::emit_entry(self, g, symbol_name, layout.size.bytes());
+        }
+
         self.instances.borrow_mut().insert(instance, g);
     }
 
@@ -55,7 +75,7 @@ impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
 
         let mono_sig = instance.fn_sig(self.tcx());
         let attrs = self.tcx.codegen_fn_attrs(instance.def_id());
-        let lldecl = self.declare_fn(symbol_name, mono_sig);
+        let lldecl = declare::declare_instance_fn(self, symbol_name, instance);
         unsafe { llvm::LLVMRustSetLinkage(lldecl, base::linkage_to_llvm(linkage)) };
         base::set_link_section(lldecl, &attrs);
         if linkage == Linkage::LinkOnceODR ||
@@ -78,12 +98,128 @@ impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
             }
         }
 
+        if self.tcx.sess.opts.debugging_opts.perf_stats &&
+           !instance.def_id().is_local() &&
+           instance.substs.types().next().is_some() {
+            self.tcx.sess.perf_stats.duplicated_generics.lock()
+                .insert(symbol_name.to_string());
+        }
+
         debug!("predefine_fn: mono_sig = {:?} instance = {:?}", mono_sig, instance);
-        if instance.def.is_inline(self.tcx) {
+        if instance.def.is_inline(self.tcx) || instance.def.is_small_drop_glue(self.tcx) {
             attributes::inline(self, lldecl, attributes::InlineAttr::Hint);
         }
         attributes::from_fn_attrs(self, lldecl, Some(instance.def.def_id()));
 
+        if attrs.flags.contains(CodegenFnAttrFlags::OFFLOAD) {
+            if self.sess().opts.debugging_opts.offload_device {
+                offload::mark_kernel(self, lldecl, instance.def_id());
+            } else {
+                offload::emit_entry(self, lldecl, symbol_name, 0);
+            }
+        }
+        if uses_float_env(self.tcx, instance) {
+            attributes::strict_fp(self, lldecl);
+            self.strict_fp_fns.borrow_mut().insert(lldecl);
+        }
+
         self.instances.borrow_mut().insert(instance, lldecl);
     }
+
+    fn predefine_multiversion_variant(&self,
+                                      instance: Instance<'tcx>,
+                                      target: Symbol) -> &'ll Value {
+        let def_id = instance.def_id();
+        let symbol_name = format!("{}.{}", self.tcx.symbol_name(instance).as_str(), target);
+        let llfn = declare::declare_instance_fn(self, &symbol_name, instance);
+        unsafe { llvm::LLVMRustSetLinkage(llfn, llvm::Linkage::InternalLinkage) };
+        attributes::from_fn_attrs(self, llfn, Some(def_id));
+        if uses_float_env(self.tcx, instance) {
+            attributes::strict_fp(self, llfn);
+            self.strict_fp_fns.borrow_mut().insert(llfn);
+        }
+
+        if target != "default" {
+            let mut features = self.tcx.codegen_fn_attrs(def_id).target_features.iter()
+                .map(|f| f.as_str().to_string())
+                .collect::<Vec<_>>();
+            features.push(target.as_str().to_string());
+            target_features::add_implied_target_features(&self.sess().target.target.arch,
+                                                         &mut features);
+            attributes::apply_target_features(self, llfn, &features);
+        }
+
+        llfn
+    }
+
+    fn define_multiversion_dispatcher(&self,
+                                      instance: Instance<'tcx>,
+                                      llfn: &'ll Value,
+                                      variants: &[(Symbol, &'ll Value)]) {
+        let fn_ty = FnType::of_instance(self, &instance);
+        let llptrty = self.val_ty(llfn);
+        let ptr_size = self.tcx.data_layout.pointer_size;
+
+        // The variant picked by the first call, null until then. Racing
+        // first calls all pick the same variant so relaxed ordering is enough.
+        let cache = self.define_private_global(llptrty);
+        unsafe { llvm::LLVMSetInitializer(cache, self.const_null(llptrty)) };
+
+        let span = self.tcx.def_span(instance.def_id());
+        let cpu_supports = langcall(self.tcx, Some(span), "", MultiversionCpuSupportsFnLangItem);
+        let cpu_supports = resolve_and_get_fn(self, cpu_supports, self.tcx.intern_substs(&[]));
+
+        let mut bx = Builder::new_block(self, llfn, "start");
+        let mut resolve = bx.build_sibling_block("resolve");
+        let mut dispatch = bx.build_sibling_block("dispatch");
+
+        let cached = bx.atomic_load(cache, AtomicOrdering::Monotonic, ptr_size);
+        let unresolved = bx.icmp(IntPredicate::IntEQ, cached, self.const_null(llptrty));
+        bx.cond_br(unresolved, resolve.llbb(), dispatch.llbb());
+
+        // Targets are listed from most to least preferred, so check them in
+        // reverse and let each supported one override the previous choice.
+        let mut selected = variants.iter()
+            .find(|&&(target, _)| target == "default")
+            .unwrap_or_else(|| bug!("#[multiversion] without a default target"))
+            .1;
+        for &(target, variant) in variants.iter().rev() {
+            if target == "default" {
+                continue;
+            }
+            let name = consts::ptrcast(self.const_cstr(target.as_str(), false), self.type_i8p());
+            let len = self.const_usize(target.as_str().len() as u64);
+            let supported = resolve.call(cpu_supports, &[name, len], None);
+            selected = resolve.select(supported, variant, selected);
+        }
+        resolve.atomic_store(selected, cache, AtomicOrdering::Monotonic, ptr_size);
+        resolve.br(dispatch.llbb());
+
+        let variant = dispatch.phi(llptrty, &[cached, selected], &[bx.llbb(), resolve.llbb()]);
+        let args = (0..unsafe { llvm::LLVMCountParams(llfn) })
+            .map(|i| llvm::get_param(llfn, i))
+            .collect::<Vec<_>>();
+        let ret = dispatch.call(variant, &args, None);
+        fn_ty.apply_attrs_callsite(&mut dispatch, ret);
+        match fn_ty.ret.mode {
+            PassMode::Ignore | PassMode::Indirect(..) => dispatch.ret_void(),
+            _ => dispatch.ret(ret),
+        }
+    }
+}
+
+/// Whether the body of `instance` reads or changes the floating-point
+/// environment with one of the `fenv_*` intrinsics.
+fn uses_float_env<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, instance: Instance<'tcx>) -> bool {
+    let mir = tcx.instance_mir(instance.def);
+    mir.basic_blocks().iter().any(|data| match data.terminator().kind {
+        mir::TerminatorKind::Call { ref func, .. } => match func.ty(mir, tcx).sty {
+            ty::FnDef(def_id, _) => {
+                tcx.fn_sig(def_id).abi() == Abi::RustIntrinsic &&
+                    tcx.item_name(def_id).as_str().starts_with("fenv_")
+            }
+            _ => false,
+        },
+        _ => false,
+    })
 }
@@ -0,0 +1,112 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Codegen for `#[offload]` functions and statics.
+//!
+//! With `-Z offload-device` only the `#[offload]` items of a crate, and what
+//! they use, are compiled, and the functions become kernels of the device.
+//! The host build of the same crate gives each of them an entry in the
+//! `omp_offloading_entries` section, laid out like the `__tgt_offload_entry`
+//! of LLVM's OpenMP offload runtime, so that the runtime can find the device
+//! symbol of a host function or static by name. The device image itself can
+//! be embedded in the host binary with `-Z offload-image`.
+
+use context::CodegenCx;
+use consts;
+use libc::c_uint;
+use llvm::{self, True};
+use rustc::hir::def_id::DefId;
+use rustc_codegen_ssa::traits::*;
+use syntax::symbol::Symbol;
+use value::Value;
+
+use std::fs;
+use std::path::Path;
+
+/// Mach-O section names need a segment, ELF ones are chosen so that the
+/// linker defines `__start_` and `__stop_` symbols for them.
+fn section_name(cx: &CodegenCx, elf: &'static str, macho: &'static str) -> &'static str {
+    if cx.sess().target.target.options.is_like_osx { macho } else { elf }
+}
+
+/// Turns the device definition of the `#[offload]` function `def_id` into a
+/// kernel, which the host can launch.
+pub fn mark_kernel(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value, def_id: DefId) {
+    let arch = &cx.sess().target.target.arch[..];
+    if arch.starts_with("nvptx") {
+        // The PTX backend emits an `.entry` instead of a `.func` only for the
+        // functions listed in `nvvm.annotations`.
+        unsafe {
+            let kernel = llvm::LLVMMDStringInContext(cx.llcx,
+                                                     "kernel".as_ptr() as *const _,
+                                                     "kernel".len() as c_uint);
+            let ops = [llfn, kernel, cx.const_i32(1)];
+            let node = llvm::LLVMMDNodeInContext(cx.llcx, ops.as_ptr(), ops.len() as c_uint);
+            llvm::LLVMAddNamedMetadataOperand(cx.llmod,
+                                              "nvvm.annotations\0".as_ptr() as *const _,
+                                              node);
+        }
+    } else if arch == "amdgpu" {
+        llvm::SetFunctionCallConv(llfn, llvm::AmdGpuKernel);
+    } else {
+        cx.sess().span_fatal(cx.tcx.def_span(def_id),
+                             &format!("`#[offload]` kernels aren't supported for `{}` targets",
+                                      arch));
+    }
+}
+
+/// Adds the host-side entry of an `#[offload]` function or static, `size`
+/// being zero for functions.
+pub fn emit_entry(cx: &CodegenCx<'ll, '_>, llval: &'ll Value, symbol_name: &str, size: u64) {
+    let name = cx.const_cstr(Symbol::intern(symbol_name).as_str(), true);
+    let entry = cx.const_struct(&[
+        consts::ptrcast(llval, cx.type_i8p()),
+        consts::ptrcast(name, cx.type_i8p()),
+        cx.const_u64(size),
+        // flags
+        cx.const_u32(0),
+        // reserved
+        cx.const_u32(0),
+    ], false);
+
+    let entry_name = format!("__rust_offload_entry.{}", symbol_name);
+    let g = cx.define_global(&entry_name, cx.val_ty(entry)).unwrap_or_else(|| {
+        bug!("symbol `{}` is already defined", entry_name)
+    });
+    unsafe {
+        llvm::LLVMSetInitializer(g, entry);
+        llvm::LLVMSetGlobalConstant(g, True);
+        llvm::LLVMRustSetLinkage(g, llvm::Linkage::InternalLinkage);
+        let section = section_name(cx, "omp_offloading_entries\0", "__DATA,__omp_offload\0");
+        llvm::LLVMSetSection(g, section.as_ptr() as *const _);
+    }
+
+    // Nothing refers to the entry, the runtime walks the section.
+    cx.used_statics.borrow_mut().push(consts::ptrcast(g, cx.type_i8p()));
+}
+
+/// Embeds the device image given with `-Z offload-image` in its own section.
+pub fn embed_image(cx: &CodegenCx<'ll, '_>, path: &Path) {
+    let image = fs::read(path).unwrap_or_else(|e| {
+        cx.sess().fatal(&format!("couldn't read offload image `{}`: {}", path.display(), e))
+    });
+    let llimage = cx.const_bytes(&image);
+    let g = cx.define_global("__rust_offload_image", cx.val_ty(llimage)).unwrap_or_else(|| {
+        bug!("symbol `__rust_offload_image` is already defined")
+    });
+    unsafe {
+        llvm::LLVMSetInitializer(g, llimage);
+        llvm::LLVMSetGlobalConstant(g, True);
+        llvm::LLVMRustSetLinkage(g, llvm::Linkage::InternalLinkage);
+        let section = section_name(cx, "rust_offload_image\0", "__DATA,__rust_offload\0");
+        llvm::LLVMSetSection(g, section.as_ptr() as *const _);
+    }
+    cx.used_statics.borrow_mut().push(consts::ptrcast(g, cx.type_i8p()));
+}
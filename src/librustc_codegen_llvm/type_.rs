@@ -180,6 +180,12 @@ impl BaseTypeMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         }
     }
 
+    fn type_scalable_vector(&self, ty: &'ll Type, min_len: u64) -> &'ll Type {
+        unsafe {
+            llvm::LLVMRustScalableVectorType(ty, min_len as c_uint)
+        }
+    }
+
     fn type_kind(&self, ty: &'ll Type) -> TypeKind {
         unsafe {
             llvm::LLVMRustGetTypeKind(ty).to_generic()
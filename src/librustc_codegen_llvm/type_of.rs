@@ -10,6 +10,7 @@
 
 use abi::{FnType, FnTypeExt};
 use common::*;
+use llvm_util;
 use rustc::hir;
 use rustc::ty::{self, Ty, TypeFoldable};
 use rustc::ty::layout::{self, Align, LayoutOf, Size, TyLayout};
@@ -37,7 +38,15 @@ fn uncached_llvm_type<'a, 'tcx>(cx: &CodegenCx<'a, 'tcx>,
             let use_x86_mmx = count == 1 && layout.size.bits() == 64 &&
                 (cx.sess().target.target.arch == "x86" ||
                  cx.sess().target.target.arch == "x86_64");
-            if use_x86_mmx {
+            if layout.ty.is_scalable_simd() {
+                if llvm_util::get_major_version() < 9 {
+                    let span = cx.tcx.def_span(layout.ty.ty_adt_def().unwrap().did);
+                    cx.sess().span_fatal(span, &format!("scalable vector type `{}` requires \
+                                                         LLVM 9 or later", layout.ty));
+                }
+                let element = layout.scalar_llvm_type_at(cx, element, Size::ZERO);
+                return cx.type_scalable_vector(element, count);
+            } else if use_x86_mmx {
                 return cx.type_x86_mmx()
             } else {
                 let element = layout.scalar_llvm_type_at(cx, element, Size::ZERO);
@@ -79,6 +88,22 @@ fn uncached_llvm_type<'a, 'tcx>(cx: &CodegenCx<'a, 'tcx>,
     };
 
     match layout.fields {
+        // Bit-fields can share bytes, so structs with them get no LLVM
+        // fields, like unions, and are accessed by byte offsets.
+        layout::FieldPlacement::Arbitrary { .. } if layout.fields.has_bit_fields() => {
+            let fill = cx.type_padding_filler(layout.size, layout.align.abi);
+            let packed = false;
+            match name {
+                None => {
+                    cx.type_struct( &[fill], packed)
+                }
+                Some(ref name) => {
+                    let llty = cx.type_named_struct( name);
+                    cx.set_struct_body(llty, &[fill], packed);
+                    llty
+                }
+            }
+        }
         layout::FieldPlacement::Union(_) => {
             let fill = cx.type_padding_filler(layout.size, layout.align.abi);
             let packed = false;
@@ -316,10 +341,17 @@ impl<'tcx> LayoutLlvmExt<'tcx> for TyLayout<'tcx> {
     }
 
     fn immediate_llvm_type<'a>(&self, cx: &CodegenCx<'a, 'tcx>) -> &'a Type {
-        if let layout::Abi::Scalar(ref scalar) = self.abi {
-            if scalar.is_bool() {
+        match self.abi {
+            layout::Abi::Scalar(ref scalar) if scalar.is_bool() => {
                 return cx.type_i1();
             }
+            // Vectors of `bool`s are masks, which are kept as `<N x i1>` (e.g.
+            // in AVX-512's `k` registers) rather than widened to bytes.
+            layout::Abi::Vector { ref element, count }
+                if element.is_bool() && !self.ty.is_scalable_simd() => {
+                return cx.type_vector(cx.type_i1(), count);
+            }
+            _ => {}
         }
         self.llvm_type(cx)
     }
@@ -395,6 +427,10 @@ impl<'tcx> LayoutLlvmExt<'tcx> for TyLayout<'tcx> {
                 bug!("TyLayout::llvm_field_index({:?}): not applicable", self)
             }
 
+            layout::FieldPlacement::Arbitrary { .. } if self.fields.has_bit_fields() => {
+                bug!("TyLayout::llvm_field_index({:?}): not applicable", self)
+            }
+
             layout::FieldPlacement::Array { .. } => {
                 index as u64
             }
@@ -43,6 +43,11 @@ impl LinkerInfo {
         }
     }
 
+    /// Returns the symbols exported from an output of the given crate type.
+    pub fn exports(&self, crate_type: CrateType) -> &[String] {
+        self.exports.get(&crate_type).map(|e| &e[..]).unwrap_or(&[])
+    }
+
     pub fn to_linker<'a>(
         &'a self,
         cmd: Command,
@@ -130,10 +135,11 @@ pub trait Linker {
     fn pgo_gen(&mut self);
     fn debuginfo(&mut self);
     fn no_default_libraries(&mut self);
-    fn build_dylib(&mut self, out_filename: &Path);
+    fn build_dylib(&mut self, out_filename: &Path, crate_type: CrateType);
     fn build_static_executable(&mut self);
     fn args(&mut self, args: &[String]);
     fn export_symbols(&mut self, tmpdir: &Path, crate_type: CrateType);
+    fn order_symbols(&mut self, tmpdir: &Path, symbols: &[String]);
     fn subsystem(&mut self, subsystem: &str);
     fn group_start(&mut self);
     fn group_end(&mut self);
@@ -351,7 +357,7 @@ impl<'a> Linker for GccLinker<'a> {
         }
     }
 
-    fn build_dylib(&mut self, out_filename: &Path) {
+    fn build_dylib(&mut self, out_filename: &Path, _crate_type: CrateType) {
         // On mac we need to tell the linker to let this library be rpathed
         if self.sess.target.target.options.is_like_osx {
             self.cmd.arg("-dynamiclib");
@@ -464,6 +470,48 @@ impl<'a> Linker for GccLinker<'a> {
         }
     }
 
+    fn order_symbols(&mut self, tmpdir: &Path, symbols: &[String]) {
+        // Codegen put the listed functions in `.text.hot.*` sections on ELF
+        // targets, which the default linker scripts keep together, but only
+        // in the order of their codegen units. Of the ELF linkers, only
+        // ld.lld can be told the order of the functions themselves.
+        let is_like_osx = self.sess.target.target.options.is_like_osx;
+        let is_lld = !is_like_osx &&
+            super::link::linker_and_flavor(self.sess).1 == LinkerFlavor::Lld(LldFlavor::Ld);
+        if !is_like_osx && !is_lld {
+            return
+        }
+
+        // Write a plain, newline-separated list of symbols
+        let path = tmpdir.join("order");
+        let res = (|| -> io::Result<()> {
+            let mut f = BufWriter::new(File::create(&path)?);
+            for sym in symbols {
+                if is_like_osx {
+                    writeln!(f, "_{}", sym)?;
+                } else {
+                    writeln!(f, "{}", sym)?;
+                }
+            }
+            Ok(())
+        })();
+        if let Err(e) = res {
+            self.sess.fatal(&format!("failed to write order file: {}", e));
+        }
+
+        let mut arg = OsString::new();
+        if !self.is_ld {
+            arg.push("-Wl,")
+        }
+        if is_like_osx {
+            arg.push("-order_file,");
+        } else {
+            arg.push("--symbol-ordering-file=");
+        }
+        arg.push(&path);
+        self.cmd.arg(arg);
+    }
+
     fn cross_lang_lto(&mut self) {
         match self.sess.opts.debugging_opts.cross_lang_lto {
             CrossLangLto::Disabled => {
@@ -490,11 +538,16 @@ impl<'a> Linker for MsvcLinker<'a> {
     fn add_object(&mut self, path: &Path) { self.cmd.arg(path); }
     fn args(&mut self, args: &[String]) { self.cmd.args(args); }
 
-    fn build_dylib(&mut self, out_filename: &Path) {
+    fn build_dylib(&mut self, out_filename: &Path, crate_type: CrateType) {
         self.cmd.arg("/DLL");
-        let mut arg: OsString = "/IMPLIB:".into();
-        arg.push(out_filename.with_extension("dll.lib"));
-        self.cmd.arg(arg);
+        if crate_type == CrateType::Cdylib && self.sess.opts.debugging_opts.write_import_lib {
+            // The import library is written by rustc itself after linking
+            self.cmd.arg("/NOIMPLIB");
+        } else {
+            let mut arg: OsString = "/IMPLIB:".into();
+            arg.push(out_filename.with_extension("dll.lib"));
+            self.cmd.arg(arg);
+        }
     }
 
     fn build_static_executable(&mut self) {
@@ -701,6 +754,11 @@ impl<'a> Linker for MsvcLinker<'a> {
     }
 
     fn finalize(&mut self) -> Command {
+        // By default the linker stamps the PE header with the time of the link;
+        // `/Brepro` replaces that timestamp with a hash of the image.
+        if self.sess.opts.debugging_opts.deterministic {
+            self.cmd.arg("/Brepro");
+        }
         ::std::mem::replace(&mut self.cmd, Command::new(""))
     }
 
@@ -708,6 +766,23 @@ impl<'a> Linker for MsvcLinker<'a> {
     fn group_start(&mut self) {}
     fn group_end(&mut self) {}
 
+    fn order_symbols(&mut self, tmpdir: &Path, symbols: &[String]) {
+        let path = tmpdir.join("order.txt");
+        let res = (|| -> io::Result<()> {
+            let mut f = BufWriter::new(File::create(&path)?);
+            for sym in symbols {
+                writeln!(f, "{}", sym)?;
+            }
+            Ok(())
+        })();
+        if let Err(e) = res {
+            self.sess.fatal(&format!("failed to write order file: {}", e));
+        }
+        let mut arg = OsString::from("/ORDER:@");
+        arg.push(&path);
+        self.cmd.arg(arg);
+    }
+
     fn cross_lang_lto(&mut self) {
         // Do nothing
     }
@@ -826,7 +901,7 @@ impl<'a> Linker for EmLinker<'a> {
         self.cmd.args(&["-s", "DEFAULT_LIBRARY_FUNCS_TO_INCLUDE=[]"]);
     }
 
-    fn build_dylib(&mut self, _out_filename: &Path) {
+    fn build_dylib(&mut self, _out_filename: &Path, _crate_type: CrateType) {
         bug!("building dynamic library is unsupported on Emscripten")
     }
 
@@ -876,6 +951,10 @@ impl<'a> Linker for EmLinker<'a> {
     fn group_start(&mut self) {}
     fn group_end(&mut self) {}
 
+    fn order_symbols(&mut self, _tmpdir: &Path, _symbols: &[String]) {
+        // Do nothing
+    }
+
     fn cross_lang_lto(&mut self) {
         // Do nothing
     }
@@ -977,7 +1056,7 @@ impl<'a> Linker for WasmLd<'a> {
     fn no_default_libraries(&mut self) {
     }
 
-    fn build_dylib(&mut self, _out_filename: &Path) {
+    fn build_dylib(&mut self, _out_filename: &Path, _crate_type: CrateType) {
     }
 
     fn export_symbols(&mut self, _tmpdir: &Path, crate_type: CrateType) {
@@ -1055,6 +1134,10 @@ impl<'a> Linker for WasmLd<'a> {
     fn group_start(&mut self) {}
     fn group_end(&mut self) {}
 
+    fn order_symbols(&mut self, _tmpdir: &Path, _symbols: &[String]) {
+        // Do nothing
+    }
+
     fn cross_lang_lto(&mut self) {
         // Do nothing for now
     }
@@ -274,7 +274,7 @@ fn exported_symbols_provider_local<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                 def: InstanceDef::Item(def_id),
                 substs,
             }) = mono_item {
-                if substs.types().next().is_some() {
+                if substs.types().next().is_some() && !is_tiny_fn(tcx, def_id) {
                     symbols.push((ExportedSymbol::Generic(def_id, substs),
                                   SymbolExportLevel::Rust));
                 }
@@ -375,6 +375,23 @@ pub fn provide_extern(providers: &mut Providers) {
     providers.upstream_monomorphizations_for = upstream_monomorphizations_for_provider;
 }
 
+/// Whether `def_id` is small enough that downstream crates are better off
+/// instantiating it again, where it can be inlined, than linking to our
+/// monomorphizations of it. Sharing tiny functions only pays off when nothing
+/// gets inlined anyway.
+fn is_tiny_fn(tcx: TyCtxt, def_id: DefId) -> bool {
+    const TINY_FN_SIZE: usize = 8;
+
+    if tcx.sess.opts.optimize == config::OptLevel::No {
+        return false;
+    }
+    let mir = tcx.optimized_mir(def_id);
+    let size = mir.basic_blocks().iter()
+        .map(|block| block.statements.len() + 1)
+        .sum::<usize>();
+    size <= TINY_FN_SIZE
+}
+
 fn symbol_export_level(tcx: TyCtxt, sym_def_id: DefId) -> SymbolExportLevel {
     // We export anything that's not mangled at the "C" layer as it probably has
     // to do with ABI concerns. We do not, however, apply such treatment to
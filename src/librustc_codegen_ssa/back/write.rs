@@ -21,27 +21,35 @@ use rustc_incremental::{copy_cgu_workproducts_to_incr_comp_cache_dir,
                         in_incr_comp_dir, in_incr_comp_dir_sess};
 use rustc::dep_graph::{WorkProduct, WorkProductId, WorkProductFileKind};
 use rustc::dep_graph::cgu_reuse_tracker::CguReuseTracker;
-use rustc::middle::cstore::EncodedMetadata;
+use rustc::hir::CodegenFnAttrFlags;
+use rustc::lint;
+use rustc::lint::builtin::AUTOVECTORIZATION_FAILURE;
+use rustc::middle::cstore::{EncodedMetadata, NativeLibraryKind};
 use rustc::session::config::{self, OutputFilenames, OutputType, Passes, Sanitizer, Lto};
 use rustc::session::Session;
+use rustc::session::search_paths::PathKind;
 use rustc::util::nodemap::FxHashMap;
 use rustc::util::time_graph::{self, TimeGraph, Timeline};
 use traits::*;
 use rustc::hir::def_id::{CrateNum, LOCAL_CRATE};
-use rustc::ty::TyCtxt;
+use rustc::ty::{Instance, TyCtxt};
 use rustc::util::common::{time_depth, set_time_depth, print_time_passes_entry};
 use rustc_fs_util::link_or_copy;
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_data_structures::svh::Svh;
 use rustc_errors::{Handler, Level, DiagnosticBuilder, FatalError, DiagnosticId};
 use rustc_errors::emitter::{Emitter};
 use syntax::attr;
 use syntax::ext::hygiene::Mark;
-use syntax_pos::MultiSpan;
+use syntax_pos::{MultiSpan, Span};
 use syntax_pos::symbol::Symbol;
 use jobserver::{Client, Acquired};
+use serialize::json;
 
 use std::any::Any;
 use std::fs;
+use std::hash::Hasher;
 use std::io;
 use std::mem;
 use std::path::{Path, PathBuf};
@@ -79,6 +87,7 @@ pub struct ModuleConfig {
     // Miscellaneous flags.  These are mostly copied from command-line
     // options.
     pub verify_llvm_ir: bool,
+    pub verify_deterministic_obj: bool,
     pub no_prepopulate_passes: bool,
     pub no_builtins: bool,
     pub time_passes: bool,
@@ -119,6 +128,7 @@ impl ModuleConfig {
             no_integrated_as: false,
 
             verify_llvm_ir: false,
+            verify_deterministic_obj: false,
             no_prepopulate_passes: false,
             no_builtins: false,
             time_passes: false,
@@ -131,6 +141,7 @@ impl ModuleConfig {
 
     fn set_flags(&mut self, sess: &Session, no_builtins: bool) {
         self.verify_llvm_ir = sess.verify_llvm_ir();
+        self.verify_deterministic_obj = sess.opts.debugging_opts.deterministic;
         self.no_prepopulate_passes = sess.opts.cg.no_prepopulate_passes;
         self.no_builtins = no_builtins || sess.target.target.options.no_builtins;
         self.time_passes = sess.time_passes();
@@ -189,6 +200,27 @@ impl<B: WriteBackendMethods> Clone for TargetMachineFactory<B> {
     }
 }
 
+/// A function marked `#[vectorize(expect)]`, whose loops that LLVM fails to
+/// vectorize are reported.
+#[derive(Clone)]
+pub struct ExpectVectorized {
+    /// Level of the `autovectorization_failure` lint at the function
+    pub level: lint::Level,
+    /// Path of the function
+    pub name: String,
+    /// Where the function is defined, which its loops are reported at
+    pub span: Span,
+}
+
+/// The bitcode libraries (`-l bitcode=NAME`) linked into the crate's modules.
+pub struct BitcodeLibs {
+    /// Name and path of each library
+    pub libs: Vec<(String, PathBuf)>,
+    /// The codegen unit that defines the libraries' symbols. The other ones
+    /// only get `available_externally` copies of them, for inlining.
+    pub home_cgu: String,
+}
+
 /// Additional resources used by optimize_and_codegen (not module specific)
 #[derive(Clone)]
 pub struct CodegenContext<B: WriteBackendMethods> {
@@ -220,6 +252,11 @@ pub struct CodegenContext<B: WriteBackendMethods> {
     pub plugin_passes: Vec<String>,
     // LLVM optimizations for which we want to print remarks.
     pub remark: Passes,
+    // Functions marked `#[vectorize(expect)]` with `autovectorization_failure`
+    // enabled, by symbol name.
+    pub expect_vectorized: Arc<FxHashMap<String, ExpectVectorized>>,
+    // Bitcode libraries to link into the regular modules before optimizing them
+    pub bitcode_libs: Option<Arc<BitcodeLibs>>,
     // Worker thread number
     pub worker: usize,
     // The incremental compilation session directory, or None if we are not
@@ -282,6 +319,9 @@ pub struct CompiledModules {
     pub modules: Vec<CompiledModule>,
     pub metadata_module: CompiledModule,
     pub allocator_module: Option<CompiledModule>,
+    // Content hashes of the modules' bitcode before LTO, keyed by module
+    // name, when `-Z codegen-hash-output` is enabled.
+    pub pre_lto_hashes: FxHashMap<String, Fingerprint>,
 }
 
 fn need_crate_bitcode_for_rlib(sess: &Session) -> bool {
@@ -397,7 +437,14 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
                     allocator_config.emit_obj = true;
                 }
             }
-            OutputType::Object => { modules_config.emit_obj = true; }
+            OutputType::Object => {
+                if sess.opts.debugging_opts.merge_objects {
+                    // The backend links the optimized bitcode into one object.
+                    modules_config.emit_bc = true;
+                } else {
+                    modules_config.emit_obj = true;
+                }
+            }
             OutputType::Metadata => { metadata_config.emit_obj = true; }
             OutputType::Exe => {
                 modules_config.emit_obj = true;
@@ -418,12 +465,17 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
     metadata_config.time_passes = false;
     allocator_config.time_passes = false;
 
+    let bitcode_libs = bitcode_libs(tcx).map(Arc::new);
+    let bitcode_libs_unchanged = bitcode_libs_unchanged(sess, bitcode_libs.as_ref()
+                                                                          .map(|libs| &**libs));
+
     let (shared_emitter, shared_emitter_main) = SharedEmitter::new();
     let (codegen_worker_send, codegen_worker_receive) = channel();
 
     let coordinator_thread = start_executing_work(backend.clone(),
                                                   tcx,
                                                   &crate_info,
+                                                  bitcode_libs,
                                                   shared_emitter,
                                                   codegen_worker_send,
                                                   coordinator_receive,
@@ -442,6 +494,7 @@ pub fn start_async_codegen<B: ExtraBackendMethods>(
         windows_subsystem,
         linker_info,
         crate_info,
+        bitcode_libs_unchanged,
 
         time_graph,
         coordinator_send: tcx.tx_to_llvm_workers.lock().clone(),
@@ -484,6 +537,146 @@ fn copy_all_cgu_workproducts_to_incr_comp_cache_dir(
     work_products
 }
 
+/// Hashes the bytes of a module's bitcode or object file for
+/// `-Z codegen-hash-output`.
+pub fn content_hash(data: &[u8]) -> Fingerprint {
+    let mut hasher = StableHasher::<Fingerprint>::new();
+    hasher.write(data);
+    hasher.finish()
+}
+
+/// Copies the object of every codegen unit to the path `template` expands to
+/// for it, relative to the output directory, for `-Z object-names`, and lists
+/// them in `<crate>.objects.json` in the order the final link should use.
+///
+/// `{crate}` is replaced with the crate's output file stem, `{target}` with
+/// the target triple, `{cgu}` with the codegen unit's name and `{index}` with
+/// its position in that list.
+fn copy_named_objects(sess: &Session,
+                      compiled_modules: &CompiledModules,
+                      crate_output: &OutputFilenames,
+                      template: &str) {
+    let mut modules: Vec<_> = compiled_modules.modules.iter()
+        .filter(|module| module.object.is_some())
+        .collect();
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut objects = Vec::new();
+    for (index, module) in modules.into_iter().enumerate() {
+        let name = template.replace("{crate}", &crate_output.filestem())
+                           .replace("{target}", sess.opts.target_triple.triple())
+                           .replace("{cgu}", &module.name)
+                           .replace("{index}", &index.to_string());
+        if objects.contains(&name) {
+            sess.err(&format!("-Z object-names template `{}` gives several objects \
+                               the name `{}`", template, name));
+            return;
+        }
+
+        let from = module.object.as_ref().unwrap();
+        let to = crate_output.out_directory.join(&name);
+        if let Some(dir) = to.parent() {
+            if let Err(e) = fs::create_dir_all(dir) {
+                sess.err(&format!("failed to create `{}`: {}", dir.display(), e));
+                return;
+            }
+        }
+        if let Err(e) = fs::copy(from, &to) {
+            sess.err(&format!("could not copy {:?} to {:?}: {}", from, to, e));
+            return;
+        }
+        objects.push(name);
+    }
+
+    #[derive(RustcEncodable)]
+    struct Manifest<'a> {
+        target: &'a str,
+        objects: Vec<String>,
+    }
+
+    let manifest = Manifest {
+        target: sess.opts.target_triple.triple(),
+        objects,
+    };
+    let manifest = format!("{}\n", json::as_pretty_json(&manifest));
+    let path = crate_output.with_extension("objects.json");
+    if let Err(e) = fs::write(&path, manifest) {
+        sess.err(&format!("failed to write `{}`: {}", path.display(), e));
+    }
+}
+
+/// Writes `<crate>.codegen-hashes.json`, which lists every module with the
+/// hash of its bitcode before LTO and the hash of the object file it ended up
+/// in. Build caches can use it to verify and share objects per codegen unit.
+///
+/// Modules that were merged away by fat LTO only have a `pre_lto` hash,
+/// and modules reused from the incremental cache only have an `object` one.
+fn emit_codegen_hashes(sess: &Session,
+                       compiled_modules: &CompiledModules,
+                       crate_output: &OutputFilenames) {
+    #[derive(RustcEncodable)]
+    struct Manifest {
+        modules: Vec<ModuleHashes>,
+    }
+
+    #[derive(RustcEncodable)]
+    struct ModuleHashes {
+        name: String,
+        kind: &'static str,
+        pre_lto: Option<String>,
+        object: Option<String>,
+    }
+
+    let entry = |name: &str, kind: &'static str, object: Option<Fingerprint>| {
+        ModuleHashes {
+            name: name.to_string(),
+            kind,
+            pre_lto: compiled_modules.pre_lto_hashes.get(name).map(|hash| hash.to_hex()),
+            object: object.map(|hash| hash.to_hex()),
+        }
+    };
+
+    let mut entries = Vec::new();
+    let modules = compiled_modules.modules.iter()
+        .chain(Some(&compiled_modules.metadata_module))
+        .chain(compiled_modules.allocator_module.as_ref());
+    for module in modules {
+        let kind = match module.kind {
+            ModuleKind::Regular => "regular",
+            ModuleKind::Metadata => "metadata",
+            ModuleKind::Allocator => "allocator",
+        };
+        let object = module.object.as_ref().and_then(|path| {
+            match fs::read(path) {
+                Ok(data) => Some(content_hash(&data)),
+                Err(e) => {
+                    sess.err(&format!("failed to read `{}` to hash it: {}",
+                                      path.display(), e));
+                    None
+                }
+            }
+        });
+        entries.push(entry(&module.name, kind, object));
+    }
+
+    let mut merged: Vec<&String> = compiled_modules.pre_lto_hashes.keys().filter(|name| {
+        !compiled_modules.modules.iter()
+            .chain(Some(&compiled_modules.metadata_module))
+            .chain(compiled_modules.allocator_module.as_ref())
+            .any(|module| module.name == **name)
+    }).collect();
+    merged.sort();
+    for name in merged {
+        entries.push(entry(name, "regular", None));
+    }
+
+    let manifest = format!("{}\n", json::as_pretty_json(&Manifest { modules: entries }));
+    let path = crate_output.with_extension("codegen-hashes.json");
+    if let Err(e) = fs::write(&path, manifest) {
+        sess.err(&format!("failed to write `{}`: {}", path.display(), e));
+    }
+}
+
 fn produce_final_output_artifacts(sess: &Session,
                                   compiled_modules: &CompiledModules,
                                   crate_output: &OutputFilenames) {
@@ -556,7 +749,13 @@ fn produce_final_output_artifacts(sess: &Session,
             }
             OutputType::Object => {
                 user_wants_objects = true;
-                copy_if_one_unit(OutputType::Object, true);
+                if sess.opts.debugging_opts.merge_objects {
+                    // Written by the backend from the modules' bitcode.
+                } else if let Some(ref template) = sess.opts.debugging_opts.object_names {
+                    copy_named_objects(sess, compiled_modules, crate_output, template);
+                } else {
+                    copy_if_one_unit(OutputType::Object, true);
+                }
             }
             OutputType::Mir |
             OutputType::Metadata |
@@ -595,10 +794,12 @@ fn produce_final_output_artifacts(sess: &Session,
         // rlib.
         let needs_crate_object = crate_output.outputs.contains_key(&OutputType::Exe);
 
-        let keep_numbered_bitcode = user_wants_bitcode && sess.codegen_units() > 1;
+        let keep_numbered_bitcode = (user_wants_bitcode && sess.codegen_units() > 1) ||
+                (user_wants_objects && sess.opts.debugging_opts.merge_objects);
 
         let keep_numbered_objects = needs_crate_object ||
-                (user_wants_objects && sess.codegen_units() > 1);
+                (user_wants_objects && sess.codegen_units() > 1 &&
+                 sess.opts.debugging_opts.object_names.is_none());
 
         for module in compiled_modules.modules.iter() {
             if let Some(ref path) = module.object {
@@ -854,6 +1055,10 @@ pub enum Message<B: WriteBackendMethods> {
         module_data: SerializedModule<B::ModuleBuffer>,
         work_product: WorkProduct,
     },
+    PreLtoHash {
+        module_name: String,
+        hash: Fingerprint,
+    },
     CodegenComplete,
     CodegenItem,
     CodegenAborted,
@@ -872,10 +1077,121 @@ enum MainThreadWorkerState {
     LLVMing,
 }
 
+/// Collects the functions whose loops that fail to vectorize are reported, by
+/// symbol name. Functions where `autovectorization_failure` is allowed are
+/// left out, so the remarks needn't be enabled at all.
+fn expect_vectorized(tcx: TyCtxt) -> FxHashMap<String, ExpectVectorized> {
+    tcx.body_owners().filter_map(|def_id| {
+        let flags = tcx.codegen_fn_attrs(def_id).flags;
+        if !flags.contains(CodegenFnAttrFlags::EXPECT_VECTORIZED) ||
+           tcx.generics_of(def_id).requires_monomorphization(tcx) {
+            return None;
+        }
+        let node_id = tcx.hir.as_local_node_id(def_id)?;
+        let (level, _) = tcx.lint_level_at_node(AUTOVECTORIZATION_FAILURE, node_id);
+        if level == lint::Allow {
+            return None;
+        }
+        let symbol = tcx.symbol_name(Instance::mono(tcx, def_id)).as_str().to_string();
+        Some((symbol, ExpectVectorized {
+            level,
+            name: tcx.item_path_str(def_id),
+            span: tcx.def_span(def_id),
+        }))
+    }).collect()
+}
+
+/// Finds the bitcode libraries of the crate in the native library search
+/// paths, as `libNAME.bc`, `NAME.bc` or an archive `libNAME.a` of bitcode.
+fn bitcode_libs(tcx: TyCtxt) -> Option<BitcodeLibs> {
+    let sess = tcx.sess;
+    let mut search = Vec::new();
+    sess.target_filesearch(PathKind::Native).for_each_lib_search_path(|path, _| {
+        search.push(path.to_path_buf());
+    });
+
+    let libs: Vec<_> = tcx.native_libraries(LOCAL_CRATE).iter()
+        .filter(|lib| lib.kind == NativeLibraryKind::NativeBitcode)
+        .filter_map(|lib| {
+            let name = lib.name?.as_str().to_string();
+            let candidates = [
+                format!("lib{}.bc", name),
+                format!("{}.bc", name),
+                format!("lib{}.a", name),
+            ];
+            let path = search.iter()
+                .flat_map(|dir| candidates.iter().map(move |file| dir.join(file)))
+                .find(|path| path.exists());
+            match path {
+                Some(path) => Some((name, path)),
+                None => {
+                    sess.err(&format!("could not find bitcode library `{}`", name));
+                    None
+                }
+            }
+        })
+        .collect();
+    if libs.is_empty() {
+        return None;
+    }
+
+    let (_, codegen_units) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
+    let home_cgu = codegen_units.iter().map(|cgu| cgu.name().to_string()).min()?;
+    Some(BitcodeLibs { libs, home_cgu })
+}
+
+const BITCODE_LIBS_FINGERPRINT_FILENAME: &str = "bitcode-libs.fingerprint";
+
+/// Hashes the bitcode libraries linked into the crate and compares the result
+/// with the one saved by the previous incremental session, saving the new one
+/// for the next. The dependency graph doesn't track the contents of the
+/// libraries, so cached codegen units can only be reused if this returns true.
+fn bitcode_libs_unchanged(sess: &Session, bitcode_libs: Option<&BitcodeLibs>) -> bool {
+    let path = match sess.incr_comp_session_dir_opt() {
+        Some(dir) => dir.join(BITCODE_LIBS_FINGERPRINT_FILENAME),
+        None => return false,
+    };
+
+    let mut hasher = StableHasher::<Fingerprint>::new();
+    if let Some(bitcode_libs) = bitcode_libs {
+        hasher.write(bitcode_libs.home_cgu.as_bytes());
+        for &(ref name, ref lib_path) in &bitcode_libs.libs {
+            hasher.write_usize(name.len());
+            hasher.write(name.as_bytes());
+            match fs::read(lib_path) {
+                Ok(data) => {
+                    hasher.write_usize(data.len());
+                    hasher.write(&data);
+                }
+                Err(_) => hasher.write_u8(0),
+            }
+        }
+    }
+    let fingerprint = hasher.finish().to_hex();
+
+    // A crate without bitcode libraries doesn't need to have saved a
+    // fingerprint, so that enabling this doesn't invalidate older sessions.
+    let unchanged = match fs::read_to_string(&path) {
+        Ok(previous) => previous == fingerprint,
+        Err(_) => bitcode_libs.is_none(),
+    };
+
+    // The session directory starts out as hard links to the files of the
+    // previous one, so replace the file instead of writing through the link.
+    let _ = fs::remove_file(&path);
+    if let Err(err) = fs::write(&path, &fingerprint) {
+        sess.warn(&format!("failed to save the bitcode library fingerprint to `{}`: {}",
+                           path.display(), err));
+    }
+
+    unchanged
+}
+
 fn start_executing_work<B: ExtraBackendMethods>(
     backend: B,
     tcx: TyCtxt,
     crate_info: &CrateInfo,
+    bitcode_libs: Option<Arc<BitcodeLibs>>,
     shared_emitter: SharedEmitter,
     codegen_worker_send: Sender<Message<B>>,
     coordinator_receive: Receiver<Box<dyn Any + Send>>,
@@ -962,6 +1278,8 @@ fn start_executing_work<B: ExtraBackendMethods>(
         exported_symbols,
         plugin_passes: sess.plugin_llvm_passes.borrow().clone(),
         remark: sess.opts.cg.remark.clone(),
+        expect_vectorized: Arc::new(expect_vectorized(tcx)),
+        bitcode_libs,
         worker: 0,
         incr_comp_session_dir: sess.incr_comp_session_dir_opt().map(|r| r.clone()),
         cgu_reuse_tracker: sess.cgu_reuse_tracker.clone(),
@@ -1135,6 +1453,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
         // This is where we collect codegen units that have gone all the way
         // through codegen and LLVM.
         let mut compiled_modules = vec![];
+        let mut pre_lto_hashes = FxHashMap::default();
         let mut compiled_metadata_module = None;
         let mut compiled_allocator_module = None;
         let mut needs_lto = Vec::new();
@@ -1408,6 +1727,11 @@ fn start_executing_work<B: ExtraBackendMethods>(
                     lto_import_only_modules.push((module_data, work_product));
                     main_thread_worker_state = MainThreadWorkerState::Idle;
                 }
+                // Workers send these before the `Done` or `NeedsLTO` message
+                // of the module, so they've all arrived once the loop exits.
+                Message::PreLtoHash { module_name, hash } => {
+                    pre_lto_hashes.insert(module_name, hash);
+                }
                 Message::Done { result: Err(()), worker_id: _ } => {
                     bug!("worker thread panicked");
                 }
@@ -1439,6 +1763,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
             modules: compiled_modules,
             metadata_module: compiled_metadata_module,
             allocator_module: compiled_allocator_module,
+            pre_lto_hashes,
         })
     });
 
@@ -1568,6 +1893,7 @@ pub fn run_assembler<B: ExtraBackendMethods>(
 
 enum SharedEmitterMessage {
     Diagnostic(Diagnostic),
+    SpannedDiagnostic(Span, Diagnostic),
     InlineAsmError(u32, String),
     AbortIfErrors,
     Fatal(String),
@@ -1596,6 +1922,22 @@ impl SharedEmitter {
     pub fn fatal(&self, msg: &str) {
         drop(self.sender.send(SharedEmitterMessage::Fatal(msg.to_string())));
     }
+
+    /// Reports `msg` at `span` in the crate being compiled, followed by `notes`.
+    pub fn span_diagnostic(&self, span: Span, lvl: Level, msg: String, notes: &[String]) {
+        drop(self.sender.send(SharedEmitterMessage::SpannedDiagnostic(span, Diagnostic {
+            msg,
+            code: None,
+            lvl,
+        })));
+        for note in notes {
+            drop(self.sender.send(SharedEmitterMessage::Diagnostic(Diagnostic {
+                msg: note.clone(),
+                code: None,
+                lvl: Level::Note,
+            })));
+        }
+    }
 }
 
 impl Emitter for SharedEmitter {
@@ -1648,6 +1990,9 @@ impl SharedEmitterMain {
                         }
                     }
                 }
+                Ok(SharedEmitterMessage::SpannedDiagnostic(span, diag)) => {
+                    sess.diagnostic().emit(&MultiSpan::from_span(span), &diag.msg, diag.lvl);
+                }
                 Ok(SharedEmitterMessage::InlineAsmError(cookie, msg)) => {
                     match Mark::from_u32(cookie).expn_info() {
                         Some(ei) => sess.span_err(ei.call_site, &msg),
@@ -1677,6 +2022,9 @@ pub struct OngoingCodegen<B: ExtraBackendMethods> {
     pub windows_subsystem: Option<String>,
     pub linker_info: LinkerInfo,
     pub crate_info: CrateInfo,
+    /// Whether the bitcode libraries are the same as in the previous
+    /// incremental session, see `bitcode_libs_unchanged`.
+    pub bitcode_libs_unchanged: bool,
     pub time_graph: Option<TimeGraph>,
     pub coordinator_send: Sender<Box<dyn Any + Send>>,
     pub codegen_worker_receive: Receiver<Message<B>>,
@@ -1713,6 +2061,9 @@ impl<B: ExtraBackendMethods> OngoingCodegen<B> {
         let work_products =
             copy_all_cgu_workproducts_to_incr_comp_cache_dir(sess,
                                                              &compiled_modules);
+        if sess.opts.debugging_opts.codegen_hash_output {
+            emit_codegen_hashes(sess, &compiled_modules, &self.output_filenames);
+        }
         produce_final_output_artifacts(sess,
                                        &compiled_modules,
                                        &self.output_filenames);
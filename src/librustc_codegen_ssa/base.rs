@@ -34,6 +34,7 @@ use rustc::ty::{self, Ty, TyCtxt};
 use rustc::ty::layout::{self, Align, TyLayout, LayoutOf, VariantIdx, HasTyCtxt};
 use rustc::ty::query::Providers;
 use rustc::middle::cstore::{self, LinkagePreference};
+use rustc::middle::exported_symbols::ExportedSymbol;
 use rustc::util::common::{time, print_time_passes_entry};
 use rustc::util::profiling::ProfileCategory;
 use rustc::session::config::{self, EntryFnType, Lto};
@@ -50,7 +51,7 @@ use mir;
 use rustc::util::time_graph;
 use rustc_mir::monomorphize::Instance;
 use rustc_mir::monomorphize::partitioning::{CodegenUnit, CodegenUnitExt};
-use mono_item::MonoItem;
+use mono_item::{MonoItem, BaseMonoItemExt};
 use rustc::util::nodemap::FxHashMap;
 use rustc_data_structures::indexed_vec::Idx;
 use rustc_data_structures::sync::Lrc;
@@ -61,6 +62,7 @@ use traits::*;
 
 use std::any::Any;
 use std::cmp;
+use std::iter;
 use std::ops::{Deref, DerefMut};
 use std::time::{Instant, Duration};
 use std::sync::mpsc;
@@ -378,8 +380,14 @@ pub fn from_immediate<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
     bx: &mut Bx,
     val: Bx::Value
 ) -> Bx::Value {
-    if bx.cx().val_ty(val) == bx.cx().type_i1() {
+    let ty = bx.cx().val_ty(val);
+    if ty == bx.cx().type_i1() {
         bx.zext(val, bx.cx().type_i8())
+    } else if bx.cx().type_kind(ty) == TypeKind::Vector &&
+              bx.cx().element_type(ty) == bx.cx().type_i1() {
+        let len = bx.cx().vector_length(ty) as u64;
+        let i8xn = bx.cx().type_vector(bx.cx().type_i8(), len);
+        bx.zext(val, i8xn)
     } else {
         val
     }
@@ -390,10 +398,15 @@ pub fn to_immediate<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
     val: Bx::Value,
     layout: layout::TyLayout,
 ) -> Bx::Value {
-    if let layout::Abi::Scalar(ref scalar) = layout.abi {
-        return to_immediate_scalar(bx, val, scalar);
+    match layout.abi {
+        layout::Abi::Scalar(ref scalar) => to_immediate_scalar(bx, val, scalar),
+        layout::Abi::Vector { ref element, count } if element.is_bool() &&
+                                                      !layout.ty.is_scalable_simd() => {
+            let i1xn = bx.cx().type_vector(bx.cx().type_i1(), count);
+            bx.trunc(val, i1xn)
+        }
+        _ => val
     }
-    val
 }
 
 pub fn to_immediate_scalar<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
@@ -451,7 +464,24 @@ pub fn codegen_instance<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
     cx.stats().borrow_mut().n_closures += 1;
 
     let mir = cx.tcx().instance_mir(instance.def);
-    mir::codegen_mir::<Bx>(cx, lldecl, &mir, instance, sig);
+
+    let targets = match instance.def {
+        ty::InstanceDef::Item(def_id) => cx.tcx().codegen_fn_attrs(def_id).multiversion,
+        _ => vec![],
+    };
+    if targets.is_empty() {
+        mir::codegen_mir::<Bx>(cx, lldecl, &mir, instance, sig);
+        return;
+    }
+
+    // `#[multiversion]`: codegen the body once per target and turn `lldecl`
+    // into a dispatcher picking the best variant the CPU supports.
+    let variants = targets.iter().map(|&target| {
+        let llfn = cx.predefine_multiversion_variant(instance, target);
+        mir::codegen_mir::<Bx>(cx, llfn, &mir, instance, sig);
+        (target, llfn)
+    }).collect::<Vec<_>>();
+    cx.define_multiversion_dispatcher(instance, lldecl, &variants);
 }
 
 /// Create the `main` function which will initialize the rust runtime and call
@@ -615,6 +645,13 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     let codegen_units = tcx.collect_and_partition_mono_items(LOCAL_CRATE).1;
     let codegen_units = (*codegen_units).clone();
 
+    if tcx.sess.opts.debugging_opts.verify_no_alloc {
+        time(tcx.sess, "verifying `#[no_alloc]` functions", || {
+            rustc_mir::monomorphize::no_alloc::verify_no_alloc(tcx, &codegen_units)
+        });
+        tcx.sess.abort_if_errors();
+    }
+
     // Force all codegen_unit queries so they are already either red or green
     // when compile_codegen_unit accesses them. We are not able to re-execute
     // the codegen_unit query from just the DepNode, so an unknown color would
@@ -677,10 +714,14 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
     ongoing_codegen.submit_pre_codegened_module_to_llvm(tcx, metadata_module);
 
     // We sort the codegen units by size. This way we can schedule work for LLVM
-    // a bit more efficiently.
+    // a bit more efficiently. With `-Z deterministic` we keep the order by name
+    // that partitioning gave us, so that the order in which codegen units are
+    // processed doesn't depend on size estimates.
     let codegen_units = {
         let mut codegen_units = codegen_units;
-        codegen_units.sort_by_cached_key(|cgu| cmp::Reverse(cgu.size_estimate()));
+        if !tcx.sess.opts.debugging_opts.deterministic {
+            codegen_units.sort_by_cached_key(|cgu| cmp::Reverse(cgu.size_estimate()));
+        }
         codegen_units
     };
 
@@ -691,7 +732,7 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
         ongoing_codegen.wait_for_signal_to_codegen_item();
         ongoing_codegen.check_for_errors(tcx.sess);
 
-        let cgu_reuse = determine_cgu_reuse(tcx, &cgu);
+        let cgu_reuse = determine_cgu_reuse(tcx, &cgu, ongoing_codegen.bitcode_libs_unchanged);
         tcx.sess.cgu_reuse_tracker.set_actual_reuse(&cgu.name().as_str(), cgu_reuse);
 
         match cgu_reuse {
@@ -839,6 +880,7 @@ impl CrateInfo {
             wasm_imports: Default::default(),
             lang_item_to_crate: Default::default(),
             missing_lang_items: Default::default(),
+            symbol_items: Default::default(),
         };
         let lang_items = tcx.lang_items();
 
@@ -897,6 +939,18 @@ impl CrateInfo {
             info.missing_lang_items.insert(cnum, missing);
         }
 
+        // Finding the item behind every symbol means decoding the spans of
+        // everything upstream crates export, so only do it when asked to.
+        let debugging_opts = &tcx.sess.opts.debugging_opts;
+        let invokes_linker = tcx.sess.crate_types.borrow()
+            .iter()
+            .any(|c| *c != config::CrateType::Rlib && *c != config::CrateType::Staticlib);
+        if invokes_linker &&
+           (debugging_opts.explain_linker_errors ||
+            debugging_opts.print_unused_monomorphizations) {
+            info.load_symbol_items(tcx);
+        }
+
         return info
     }
 
@@ -908,6 +962,49 @@ impl CrateInfo {
             (import_name.to_string(), module.clone())
         }));
     }
+
+    fn load_symbol_items(&mut self, tcx: TyCtxt) {
+        // Everything defined by this crate...
+        let (_, cgus) = tcx.collect_and_partition_mono_items(LOCAL_CRATE);
+        for cgu in cgus.iter() {
+            for mono_item in cgu.items().keys() {
+                let def_id = match *mono_item {
+                    MonoItem::Fn(instance) => instance.def_id(),
+                    MonoItem::Static(def_id) => def_id,
+                    MonoItem::GlobalAsm(..) => continue,
+                };
+                let symbol_name = mono_item.symbol_name(tcx).to_string();
+                self.symbol_items.insert(symbol_name, (LOCAL_CRATE, tcx.def_span(def_id)));
+            }
+        }
+
+        // ... everything upstream crates export ...
+        let crates = tcx.crates();
+        for &cnum in crates.iter() {
+            for &(ref symbol, _) in tcx.exported_symbols(cnum).iter() {
+                let def_id = match *symbol {
+                    ExportedSymbol::NonGeneric(def_id) |
+                    ExportedSymbol::Generic(def_id, _) => def_id,
+                    ExportedSymbol::NoDefId(_) => continue,
+                };
+                let symbol_name = symbol.symbol_name(tcx).to_string();
+                self.symbol_items.entry(symbol_name)
+                    .or_insert_with(|| (cnum, tcx.def_span(def_id)));
+            }
+        }
+
+        // ... and everything declared in `extern` blocks, which is what
+        // usually goes missing.
+        for &cnum in iter::once(&LOCAL_CRATE).chain(crates.iter()) {
+            for module in tcx.foreign_modules(cnum).iter() {
+                for &def_id in &module.foreign_items {
+                    let symbol_name = tcx.symbol_name(Instance::mono(tcx, def_id)).to_string();
+                    self.symbol_items.entry(symbol_name)
+                        .or_insert_with(|| (cnum, tcx.def_span(def_id)));
+                }
+            }
+        }
+    }
 }
 
 fn is_codegened_item(tcx: TyCtxt, id: DefId) -> bool {
@@ -948,12 +1045,19 @@ pub fn provide_both(providers: &mut Providers) {
 }
 
 fn determine_cgu_reuse<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
-                                 cgu: &CodegenUnit<'tcx>)
+                                 cgu: &CodegenUnit<'tcx>,
+                                 bitcode_libs_unchanged: bool)
                                  -> CguReuse {
     if !tcx.dep_graph.is_fully_enabled() {
         return CguReuse::No
     }
 
+    // The contents of bitcode libraries aren't tracked by the dependency
+    // graph, so a cached module may have been linked against older ones.
+    if !bitcode_libs_unchanged {
+        return CguReuse::No
+    }
+
     let work_product_id = &cgu.work_product_id();
     if tcx.dep_graph.previous_work_product(work_product_id).is_none() {
         // We don't have anything cached for this CGU. This can happen
@@ -39,6 +39,12 @@ pub fn size_and_align_of_dst<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
             let vtable = info.unwrap();
             (meth::SIZE.get_usize(bx, vtable), meth::ALIGN.get_usize(bx, vtable))
         }
+        ty::Foreign(_) => {
+            // Nothing is known about extern types, so behave like their
+            // (empty) layout does.
+            (bx.cx().const_usize(layout.size.bytes()),
+             bx.cx().const_usize(layout.align.abi.bytes()))
+        }
         ty::Slice(_) | ty::Str => {
             let unit = layout.field(bx.cx(), 0);
             // The info in this case is the length of the str, so the size is that
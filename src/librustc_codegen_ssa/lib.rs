@@ -42,6 +42,7 @@ extern crate rustc_data_structures;
 extern crate rustc_allocator;
 extern crate rustc_fs_util;
 extern crate serialize;
+extern crate serialize as rustc_serialize; // used by deriving
 extern crate rustc_errors;
 extern crate rustc_demangle;
 extern crate cc;
@@ -60,6 +61,7 @@ use rustc_data_structures::sync::Lrc;
 use rustc_data_structures::svh::Svh;
 use rustc::middle::cstore::{LibSource, CrateSource, NativeLibrary};
 use syntax_pos::symbol::Symbol;
+use syntax_pos::Span;
 
 // NB: This module needs to be declared first so diagnostics are
 // registered before they are used.
@@ -169,6 +171,10 @@ pub struct CrateInfo {
     pub wasm_imports: FxHashMap<String, String>,
     pub lang_item_to_crate: FxHashMap<LangItem, CrateNum>,
     pub missing_lang_items: FxHashMap<CrateNum, Vec<LangItem>>,
+    /// The crate and item span behind each symbol the linker may complain
+    /// about, used to explain linker errors. Only filled in with
+    /// `-Z explain-linker-errors` or `-Z print-unused-monomorphizations`.
+    pub symbol_items: FxHashMap<String, (CrateNum, Span)>,
 }
 
 
@@ -8,23 +8,30 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use rustc::hir::def_id::DefId;
 use rustc::middle::lang_items;
 use rustc::ty::{self, Ty, TypeFoldable};
-use rustc::ty::layout::{self, LayoutOf, HasTyCtxt};
+use rustc::ty::layout::{self, Align, LayoutOf, HasTyCtxt};
 use rustc::mir;
 use rustc::mir::interpret::EvalErrorKind;
 use rustc_target::abi::call::{ArgType, FnType, PassMode};
 use rustc_target::spec::abi::Abi;
+use rustc_target::spec::PanicStrategy;
 use base;
 use MemFlags;
 use common::{self, IntPredicate};
 use meth;
 use rustc_mir::monomorphize;
+use rustc_allocator::ALLOCATOR_METHODS;
+use rustc_data_structures::stable_hasher::StableHasher;
 
 use traits::*;
 
+use syntax::attr::InlineAttr;
 use syntax::symbol::Symbol;
-use syntax_pos::Pos;
+use syntax_pos::{Pos, Span};
+
+use std::hash::Hash;
 
 use super::{FunctionCx, LocalRef};
 use super::place::PlaceRef;
@@ -48,6 +55,88 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         self.codegen_terminator(bx, bb, data.terminator());
     }
 
+    /// Whether panics are lowered to a bare abort (`-Z panic-abort-minimal`).
+    fn minimal_panics(&self, bx: &Bx) -> bool {
+        let sess = bx.tcx().sess;
+        sess.opts.debugging_opts.panic_abort_minimal &&
+            sess.panic_strategy() == PanicStrategy::Abort
+    }
+
+    /// With `-Z instrument-allocators`, the allocator method (`alloc`,
+    /// `dealloc`, ...) that a call to `def` goes to, if any.
+    fn instrumented_allocator_method(
+        &self,
+        bx: &Bx,
+        def: Option<ty::InstanceDef<'tcx>>,
+    ) -> Option<&'static str> {
+        let tcx = bx.tcx();
+        if !tcx.sess.opts.debugging_opts.instrument_allocators {
+            return None;
+        }
+        let def_id = match def {
+            Some(ty::InstanceDef::Item(def_id)) if tcx.is_foreign_item(def_id) => def_id,
+            _ => return None,
+        };
+        let name = tcx.item_name(def_id).as_str();
+        ALLOCATOR_METHODS.iter()
+            .map(|method| method.name)
+            .find(|method| *name == format!("__rust_{}", method))
+    }
+
+    /// An ID for the call at `span`, reported along with the allocations it
+    /// makes. It only depends on the calling function's symbol name and the
+    /// position of the call, with the file name as remapped by
+    /// `--remap-path-prefix`, and is hashed the same way on every host.
+    fn allocation_callsite_id(&self, bx: &Bx, span: Span) -> u64 {
+        let loc = bx.cx().sess().source_map().lookup_char_pos(span.lo());
+        let mut hasher = StableHasher::<u64>::new();
+        bx.tcx().symbol_name(self.instance).as_str().hash(&mut hasher);
+        loc.file.name.to_string().hash(&mut hasher);
+        (loc.line as u64).hash(&mut hasher);
+        (loc.col.to_usize() as u64).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Counts a call through a vtable for `-Z count-virtual-calls`, by passing
+    /// the `count_virtual_call` lang item a `std::rt::VirtualCallSite` for
+    /// this call site: its count, the next site counted, the trait method and
+    /// where the call is.
+    fn count_virtual_call(&mut self, bx: &mut Bx, method: DefId, span: Span) {
+        let tcx = bx.tcx();
+        let loc = bx.cx().sess().source_map().lookup_char_pos(span.lo());
+        let location = format!("{}:{}:{}", loc.file.name, loc.line, loc.col.to_usize() + 1);
+        let location = bx.cx().const_str_slice(Symbol::intern(&location).as_str());
+        let method = bx.cx().const_str_slice(Symbol::intern(&tcx.item_path_str(method)).as_str());
+        let site = bx.cx().const_struct(&[
+            bx.cx().const_usize(0),
+            bx.cx().const_null(bx.cx().type_i8p()),
+            method,
+            location,
+        ], false);
+        let align = tcx.data_layout.pointer_align.abi;
+        let site = bx.cx().static_addr_of_mut(site, align, Some("virtual_call_site"));
+
+        let def_id = common::langcall(tcx, Some(span), "", lang_items::CountVirtualCallFnLangItem);
+        let llfn = bx.cx().get_fn(ty::Instance::mono(tcx, def_id));
+        bx.call(llfn, &[site], None);
+    }
+
+    /// Aborts, the way the target does for `intrinsics::abort`, instead of
+    /// calling a panic entry point. The pointer `payload` the entry point
+    /// would have been passed (the message and location, or just the location
+    /// for bounds checks and formatted panics) is kept in a stack slot so that
+    /// a debugger can still find it, without pulling in any of `core::fmt`.
+    fn codegen_minimal_panic(&mut self, bx: &mut Bx, payload: Bx::Value) {
+        let ptr_ty = bx.cx().type_i8p();
+        let align = bx.tcx().data_layout.pointer_align.abi;
+        let slot = bx.alloca(ptr_ty, "panic_payload", align);
+        let payload = bx.pointercast(payload, ptr_ty);
+        bx.store_with_flags(payload, slot, align, MemFlags::VOLATILE);
+        let abort = bx.cx().abort_fn();
+        bx.call(abort, &[], None);
+        bx.unreachable();
+    }
+
     fn codegen_terminator(
         &mut self,
         mut bx: Bx,
@@ -130,7 +219,7 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             llargs: &[Bx::Value],
             destination: Option<(ReturnDest<'tcx, Bx::Value>, mir::BasicBlock)>,
             cleanup: Option<mir::BasicBlock>
-        | {
+        | -> Bx::Value {
             if let Some(cleanup) = cleanup {
                 let ret_bx = if let Some((_, target)) = destination {
                     this.blocks[target]
@@ -149,6 +238,7 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     this.set_debug_loc(&mut ret_bx, terminator.source_info);
                     this.store_return(&mut ret_bx, ret_dest, &fn_ty.ret, invokeret);
                 }
+                invokeret
             } else {
                 let llret = bx.call(fn_ptr, &llargs, funclet(this));
                 bx.apply_attrs_callsite(&fn_ty, llret);
@@ -166,6 +256,7 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 } else {
                     bx.unreachable();
                 }
+                llret
             }
         };
 
@@ -196,7 +287,7 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
 
             mir::TerminatorKind::Abort => {
                 // Call core::intrinsics::abort()
-                let fnname = bx.cx().get_intrinsic(&("llvm.trap"));
+                let fnname = bx.cx().abort_fn();
                 bx.call(fnname, &[], None);
                 bx.unreachable();
             }
@@ -206,18 +297,33 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             }
 
             mir::TerminatorKind::SwitchInt { ref discr, switch_ty, ref values, ref targets } => {
+                let expected_discr = self.expected_value(&bx, bb, discr);
                 let discr = self.codegen_operand(&mut bx, discr);
                 if targets.len() == 2 {
                     // If there are two targets, emit br instead of switch
                     let lltrue = llblock(self, targets[0]);
                     let llfalse = llblock(self, targets[1]);
+                    // Whether we expect to branch to `targets[0]`, based on
+                    // which of the targets lead to a call to `cold_path`.
+                    let expect = match (self.is_cold_path(&bx, targets[0]),
+                                        self.is_cold_path(&bx, targets[1])) {
+                        (true, false) => Some(false),
+                        (false, true) => Some(true),
+                        _ => None,
+                    };
                     if switch_ty == bx.tcx().types.bool {
+                        // A `likely`/`unlikely` condition says which way to go,
+                        // unless a `cold_path` already did.
+                        let expect = expect.or_else(|| expected_discr.map(|value| {
+                            if let [0] = values[..] { !value } else { value }
+                        }));
                         // Don't generate trivial icmps when switching on bool
                         if let [0] = values[..] {
-                            bx.cond_br(discr.immediate(), llfalse, lltrue);
+                            bx.cond_br_with_expect(discr.immediate(), llfalse, lltrue,
+                                                   expect.map(|e| !e));
                         } else {
                             assert_eq!(&values[..], &[1]);
-                            bx.cond_br(discr.immediate(), lltrue, llfalse);
+                            bx.cond_br_with_expect(discr.immediate(), lltrue, llfalse, expect);
                         }
                     } else {
                         let switch_llty = bx.cx().immediate_backend_type(
@@ -225,8 +331,28 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                         );
                         let llval = bx.cx().const_uint_big(switch_llty, values[0]);
                         let cmp = bx.icmp(IntPredicate::IntEQ, discr.immediate(), llval);
-                        bx.cond_br(cmp, lltrue, llfalse);
+                        bx.cond_br_with_expect(cmp, lltrue, llfalse, expect);
                     }
+                } else if let Some((dest, table, align, target)) =
+                    self.switch_table(&mut bx, switch_ty, values, targets)
+                {
+                    // The `otherwise` arm is unreachable and the values are
+                    // dense, so `discr - values[0]` always indexes the table.
+                    let switch_llty = bx.cx().immediate_backend_type(
+                        bx.cx().layout_of(switch_ty)
+                    );
+                    let first = bx.cx().const_uint_big(switch_llty, values[0]);
+                    let index = bx.sub(discr.immediate(), first);
+                    let len = bx.cx().const_uint_big(switch_llty, values.len() as u128);
+                    let in_range = bx.icmp(IntPredicate::IntULT, index, len);
+                    base::call_assume(&mut bx, in_range);
+                    let index = bx.intcast(index, bx.cx().type_isize(), false);
+                    let zero = bx.cx().const_usize(0);
+                    let elem = bx.inbounds_gep(table, &[zero, index]);
+                    let val = bx.load(elem, align);
+                    let dest = self.codegen_place(&mut bx, dest);
+                    bx.store(val, dest.llval, dest.align);
+                    funclet_br(self, &mut bx, target);
                 } else {
                     let (otherwise, targets) = targets.split_last().unwrap();
                     let switch = bx.switch(discr.immediate(),
@@ -383,12 +509,8 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 // Get the location information.
                 let loc = bx.cx().sess().source_map().lookup_char_pos(span.lo());
                 let filename = Symbol::intern(&loc.file.name.to_string()).as_str();
-                let filename = bx.cx().const_str_slice(filename);
-                let line = bx.cx().const_u32(loc.line as u32);
-                let col = bx.cx().const_u32(loc.col.to_usize() as u32 + 1);
-                let align = tcx.data_layout.aggregate_align.abi
-                    .max(tcx.data_layout.i32_align.abi)
-                    .max(tcx.data_layout.pointer_align.abi);
+                let line = loc.line as u32;
+                let col = loc.col.to_usize() as u32 + 1;
 
                 // Put together the arguments to the panic entry point.
                 let (lang_item, args) = match *msg {
@@ -396,33 +518,25 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                         let len = self.codegen_operand(&mut bx, len).immediate();
                         let index = self.codegen_operand(&mut bx, index).immediate();
 
-                        let file_line_col = bx.cx().const_struct(&[filename, line, col], false);
-                        let file_line_col = bx.cx().static_addr_of(
-                            file_line_col,
-                            align,
-                            Some("panic_bounds_check_loc")
-                        );
+                        let file_line_col = bx.cx().panic_location(None, filename, line, col);
                         (lang_items::PanicBoundsCheckFnLangItem,
                          vec![file_line_col, index, len])
                     }
                     _ => {
                         let str = msg.description();
                         let msg_str = Symbol::intern(str).as_str();
-                        let msg_str = bx.cx().const_str_slice(msg_str);
-                        let msg_file_line_col = bx.cx().const_struct(
-                            &[msg_str, filename, line, col],
-                            false
-                        );
-                        let msg_file_line_col = bx.cx().static_addr_of(
-                            msg_file_line_col,
-                            align,
-                            Some("panic_loc")
-                        );
+                        let msg_file_line_col =
+                            bx.cx().panic_location(Some(msg_str), filename, line, col);
                         (lang_items::PanicFnLangItem,
                          vec![msg_file_line_col])
                     }
                 };
 
+                if self.minimal_panics(&bx) {
+                    self.codegen_minimal_panic(&mut bx, args[0]);
+                    return;
+                }
+
                 // Obtain the panic entry point.
                 let def_id = common::langcall(bx.tcx(), Some(span), "", lang_item);
                 let instance = ty::Instance::mono(bx.tcx(), def_id);
@@ -442,7 +556,8 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 ref args,
                 ref destination,
                 cleanup,
-                from_hir_call: _
+                from_hir_call: _,
+                inline,
             } => {
                 // Create the callee. This is a fn ptr or zero-sized and hence a kind of scalar.
                 let callee = self.codegen_operand(&mut bx, func);
@@ -476,6 +591,27 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 };
                 let intrinsic = intrinsic.as_ref().map(|s| &s[..]);
 
+                // Calls to the panic entry points the compiler itself emits
+                // are lowered the same way as failed assertions.
+                if let Some(ty::InstanceDef::Item(def_id)) = def {
+                    let lang_items = bx.tcx().lang_items();
+                    // `panic_fmt` takes the `fmt::Arguments` first, which are
+                    // dropped, and the location second.
+                    let payload_arg = if Some(def_id) == lang_items.panic_fn() ||
+                                         Some(def_id) == lang_items.panic_bounds_check_fn() {
+                        Some(0)
+                    } else if Some(def_id) == lang_items.panic_fmt() {
+                        Some(1)
+                    } else {
+                        None
+                    };
+                    if let (true, Some(i)) = (self.minimal_panics(&bx), payload_arg) {
+                        let payload = self.codegen_operand(&mut bx, &args[i]).immediate();
+                        self.codegen_minimal_panic(&mut bx, payload);
+                        return;
+                    }
+                }
+
                 if intrinsic == Some("transmute") {
                     if let Some(destination_ref) = destination.as_ref() {
                         let &(ref dest, target) = destination_ref;
@@ -510,38 +646,55 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                         funclet_br(self, &mut bx, target);
                         return;
                     }
+                    // The ABI of functions that enable target features may
+                    // depend on them, so it's computed from the instance.
+                    Some(ty::InstanceDef::Item(def_id))
+                        if extra_args.is_empty() &&
+                           !bx.tcx().codegen_fn_attrs(def_id).target_features.is_empty() => {
+                        bx.cx().fn_type_of_instance(instance.as_ref().unwrap())
+                    }
                     _ => bx.cx().new_fn_type(sig, &extra_args)
                 };
 
-                // emit a panic instead of instantiating an uninhabited type
-                if (intrinsic == Some("init") || intrinsic == Some("uninit")) &&
-                    fn_ty.ret.layout.abi.is_uninhabited()
-                {
+                // Emit a panic instead of instantiating an uninhabited type, and for
+                // the `assert_*` intrinsics whose assertion fails for this instance.
+                let panic_msg = match intrinsic {
+                    Some("init") | Some("uninit") if fn_ty.ret.layout.abi.is_uninhabited() => {
+                        Some(format!(
+                            "Attempted to instantiate uninhabited type {} using mem::{}",
+                            sig.output(),
+                            if intrinsic == Some("init") { "zeroed" } else { "uninitialized" }
+                        ))
+                    }
+                    Some("assert_inhabited") | Some("assert_zero_valid") => {
+                        let ty = instance.as_ref().unwrap().substs.type_at(0);
+                        let layout = bx.cx().layout_of(ty);
+                        if layout.abi.is_uninhabited() {
+                            Some(format!("attempted to instantiate uninhabited type `{}`", ty))
+                        } else if intrinsic == Some("assert_zero_valid") &&
+                                  !layout.might_permit_zero_init(bx.cx()) {
+                            Some(format!("attempted to zero-initialize type `{}`, which is invalid",
+                                         ty))
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                };
+                if let Some(str) = panic_msg {
                     let loc = bx.cx().sess().source_map().lookup_char_pos(span.lo());
                     let filename = Symbol::intern(&loc.file.name.to_string()).as_str();
-                    let filename = bx.cx().const_str_slice(filename);
-                    let line = bx.cx().const_u32(loc.line as u32);
-                    let col = bx.cx().const_u32(loc.col.to_usize() as u32 + 1);
-                    let align = tcx.data_layout.aggregate_align.abi
-                        .max(tcx.data_layout.i32_align.abi)
-                        .max(tcx.data_layout.pointer_align.abi);
-
-                    let str = format!(
-                        "Attempted to instantiate uninhabited type {} using mem::{}",
-                        sig.output(),
-                        if intrinsic == Some("init") { "zeroed" } else { "uninitialized" }
-                    );
+                    let line = loc.line as u32;
+                    let col = loc.col.to_usize() as u32 + 1;
+
                     let msg_str = Symbol::intern(&str).as_str();
-                    let msg_str = bx.cx().const_str_slice(msg_str);
-                    let msg_file_line_col = bx.cx().const_struct(
-                        &[msg_str, filename, line, col],
-                        false,
-                    );
-                    let msg_file_line_col = bx.cx().static_addr_of(
-                        msg_file_line_col,
-                        align,
-                        Some("panic_loc"),
-                    );
+                    let msg_file_line_col =
+                        bx.cx().panic_location(Some(msg_str), filename, line, col);
+
+                    if self.minimal_panics(&bx) {
+                        self.codegen_minimal_panic(&mut bx, msg_file_line_col);
+                        return;
+                    }
 
                     // Obtain the panic entry point.
                     let def_id =
@@ -638,6 +791,49 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                             }
                         }
 
+                        // The alignment passed to simd_{gather,scatter}_with_align
+                        // must be constant as well, which const-qualification
+                        // checks in the same way.
+                        if i == 3 && (intrinsic == Some("simd_gather_with_align") ||
+                                      intrinsic == Some("simd_scatter_with_align")) {
+                            let c = match *arg {
+                                mir::Operand::Copy(mir::Place::Promoted(box(index, _))) |
+                                mir::Operand::Move(mir::Place::Promoted(box(index, _))) => {
+                                    let param_env = ty::ParamEnv::reveal_all();
+                                    let cid = mir::interpret::GlobalId {
+                                        instance: self.instance,
+                                        promoted: Some(index),
+                                    };
+                                    bx.tcx().const_eval(param_env.and(cid))
+                                }
+                                mir::Operand::Copy(_) |
+                                mir::Operand::Move(_) => {
+                                    span_bug!(span, "alignment must be constant");
+                                }
+                                mir::Operand::Constant(ref constant) => {
+                                    self.eval_mir_constant(&bx, constant)
+                                }
+                            };
+                            let align = match c {
+                                Ok(c) => {
+                                    let param_env = ty::ParamEnv::reveal_all();
+                                    c.unwrap_bits(bx.tcx(), param_env.and(c.ty)) as u32
+                                }
+                                Err(_) => {
+                                    bx.tcx().sess.span_err(
+                                        terminator.source_info.span,
+                                        "could not evaluate the alignment at compile time",
+                                    );
+                                    // We've errored, so any valid alignment will do.
+                                    1
+                                }
+                            };
+                            return OperandRef {
+                                val: Immediate(bx.cx().const_u32(align)),
+                                layout: bx.cx().layout_of(bx.tcx().types.u32),
+                            };
+                        }
+
                         self.codegen_operand(&mut bx, arg)
                     }).collect();
 
@@ -670,7 +866,10 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 'make_args: for (i, arg) in first_args.iter().enumerate() {
                     let mut op = self.codegen_operand(&mut bx, arg);
 
-                    if let (0, Some(ty::InstanceDef::Virtual(_, idx))) = (i, def) {
+                    if let (0, Some(ty::InstanceDef::Virtual(method, idx))) = (i, def) {
+                        if bx.tcx().sess.opts.debugging_opts.count_virtual_calls {
+                            self.count_virtual_call(&mut bx, method, span);
+                        }
                         if let Pair(..) = op.val {
                             // In the case of Rc<Self>, we need to explicitly pass a
                             // *mut RcBox<Self> with a Scalar (not ScalarPair) ABI. This is a hack
@@ -737,15 +936,40 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                         &fn_ty.args[first_args.len()..])
                 }
 
-                let fn_ptr = match (llfn, instance) {
+                let mut fn_ptr = match (llfn, instance) {
                     (Some(llfn), _) => llfn,
                     (None, Some(instance)) => bx.cx().get_fn(instance),
                     _ => span_bug!(span, "no llfn for call"),
                 };
 
-                do_call(self, &mut bx, fn_ty, fn_ptr, &llargs,
-                        destination.as_ref().map(|&(_, target)| (ret_dest, target)),
-                        cleanup);
+                if let Some(method) = self.instrumented_allocator_method(&bx, def) {
+                    fn_ptr = bx.cx().instrumented_alloc_fn(method, fn_ptr);
+                    let callsite = self.allocation_callsite_id(&bx, span);
+                    llargs.push(bx.cx().const_u64(callsite));
+                }
+
+                let llret = do_call(self, &mut bx, fn_ty, fn_ptr, &llargs,
+                                    destination.as_ref().map(|&(_, target)| (ret_dest, target)),
+                                    cleanup);
+
+                match inline {
+                    InlineAttr::Always => bx.always_inline(llret),
+                    InlineAttr::Never => {
+                        // LLVM inlines an `alwaysinline` callee even where
+                        // the call site says `noinline`.
+                        if let Some(ty::InstanceDef::Item(def_id)) = def {
+                            if bx.tcx().codegen_fn_attrs(def_id).inline == InlineAttr::Always {
+                                bx.tcx().sess.span_err(
+                                    span,
+                                    "`#[inline(never)]` can't override the \
+                                     `#[inline(always)]` of the function it calls",
+                                );
+                            }
+                        }
+                        bx.do_not_inline(llret)
+                    }
+                    InlineAttr::Hint | InlineAttr::None => {}
+                }
             }
             mir::TerminatorKind::GeneratorDrop |
             mir::TerminatorKind::Yield { .. } => bug!("generator ops in codegen"),
@@ -754,6 +978,168 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         }
     }
 
+    /// Returns whether control flow entering `bb` is certain to reach a call
+    /// to the `cold_path` intrinsic, following blocks with a single successor.
+    fn is_cold_path(&self, bx: &Bx, mut bb: mir::BasicBlock) -> bool {
+        for _ in 0..self.mir.basic_blocks().len() {
+            match self.mir[bb].terminator().kind {
+                mir::TerminatorKind::Call { ref func, ref destination, .. } => {
+                    if let ty::FnDef(def_id, _) = func.ty(self.mir, bx.tcx()).sty {
+                        if bx.tcx().fn_sig(def_id).abi() == Abi::RustIntrinsic &&
+                           &bx.tcx().item_name(def_id).as_str()[..] == "cold_path" {
+                            return true;
+                        }
+                    }
+                    match *destination {
+                        Some((_, target)) => bb = target,
+                        None => return false,
+                    }
+                }
+                mir::TerminatorKind::Goto { target } |
+                mir::TerminatorKind::Drop { target, .. } => bb = target,
+                _ => return false,
+            }
+        }
+        false
+    }
+
+    /// Recognizes a `SwitchInt` over dense values with an unreachable
+    /// `otherwise`, where every arm only stores an integer constant to the
+    /// same local and jumps to the same block. That's what matching a C-like
+    /// enum to constants becomes, and it's cheaper done by loading from a
+    /// table of the constants than by branching.
+    ///
+    /// Returns the local, the table with its alignment, and the block the
+    /// arms jump to.
+    fn switch_table(
+        &mut self,
+        bx: &mut Bx,
+        switch_ty: Ty<'tcx>,
+        values: &[u128],
+        targets: &[mir::BasicBlock],
+    ) -> Option<(&'a mir::Place<'tcx>, Bx::Value, Align, mir::BasicBlock)> {
+        const MIN_TABLE_LEN: usize = 3;
+
+        let mir = self.mir;
+        let (&otherwise, targets) = targets.split_last()?;
+        if values.len() < MIN_TABLE_LEN || !mir[otherwise].statements.is_empty() {
+            return None;
+        }
+        match mir[otherwise].terminator().kind {
+            mir::TerminatorKind::Unreachable => {}
+            _ => return None,
+        }
+
+        let size = bx.cx().layout_of(switch_ty).size;
+        let mask = !0u128 >> (128 - size.bits());
+        let dense = values.iter().enumerate().all(|(i, &value)| {
+            value.wrapping_sub(values[0]) & mask == i as u128
+        });
+        if !dense {
+            return None;
+        }
+
+        let mut dest = None;
+        let mut next = None;
+        let mut constants = Vec::with_capacity(targets.len());
+        for &target in targets {
+            let data = &mir[target];
+            if data.statements.len() != 1 {
+                return None;
+            }
+            let (place, constant) = match data.statements[0].kind {
+                mir::StatementKind::Assign(ref place, ref rvalue) => match **rvalue {
+                    mir::Rvalue::Use(mir::Operand::Constant(ref constant)) => (place, constant),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            let target = match data.terminator().kind {
+                mir::TerminatorKind::Goto { target } => target,
+                _ => return None,
+            };
+            if *dest.get_or_insert(place) != place || *next.get_or_insert(target) != target {
+                return None;
+            }
+            constants.push(constant);
+        }
+        let dest = dest?;
+        match *dest {
+            mir::Place::Local(local) => match self.locals[local] {
+                LocalRef::Place(_) => {}
+                _ => return None,
+            },
+            _ => return None,
+        }
+
+        let layout = bx.cx().layout_of(self.monomorphize(&constants[0].ty));
+        match layout.ty.sty {
+            ty::Int(_) | ty::Uint(_) => {}
+            _ => return None,
+        }
+        let llty = bx.cx().immediate_backend_type(layout);
+        let mut elems = Vec::with_capacity(constants.len());
+        for constant in constants {
+            let bits = self.eval_mir_constant(bx, constant).ok()?
+                .assert_bits(bx.tcx(), ty::ParamEnv::reveal_all().and(layout.ty))?;
+            elems.push(bx.cx().const_uint_big(llty, bits));
+        }
+        let table = bx.cx().const_array(llty, &elems);
+        let align = layout.align.abi;
+        let table = bx.cx().static_addr_of(table, align, Some("switch_table"));
+        Some((dest, table, align, next?))
+    }
+
+    /// The value `discr`, switched on at the end of `bb`, is expected to have,
+    /// if it is the result of the `likely` or `unlikely` intrinsics.
+    ///
+    /// That is only known if the intrinsic is called by the only predecessor
+    /// of `bb` and `bb` doesn't overwrite its result before the switch.
+    fn expected_value(
+        &self,
+        bx: &Bx,
+        bb: mir::BasicBlock,
+        discr: &mir::Operand<'tcx>
+    ) -> Option<bool> {
+        let local = match *discr {
+            mir::Operand::Copy(mir::Place::Local(local)) |
+            mir::Operand::Move(mir::Place::Local(local)) => local,
+            _ => return None,
+        };
+        let reassigned = self.mir[bb].statements.iter().any(|statement| {
+            match statement.kind {
+                mir::StatementKind::Assign(ref place, _) => place.base_local() == Some(local),
+                _ => false,
+            }
+        });
+        if reassigned {
+            return None;
+        }
+        let predecessors = self.mir.predecessors_for(bb);
+        if predecessors.len() != 1 {
+            return None;
+        }
+        match self.mir[predecessors[0]].terminator().kind {
+            mir::TerminatorKind::Call {
+                ref func,
+                destination: Some((mir::Place::Local(dest), target)),
+                ..
+            } if dest == local && target == bb => {
+                if let ty::FnDef(def_id, _) = func.ty(self.mir, bx.tcx()).sty {
+                    if bx.tcx().fn_sig(def_id).abi() == Abi::RustIntrinsic {
+                        match &bx.tcx().item_name(def_id).as_str()[..] {
+                            "likely" => return Some(true),
+                            "unlikely" => return Some(false),
+                            _ => {}
+                        }
+                    }
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
     fn codegen_argument(
         &mut self,
         bx: &mut Bx,
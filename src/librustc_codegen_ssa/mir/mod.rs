@@ -210,7 +210,7 @@ pub fn codegen_mir<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
     instance: Instance<'tcx>,
     sig: ty::FnSig<'tcx>,
 ) {
-    let fn_ty = cx.new_fn_type(sig, &[]);
+    let fn_ty = cx.fn_type_of_instance(&instance);
     debug!("fn_ty: {:?}", fn_ty);
     let debug_context =
         cx.create_function_debug_context(instance, sig, llfn, mir);
@@ -364,6 +364,19 @@ pub fn codegen_mir<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
             }
         }
     }
+
+    // Now that all the branches back to them exist, attach the hints of
+    // `#[unroll]` and the like to the loops.
+    for (bb, data) in mir.basic_blocks().iter_enumerated() {
+        if !visited.contains(bb.index()) {
+            continue;
+        }
+        for statement in &data.statements {
+            if let mir::StatementKind::LoopHint(ref hints) = statement.kind {
+                bx.set_loop_hints(fx.blocks[bb], hints, statement.source_info.span);
+            }
+        }
+    }
 }
 
 fn create_funclets<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
@@ -675,5 +688,5 @@ mod block;
 pub mod constant;
 pub mod place;
 pub mod operand;
-mod rvalue;
+pub mod rvalue;
 mod statement;
@@ -153,6 +153,7 @@ impl<'a, 'tcx: 'a, V: CodegenObject> OperandRef<'tcx, V> {
             llextra,
             layout,
             align: layout.align.abi,
+            bit_field: None,
         }
     }
 
@@ -318,6 +319,18 @@ impl<'a, 'tcx: 'a, V: CodegenObject> OperandValue<V> {
         if dest.layout.is_zst() {
             return;
         }
+        if let Some(bit_field) = dest.bit_field {
+            let val = match self {
+                OperandValue::Immediate(s) => s,
+                OperandValue::Ref(r, None, source_align) => {
+                    let val = bx.load(r, source_align);
+                    base::to_immediate(bx, val, dest.layout)
+                }
+                _ => bug!("unexpected bit-field value {:?}", self)
+            };
+            dest.store_bit_field(bx, bit_field, val, flags);
+            return;
+        }
         match self {
             OperandValue::Ref(r, None, source_align) => {
                 base::memcpy_ty(bx, dest.llval, dest.align, r, source_align,
@@ -474,7 +487,7 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                         }
                         // Allow RalfJ to sleep soundly knowing that even refactorings that remove
                         // the above error (or silence it under some conditions) will not cause UB
-                        let fnname = bx.cx().get_intrinsic(&("llvm.trap"));
+                        let fnname = bx.cx().abort_fn();
                         bx.call(fnname, &[], None);
                         // We've errored, so we don't have to produce working code.
                         let layout = bx.cx().layout_of(ty);
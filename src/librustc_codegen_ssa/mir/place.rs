@@ -9,10 +9,11 @@
 // except according to those terms.
 
 use rustc::ty::{self, Ty};
-use rustc::ty::layout::{self, Align, TyLayout, LayoutOf, VariantIdx, HasTyCtxt};
+use rustc::ty::layout::{self, Align, BitField, TyLayout, LayoutOf, VariantIdx, HasTyCtxt};
 use rustc::mir;
 use rustc::mir::tcx::PlaceTy;
 use MemFlags;
+use base;
 use common::IntPredicate;
 use glue;
 
@@ -34,6 +35,10 @@ pub struct PlaceRef<'tcx, V> {
 
     /// What alignment we know for this place
     pub align: Align,
+
+    /// Where the bits of this place are relative to `llval`, if it's a
+    /// bit-field, which can only be loaded and stored through its storage.
+    pub bit_field: Option<BitField>,
 }
 
 impl<'a, 'tcx: 'a, V: CodegenObject> PlaceRef<'tcx, V> {
@@ -47,7 +52,8 @@ impl<'a, 'tcx: 'a, V: CodegenObject> PlaceRef<'tcx, V> {
             llval,
             llextra: None,
             layout,
-            align
+            align,
+            bit_field: None,
         }
     }
 
@@ -124,9 +130,25 @@ impl<'a, 'tcx: 'a, V: CodegenObject> PlaceRef<'tcx, V> {
                 },
                 layout: field,
                 align: effective_field_align,
+                bit_field: None,
             }
         };
 
+        // Fields of structs with bit-fields can share bytes, so there's no
+        // LLVM field for them; address them by their byte offsets instead.
+        if self.layout.fields.has_bit_fields() {
+            let byte_ptr = bx.pointercast(self.llval, bx.cx().type_i8p());
+            let byte_ptr = bx.inbounds_gep(byte_ptr, &[bx.cx().const_usize(offset.bytes())]);
+            let ll_fty = bx.cx().backend_type(field);
+            return PlaceRef {
+                llval: bx.pointercast(byte_ptr, bx.cx().type_ptr_to(ll_fty)),
+                llextra: None,
+                layout: field,
+                align: effective_field_align,
+                bit_field: self.layout.fields.bit_field(ix),
+            };
+        }
+
         // Simple cases, which don't need DST adjustment:
         //   * no metadata available - just log the case
         //   * known alignment - sized types, [T], str or a foreign type
@@ -198,6 +220,7 @@ impl<'a, 'tcx: 'a, V: CodegenObject> PlaceRef<'tcx, V> {
             llextra: self.llextra,
             layout: field,
             align: effective_field_align,
+            bit_field: None,
         }
     }
 
@@ -272,6 +295,56 @@ impl<'a, 'tcx: 'a, V: CodegenObject> PlaceRef<'tcx, V> {
         }
     }
 
+    /// Obtain the tag as stored in memory, without translating niche values
+    /// into discriminants. Layouts that don't store a tag give the same result
+    /// as `codegen_get_discr`.
+    pub fn codegen_get_raw_discr<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
+        self,
+        bx: &mut Bx,
+        cast_to: Ty<'tcx>
+    ) -> V {
+        if let layout::Variants::Single { .. } = self.layout.variants {
+            return self.codegen_get_discr(bx, cast_to);
+        }
+        let cast_to = bx.cx().immediate_backend_type(bx.cx().layout_of(cast_to));
+        if self.layout.abi.is_uninhabited() {
+            return bx.cx().const_undef(cast_to);
+        }
+
+        let discr = self.project_field(bx, 0);
+        let lldiscr = bx.load_operand(discr).immediate();
+        let lldiscr = match discr.layout.abi {
+            layout::Abi::Scalar(ref scalar) if scalar.value == layout::Pointer => {
+                bx.ptrtoint(lldiscr, bx.cx().type_isize())
+            }
+            _ => lldiscr,
+        };
+        bx.intcast(lldiscr, cast_to, false)
+    }
+
+    /// Store `tag` as the raw tag, truncating it to the tag's size; the
+    /// inverse of `codegen_get_raw_discr`. Does nothing if no tag is stored.
+    pub fn codegen_set_raw_discr<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
+        &self,
+        bx: &mut Bx,
+        tag: V
+    ) {
+        if let layout::Variants::Single { .. } = self.layout.variants {
+            return;
+        }
+
+        let discr = self.project_field(bx, 0);
+        let discr_llty = bx.cx().immediate_backend_type(discr.layout);
+        let tag = match discr.layout.abi {
+            layout::Abi::Scalar(ref scalar) if scalar.value == layout::Pointer => {
+                let tag = bx.intcast(tag, bx.cx().type_isize(), false);
+                bx.inttoptr(tag, discr_llty)
+            }
+            _ => bx.intcast(tag, discr_llty, false),
+        };
+        OperandValue::Immediate(tag).store(bx, discr);
+    }
+
     /// Set the discriminant for a new value of the given case of the given
     /// representation.
     pub fn codegen_set_discr<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
@@ -330,6 +403,72 @@ impl<'a, 'tcx: 'a, V: CodegenObject> PlaceRef<'tcx, V> {
         }
     }
 
+    /// Whether this place is a signed integer, which bit-fields are
+    /// sign-extended for.
+    fn is_signed_int(&self) -> bool {
+        match self.layout.abi {
+            layout::Abi::Scalar(ref scalar) => match scalar.value {
+                layout::Int(_, signed) => signed,
+                _ => false
+            },
+            _ => false
+        }
+    }
+
+    /// Load the value of a bit-field place, as an immediate.
+    pub fn load_bit_field<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
+        &self,
+        bx: &mut Bx,
+        bit_field: BitField,
+    ) -> V {
+        let storage_bits = bit_field.storage.bits();
+        let storage_ty = bx.cx().type_ix(storage_bits);
+        let llptr = bx.pointercast(self.llval, bx.cx().type_ptr_to(storage_ty));
+        let storage = bx.load(llptr, self.align);
+
+        // Move the field to the top of the storage, then back down to the
+        // bottom, which sign-extends it if it's signed.
+        let top = storage_bits - bit_field.shift - bit_field.width;
+        let storage = bx.shl(storage, bx.cx().const_uint(storage_ty, top));
+        let signed = self.is_signed_int();
+        let down = bx.cx().const_uint(storage_ty, storage_bits - bit_field.width);
+        let value = if signed {
+            bx.ashr(storage, down)
+        } else {
+            bx.lshr(storage, down)
+        };
+        let value = bx.intcast(value, bx.cx().backend_type(self.layout), signed);
+        base::to_immediate(bx, value, self.layout)
+    }
+
+    /// Store an immediate into a bit-field place, keeping the bits of its
+    /// storage that belong to other fields.
+    pub fn store_bit_field<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
+        &self,
+        bx: &mut Bx,
+        bit_field: BitField,
+        value: V,
+        flags: MemFlags,
+    ) {
+        let storage_bits = bit_field.storage.bits();
+        let storage_ty = bx.cx().type_ix(storage_bits);
+        let llptr = bx.pointercast(self.llval, bx.cx().type_ptr_to(storage_ty));
+        let storage = bx.load(llptr, self.align);
+
+        let value = base::from_immediate(bx, value);
+        let value = bx.intcast(value, storage_ty, false);
+        let shift = bx.cx().const_uint(storage_ty, bit_field.shift);
+        let ones = bx.cx().const_int(storage_ty, -1);
+        let mask = bx.lshr(ones, bx.cx().const_uint(storage_ty, storage_bits - bit_field.width));
+        let value = bx.and(value, mask);
+        let value = bx.shl(value, shift);
+        let mask = bx.shl(mask, shift);
+        let mask = bx.not(mask);
+        let storage = bx.and(storage, mask);
+        let storage = bx.or(storage, value);
+        bx.store_with_flags(storage, llptr, self.align, flags);
+    }
+
     pub fn project_index<Bx: BuilderMethods<'a, 'tcx, Value = V>>(
         &self,
         bx: &mut Bx,
@@ -339,7 +478,8 @@ impl<'a, 'tcx: 'a, V: CodegenObject> PlaceRef<'tcx, V> {
             llval: bx.inbounds_gep(self.llval, &[bx.cx().const_usize(0), llindex]),
             llextra: None,
             layout: self.layout.field(bx.cx(), 0),
-            align: self.align
+            align: self.align,
+            bit_field: None,
         }
     }
 
@@ -413,7 +553,7 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                         // and compile-time agree on values
                         // With floats that won't always be true
                         // so we generate an abort
-                        let fnname = bx.cx().get_intrinsic(&("llvm.trap"));
+                        let fnname = bx.cx().abort_fn();
                         bx.call(fnname, &[], None);
                         let llval = bx.cx().const_undef(
                             bx.cx().type_ptr_to(bx.cx().backend_type(layout))
@@ -424,7 +564,13 @@ impl<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             }
             mir::Place::Static(box mir::Static { def_id, ty }) => {
                 let layout = cx.layout_of(self.monomorphize(&ty));
-                PlaceRef::new_sized(cx.get_static(def_id), layout, layout.align.abi)
+                // Statics placed in another address space are accessed
+                // through a pointer cast to the generic one.
+                let llval = cx.static_ptrcast(
+                    cx.get_static(def_id),
+                    cx.type_ptr_to(cx.backend_type(layout)),
+                );
+                PlaceRef::new_sized(llval, layout, layout.align.abi)
             },
             mir::Place::Projection(box mir::Projection {
                 ref base,
@@ -19,7 +19,7 @@ use std::{u128, i128};
 use base;
 use MemFlags;
 use callee;
-use common::{self, RealPredicate, IntPredicate};
+use common::{self, RealPredicate, IntPredicate, TypeKind};
 use rustc_mir::monomorphize;
 
 use traits::*;
@@ -870,8 +870,37 @@ fn cast_float_to_int<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
         return fptosui_result;
     }
 
-    let int_width = bx.cx().int_width(int_ty);
-    let float_width = bx.cx().float_width(float_ty);
+    saturate_float_to_int(bx, signed, x, fptosui_result, float_ty, int_ty)
+}
+
+/// Turns the result `fptosui_result` of an `fpto[su]i` of `x` into that of a
+/// saturating conversion, as defined for Rust's `as` casts. `float_ty` and
+/// `int_ty` can also be vector types, in which case all the elements are
+/// converted independently.
+pub fn saturate_float_to_int<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
+    bx: &mut Bx,
+    signed: bool,
+    x: Bx::Value,
+    fptosui_result: Bx::Value,
+    float_ty: Bx::Type,
+    int_ty: Bx::Type
+) -> Bx::Value {
+    // For vectors, the bounds are computed on the elements and then splatted.
+    let vector_len = match bx.cx().type_kind(float_ty) {
+        TypeKind::Vector => Some(bx.cx().vector_length(float_ty)),
+        _ => None,
+    };
+    let (float_elem_ty, int_elem_ty) = match vector_len {
+        Some(_) => (bx.cx().element_type(float_ty), bx.cx().element_type(int_ty)),
+        None => (float_ty, int_ty),
+    };
+    let splat = |bx: &mut Bx, elem: Bx::Value| match vector_len {
+        Some(len) => bx.cx().const_vector(&vec![elem; len]),
+        None => elem,
+    };
+
+    let int_width = bx.cx().int_width(int_elem_ty);
+    let float_width = bx.cx().float_width(float_elem_ty);
     // LLVM's fpto[su]i returns undef when the input x is infinite, NaN, or does not fit into the
     // destination integer type after rounding towards zero. This `undef` value can cause UB in
     // safe code (see issue #10184), so we implement a saturating conversion on top of it:
@@ -930,6 +959,7 @@ fn cast_float_to_int<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
             64 => bx.cx().const_u64(bits as u64),
             n => bug!("unsupported float width {}", n),
         };
+        let bits_llval = splat(bx, bits_llval);
         bx.bitcast(bits_llval, float_ty)
     };
     let (f_min, f_max) = match float_width {
@@ -982,8 +1012,10 @@ fn cast_float_to_int<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
     // performed is ultimately up to the backend, but at least x86 does perform them.
     let less_or_nan = bx.fcmp(RealPredicate::RealULT, x, f_min);
     let greater = bx.fcmp(RealPredicate::RealOGT, x, f_max);
-    let int_max = bx.cx().const_uint_big(int_ty, int_max(signed, int_width));
-    let int_min = bx.cx().const_uint_big(int_ty, int_min(signed, int_width) as u128);
+    let int_max = bx.cx().const_uint_big(int_elem_ty, int_max(signed, int_width));
+    let int_max = splat(bx, int_max);
+    let int_min = bx.cx().const_uint_big(int_elem_ty, int_min(signed, int_width) as u128);
+    let int_min = splat(bx, int_min);
     let s0 = bx.select(less_or_nan, int_min, fptosui_result);
     let s1 = bx.select(greater, int_max, s0);
 
@@ -992,7 +1024,8 @@ fn cast_float_to_int<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
     // Therefore we only need to execute this step for signed integer types.
     if signed {
         // LLVM has no isNaN predicate, so we use (x == x) instead
-        let zero = bx.cx().const_uint(int_ty, 0);
+        let zero = bx.cx().const_uint(int_elem_ty, 0);
+        let zero = splat(bx, zero);
         let cmp = bx.fcmp(RealPredicate::RealOEQ, x, x);
         bx.select(cmp, s1, zero)
     } else {
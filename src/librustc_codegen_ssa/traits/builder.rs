@@ -17,6 +17,7 @@ use super::HasCodegen;
 use common::{AtomicOrdering, AtomicRmwBinOp, IntPredicate, RealPredicate, SynchronizationScope};
 use mir::operand::OperandRef;
 use mir::place::PlaceRef;
+use rustc::mir::LoopHints;
 use rustc::ty::layout::{Align, Size};
 use std::ffi::CStr;
 use MemFlags;
@@ -24,6 +25,7 @@ use MemFlags;
 use std::borrow::Cow;
 use std::ops::Range;
 use syntax::ast::AsmDialect;
+use syntax_pos::Span;
 
 pub trait BuilderMethods<'a, 'tcx: 'a>:
     HasCodegen<'tcx>
@@ -53,6 +55,15 @@ pub trait BuilderMethods<'a, 'tcx: 'a>:
         then_llbb: Self::BasicBlock,
         else_llbb: Self::BasicBlock,
     );
+    /// Like `cond_br`, but if `expect` is `Some`, records that `cond` is
+    /// expected to have that value.
+    fn cond_br_with_expect(
+        &mut self,
+        cond: Self::Value,
+        then_llbb: Self::BasicBlock,
+        else_llbb: Self::BasicBlock,
+        expect: Option<bool>,
+    );
     fn switch(
         &mut self,
         v: Self::Value,
@@ -222,6 +233,8 @@ pub trait BuilderMethods<'a, 'tcx: 'a>:
         mask: Self::Value,
     ) -> Self::Value;
     fn vector_splat(&mut self, num_elts: usize, elt: Self::Value) -> Self::Value;
+    fn vector_reduce_fadd(&mut self, acc: Self::Value, src: Self::Value) -> Self::Value;
+    fn vector_reduce_fmul(&mut self, acc: Self::Value, src: Self::Value) -> Self::Value;
     fn vector_reduce_fadd_fast(&mut self, acc: Self::Value, src: Self::Value) -> Self::Value;
     fn vector_reduce_fmul_fast(&mut self, acc: Self::Value, src: Self::Value) -> Self::Value;
     fn vector_reduce_add(&mut self, src: Self::Value) -> Self::Value;
@@ -319,5 +332,9 @@ pub trait BuilderMethods<'a, 'tcx: 'a>:
     fn zext(&mut self, val: Self::Value, dest_ty: Self::Type) -> Self::Value;
 
     unsafe fn delete_basic_block(&mut self, bb: Self::BasicBlock);
+    /// Attaches `hints` to the branches back to the loop header `header`.
+    /// Hints the backend can't honor are dropped with a warning at `span`.
+    fn set_loop_hints(&mut self, header: Self::BasicBlock, hints: &LoopHints, span: Span);
     fn do_not_inline(&mut self, llret: Self::Value);
+    fn always_inline(&mut self, llret: Self::Value);
 }
@@ -23,6 +23,7 @@ pub trait ConstMethods<'tcx>: Backend<'tcx> {
     fn const_int(&self, t: Self::Type, i: i64) -> Self::Value;
     fn const_uint(&self, t: Self::Type, i: u64) -> Self::Value;
     fn const_uint_big(&self, t: Self::Type, u: u128) -> Self::Value;
+    fn const_real(&self, t: Self::Type, val: f64) -> Self::Value;
     fn const_bool(&self, val: bool) -> Self::Value;
     fn const_i32(&self, i: i32) -> Self::Value;
     fn const_u32(&self, i: u32) -> Self::Value;
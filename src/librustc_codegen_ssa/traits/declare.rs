@@ -13,6 +13,7 @@ use rustc::hir::def_id::DefId;
 use rustc::mir::mono::{Linkage, Visibility};
 use rustc::ty;
 use rustc_mir::monomorphize::Instance;
+use syntax::symbol::Symbol;
 
 pub trait DeclareMethods<'tcx>: Backend<'tcx> {
     /// Declare a global value.
@@ -86,4 +87,22 @@ pub trait PreDefineMethods<'tcx>: Backend<'tcx> {
         visibility: Visibility,
         symbol_name: &str,
     );
+
+    /// Declares the internal copy of a `#[multiversion]` function compiled
+    /// with `target` enabled, `"default"` meaning no extra features.
+    fn predefine_multiversion_variant(
+        &self,
+        instance: Instance<'tcx>,
+        target: Symbol,
+    ) -> Self::Value;
+
+    /// Defines `llfn` as forwarding its arguments to the first of `variants`
+    /// the running CPU supports. The choice is made on the first call and
+    /// cached afterwards.
+    fn define_multiversion_dispatcher(
+        &self,
+        instance: Instance<'tcx>,
+        llfn: Self::Value,
+        variants: &[(Symbol, Self::Value)],
+    );
 }
@@ -28,6 +28,12 @@ pub trait MiscMethods<'tcx>: Backend<'tcx> {
     fn get_param(&self, llfn: Self::Value, index: c_uint) -> Self::Value;
     fn eh_personality(&self) -> Self::Value;
     fn eh_unwind_resume(&self) -> Self::Value;
+    /// The function to call to abort, as chosen by the target's `abort_strategy`.
+    fn abort_fn(&self) -> Self::Value;
+    /// The wrapper `-Z instrument-allocators` routes calls to the allocator
+    /// function `callee`, for the allocator method `method`, through. It takes
+    /// the same arguments followed by a `u64` call-site ID.
+    fn instrumented_alloc_fn(&self, method: &str, callee: Self::Value) -> Self::Value;
     fn sess(&self) -> &Session;
     fn stats(&self) -> &RefCell<Stats>;
     fn consume_stats(self) -> RefCell<Stats>;
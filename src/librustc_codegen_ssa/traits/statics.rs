@@ -11,12 +11,23 @@
 use super::Backend;
 use rustc::hir::def_id::DefId;
 use rustc::ty::layout::Align;
+use syntax::symbol::LocalInternedString;
 
 pub trait StaticMethods<'tcx>: Backend<'tcx> {
     fn static_ptrcast(&self, val: Self::Value, ty: Self::Type) -> Self::Value;
     fn static_bitcast(&self, val: Self::Value, ty: Self::Type) -> Self::Value;
     fn static_addr_of_mut(&self, cv: Self::Value, align: Align, kind: Option<&str>) -> Self::Value;
     fn static_addr_of(&self, cv: Self::Value, align: Align, kind: Option<&str>) -> Self::Value;
+    /// Returns a pointer to the `(&'static str, u32, u32)` file, line and
+    /// column of a panic, preceded by the `&'static str` message if there is
+    /// one, as the panic lang items take them.
+    fn panic_location(
+        &self,
+        msg: Option<LocalInternedString>,
+        file: LocalInternedString,
+        line: u32,
+        col: u32,
+    ) -> Self::Value;
     fn get_static(&self, def_id: DefId) -> Self::Value;
     fn codegen_static(&self, def_id: DefId, is_mutable: bool);
     unsafe fn static_replace_all_uses(&self, old_g: Self::Value, new_g: Self::Value);
@@ -44,6 +44,8 @@ pub trait BaseTypeMethods<'tcx>: Backend<'tcx> {
     fn type_named_struct(&self, name: &str) -> Self::Type;
     fn type_array(&self, ty: Self::Type, len: u64) -> Self::Type;
     fn type_vector(&self, ty: Self::Type, len: u64) -> Self::Type;
+    /// A vector of `min_len * vscale` elements, where `vscale` is only known at runtime.
+    fn type_scalable_vector(&self, ty: Self::Type, min_len: u64) -> Self::Type;
     fn type_kind(&self, ty: Self::Type) -> TypeKind;
     fn set_struct_body(&self, ty: Self::Type, els: &[Self::Type], packed: bool);
     fn type_ptr_to(&self, ty: Self::Type) -> Self::Type;
@@ -85,6 +85,13 @@
 //!   with each other. This facility is mainly intended to be used by build
 //!   tools like Cargo.
 //!
+//! - Finally, a crate compiled with `-Z symbol-prefix=<prefix>` gets `<prefix>`
+//!   pushed as the outermost path component of all of its mangled symbol
+//!   names. Since the prefix is stored in the crate's metadata, downstream
+//!   crates compute the very same names. This makes it possible to link
+//!   several copies of the same staticlib into a single binary, something the
+//!   disambiguator alone cannot do since it is fixed by the build tool.
+//!
 //! A note on symbol name stability
 //! -------------------------------
 //! Previous versions of the compiler resorted to feeding NodeIds into the
@@ -232,6 +239,11 @@ fn get_symbol_hash<'a, 'tcx>(
 
 fn def_symbol_name<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId) -> ty::SymbolName {
     let mut buffer = SymbolPathBuffer::new();
+    // The prefix is recorded in the defining crate's metadata, so every crate
+    // referring to this item computes the same name for it.
+    if let Some(prefix) = tcx.symbol_prefix(def_id.krate) {
+        buffer.push(&prefix.as_str());
+    }
     item_path::with_forced_absolute_paths(|| {
         tcx.push_item_path(&mut buffer, def_id, false);
     });
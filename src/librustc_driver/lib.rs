@@ -118,6 +118,7 @@ use std::thread;
 use syntax::ast;
 use syntax::source_map::{SourceMap, FileLoader, RealFileLoader};
 use syntax::feature_gate::{GatedCfg, UnstableFeatures};
+use syntax::symbol::Symbol;
 use syntax::parse::{self, PResult};
 use syntax_pos::{DUMMY_SP, MultiSpan, FileName};
 
@@ -145,11 +146,29 @@ pub mod target_features {
                              codegen_backend: &dyn CodegenBackend) {
         let tf = Symbol::intern("target_feature");
 
-        cfg.extend(codegen_backend.target_features(sess).into_iter().map(|feat| (tf, Some(feat))));
+        let features = codegen_backend.target_features(sess);
+        // `cmpxchg16b` gives x86_64 128-bit atomics, which its baseline CPU
+        // doesn't have. This only affects crates built with the feature: the
+        // prebuilt libcore is built without it, so has no `AtomicU128`, and
+        // the atomic intrinsics are the only way to reach them.
+        let has_cmpxchg16b = sess.target.target.arch == "x86_64" &&
+            features.iter().any(|feat| *feat == "cmpxchg16b");
+        cfg.extend(features.into_iter().map(|feat| (tf, Some(feat))));
+
+        if has_cmpxchg16b {
+            cfg.insert((Symbol::intern("target_has_atomic"), Some(Symbol::intern("128"))));
+        }
 
         if sess.crt_static_feature() {
             cfg.insert((tf, Some(Symbol::intern("crt-static"))));
         }
+
+        let mut known_target_features = sess.parse_sess.known_target_features.borrow_mut();
+        known_target_features.extend(codegen_backend.known_target_features());
+        // `crt-static` isn't a codegen feature, but is asked for like one.
+        if !known_target_features.is_empty() {
+            known_target_features.insert(Symbol::intern("crt-static"));
+        }
     }
 }
 
@@ -1104,6 +1123,23 @@ impl RustcDefaultCalls {
                         println!("{}", cfg);
                     }
                 }
+                EnabledFeatures => {
+                    // These are the features `cfg(target_feature)` is set for, i.e. those
+                    // implied by the target CPU and `-C target-feature` that codegen assumes.
+                    let tf = Symbol::intern("target_feature");
+                    let mut features = sess.parse_sess.config.iter().filter_map(|&(name, value)| {
+                        if name == tf {
+                            value.map(|value| value.to_string())
+                        } else {
+                            None
+                        }
+                    }).collect::<Vec<String>>();
+
+                    features.sort();
+                    for feature in features {
+                        println!("{}", feature);
+                    }
+                }
                 RelocationModels | CodeModels | TlsModels | TargetCPUs | TargetFeatures => {
                     codegen_backend.print(*req, sess);
                 }
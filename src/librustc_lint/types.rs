@@ -11,6 +11,7 @@
 #![allow(non_snake_case)]
 
 use rustc::hir::Node;
+use rustc::session::Session;
 use rustc::ty::subst::Substs;
 use rustc::ty::{self, AdtKind, ParamEnv, Ty, TyCtxt};
 use rustc::ty::layout::{self, IntegerExt, LayoutOf, VariantIdx};
@@ -427,6 +428,21 @@ declare_lint! {
     "proper use of libc types in foreign modules"
 }
 
+declare_lint! {
+    I128_FFI_ABI_CHANGE,
+    Allow,
+    "128-bit integers in foreign modules, whose ABI changed to match C's `__int128`"
+}
+
+/// Whether `i128` and `u128` are laid out and passed like `__int128` on this
+/// target, which makes them FFI-safe.
+fn target_matches_c_int128(sess: &Session) -> bool {
+    match &sess.target.target.arch[..] {
+        "x86_64" | "aarch64" => true,
+        _ => false,
+    }
+}
+
 struct ImproperCTypesVisitor<'a, 'tcx: 'a> {
     cx: &'a LateContext<'a, 'tcx>,
 }
@@ -645,7 +661,8 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
                 help: Some("consider using `u32` or `libc::wchar_t` instead"),
             },
 
-            ty::Int(ast::IntTy::I128) | ty::Uint(ast::UintTy::U128) => FfiUnsafe {
+            ty::Int(ast::IntTy::I128) | ty::Uint(ast::UintTy::U128)
+                if !target_matches_c_int128(cx.sess) => FfiUnsafe {
                 ty: ty,
                 reason: "128-bit integers don't currently have a known stable ABI",
                 help: None,
@@ -740,8 +757,24 @@ impl<'a, 'tcx> ImproperCTypesVisitor<'a, 'tcx> {
         // any generic types right now:
         let ty = self.cx.tcx.normalize_erasing_regions(ParamEnv::reveal_all(), ty);
 
-        match self.check_type_for_ffi(&mut FxHashSet::default(), ty) {
-            FfiResult::FfiSafe => {}
+        let mut cache = FxHashSet::default();
+        match self.check_type_for_ffi(&mut cache, ty) {
+            FfiResult::FfiSafe => {
+                // Before 128-bit integers were made to match `__int128`, they
+                // were only 8-byte aligned on x86_64 and returned indirectly
+                // on Windows, so code built by older compilers disagrees.
+                if self.cx.sess().target.target.arch == "x86_64" {
+                    let types = &self.cx.tcx.types;
+                    let int128 = [types.i128, types.u128].iter().find(|ty| cache.contains(*ty));
+                    if let Some(int128) = int128 {
+                        self.cx.span_lint(I128_FFI_ABI_CHANGE,
+                                          sp,
+                                          &format!("`extern` block uses type `{}`, whose ABI \
+                                                    differs from older versions of rustc",
+                                                   int128));
+                    }
+                }
+            }
             FfiResult::FfiPhantom(ty) => {
                 self.cx.span_lint(IMPROPER_CTYPES,
                                   sp,
@@ -794,7 +827,7 @@ pub struct ImproperCTypes;
 
 impl LintPass for ImproperCTypes {
     fn get_lints(&self) -> LintArray {
-        lint_array!(IMPROPER_CTYPES)
+        lint_array!(IMPROPER_CTYPES, I128_FFI_ABI_CHANGE)
     }
 }
 
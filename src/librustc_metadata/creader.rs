@@ -173,9 +173,16 @@ impl<'a> CrateLoader<'a> {
     fn verify_no_symbol_conflicts(&self,
                                   span: Span,
                                   root: &CrateRoot) {
+        // Crates with different symbol prefixes never produce conflicting
+        // symbols, regardless of their names and disambiguators.
+        let local_symbol_prefix = self.sess.opts.debugging_opts.symbol_prefix
+            .as_ref()
+            .map(|prefix| Symbol::intern(prefix));
+
         // Check for (potential) conflicts with the local crate
         if self.local_crate_name == root.name &&
-           self.sess.local_crate_disambiguator() == root.disambiguator {
+           self.sess.local_crate_disambiguator() == root.disambiguator &&
+           local_symbol_prefix == root.symbol_prefix {
             span_fatal!(self.sess, span, E0519,
                         "the current crate is indistinguishable from one of its \
                          dependencies: it has the same crate-name `{}` and was \
@@ -188,6 +195,7 @@ impl<'a> CrateLoader<'a> {
         self.cstore.iter_crate_data(|_, other| {
             if other.root.name == root.name && // same crate-name
                other.root.disambiguator == root.disambiguator &&  // same crate-disambiguator
+               other.root.symbol_prefix == root.symbol_prefix && // same symbol prefix
                other.root.hash != root.hash { // but different SVH
                 span_fatal!(self.sess, span, E0523,
                         "found two different crates with name `{}` that are \
@@ -213,6 +213,7 @@ provide! { <'tcx> tcx, def_id, other, cdata,
     original_crate_name => { cdata.root.name }
 
     extra_filename => { cdata.root.extra_filename.clone() }
+    symbol_prefix => { cdata.root.symbol_prefix }
 
 
     implementations_of_trait => {
@@ -272,7 +273,8 @@ pub fn provide<'tcx>(providers: &mut Providers<'tcx>) {
         is_statically_included_foreign_item: |tcx, id| {
             match tcx.native_library_kind(id) {
                 Some(NativeLibraryKind::NativeStatic) |
-                Some(NativeLibraryKind::NativeStaticNobundle) => true,
+                Some(NativeLibraryKind::NativeStaticNobundle) |
+                Some(NativeLibraryKind::NativeBitcode) => true,
                 _ => false,
             }
         },
@@ -589,11 +589,12 @@ impl<'cx, 'gcx, 'tcx> DataflowResultsConsumer<'cx, 'tcx> for MirBorrowckCtxt<'cx
             }
             StatementKind::Nop
             | StatementKind::AscribeUserType(..)
+            | StatementKind::LoopHint(_)
             | StatementKind::Retag { .. }
             | StatementKind::EscapeToRaw { .. }
             | StatementKind::StorageLive(..) => {
-                // `Nop`, `AscribeUserType`, `Retag`, and `StorageLive` are irrelevant
-                // to borrow check.
+                // `Nop`, `AscribeUserType`, `LoopHint`, `Retag`, and `StorageLive` are
+                // irrelevant to borrow check.
             }
             StatementKind::StorageDead(local) => {
                 self.access_place(
@@ -687,6 +688,7 @@ impl<'cx, 'gcx, 'tcx> DataflowResultsConsumer<'cx, 'tcx> for MirBorrowckCtxt<'cx
                 ref destination,
                 cleanup: _,
                 from_hir_call: _,
+                inline: _,
             } => {
                 self.consume_operand(ContextKind::CallOperator.new(loc), (func, span), flow_state);
                 for arg in args {
@@ -134,11 +134,12 @@ impl<'cx, 'tcx, 'gcx> Visitor<'tcx> for InvalidationGenerator<'cx, 'tcx, 'gcx> {
             }
             StatementKind::Nop |
             StatementKind::AscribeUserType(..) |
+            StatementKind::LoopHint(_) |
             StatementKind::Retag { .. } |
             StatementKind::EscapeToRaw { .. } |
             StatementKind::StorageLive(..) => {
-                // `Nop`, `AscribeUserType`, `Retag`, and `StorageLive` are irrelevant
-                // to borrow check.
+                // `Nop`, `AscribeUserType`, `LoopHint`, `Retag`, and `StorageLive` are
+                // irrelevant to borrow check.
             }
             StatementKind::StorageDead(local) => {
                 self.access_place(
@@ -203,6 +204,7 @@ impl<'cx, 'tcx, 'gcx> Visitor<'tcx> for InvalidationGenerator<'cx, 'tcx, 'gcx> {
                 ref destination,
                 cleanup: _,
                 from_hir_call: _,
+                inline: _,
             } => {
                 self.consume_operand(ContextKind::CallOperator.new(location), func);
                 for arg in args {
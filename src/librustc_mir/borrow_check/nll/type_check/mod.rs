@@ -1316,6 +1316,7 @@ impl<'a, 'gcx, 'tcx> TypeChecker<'a, 'gcx, 'tcx> {
             | StatementKind::InlineAsm { .. }
             | StatementKind::Retag { .. }
             | StatementKind::EscapeToRaw { .. }
+            | StatementKind::LoopHint(_)
             | StatementKind::Nop => {}
         }
     }
@@ -195,6 +195,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
             ExprKind::Loop {
                 condition: opt_cond_expr,
                 body,
+                hints,
             } => {
                 // [block] --> [loop_block] -/eval. cond./-> [loop_block_end] -1-> [exit_block]
                 //                  ^                               |
@@ -225,6 +226,14 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                     TerminatorKind::Goto { target: loop_block },
                 );
 
+                // mark the loop header for codegen to find the branches back to it
+                if !hints.is_empty() {
+                    this.cfg.push(loop_block, Statement {
+                        source_info,
+                        kind: StatementKind::LoopHint(hints),
+                    });
+                }
+
                 this.in_breakable_scope(
                     Some(loop_block),
                     exit_block,
@@ -274,7 +283,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                 );
                 exit_block.unit()
             }
-            ExprKind::Call { ty, fun, args, from_hir_call } => {
+            ExprKind::Call { ty, fun, args, from_hir_call, inline } => {
                 // FIXME(canndrew): This is_never should probably be an is_uninhabited
                 let diverges = expr.ty.is_never();
                 let intrinsic = match ty.sty {
@@ -338,6 +347,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                                 Some((destination.clone(), success))
                             },
                             from_hir_call,
+                            inline,
                         },
                     );
                     success.unit()
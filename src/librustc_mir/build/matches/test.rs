@@ -25,6 +25,7 @@ use rustc::ty::util::IntTypeExt;
 use rustc::ty::layout::VariantIdx;
 use rustc::mir::*;
 use rustc::hir::{RangeEnd, Mutability};
+use syntax::attr::InlineAttr;
 use syntax_pos::Span;
 use std::cmp::Ordering;
 
@@ -363,6 +364,7 @@ impl<'a, 'gcx, 'tcx> Builder<'a, 'gcx, 'tcx> {
                         destination: Some((eq_result.clone(), eq_block)),
                         cleanup: Some(cleanup),
                         from_hir_call: false,
+                        inline: InlineAttr::None,
                     });
 
                     // check the result
@@ -623,8 +623,9 @@ fn should_abort_on_panic<'a, 'gcx, 'tcx>(tcx: TyCtxt<'a, 'gcx, 'tcx>,
     match attr::find_unwind_attr(Some(tcx.sess.diagnostic()), attrs) {
         None => {
             // FIXME(rust-lang/rust#48251) -- Had to disable
-            // abort-on-panic for backwards compatibility reasons.
-            false
+            // abort-on-panic for backwards compatibility reasons,
+            // so it's opt-in for now.
+            tcx.sess.opts.debugging_opts.ffi_unwind_guards
         }
 
         Some(UnwindAttr::Allowed) => false,
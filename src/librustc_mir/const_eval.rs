@@ -21,7 +21,7 @@ use rustc::hir::def::Def;
 use rustc::mir::interpret::{ConstEvalErr, ErrorHandled};
 use rustc::mir;
 use rustc::ty::{self, TyCtxt, Instance, query::TyCtxtAt};
-use rustc::ty::layout::{self, LayoutOf, TyLayout, VariantIdx};
+use rustc::ty::layout::{self, Align, LayoutOf, Size, TyLayout, VariantIdx};
 use rustc::ty::subst::Subst;
 use rustc::traits::Reveal;
 use rustc_data_structures::indexed_vec::IndexVec;
@@ -214,6 +214,19 @@ fn eval_body_using_ecx<'mir, 'tcx>(
     };
     ecx.memory.intern_static(ret.ptr.to_ptr()?.alloc_id, mutability)?;
 
+    // Interning took everything the result points to, so all heap memory that
+    // is left was leaked. (Other leftovers, like the locals of the body, are
+    // just dropped along with `ecx`.)
+    let leaked = ecx.memory.alloc_map().values().any(|&(kind, _)| match kind {
+        MemoryKind::Machine(ConstMemoryKind::Heap) => true,
+        _ => false,
+    });
+    if leaked {
+        return err!(MachineError(
+            "memory allocated with `const_allocate` was never deallocated".to_string(),
+        ));
+    }
+
     debug!("eval_body_using_ecx done: {:?}", *ret);
     Ok(ret)
 }
@@ -342,27 +355,37 @@ impl<K: Hash + Eq, V> interpret::AllocMap<K, V> for FxHashMap<K, V> {
 type CompileTimeEvalContext<'a, 'mir, 'tcx> =
     EvalContext<'a, 'mir, 'tcx, CompileTimeInterpreter<'a, 'mir, 'tcx>>;
 
-impl interpret::MayLeak for ! {
+/// The memory kinds the compile-time interpreter adds to the builtin ones.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum ConstMemoryKind {
+    /// Memory allocated with the `const_allocate` intrinsic.
+    Heap,
+}
+
+impl interpret::MayLeak for ConstMemoryKind {
     #[inline(always)]
     fn may_leak(self) -> bool {
-        // `self` is uninhabited
-        self
+        match self {
+            // Heap memory must be deallocated, unless it becomes part of the
+            // final value -- which interning rejects.
+            ConstMemoryKind::Heap => false,
+        }
     }
 }
 
 impl<'a, 'mir, 'tcx> interpret::Machine<'a, 'mir, 'tcx>
     for CompileTimeInterpreter<'a, 'mir, 'tcx>
 {
-    type MemoryKinds = !;
+    type MemoryKinds = ConstMemoryKind;
     type PointerTag = ();
 
     type FrameExtra = ();
     type MemoryExtra = ();
     type AllocExtra = ();
 
-    type MemoryMap = FxHashMap<AllocId, (MemoryKind<!>, Allocation)>;
+    type MemoryMap = FxHashMap<AllocId, (MemoryKind<ConstMemoryKind>, Allocation)>;
 
-    const STATIC_KIND: Option<!> = None; // no copying of statics allowed
+    const STATIC_KIND: Option<ConstMemoryKind> = None; // no copying of statics allowed
 
     #[inline(always)]
     fn enforce_validity(_ecx: &EvalContext<'a, 'mir, 'tcx, Self>) -> bool {
@@ -415,8 +438,41 @@ impl<'a, 'mir, 'tcx> interpret::Machine<'a, 'mir, 'tcx>
         if ecx.emulate_intrinsic(instance, args, dest)? {
             return Ok(());
         }
-        // An intrinsic that we do not support
         let intrinsic_name = &ecx.tcx.item_name(instance.def_id()).as_str()[..];
+        match intrinsic_name {
+            "const_allocate" => {
+                let size = ecx.read_scalar(args[0])?.to_usize(ecx)?;
+                let align = ecx.read_scalar(args[1])?.to_usize(ecx)?;
+                let align = match Align::from_bytes(align) {
+                    Ok(align) => align,
+                    Err(err) => return err!(Intrinsic(format!("const_allocate: {}", err))),
+                };
+                let ptr = ecx.memory.allocate(
+                    Size::from_bytes(size),
+                    align,
+                    MemoryKind::Machine(ConstMemoryKind::Heap),
+                )?;
+                ecx.write_scalar(Scalar::Ptr(ptr), dest)?;
+                return Ok(());
+            }
+            "const_deallocate" => {
+                let ptr = ecx.read_scalar(args[0])?.not_undef()?.to_ptr()?;
+                let size = ecx.read_scalar(args[1])?.to_usize(ecx)?;
+                let align = ecx.read_scalar(args[2])?.to_usize(ecx)?;
+                let align = match Align::from_bytes(align) {
+                    Ok(align) => align,
+                    Err(err) => return err!(Intrinsic(format!("const_deallocate: {}", err))),
+                };
+                ecx.memory.deallocate(
+                    ptr,
+                    Some((Size::from_bytes(size), align)),
+                    MemoryKind::Machine(ConstMemoryKind::Heap),
+                )?;
+                return Ok(());
+            }
+            _ => {}
+        }
+        // An intrinsic that we do not support
         Err(
             ConstEvalError::NeedsRfc(format!("calling intrinsic `{}`", intrinsic_name)).into()
         )
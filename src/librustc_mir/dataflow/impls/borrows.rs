@@ -309,6 +309,7 @@ impl<'a, 'gcx, 'tcx> BitDenotation for Borrows<'a, 'gcx, 'tcx> {
             mir::StatementKind::Retag { .. } |
             mir::StatementKind::EscapeToRaw { .. } |
             mir::StatementKind::AscribeUserType(..) |
+            mir::StatementKind::LoopHint(_) |
             mir::StatementKind::Nop => {}
 
         }
@@ -304,6 +304,7 @@ impl<'b, 'a, 'gcx, 'tcx> Gatherer<'b, 'a, 'gcx, 'tcx> {
             StatementKind::Retag { .. } |
             StatementKind::EscapeToRaw { .. } |
             StatementKind::AscribeUserType(..) |
+            StatementKind::LoopHint(_) |
             StatementKind::Nop => {}
         }
     }
@@ -385,6 +386,7 @@ impl<'b, 'a, 'gcx, 'tcx> Gatherer<'b, 'a, 'gcx, 'tcx> {
                 ref destination,
                 cleanup: _,
                 from_hir_call: _,
+                inline: _,
             } => {
                 self.gather_operand(func);
                 for arg in args {
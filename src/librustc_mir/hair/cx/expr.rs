@@ -21,7 +21,8 @@ use rustc::ty::adjustment::{Adjustment, Adjust, AutoBorrow, AutoBorrowMutability
 use rustc::ty::cast::CastKind as TyCastKind;
 use rustc::hir;
 use rustc::hir::def_id::LocalDefId;
-use rustc::mir::{BorrowKind};
+use rustc::mir::{BorrowKind, LoopHints};
+use syntax::attr::{self, InlineAttr};
 use syntax_pos::Span;
 
 impl<'tcx> Mirror<'tcx> for &'tcx hir::Expr {
@@ -236,6 +237,7 @@ fn make_mirror_unadjusted<'a, 'gcx, 'tcx>(cx: &mut Cx<'a, 'gcx, 'tcx>,
         // Here comes the interesting stuff:
         hir::ExprKind::MethodCall(_, method_span, ref args) => {
             // Rewrite a.b(c) into UFCS form like Trait::b(a, c)
+            let inline = call_site_inline(expr);
             let expr = method_callee(cx, expr, method_span,None);
             let args = args.iter()
                 .map(|e| e.to_ref())
@@ -245,6 +247,7 @@ fn make_mirror_unadjusted<'a, 'gcx, 'tcx>(cx: &mut Cx<'a, 'gcx, 'tcx>,
                 fun: expr.to_ref(),
                 args,
                 from_hir_call: true,
+                inline,
             }
         }
 
@@ -272,6 +275,7 @@ fn make_mirror_unadjusted<'a, 'gcx, 'tcx>(cx: &mut Cx<'a, 'gcx, 'tcx>,
                     fun: method.to_ref(),
                     args: vec![fun.to_ref(), tupled_args.to_ref()],
                     from_hir_call: true,
+                    inline: call_site_inline(expr),
                 }
             } else {
                 let adt_data = if let hir::ExprKind::Path(hir::QPath::Resolved(_, ref path)) =
@@ -320,6 +324,7 @@ fn make_mirror_unadjusted<'a, 'gcx, 'tcx>(cx: &mut Cx<'a, 'gcx, 'tcx>,
                         fun: fun.to_ref(),
                         args: args.to_ref(),
                         from_hir_call: true,
+                        inline: call_site_inline(expr),
                     }
                 }
             }
@@ -624,12 +629,14 @@ fn make_mirror_unadjusted<'a, 'gcx, 'tcx>(cx: &mut Cx<'a, 'gcx, 'tcx>,
             ExprKind::Loop {
                 condition: Some(cond.to_ref()),
                 body: block::to_expr_ref(cx, body),
+                hints: LoopHints::from_attrs(&expr.attrs),
             }
         }
         hir::ExprKind::Loop(ref body, _, _) => {
             ExprKind::Loop {
                 condition: None,
                 body: block::to_expr_ref(cx, body),
+                hints: LoopHints::from_attrs(&expr.attrs),
             }
         }
         hir::ExprKind::Field(ref source, ..) => {
@@ -1120,9 +1127,28 @@ fn overloaded_operator<'a, 'gcx, 'tcx>(cx: &mut Cx<'a, 'gcx, 'tcx>,
         fun: fun.to_ref(),
         args,
         from_hir_call: false,
+        inline: InlineAttr::None,
     }
 }
 
+/// The `#[inline(always)]` or `#[inline(never)]` written on a call
+/// expression, if any. Misplaced or malformed ones are reported by
+/// `check_attr`.
+fn call_site_inline(expr: &hir::Expr) -> InlineAttr {
+    expr.attrs.iter()
+        .filter(|a| a.check_name("inline"))
+        .filter_map(|a| a.meta_item_list())
+        .fold(InlineAttr::None, |ia, items| {
+            if attr::list_contains_name(&items[..], "always") {
+                InlineAttr::Always
+            } else if attr::list_contains_name(&items[..], "never") {
+                InlineAttr::Never
+            } else {
+                ia
+            }
+        })
+}
+
 fn overloaded_place<'a, 'gcx, 'tcx>(
     cx: &mut Cx<'a, 'gcx, 'tcx>,
     expr: &'tcx hir::Expr,
@@ -1164,6 +1190,7 @@ fn overloaded_place<'a, 'gcx, 'tcx>(
             fun: fun.to_ref(),
             args,
             from_hir_call: false,
+            inline: InlineAttr::None,
         },
     };
 
@@ -14,7 +14,7 @@
 //! unit-tested and separated from the Rust source and compiler data
 //! structures.
 
-use rustc::mir::{BinOp, BorrowKind, UserTypeAnnotation, Field, UnOp};
+use rustc::mir::{BinOp, BorrowKind, UserTypeAnnotation, Field, LoopHints, UnOp};
 use rustc::hir::def_id::DefId;
 use rustc::middle::region;
 use rustc::ty::subst::Substs;
@@ -22,6 +22,7 @@ use rustc::ty::{AdtDef, UpvarSubsts, Region, Ty, Const};
 use rustc::ty::layout::VariantIdx;
 use rustc::hir;
 use syntax::ast;
+use syntax::attr::InlineAttr;
 use syntax_pos::Span;
 use self::cx::Cx;
 
@@ -161,6 +162,8 @@ pub enum ExprKind<'tcx> {
         // Whether this is from a call in HIR, rather than from an overloaded
         // operator. True for overloaded function call.
         from_hir_call: bool,
+        // `#[inline(always)]` or `#[inline(never)]` on the call expression.
+        inline: InlineAttr,
     },
     Deref {
         arg: ExprRef<'tcx>,
@@ -209,6 +212,7 @@ pub enum ExprKind<'tcx> {
     Loop {
         condition: Option<ExprRef<'tcx>>,
         body: ExprRef<'tcx>,
+        hints: LoopHints,
     },
     Match {
         discriminant: ExprRef<'tcx>,
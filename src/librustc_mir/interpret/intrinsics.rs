@@ -13,8 +13,9 @@
 //! and miri.
 
 use syntax::symbol::Symbol;
-use rustc::ty;
-use rustc::ty::layout::{LayoutOf, Primitive};
+use rustc::ty::{self, TyCtxt};
+use rustc::ty::layout::{LayoutOf, LayoutError, Primitive};
+use rustc::ty::subst::Substs;
 use rustc::mir::BinOp;
 use rustc::mir::interpret::{
     EvalResult, EvalErrorKind, Scalar,
@@ -25,6 +26,53 @@ use super::{
 };
 
 
+/// Evaluates an intrinsic whose value only depends on its type parameters,
+/// returning `None` if `name` isn't one. Both CTFE and codegen go through
+/// this, so that a `const` and the same expression at runtime always agree.
+pub fn eval_nullary_intrinsic<'a, 'tcx>(
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    name: &str,
+    substs: &'tcx Substs<'tcx>,
+) -> Option<Result<u128, LayoutError<'tcx>>> {
+    let layout = |ty| tcx.layout_of(param_env.and(ty));
+    let niche = |ty| tcx.layout_niche(param_env.and(ty));
+    Some(Ok(match name {
+        "size_of" => match layout(substs.type_at(0)) {
+            Ok(layout) => layout.size.bytes() as u128,
+            Err(err) => return Some(Err(err)),
+        },
+        "min_align_of" => match layout(substs.type_at(0)) {
+            Ok(layout) => layout.align.abi.bytes() as u128,
+            Err(err) => return Some(Err(err)),
+        },
+        "pref_align_of" => match layout(substs.type_at(0)) {
+            Ok(layout) => layout.align.pref.bytes() as u128,
+            Err(err) => return Some(Err(err)),
+        },
+        "needs_drop" => substs.type_at(0).needs_drop(tcx, param_env) as u128,
+        "has_niche" => match niche(substs.type_at(0)) {
+            Ok(niche) => niche.is_some() as u128,
+            Err(err) => return Some(Err(err)),
+        },
+        "niche_offset" => match niche(substs.type_at(0)) {
+            Ok(niche) => niche.map_or(0, |(offset, _)| offset.bytes() as u128),
+            Err(err) => return Some(Err(err)),
+        },
+        "niche_range_start" => match niche(substs.type_at(0)) {
+            Ok(niche) => niche.map_or(0, |(_, scalar)| *scalar.valid_range.start()),
+            Err(err) => return Some(Err(err)),
+        },
+        "niche_range_end" => match niche(substs.type_at(0)) {
+            Ok(niche) => niche.map_or(0, |(_, scalar)| *scalar.valid_range.end()),
+            Err(err) => return Some(Err(err)),
+        },
+        "type_id" => tcx.type_id_hash(substs.type_at(0)) as u64 as u128,
+        "type_id128" => tcx.type_id_hash(substs.type_at(0)),
+        _ => return None,
+    }))
+}
+
 fn numeric_intrinsic<'tcx, Tag>(
     name: &str,
     bits: u128,
@@ -58,33 +106,28 @@ impl<'a, 'mir, 'tcx, M: Machine<'a, 'mir, 'tcx>> EvalContext<'a, 'mir, 'tcx, M>
 
         let intrinsic_name = &self.tcx.item_name(instance.def_id()).as_str()[..];
         match intrinsic_name {
-            "min_align_of" => {
-                let elem_ty = substs.type_at(0);
-                let elem_align = self.layout_of(elem_ty)?.align.abi.bytes();
-                let align_val = Scalar::from_uint(elem_align, dest.layout.size);
-                self.write_scalar(align_val, dest)?;
-            }
-
-            "needs_drop" => {
-                let ty = substs.type_at(0);
-                let ty_needs_drop = ty.needs_drop(self.tcx.tcx, self.param_env);
-                let val = Scalar::from_bool(ty_needs_drop);
-                self.write_scalar(val, dest)?;
+            | "min_align_of"
+            | "pref_align_of"
+            | "needs_drop"
+            | "has_niche"
+            | "niche_offset"
+            | "niche_range_start"
+            | "niche_range_end"
+            | "size_of"
+            | "type_id"
+            | "type_id128" => {
+                let val = eval_nullary_intrinsic(self.tcx.tcx, self.param_env,
+                                                 intrinsic_name, substs)
+                    .unwrap()
+                    .map_err(EvalErrorKind::Layout)?;
+                self.write_scalar(Scalar::from_uint(val, dest.layout.size), dest)?;
             }
 
-            "size_of" => {
-                let ty = substs.type_at(0);
-                let size = self.layout_of(ty)?.size.bytes() as u128;
-                let size_val = Scalar::from_uint(size, dest.layout.size);
-                self.write_scalar(size_val, dest)?;
+            "is_val_statically_known" => {
+                // Everything is known during const evaluation.
+                self.write_scalar(Scalar::from_bool(true), dest)?;
             }
 
-            "type_id" => {
-                let ty = substs.type_at(0);
-                let type_id = self.tcx.type_id_hash(ty) as u128;
-                let id_val = Scalar::from_uint(type_id, dest.layout.size);
-                self.write_scalar(id_val, dest)?;
-            }
             | "ctpop"
             | "cttz"
             | "cttz_nonzero"
@@ -171,6 +214,14 @@ impl<'a, 'mir, 'tcx, M: Machine<'a, 'mir, 'tcx>> EvalContext<'a, 'mir, 'tcx, M>
             "transmute" => {
                 self.copy_op_transmute(args[0], dest)?;
             }
+            "read_via_copy" => {
+                let src = self.deref_operand(args[0])?;
+                self.copy_op(src.into(), dest)?;
+            }
+            "write_via_move" => {
+                let dst = self.deref_operand(args[0])?;
+                self.copy_op(args[1], dst.into())?;
+            }
 
             _ => return Ok(false),
         }
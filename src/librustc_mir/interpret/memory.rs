@@ -632,7 +632,10 @@ where
         // remove allocation
         let (kind, mut alloc) = self.alloc_map.remove(&alloc_id).unwrap();
         match kind {
-            MemoryKind::Machine(_) => bug!("Static cannot refer to machine memory"),
+            // Heap memory of the const evaluator that was never deallocated
+            MemoryKind::Machine(_) => return err!(ValidationFailure(
+                "encountered pointer to heap memory in final constant".into(),
+            )),
             MemoryKind::Stack | MemoryKind::Vtable => {},
         }
         // ensure llvm knows not to put this into immutable memory
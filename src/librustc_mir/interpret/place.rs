@@ -353,6 +353,9 @@ where
         base: MPlaceTy<'tcx, M::PointerTag>,
         field: u64,
     ) -> EvalResult<'tcx, MPlaceTy<'tcx, M::PointerTag>> {
+        if base.layout.fields.bit_field(usize::try_from(field).unwrap_or(0)).is_some() {
+            return err!(Unimplemented("bit-fields are not supported".to_string()));
+        }
         // Not using the layout method because we want to compute on u64
         let offset = match base.layout.fields {
             layout::FieldPlacement::Arbitrary { ref offsets, .. } =>
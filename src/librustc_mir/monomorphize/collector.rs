@@ -194,6 +194,7 @@ use rustc::ty::subst::Substs;
 use rustc::ty::{self, TypeFoldable, Ty, TyCtxt, GenericParamDefKind};
 use rustc::ty::adjustment::CustomCoerceUnsized;
 use rustc::session::config;
+use rustc_target::spec::abi::Abi;
 use rustc::mir::{self, Location, Promoted};
 use rustc::mir::visit::Visitor as MirVisitor;
 use rustc::mir::mono::MonoItem;
@@ -346,7 +347,22 @@ fn collect_roots<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
         tcx.hir.krate().visit_all_item_likes(&mut visitor);
 
-        visitor.push_extra_entry_roots();
+        if !tcx.sess.opts.debugging_opts.offload_device {
+            visitor.push_extra_entry_roots();
+        }
+    }
+
+    // When compiling for an offload device only the `#[offload]` kernels and
+    // statics are roots, everything else they need is collected from them.
+    if tcx.sess.opts.debugging_opts.offload_device {
+        roots.retain(|root| {
+            let def_id = match *root {
+                MonoItem::Fn(instance) => instance.def_id(),
+                MonoItem::Static(def_id) => def_id,
+                MonoItem::GlobalAsm(..) => return false,
+            };
+            tcx.codegen_fn_attrs(def_id).flags.contains(CodegenFnAttrFlags::OFFLOAD)
+        });
     }
 
     // We can only codegen items that are instantiable - items all of
@@ -617,6 +633,7 @@ impl<'a, 'tcx> MirVisitor<'tcx> for MirNeighborCollector<'a, 'tcx> {
                     &callee_ty,
                 );
                 visit_fn_use(self.tcx, callee_ty, true, &mut self.output);
+                visit_intrinsic_use(self.tcx, callee_ty, &mut self.output);
             }
             mir::TerminatorKind::Drop { ref location, .. } |
             mir::TerminatorKind::DropAndReplace { ref location, .. } => {
@@ -684,6 +701,28 @@ fn visit_fn_use<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     }
 }
 
+// Some intrinsics are lowered to calls of other functions, which have to be
+// collected along with the intrinsic call itself.
+fn visit_intrinsic_use<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                 ty: Ty<'tcx>,
+                                 output: &mut Vec<MonoItem<'tcx>>)
+{
+    if let ty::FnDef(def_id, substs) = ty.sty {
+        if tcx.fn_sig(def_id).abi() != Abi::RustIntrinsic {
+            return;
+        }
+        match &*tcx.item_name(def_id).as_str() {
+            "size_of_val" | "min_align_of_val" => {
+                if let Some((size_fn, align_fn)) = tcx.extern_size_methods(substs.type_at(0)) {
+                    visit_instance_use(tcx, size_fn, true, output);
+                    visit_instance_use(tcx, align_fn, true, output);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 fn visit_instance_use<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                 instance: ty::Instance<'tcx>,
                                 is_direct_call: bool,
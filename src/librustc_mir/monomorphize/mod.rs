@@ -19,6 +19,7 @@ pub use self::item::{MonoItem, MonoItemExt};
 
 pub mod collector;
 pub mod item;
+pub mod no_alloc;
 pub mod partitioning;
 
 #[inline(never)] // give this a place in the profiler
@@ -0,0 +1,298 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Verification of `#[no_alloc]` functions for `-Z verify-no-alloc`.
+//!
+//! Starting at every instance of a `#[no_alloc]` function that gets
+//! codegened, this walks the monomorphized MIR of everything it calls or
+//! drops, and reports the call chain to the first place that allocates (a
+//! call to the global allocator or a `box` expression) or formats a panic
+//! message. Calls that can't be followed, to functions without MIR (such as
+//! non-generic functions of other crates) or through function pointers or
+//! trait objects, are reported too, as they may do either.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use rustc::hir::CodegenFnAttrFlags;
+use rustc::hir::def_id::DefId;
+use rustc::mir::{self, Location};
+use rustc::mir::mono::{CodegenUnit, MonoItem};
+use rustc::mir::visit::Visitor;
+use rustc::ty::{self, Instance, InstanceDef, TyCtxt};
+use rustc::util::nodemap::{FxHashMap, FxHashSet};
+use rustc_target::spec::PanicStrategy;
+use syntax_pos::Span;
+
+use monomorphize::{self, MonoItemExt};
+
+/// The functions of the global allocator that hand out memory.
+const ALLOCATING_FNS: &[&str] = &["__rust_alloc", "__rust_alloc_zeroed", "__rust_realloc"];
+
+/// The function of the global allocator that frees memory, which is fine to
+/// call even though it has no MIR.
+const DEALLOCATING_FN: &str = "__rust_dealloc";
+
+#[derive(Clone, PartialEq)]
+enum Forbidden {
+    Alloc,
+    PanicFmt,
+    /// A call that can't be followed, to the described callee.
+    Unverifiable(String),
+}
+
+/// Verifies the `#[no_alloc]` functions among the items of `codegen_units`.
+pub fn verify_no_alloc<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                 codegen_units: &[Arc<CodegenUnit<'tcx>>]) {
+    let mut roots: Vec<_> = codegen_units.iter()
+        .flat_map(|cgu| cgu.items().keys())
+        .filter_map(|item| match *item {
+            MonoItem::Fn(instance) => {
+                let flags = tcx.codegen_fn_attrs(instance.def_id()).flags;
+                if flags.contains(CodegenFnAttrFlags::NO_ALLOC) {
+                    Some((item.symbol_name(tcx), instance))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+        .collect();
+    // Report in the same order every time, and each function once even if
+    // it's in several codegen units.
+    roots.sort_by_key(|&(name, _)| name);
+    roots.dedup_by_key(|&mut (name, _)| name);
+
+    for (_, root) in roots {
+        verify_instance(tcx, root);
+    }
+}
+
+fn verify_instance<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, root: Instance<'tcx>) {
+    // The instance that each one reached was first called or dropped from,
+    // and where, to reconstruct the call chain.
+    let mut callers: FxHashMap<Instance<'tcx>, (Instance<'tcx>, Span)> = FxHashMap::default();
+    let mut visited = FxHashSet::default();
+    let mut queue = VecDeque::new();
+    visited.insert(root);
+    queue.push_back(root);
+
+    while let Some(instance) = queue.pop_front() {
+        let mir = match instance.def {
+            InstanceDef::Intrinsic(..) => continue,
+            _ => tcx.instance_mir(instance.def),
+        };
+
+        let mut visitor = CallVisitor {
+            tcx,
+            instance,
+            mir,
+            callees: vec![],
+            forbidden: None,
+        };
+        visitor.visit_mir(mir);
+
+        if let Some((span, forbidden, what)) = visitor.forbidden {
+            report(tcx, root, instance, span, forbidden, &what, &callers);
+            return;
+        }
+        for (callee, span) in visitor.callees {
+            if visited.insert(callee) {
+                callers.insert(callee, (instance, span));
+                queue.push_back(callee);
+            }
+        }
+    }
+}
+
+fn report<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                    root: Instance<'tcx>,
+                    instance: Instance<'tcx>,
+                    span: Span,
+                    forbidden: Forbidden,
+                    what: &str,
+                    callers: &FxHashMap<Instance<'tcx>, (Instance<'tcx>, Span)>) {
+    let mut chain = vec![];
+    let mut callee = instance;
+    while let Some(&(caller, span)) = callers.get(&callee) {
+        chain.push((caller, callee, span));
+        callee = caller;
+    }
+
+    let msg = match forbidden {
+        Forbidden::Alloc => format!("`#[no_alloc]` function `{}` may allocate", root),
+        Forbidden::PanicFmt => {
+            format!("`#[no_alloc]` function `{}` may format a panic message", root)
+        }
+        Forbidden::Unverifiable(ref callee) => {
+            format!("cannot verify call to {} from `#[no_alloc]` function `{}`", callee, root)
+        }
+    };
+    let mut err = tcx.sess.struct_span_err(tcx.def_span(root.def_id()), &msg);
+    for &(caller, callee, span) in chain.iter().rev() {
+        err.span_note(span, &format!("`{}` calls `{}` here", caller, callee));
+    }
+    err.span_note(span, &format!("`{}` {} here", instance, what));
+    err.emit();
+}
+
+struct CallVisitor<'a, 'tcx: 'a> {
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    instance: Instance<'tcx>,
+    mir: &'a mir::Mir<'tcx>,
+    callees: Vec<(Instance<'tcx>, Span)>,
+    forbidden: Option<(Span, Forbidden, String)>,
+}
+
+impl<'a, 'tcx> CallVisitor<'a, 'tcx> {
+    fn forbid(&mut self, location: Location, forbidden: Forbidden, what: String) {
+        if self.forbidden.is_none() {
+            let span = self.mir.source_info(location).span;
+            self.forbidden = Some((span, forbidden, what));
+        }
+    }
+
+    /// With `-Z panic-abort-minimal`, panics are a bare abort that doesn't
+    /// format anything.
+    fn minimal_panics(&self) -> bool {
+        let sess = self.tcx.sess;
+        sess.opts.debugging_opts.panic_abort_minimal &&
+            sess.panic_strategy() == PanicStrategy::Abort
+    }
+
+    fn forbidden_callee(&self, def_id: DefId) -> Option<Forbidden> {
+        let tcx = self.tcx;
+        let lang_items = tcx.lang_items();
+        let name = tcx.item_name(def_id).as_str();
+
+        if (tcx.is_foreign_item(def_id) && ALLOCATING_FNS.contains(&&*name)) ||
+           Some(def_id) == lang_items.exchange_malloc_fn() {
+            return Some(Forbidden::Alloc);
+        }
+
+        let formatting_lang_item = [
+            lang_items.begin_panic_fn(),
+            lang_items.panic_impl(),
+        ].contains(&Some(def_id));
+        let panic_lang_item = [
+            lang_items.panic_fn(),
+            lang_items.panic_bounds_check_fn(),
+            lang_items.panic_fmt(),
+        ].contains(&Some(def_id));
+        let krate = tcx.crate_name(def_id.krate).as_str();
+        let formatting_fn = &*krate == "std" && &*name == "begin_panic_fmt";
+        if formatting_lang_item || formatting_fn ||
+           (panic_lang_item && !self.minimal_panics()) {
+            return Some(Forbidden::PanicFmt);
+        }
+        None
+    }
+
+    /// Whether a call to `def_id` is known not to allocate or format even
+    /// though it can't be walked.
+    fn allowed_callee(&self, def_id: DefId) -> bool {
+        let tcx = self.tcx;
+        let lang_items = tcx.lang_items();
+        let panic_lang_item = [
+            lang_items.panic_fn(),
+            lang_items.panic_bounds_check_fn(),
+        ].contains(&Some(def_id));
+        (tcx.is_foreign_item(def_id) && &*tcx.item_name(def_id).as_str() == DEALLOCATING_FN) ||
+            (panic_lang_item && self.minimal_panics())
+    }
+
+    /// Queue `callee` to be walked, unless its MIR can't be, in which case
+    /// it's reported.
+    fn visit_callee(&mut self, callee: Instance<'tcx>, location: Location) {
+        let unverifiable = match callee.def {
+            InstanceDef::Virtual(..) => {
+                Some(format!("trait object method `{}`", callee))
+            }
+            InstanceDef::Item(def_id) if !self.tcx.is_mir_available(def_id) => {
+                Some(format!("`{}`, which has no MIR", callee))
+            }
+            _ => None,
+        };
+        match unverifiable {
+            Some(callee_desc) => {
+                let what = format!("calls {}", callee_desc);
+                self.forbid(location, Forbidden::Unverifiable(callee_desc), what);
+            }
+            None => {
+                let span = self.mir.source_info(location).span;
+                self.callees.push((callee, span));
+            }
+        }
+    }
+
+    fn monomorphize<T>(&self, value: &T) -> T
+        where T: ty::TypeFoldable<'tcx>
+    {
+        self.tcx.subst_and_normalize_erasing_regions(
+            self.instance.substs,
+            ty::ParamEnv::reveal_all(),
+            value,
+        )
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for CallVisitor<'a, 'tcx> {
+    fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
+        if let mir::Rvalue::NullaryOp(mir::NullOp::Box, _) = *rvalue {
+            let what = "allocates in a `box` expression".to_string();
+            self.forbid(location, Forbidden::Alloc, what);
+        }
+        self.super_rvalue(rvalue, location);
+    }
+
+    fn visit_terminator_kind(&mut self,
+                             block: mir::BasicBlock,
+                             kind: &mir::TerminatorKind<'tcx>,
+                             location: Location) {
+        match *kind {
+            mir::TerminatorKind::Call { ref func, .. } => {
+                let callee_ty = self.monomorphize(&func.ty(self.mir, self.tcx));
+                match callee_ty.sty {
+                    ty::FnDef(def_id, substs) => {
+                        if let Some(forbidden) = self.forbidden_callee(def_id) {
+                            let what = format!("calls `{}`", self.tcx.item_path_str(def_id));
+                            self.forbid(location, forbidden, what);
+                        } else if !self.allowed_callee(def_id) {
+                            let param_env = ty::ParamEnv::reveal_all();
+                            if let Some(callee) = Instance::resolve(self.tcx,
+                                                                    param_env,
+                                                                    def_id,
+                                                                    substs) {
+                                self.visit_callee(callee, location);
+                            }
+                        }
+                    }
+                    _ => {
+                        let what = "calls a function pointer".to_string();
+                        let callee = "a function pointer".to_string();
+                        self.forbid(location, Forbidden::Unverifiable(callee), what);
+                    }
+                }
+            }
+            mir::TerminatorKind::Drop { location: ref place, .. } |
+            mir::TerminatorKind::DropAndReplace { location: ref place, .. } => {
+                let ty = self.monomorphize(&place.ty(self.mir, self.tcx).to_ty(self.tcx));
+                self.visit_callee(monomorphize::resolve_drop_in_place(self.tcx, ty), location);
+            }
+            mir::TerminatorKind::Assert { ref msg, .. } if !self.minimal_panics() => {
+                let what = format!("formats the panic message `{}`", msg.description());
+                self.forbid(location, Forbidden::PanicFmt, what);
+            }
+            _ => {}
+        }
+
+        self.super_terminator_kind(block, kind, location);
+    }
+}
@@ -108,13 +108,14 @@ use std::sync::Arc;
 
 use syntax::ast::NodeId;
 use syntax::symbol::InternedString;
+use syntax_pos::DUMMY_SP;
 use rustc::dep_graph::{WorkProductId, WorkProduct, DepNode, DepConstructor};
 use rustc::hir::CodegenFnAttrFlags;
 use rustc::hir::def_id::{CrateNum, DefId, LOCAL_CRATE, CRATE_DEF_INDEX};
 use rustc::hir::map::DefPathData;
 use rustc::mir::mono::{Linkage, Visibility, CodegenUnitNameBuilder};
 use rustc::middle::exported_symbols::SymbolExportLevel;
-use rustc::ty::{self, TyCtxt, InstanceDef};
+use rustc::ty::{self, Instance, InstanceDef, Ty, TyCtxt};
 use rustc::ty::item_path::characteristic_def_id_of_type;
 use rustc::ty::query::Providers;
 use rustc::util::common::time;
@@ -124,6 +125,7 @@ use rustc::mir::mono::MonoItem;
 use monomorphize::collector::InliningMap;
 use monomorphize::collector::{self, MonoItemCollectionMode};
 use monomorphize::item::{MonoItemExt, InstantiationMode};
+use monomorphize::resolve_drop_in_place;
 
 pub use rustc::mir::mono::CodegenUnit;
 
@@ -268,6 +270,14 @@ pub fn partition<'a, 'tcx, I>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         internalize_symbols(tcx, &mut post_inlining, inlining_map);
     }
 
+    // Drop glue that is identical to other drop glue of the same codegen unit
+    // is emitted as an alias of it instead of being codegened again.
+    if tcx.sess.opts.debugging_opts.merge_shims {
+        for cgu in &mut post_inlining.codegen_units {
+            merge_identical_drop_glue(tcx, cgu);
+        }
+    }
+
     // Finally, sort by codegen unit name, so that we get deterministic results
     let PostInliningPartitioning {
         codegen_units: mut result,
@@ -757,6 +767,105 @@ fn internalize_symbols<'a, 'tcx>(_tcx: TyCtxt<'a, 'tcx, 'tcx>,
     }
 }
 
+/// Makes the drop glue of `cgu` an alias of other, identical drop glue of it.
+///
+/// The drop glue of a struct, tuple or closure without a `Drop` impl only
+/// drops its fields which need dropping, in order. So it's identical to the
+/// glue of another such type whose fields needing drop are at the same
+/// offsets and are dropped by the same, or in turn identical, glue. Any other
+/// drop glue, like one calling `Drop::drop`, is specific to its type. Only
+/// glue internal to `cgu` is merged, as an alias has to be next to its target.
+fn merge_identical_drop_glue<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                       cgu: &mut CodegenUnit<'tcx>) {
+    let mut candidates: Vec<_> = cgu.items().iter().filter_map(|(&mono_item, &(linkage, _))| {
+        match mono_item {
+            MonoItem::Fn(instance) if linkage == Linkage::Internal => match instance.def {
+                InstanceDef::DropGlue(_, Some(ty)) => Some((instance, ty)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }).collect();
+    // The first of identical glue in this order is the one that is kept.
+    candidates.sort_by_key(|&(instance, _)| instance.symbol_name(tcx));
+
+    // Merging glue can make the glue of types containing it identical too, so
+    // this is repeated until nothing changes.
+    let mut aliases = FxHashMap::default();
+    loop {
+        let mut by_key = FxHashMap::default();
+        let mut merged = false;
+        for &(instance, ty) in &candidates {
+            if aliases.contains_key(&instance) {
+                continue;
+            }
+            let key = match drop_glue_key(tcx, ty, &aliases) {
+                Some(key) => key,
+                None => continue,
+            };
+            match by_key.entry(key) {
+                Entry::Occupied(entry) => {
+                    aliases.insert(instance, *entry.get());
+                    merged = true;
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(instance);
+                }
+            }
+        }
+        if !merged {
+            break;
+        }
+    }
+
+    for &alias in aliases.keys() {
+        let target = canonical_instance(alias, &aliases);
+        debug!("merge_identical_drop_glue: {:?} is an alias of {:?}", alias, target);
+        cgu.aliases_mut().insert(MonoItem::Fn(alias), MonoItem::Fn(target));
+    }
+}
+
+/// What the drop glue of `ty` does: which of its fields are dropped, at which
+/// offsets and by which glue. `None` if the glue does anything else.
+fn drop_glue_key<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                           ty: Ty<'tcx>,
+                           aliases: &FxHashMap<Instance<'tcx>, Instance<'tcx>>)
+                           -> Option<Vec<(u64, Instance<'tcx>)>> {
+    let param_env = ty::ParamEnv::reveal_all();
+    if !ty.is_sized(tcx.at(DUMMY_SP), param_env) {
+        return None;
+    }
+    let field_tys: Vec<Ty<'tcx>> = match ty.sty {
+        ty::Adt(def, substs) if def.is_struct() && !def.is_box() &&
+                                !def.has_dtor(tcx) && !def.repr.packed() => {
+            def.non_enum_variant().fields.iter()
+                .map(|field| tcx.normalize_erasing_regions(param_env, field.ty(tcx, substs)))
+                .collect()
+        }
+        ty::Tuple(tys) => tys.to_vec(),
+        ty::Closure(def_id, substs) => substs.upvar_tys(def_id, tcx).collect(),
+        _ => return None,
+    };
+    let layout = tcx.layout_of(param_env.and(ty)).ok()?;
+    Some(field_tys.into_iter()
+        .enumerate()
+        .filter(|&(_, field_ty)| field_ty.needs_drop(tcx, param_env))
+        .map(|(i, field_ty)| {
+            let glue = resolve_drop_in_place(tcx, field_ty);
+            (layout.fields.offset(i).bytes(), canonical_instance(glue, aliases))
+        })
+        .collect())
+}
+
+fn canonical_instance<'tcx>(mut instance: Instance<'tcx>,
+                            aliases: &FxHashMap<Instance<'tcx>, Instance<'tcx>>)
+                            -> Instance<'tcx> {
+    while let Some(&target) = aliases.get(&instance) {
+        instance = target;
+    }
+    instance
+}
+
 fn characteristic_def_id_of_mono_item<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                                                  mono_item: MonoItem<'tcx>)
                                                  -> Option<DefId> {
@@ -21,6 +21,7 @@ use rustc_data_structures::indexed_vec::{IndexVec, Idx};
 
 use rustc_target::spec::abi::Abi;
 use syntax::ast;
+use syntax::attr::InlineAttr;
 use syntax_pos::Span;
 
 use std::fmt;
@@ -491,6 +492,7 @@ impl<'a, 'tcx> CloneShimBuilder<'a, 'tcx> {
             destination: Some((dest, next)),
             cleanup: Some(cleanup),
             from_hir_call: true,
+            inline: InlineAttr::None,
         }, false);
     }
 
@@ -800,6 +802,7 @@ fn build_call_shim<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
             None
         },
         from_hir_call: true,
+        inline: InlineAttr::None,
     }, false);
 
     if let Adjustment::RefMut = rcvr_adjustment {
@@ -28,6 +28,7 @@ use util;
 
 pub struct UnsafetyChecker<'a, 'tcx: 'a> {
     mir: &'a Mir<'tcx>,
+    def_id: DefId,
     min_const_fn: bool,
     source_scope_local_data: &'a IndexVec<SourceScope, SourceScopeLocalData>,
     violations: Vec<UnsafetyViolation>,
@@ -42,12 +43,14 @@ impl<'a, 'gcx, 'tcx> UnsafetyChecker<'a, 'tcx> {
     fn new(
         min_const_fn: bool,
         mir: &'a Mir<'tcx>,
+        def_id: DefId,
         source_scope_local_data: &'a IndexVec<SourceScope, SourceScopeLocalData>,
         tcx: TyCtxt<'a, 'tcx, 'tcx>,
         param_env: ty::ParamEnv<'tcx>,
     ) -> Self {
         Self {
             mir,
+            def_id,
             min_const_fn,
             source_scope_local_data,
             violations: vec![],
@@ -94,6 +97,8 @@ impl<'a, 'tcx> Visitor<'tcx> for UnsafetyChecker<'a, 'tcx> {
                     self.require_unsafe("call to unsafe function",
                         "consult the function's documentation for information on how to avoid \
                          undefined behavior")
+                } else if let ty::FnDef(func_id, _) = func_ty.sty {
+                    self.check_target_features(func_id);
                 }
             }
         }
@@ -115,6 +120,7 @@ impl<'a, 'tcx> Visitor<'tcx> for UnsafetyChecker<'a, 'tcx> {
             StatementKind::Retag { .. } |
             StatementKind::EscapeToRaw { .. } |
             StatementKind::AscribeUserType(..) |
+            StatementKind::LoopHint(_) |
             StatementKind::Nop => {
                 // safe (at least as emitted during MIR construction)
             }
@@ -134,8 +140,14 @@ impl<'a, 'tcx> Visitor<'tcx> for UnsafetyChecker<'a, 'tcx> {
         if let &Rvalue::Aggregate(box ref aggregate, _) = rvalue {
             match aggregate {
                 &AggregateKind::Array(..) |
-                &AggregateKind::Tuple |
-                &AggregateKind::Adt(..) => {}
+                &AggregateKind::Tuple => {}
+                &AggregateKind::Adt(def, ..) => {
+                    if def.pointer_niche(self.tcx).is_some() {
+                        self.require_unsafe("initializing type with `#[pointer_niche]`",
+                            "the value must not use the bits that `#[pointer_niche]` declares \
+                             unused; other values are undefined behavior")
+                    }
+                }
                 &AggregateKind::Closure(def_id, _) |
                 &AggregateKind::Generator(def_id, _, _) => {
                     let UnsafetyCheckResult {
@@ -168,6 +180,9 @@ impl<'a, 'tcx> Visitor<'tcx> for UnsafetyChecker<'a, 'tcx> {
                     kind: UnsafetyViolationKind::BorrowPacked(lint_root)
                 }], &[]);
             }
+            if util::is_bit_field(self.tcx, self.mir, place) {
+                self.tcx.sess.span_err(self.source_info.span, "cannot borrow a bit-field");
+            }
         }
 
         match place {
@@ -192,6 +207,11 @@ impl<'a, 'tcx> Visitor<'tcx> for UnsafetyChecker<'a, 'tcx> {
                              behavior")
                     }
                     ty::Adt(adt, _) => {
+                        if context.is_mutating_use() && adt.pointer_niche(self.tcx).is_some() {
+                            self.require_unsafe("mutation of field of type with `#[pointer_niche]`",
+                                "the value must not use the bits that `#[pointer_niche]` \
+                                 declares unused; other values are undefined behavior")
+                        }
                         if adt.is_union() {
                             if context == PlaceContext::MutatingUse(MutatingUseContext::Store) ||
                                 context == PlaceContext::MutatingUse(MutatingUseContext::Drop) ||
@@ -260,6 +280,27 @@ impl<'a, 'tcx> Visitor<'tcx> for UnsafetyChecker<'a, 'tcx> {
 }
 
 impl<'a, 'tcx> UnsafetyChecker<'a, 'tcx> {
+    /// Calls to safe `#[target_feature]` functions are only safe from
+    /// functions that enable (at least) the same features, or if the whole
+    /// crate is compiled with them.
+    fn check_target_features(&mut self, func_id: DefId) {
+        let tcx = self.tcx;
+        let callee_features = &tcx.codegen_fn_attrs(func_id).target_features;
+        if callee_features.is_empty() {
+            return;
+        }
+        let self_features = &tcx.codegen_fn_attrs(self.def_id).target_features;
+        let target_feature = Symbol::intern("target_feature");
+        let all_enabled = callee_features.iter().all(|&feature| {
+            self_features.contains(&feature) ||
+                tcx.sess.parse_sess.config.contains(&(target_feature, Some(feature)))
+        });
+        if !all_enabled {
+            self.require_unsafe("call to function with `#[target_feature]`",
+                "can only be called if the required target features are available")
+        }
+    }
+
     fn require_unsafe(&mut self,
                       description: &'static str,
                       details: &'static str)
@@ -385,7 +426,7 @@ fn unsafety_check_result<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId)
     let param_env = tcx.param_env(def_id);
     let mut checker = UnsafetyChecker::new(
         tcx.is_const_fn(def_id) && tcx.is_min_const_fn(def_id),
-        mir, source_scope_local_data, tcx, param_env);
+        mir, def_id, source_scope_local_data, tcx, param_env);
     checker.visit_mir(mir);
 
     check_unused_unsafe(tcx, def_id, &checker.used_unsafe, &mut checker.inherited_blocks);
@@ -46,6 +46,7 @@ struct CallSite<'tcx> {
     substs: &'tcx Substs<'tcx>,
     bb: BasicBlock,
     location: SourceInfo,
+    inline: attr::InlineAttr,
 }
 
 impl MirPass for Inline {
@@ -179,7 +180,7 @@ impl<'a, 'tcx> Inliner<'a, 'tcx> {
 
         // Only consider direct calls to functions
         let terminator = bb_data.terminator();
-        if let TerminatorKind::Call { func: ref op, .. } = terminator.kind {
+        if let TerminatorKind::Call { func: ref op, inline, .. } = terminator.kind {
             if let ty::FnDef(callee_def_id, substs) = op.ty(caller_mir, self.tcx).sty {
                 let instance = Instance::resolve(self.tcx,
                                                  param_env,
@@ -194,7 +195,8 @@ impl<'a, 'tcx> Inliner<'a, 'tcx> {
                     callee: instance.def_id(),
                     substs: instance.substs,
                     bb,
-                    location: terminator.source_info
+                    location: terminator.source_info,
+                    inline,
                 });
             }
         }
@@ -244,7 +246,14 @@ impl<'a, 'tcx> Inliner<'a, 'tcx> {
 
         let codegen_fn_attrs = tcx.codegen_fn_attrs(callsite.callee);
 
-        let hinted = match codegen_fn_attrs.inline {
+        // An `#[inline(always)]`/`#[inline(never)]` on the call itself wins
+        // over the one on the callee.
+        let inline = match callsite.inline {
+            attr::InlineAttr::None => codegen_fn_attrs.inline,
+            inline => inline,
+        };
+
+        let hinted = match inline {
             // Just treat inline(always) as a hint for now,
             // there are cases that prevent inlining that we
             // need to check for first.
@@ -306,6 +315,7 @@ impl<'a, 'tcx> Inliner<'a, 'tcx> {
                 match stmt.kind {
                     StatementKind::StorageLive(_) |
                     StatementKind::StorageDead(_) |
+                    StatementKind::LoopHint(_) |
                     StatementKind::Nop => {}
                     _ => cost += INSTR_COST
                 }
@@ -379,7 +389,7 @@ impl<'a, 'tcx> Inliner<'a, 'tcx> {
             }
         }
 
-        if let attr::InlineAttr::Always = codegen_fn_attrs.inline {
+        if let attr::InlineAttr::Always = inline {
             debug!("INLINING {:?} because inline(always) [cost={}]", callsite, cost);
             true
         } else {
@@ -0,0 +1,52 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Lints `simd_extract` calls whose lane index is only known at run-time.
+//! Those can't use the lane-immediate instructions and go through the stack
+//! instead. `simd_insert` is lowered to a lane-masked blend in that case,
+//! which stays in registers, so it isn't linted.
+
+use rustc::lint::builtin::DYNAMIC_SIMD_INDEX;
+use rustc::mir::*;
+use rustc::ty::{self, TyCtxt};
+use rustc_target::spec::abi::Abi;
+use transform::{MirPass, MirSource};
+
+pub struct LintDynamicSimdIndex;
+
+impl MirPass for LintDynamicSimdIndex {
+    fn run_pass<'a, 'tcx>(&self, tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                          _src: MirSource, mir: &mut Mir<'tcx>) {
+        let source_scope_local_data = match mir.source_scope_local_data {
+            ClearCrossCrate::Set(ref data) => data,
+            ClearCrossCrate::Clear => return,
+        };
+        for data in mir.basic_blocks() {
+            let terminator = data.terminator();
+            let (func, args) = match terminator.kind {
+                TerminatorKind::Call { ref func, ref args, .. } => (func, args),
+                _ => continue,
+            };
+            match func.ty(mir, tcx).sty {
+                ty::FnDef(callee, _) if tcx.fn_sig(callee).abi() == Abi::PlatformIntrinsic &&
+                    &*tcx.item_name(callee).as_str() == "simd_extract" => {}
+                _ => continue,
+            }
+            if let Operand::Constant(_) = args[1] {
+                continue;
+            }
+            let source_info = terminator.source_info;
+            tcx.lint_node(DYNAMIC_SIMD_INDEX,
+                          source_scope_local_data[source_info.scope].lint_root,
+                          source_info.span,
+                          "`simd_extract` with a non-constant lane index");
+        }
+    }
+}
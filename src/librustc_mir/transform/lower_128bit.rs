@@ -122,6 +122,7 @@ impl Lower128Bit {
                             destination: Some((place, bb)),
                             cleanup: None,
                             from_hir_call: false,
+                            inline: syntax::attr::InlineAttr::None,
                         },
                     });
             }
@@ -46,6 +46,7 @@ pub mod const_prop;
 pub mod generator;
 pub mod inline;
 pub mod lower_128bit;
+pub mod lint_simd_index;
 pub mod uniform_array_move_out;
 
 pub(crate) fn provide(providers: &mut Providers) {
@@ -230,6 +231,7 @@ fn mir_validated<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId) -> &'tcx
     run_passes(tcx, &mut mir, def_id, MirPhase::Validated, &[
         // What we need to run borrowck etc.
         &qualify_consts::QualifyAndPromoteConstants,
+        &lint_simd_index::LintDynamicSimdIndex,
         &simplify::SimplifyCfg::new("qualify-consts"),
     ]);
     tcx.alloc_steal_mir(mir)
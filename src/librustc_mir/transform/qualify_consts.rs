@@ -893,6 +893,7 @@ impl<'a, 'tcx> Visitor<'tcx> for Qualifier<'a, 'tcx, 'tcx> {
             let fn_ty = func.ty(self.mir, self.tcx);
             let mut callee_def_id = None;
             let mut is_shuffle = false;
+            let mut constant_intrinsic_argument = None;
             let mut is_const_fn = false;
             let mut is_promotable_const_fn = false;
             if let ty::FnDef(def_id, _) = fn_ty.sty {
@@ -905,7 +906,15 @@ impl<'a, 'tcx> Visitor<'tcx> for Qualifier<'a, 'tcx, 'tcx> {
                             | "size_of"
                             | "min_align_of"
                             | "needs_drop"
+                            | "has_niche"
+                            | "niche_offset"
+                            | "niche_range_start"
+                            | "niche_range_end"
                             | "type_id"
+                            | "type_id128"
+                            | "const_allocate"
+                            | "const_deallocate"
+                            | "is_val_statically_known"
                             | "bswap"
                             | "bitreverse"
                             | "ctpop"
@@ -947,6 +956,15 @@ impl<'a, 'tcx> Visitor<'tcx> for Qualifier<'a, 'tcx, 'tcx> {
                                 is_shuffle = true;
                             }
 
+                            "simd_gather_with_align" | "simd_scatter_with_align" => {
+                                constant_intrinsic_argument = Some(3);
+                            }
+
+                            "fadd_flags" | "fsub_flags" | "fmul_flags" | "fdiv_flags" |
+                            "frem_flags" => {
+                                constant_intrinsic_argument = Some(2);
+                            }
+
                             _ => {}
                         }
                     }
@@ -1034,9 +1052,14 @@ impl<'a, 'tcx> Visitor<'tcx> for Qualifier<'a, 'tcx, 'tcx> {
                 }
             }
 
-            let constant_arguments = callee_def_id.and_then(|id| {
-                args_required_const(self.tcx, id)
-            });
+            let constant_arguments = if let Some(index) = constant_intrinsic_argument {
+                // The alignment of `simd_{gather,scatter}_with_align` and the
+                // flags of `f*_flags`, like the shuffle indices, have to be
+                // known when the intrinsic is lowered.
+                Some(vec![index].into_iter().collect())
+            } else {
+                callee_def_id.and_then(|id| args_required_const(self.tcx, id))
+            };
             for (i, arg) in args.iter().enumerate() {
                 self.nest(|this| {
                     this.visit_operand(arg, location);
@@ -1220,6 +1243,7 @@ impl<'a, 'tcx> Visitor<'tcx> for Qualifier<'a, 'tcx, 'tcx> {
                 StatementKind::Retag { .. } |
                 StatementKind::EscapeToRaw { .. } |
                 StatementKind::AscribeUserType(..) |
+                StatementKind::LoopHint(_) |
                 StatementKind::Nop => {}
             }
         });
@@ -1328,6 +1352,10 @@ impl MirPass for QualifyAndPromoteConstants {
 
             // Do the actual promotion, now that we know what's viable.
             promote_consts::promote_candidates(mir, tcx, temps, candidates);
+
+            if mode == Mode::Fn {
+                check_shuffle_indices(tcx, def_id, mir);
+            }
         } else {
             let promoted_temps = if mode == Mode::Const {
                 // Already computed by `mir_const_qualif`.
@@ -1389,6 +1417,60 @@ impl MirPass for QualifyAndPromoteConstants {
     }
 }
 
+/// Reports out-of-bounds `simd_shuffle` indices before monomorphization, so
+/// that they point at the index and also show up in `cargo check`. This works
+/// when the input vector type is known and the index array was promoted from
+/// literals; generic shuffles are still checked during codegen.
+fn check_shuffle_indices<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                   def_id: DefId,
+                                   mir: &Mir<'tcx>) {
+    let param_env = tcx.param_env(def_id);
+    for data in mir.basic_blocks() {
+        let (func, args) = match data.terminator().kind {
+            TerminatorKind::Call { ref func, ref args, .. } => (func, args),
+            _ => continue,
+        };
+        match func.ty(mir, tcx).sty {
+            ty::FnDef(callee, _) if tcx.fn_sig(callee).abi() == Abi::PlatformIntrinsic &&
+                tcx.item_name(callee).as_str().starts_with("simd_shuffle") => {}
+            _ => continue,
+        }
+        let in_ty = args[0].ty(mir, tcx);
+        if !in_ty.is_simd() || in_ty.is_scalable_simd() {
+            continue;
+        }
+        let promoted = match args[2] {
+            Operand::Copy(Place::Promoted(box (index, _))) |
+            Operand::Move(Place::Promoted(box (index, _))) => &mir.promoted[index],
+            _ => continue,
+        };
+        let indices = promoted.basic_blocks().iter()
+            .flat_map(|data| &data.statements)
+            .filter_map(|statement| match statement.kind {
+                StatementKind::Assign(Place::Local(RETURN_PLACE), box ref rvalue) => Some(rvalue),
+                _ => None,
+            })
+            .filter_map(|rvalue| match *rvalue {
+                Rvalue::Aggregate(box AggregateKind::Array(_), ref indices) => Some(indices),
+                _ => None,
+            })
+            .next();
+        let limit = in_ty.simd_size(tcx) as u128 * 2;
+        for (i, index) in indices.into_iter().flatten().enumerate() {
+            if let Operand::Constant(ref constant) = *index {
+                match constant.literal.assert_bits(tcx, param_env.and(constant.ty)) {
+                    Some(bits) if bits >= limit => {
+                        tcx.sess.span_err(constant.span,
+                                          &format!("shuffle index #{} is out of bounds \
+                                                    (limit {})", i, limit));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
 fn args_required_const(tcx: TyCtxt, def_id: DefId) -> Option<FxHashSet<usize>> {
     let attrs = tcx.get_attrs(def_id);
     let attr = attrs.iter().find(|a| a.check_name("rustc_args_required_const"))?;
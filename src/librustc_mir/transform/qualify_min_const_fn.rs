@@ -244,6 +244,7 @@ fn check_statement(
         | StatementKind::Retag { .. }
         | StatementKind::EscapeToRaw { .. }
         | StatementKind::AscribeUserType(..)
+        | StatementKind::LoopHint(_)
         | StatementKind::Nop => Ok(()),
     }
 }
@@ -335,6 +336,7 @@ fn check_terminator(
             from_hir_call: _,
             destination: _,
             cleanup: _,
+            inline: _,
         } => {
             let fn_ty = func.ty(mir, tcx);
             if let ty::FnDef(def_id, _) = fn_ty.sty {
@@ -53,6 +53,7 @@ impl RemoveNoopLandingPads {
                 StatementKind::StorageLive(_) |
                 StatementKind::StorageDead(_) |
                 StatementKind::AscribeUserType(..) |
+                StatementKind::LoopHint(_) |
                 StatementKind::Nop => {
                     // These are all nops in a landing pad
                 }
@@ -72,3 +72,23 @@ fn is_within_packed<'a, 'tcx, L>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
     false
 }
+
+/// Return `true` if this place is a bit-field, which has no address of its
+/// own and so can't be borrowed.
+pub fn is_bit_field<'a, 'tcx, L>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
+                                 local_decls: &L,
+                                 place: &Place<'tcx>)
+                                 -> bool
+    where L: HasLocalDecls<'tcx>
+{
+    if let &Place::Projection(box Projection {
+        ref base, elem: ProjectionElem::Field(field, _)
+    }) = place {
+        let ty = base.ty(local_decls, tcx).to_ty(tcx);
+        if let ty::Adt(def, _) = ty.sty {
+            return def.is_struct() && def.repr.c() &&
+                def.non_enum_variant().fields[field.index()].bit_width(tcx).is_some();
+        }
+    }
+    false
+}
@@ -18,6 +18,7 @@ use rustc::ty::layout::VariantIdx;
 use rustc::ty::subst::Substs;
 use rustc::ty::util::IntTypeExt;
 use rustc_data_structures::indexed_vec::Idx;
+use syntax::attr::InlineAttr;
 use util::patch::MirPatch;
 
 use std::u32;
@@ -547,6 +548,7 @@ impl<'l, 'b, 'tcx, D> DropCtxt<'l, 'b, 'tcx, D>
                     destination: Some((unit_temp, succ)),
                     cleanup: unwind.into_option(),
                     from_hir_call: true,
+                    inline: InlineAttr::None,
                 },
                 source_info: self.source_info,
             }),
@@ -907,6 +909,7 @@ impl<'l, 'b, 'tcx, D> DropCtxt<'l, 'b, 'tcx, D>
             destination: Some((unit_temp, target)),
             cleanup: None,
             from_hir_call: false,
+            inline: InlineAttr::None,
         }; // FIXME(#43234)
         let free_block = self.new_block(unwind, call);
 
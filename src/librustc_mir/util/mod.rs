@@ -23,7 +23,7 @@ pub(crate) mod pretty;
 pub mod liveness;
 pub mod collect_writes;
 
-pub use self::alignment::is_disaligned;
+pub use self::alignment::{is_bit_field, is_disaligned};
 pub use self::pretty::{dump_enabled, dump_mir, write_mir_pretty, PassWhere};
 pub use self::graphviz::{write_mir_graphviz};
 pub use self::graphviz::write_node_label as write_graphviz_node_label;
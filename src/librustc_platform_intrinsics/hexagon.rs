@@ -2929,6 +2929,166 @@ pub fn find(name: &str) -> Option<Intrinsic> {
             output: &::U16x64,
             definition: Named("llvm.hexagon.V6.vxor.128B")
         },
+        "Vb_vsplat_R64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 1] = [&::I32]; &INPUTS },
+            output: &::I8x64,
+            definition: Named("llvm.hexagon.V6.lvsplatb")
+        },
+        "Vh_vsplat_R64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 1] = [&::I32]; &INPUTS },
+            output: &::I16x32,
+            definition: Named("llvm.hexagon.V6.lvsplath")
+        },
+        "Vb_vsplat_R128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 1] = [&::I32]; &INPUTS },
+            output: &::I8x128,
+            definition: Named("llvm.hexagon.V6.lvsplatb.128B")
+        },
+        "Vh_vsplat_R128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 1] = [&::I32]; &INPUTS },
+            output: &::I16x64,
+            definition: Named("llvm.hexagon.V6.lvsplath.128B")
+        },
+        "Vb_vadd_VbVb_sat64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x64, &::I8x64]; &INPUTS },
+            output: &::I8x64,
+            definition: Named("llvm.hexagon.V6.vaddbsat")
+        },
+        "Vb_vadd_VbVb_sat128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x128, &::I8x128]; &INPUTS },
+            output: &::I8x128,
+            definition: Named("llvm.hexagon.V6.vaddbsat.128B")
+        },
+        "Vuw_vadd_VuwVuw_sat64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x16, &::U32x16]; &INPUTS },
+            output: &::U32x16,
+            definition: Named("llvm.hexagon.V6.vadduwsat")
+        },
+        "Vuw_vadd_VuwVuw_sat128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x32, &::U32x32]; &INPUTS },
+            output: &::U32x32,
+            definition: Named("llvm.hexagon.V6.vadduwsat.128B")
+        },
+        "Wb_vadd_WbWb_sat64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x128, &::I8x128]; &INPUTS },
+            output: &::I8x128,
+            definition: Named("llvm.hexagon.V6.vaddbsat.dv")
+        },
+        "Wb_vadd_WbWb_sat128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x256, &::I8x256]; &INPUTS },
+            output: &::I8x256,
+            definition: Named("llvm.hexagon.V6.vaddbsat.dv.128B")
+        },
+        "Wuw_vadd_WuwWuw_sat64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x32, &::U32x32]; &INPUTS },
+            output: &::U32x32,
+            definition: Named("llvm.hexagon.V6.vadduwsat.dv")
+        },
+        "Wuw_vadd_WuwWuw_sat128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x64, &::U32x64]; &INPUTS },
+            output: &::U32x64,
+            definition: Named("llvm.hexagon.V6.vadduwsat.dv.128B")
+        },
+        "Vb_vsub_VbVb_sat64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x64, &::I8x64]; &INPUTS },
+            output: &::I8x64,
+            definition: Named("llvm.hexagon.V6.vsubbsat")
+        },
+        "Vb_vsub_VbVb_sat128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x128, &::I8x128]; &INPUTS },
+            output: &::I8x128,
+            definition: Named("llvm.hexagon.V6.vsubbsat.128B")
+        },
+        "Vuw_vsub_VuwVuw_sat64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x16, &::U32x16]; &INPUTS },
+            output: &::U32x16,
+            definition: Named("llvm.hexagon.V6.vsubuwsat")
+        },
+        "Vuw_vsub_VuwVuw_sat128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x32, &::U32x32]; &INPUTS },
+            output: &::U32x32,
+            definition: Named("llvm.hexagon.V6.vsubuwsat.128B")
+        },
+        "Wb_vsub_WbWb_sat64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x128, &::I8x128]; &INPUTS },
+            output: &::I8x128,
+            definition: Named("llvm.hexagon.V6.vsubbsat.dv")
+        },
+        "Wb_vsub_WbWb_sat128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x256, &::I8x256]; &INPUTS },
+            output: &::I8x256,
+            definition: Named("llvm.hexagon.V6.vsubbsat.dv.128B")
+        },
+        "Wuw_vsub_WuwWuw_sat64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x32, &::U32x32]; &INPUTS },
+            output: &::U32x32,
+            definition: Named("llvm.hexagon.V6.vsubuwsat.dv")
+        },
+        "Wuw_vsub_WuwWuw_sat128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x64, &::U32x64]; &INPUTS },
+            output: &::U32x64,
+            definition: Named("llvm.hexagon.V6.vsubuwsat.dv.128B")
+        },
+        "Vub_vlsr_VubR64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U8x64, &::U32]; &INPUTS },
+            output: &::U8x64,
+            definition: Named("llvm.hexagon.V6.vlsrb")
+        },
+        "Vub_vlsr_VubR128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U8x128, &::U32]; &INPUTS },
+            output: &::U8x128,
+            definition: Named("llvm.hexagon.V6.vlsrb.128B")
+        },
+        "Vb_vmax_VbVb64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x64, &::I8x64]; &INPUTS },
+            output: &::I8x64,
+            definition: Named("llvm.hexagon.V6.vmaxb")
+        },
+        "Vb_vmax_VbVb128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x128, &::I8x128]; &INPUTS },
+            output: &::I8x128,
+            definition: Named("llvm.hexagon.V6.vmaxb.128B")
+        },
+        "Vb_vmin_VbVb64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x64, &::I8x64]; &INPUTS },
+            output: &::I8x64,
+            definition: Named("llvm.hexagon.V6.vminb")
+        },
+        "Vb_vmin_VbVb128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::I8x128, &::I8x128]; &INPUTS },
+            output: &::I8x128,
+            definition: Named("llvm.hexagon.V6.vminb.128B")
+        },
+        "Vub_vround_VuhVuh_sat64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U16x32, &::U16x32]; &INPUTS },
+            output: &::U8x64,
+            definition: Named("llvm.hexagon.V6.vrounduhub")
+        },
+        "Vuh_vround_VuwVuw_sat64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x16, &::U32x16]; &INPUTS },
+            output: &::U16x32,
+            definition: Named("llvm.hexagon.V6.vrounduwuh")
+        },
+        "Vub_vround_VuhVuh_sat128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U16x64, &::U16x64]; &INPUTS },
+            output: &::U8x128,
+            definition: Named("llvm.hexagon.V6.vrounduhub.128B")
+        },
+        "Vuh_vround_VuwVuw_sat128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x32, &::U32x32]; &INPUTS },
+            output: &::U16x64,
+            definition: Named("llvm.hexagon.V6.vrounduwuh.128B")
+        },
+        "Vuh_vsat_VuwVuw64" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x16, &::U32x16]; &INPUTS },
+            output: &::U16x32,
+            definition: Named("llvm.hexagon.V6.vsatuwuh")
+        },
+        "Vuh_vsat_VuwVuw128" => Intrinsic {
+            inputs: { static INPUTS: [&'static Type; 2] = [&::U32x32, &::U32x32]; &INPUTS },
+            output: &::U16x64,
+            definition: Named("llvm.hexagon.V6.vsatuwuh.128B")
+        },
         _ => return None,
     })
 }
@@ -11,9 +11,35 @@
 #![allow(non_upper_case_globals)]
 
 use abi::call::{FnType, ArgType};
+use abi::Abi;
+
+/// Whether `arg` is a vector that fits an HVX vector register or register
+/// pair, in either the 64-byte or the 128-byte vector length mode. These are
+/// passed and returned in HVX registers.
+fn is_hvx_vector<Ty>(arg: &ArgType<Ty>) -> bool {
+    match arg.layout.abi {
+        Abi::Vector { .. } => {
+            let bits = arg.layout.size.bits();
+            bits == 512 || bits == 1024 || bits == 2048
+        }
+        _ => false,
+    }
+}
+
+/// Whether `arg` has to be passed in memory rather than in registers.
+fn is_indirect<Ty>(arg: &ArgType<Ty>) -> bool {
+    if is_hvx_vector(arg) {
+        return false;
+    }
+    let in_memory = match arg.layout.abi {
+        Abi::Vector { .. } => true,
+        _ => arg.layout.is_aggregate(),
+    };
+    in_memory && arg.layout.size.bits() > 64
+}
 
 fn classify_ret_ty<Ty>(ret: &mut ArgType<Ty>) {
-    if ret.layout.is_aggregate() && ret.layout.size.bits() > 64 {
+    if is_indirect(ret) {
         ret.make_indirect();
     } else {
         ret.extend_integer_width_to(32);
@@ -21,7 +47,7 @@ fn classify_ret_ty<Ty>(ret: &mut ArgType<Ty>) {
 }
 
 fn classify_arg_ty<Ty>(arg: &mut ArgType<Ty>) {
-    if arg.layout.is_aggregate() && arg.layout.size.bits() > 64 {
+    if is_indirect(arg) {
         arg.make_indirect();
     } else {
         arg.extend_integer_width_to(32);
@@ -480,6 +480,10 @@ pub struct FnType<'a, Ty> {
     pub variadic: bool,
 
     pub conv: Conv,
+
+    /// The target features enabled for the function, as `+feature`, which
+    /// some C ABIs pass wider vectors in registers with.
+    pub target_features: Vec<String>,
 }
 
 impl<'a, Ty> FnType<'a, Ty> {
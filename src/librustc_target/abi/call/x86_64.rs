@@ -11,8 +11,9 @@
 // The classification code for the x86_64 ABI is taken from the clay language
 // https://github.com/jckarter/clay/blob/master/compiler/src/externals.cpp
 
-use abi::call::{ArgType, CastTarget, FnType, Reg, RegKind};
+use abi::call::{ArgType, CastTarget, FnType, PassMode, Reg, RegKind};
 use abi::{self, Abi, HasDataLayout, LayoutOf, Size, TyLayout, TyLayoutMethods};
+use spec::HasTargetSpec;
 
 /// Classification of "eightbyte" components.
 // NB: the order of the variants is from general to specific,
@@ -31,16 +32,46 @@ struct Memory;
 const LARGEST_VECTOR_SIZE: usize = 512;
 const MAX_EIGHTBYTES: usize = LARGEST_VECTOR_SIZE / 64;
 
-fn classify_arg<'a, Ty, C>(cx: &C, arg: &ArgType<'a, Ty>)
+/// The widest vector, in eightbytes, which the features enabled for the
+/// function, on top of the target's baseline ones, let us pass in a single
+/// register (XMM, YMM or ZMM), like C compilers do.
+fn native_vector_eightbytes<C: HasTargetSpec>(cx: &C, fn_features: &[String]) -> usize {
+    let features = &cx.target_spec().options.features;
+    let has = |feature: &str| {
+        features.split(',').chain(fn_features.iter().map(|f| &f[..])).any(|f| f == feature)
+    };
+    if has("+avx512f") {
+        512 / 64
+    } else if has("+avx") || has("+avx2") {
+        256 / 64
+    } else {
+        128 / 64
+    }
+}
+
+/// Merges the class of a field into that of the eightbyte it overlaps.
+/// A missing class is NO_CLASS, while MEMORY is handled by the caller.
+fn merge(old: Option<Class>, new: Class) -> Class {
+    match old {
+        // If one class is NO_CLASS, the resulting class is the other class.
+        None => new,
+        // If one class is INTEGER, the result is INTEGER, otherwise SSE
+        // wins over SSEUP (the order of the variants encodes this).
+        Some(old) => old.min(new),
+    }
+}
+
+fn classify_arg<'a, Ty, C>(cx: &C, arg: &ArgType<'a, Ty>, vector_eightbytes: usize)
                           -> Result<[Option<Class>; MAX_EIGHTBYTES], Memory>
     where Ty: TyLayoutMethods<'a, C> + Copy,
-          C: LayoutOf<Ty = Ty, TyLayout = TyLayout<'a, Ty>> + HasDataLayout
+          C: LayoutOf<Ty = Ty, TyLayout = TyLayout<'a, Ty>> + HasDataLayout + HasTargetSpec
 {
     fn classify<'a, Ty, C>(cx: &C, layout: TyLayout<'a, Ty>,
                           cls: &mut [Option<Class>], off: Size) -> Result<(), Memory>
         where Ty: TyLayoutMethods<'a, C> + Copy,
             C: LayoutOf<Ty = Ty, TyLayout = TyLayout<'a, Ty>> + HasDataLayout
     {
+        // Unaligned fields make the whole argument MEMORY.
         if !off.is_aligned(layout.align.abi) {
             if !layout.is_zst() {
                 return Err(Memory);
@@ -67,6 +98,15 @@ fn classify_arg<'a, Ty, C>(cx: &C, arg: &ArgType<'a, Ty>)
                     abi::Variants::Single { .. } => {
                         for i in 0..layout.fields.count() {
                             let field_off = off + layout.fields.offset(i);
+                            // Bit-fields are integers, but needn't be aligned.
+                            if let Some(bit_field) = layout.fields.bit_field(i) {
+                                let first = (field_off.bytes() / 8) as usize;
+                                let last = ((field_off + bit_field.storage).bytes() - 1) / 8;
+                                for cls in &mut cls[first..=last as usize] {
+                                    *cls = Some(merge(*cls, Class::Int));
+                                }
+                                continue;
+                            }
                             classify(cx, layout.field(cx, i), cls, field_off)?;
                         }
                         return Ok(());
@@ -82,7 +122,7 @@ fn classify_arg<'a, Ty, C>(cx: &C, arg: &ArgType<'a, Ty>)
         let first = (off.bytes() / 8) as usize;
         let last = ((off.bytes() + layout.size.bytes() - 1) / 8) as usize;
         for cls in &mut cls[first..=last] {
-            *cls = Some(cls.map_or(c, |old| old.min(c)));
+            *cls = Some(merge(*cls, c));
 
             // Everything after the first Sse "eightbyte"
             // component is the upper half of a register.
@@ -101,7 +141,15 @@ fn classify_arg<'a, Ty, C>(cx: &C, arg: &ArgType<'a, Ty>)
 
     let mut cls = [None; MAX_EIGHTBYTES];
     classify(cx, arg.layout, &mut cls, Size::ZERO)?;
+
+    // Post-merger cleanup, following the psABI:
     if n > 2 {
+        // An argument larger than two eightbytes is only passed in registers
+        // if it is a single vector which fits in one of the target's vector
+        // registers, i.e. its eightbytes are classified SSE, SSEUP, ... SSEUP.
+        if n > vector_eightbytes {
+            return Err(Memory);
+        }
         if cls[0] != Some(Class::Sse) {
             return Err(Memory);
         }
@@ -109,15 +157,15 @@ fn classify_arg<'a, Ty, C>(cx: &C, arg: &ArgType<'a, Ty>)
             return Err(Memory);
         }
     } else {
-        let mut i = 0;
-        while i < n {
+        // An SSEUP eightbyte which isn't preceded by SSE or SSEUP is
+        // converted to SSE.
+        for i in 0..n {
             if cls[i] == Some(Class::SseUp) {
-                cls[i] = Some(Class::Sse);
-            } else if cls[i] == Some(Class::Sse) {
-                i += 1;
-                while i != n && cls[i] == Some(Class::SseUp) { i += 1; }
-            } else {
-                i += 1;
+                let preceded = i > 0 && (cls[i - 1] == Some(Class::Sse) ||
+                                         cls[i - 1] == Some(Class::SseUp));
+                if !preceded {
+                    cls[i] = Some(Class::Sse);
+                }
             }
         }
     }
@@ -180,13 +228,14 @@ fn cast_target(cls: &[Option<Class>], size: Size) -> CastTarget {
 
 pub fn compute_abi_info<'a, Ty, C>(cx: &C, fty: &mut FnType<'a, Ty>)
     where Ty: TyLayoutMethods<'a, C> + Copy,
-          C: LayoutOf<Ty = Ty, TyLayout = TyLayout<'a, Ty>> + HasDataLayout
+          C: LayoutOf<Ty = Ty, TyLayout = TyLayout<'a, Ty>> + HasDataLayout + HasTargetSpec
 {
     let mut int_regs = 6; // RDI, RSI, RDX, RCX, R8, R9
     let mut sse_regs = 8; // XMM0-7
+    let vector_eightbytes = native_vector_eightbytes(cx, &fty.target_features);
 
     let mut x86_64_ty = |arg: &mut ArgType<'a, Ty>, is_arg: bool| {
-        let mut cls_or_mem = classify_arg(cx, arg);
+        let mut cls_or_mem = classify_arg(cx, arg, vector_eightbytes);
 
         let mut needed_int = 0;
         let mut needed_sse = 0;
@@ -199,7 +248,17 @@ pub fn compute_abi_info<'a, Ty, C>(cx: &C, fty: &mut FnType<'a, Ty>)
                         _ => {}
                     }
                 }
-                if arg.layout.is_aggregate() && (int_regs < needed_int || sse_regs < needed_sse) {
+                // Like aggregates, `__int128` is never split between the last
+                // register and the stack.
+                let is_int128 = match arg.layout.abi {
+                    Abi::Scalar(ref scalar) => match scalar.value {
+                        abi::Int(abi::I128, _) => true,
+                        _ => false,
+                    },
+                    _ => false,
+                };
+                if (arg.layout.is_aggregate() || is_int128) &&
+                   (int_regs < needed_int || sse_regs < needed_sse) {
                     cls_or_mem = Err(Memory);
                 }
             }
@@ -209,6 +268,14 @@ pub fn compute_abi_info<'a, Ty, C>(cx: &C, fty: &mut FnType<'a, Ty>)
             Err(Memory) => {
                 if is_arg {
                     arg.make_indirect_byval();
+                    // Without an explicit alignment LLVM aligns the copy on the
+                    // stack as its data layout says, which need not know that
+                    // `i128` is 16-byte aligned.
+                    if arg.layout.align.abi.bytes() > 8 {
+                        if let PassMode::Indirect(ref mut attrs, _) = arg.mode {
+                            attrs.pointee_align = Some(arg.layout.align.abi);
+                        }
+                    }
                 } else {
                     // `sret` parameter thus one less integer register available
                     arg.make_indirect();
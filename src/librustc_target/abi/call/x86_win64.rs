@@ -8,8 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use abi::call::{ArgType, FnType, Reg};
-use abi::Abi;
+use abi::call::{ArgType, FnType, Reg, RegKind};
+use abi::{Abi, Size};
 
 // Win64 ABI: http://msdn.microsoft.com/en-us/library/zthk2dkh.aspx
 
@@ -42,7 +42,17 @@ pub fn compute_abi_info<Ty>(fty: &mut FnType<Ty>) {
     };
 
     if !fty.ret.is_ignore() {
-        fixup(&mut fty.ret);
+        match fty.ret.layout.abi {
+            // Like Clang (and MinGW's GCC), return `__int128` in `xmm0`
+            // instead of through a hidden pointer.
+            Abi::Scalar(_) if fty.ret.layout.size.bytes() == 16 => {
+                fty.ret.cast_to(Reg {
+                    kind: RegKind::Vector,
+                    size: Size::from_bytes(16)
+                });
+            }
+            _ => fixup(&mut fty.ret)
+        }
     }
     for arg in &mut fty.args {
         if arg.is_ignore() { continue; }
@@ -40,6 +40,8 @@ pub struct TargetDataLayout {
     pub vector_align: Vec<(Size, AbiAndPrefAlign)>,
 
     pub instruction_address_space: u32,
+    /// The address space globals are placed in unless told otherwise.
+    pub globals_address_space: u32,
 }
 
 impl Default for TargetDataLayout {
@@ -64,6 +66,7 @@ impl Default for TargetDataLayout {
                 (Size::from_bits(128), AbiAndPrefAlign::new(align(128))),
             ],
             instruction_address_space: 0,
+            globals_address_space: 0,
         }
     }
 }
@@ -119,6 +122,9 @@ impl TargetDataLayout {
                 [p] if p.starts_with("P") => {
                     dl.instruction_address_space = parse_address_space(&p[1..], "P")?
                 }
+                [g] if g.starts_with("G") => {
+                    dl.globals_address_space = parse_address_space(&g[1..], "G")?
+                }
                 ["a", ref a..] => dl.aggregate_align = align(a, "a")?,
                 ["f32", ref a..] => dl.f32_align = align(a, "f32")?,
                 ["f64", ref a..] => dl.f64_align = align(a, "f64")?,
@@ -672,6 +678,19 @@ impl Scalar {
     }
 }
 
+/// Where the bits of a bit-field (a field declared with `#[bits(N)]`) are,
+/// relative to the field's offset.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitField {
+    /// The bytes from the field's offset on that hold any of its bits. They're
+    /// accessed together, as one integer in the target's byte order.
+    pub storage: Size,
+    /// The position of the field's lowest bit in that integer.
+    pub shift: u64,
+    /// The number of bits in the field.
+    pub width: u64,
+}
+
 /// Describes how the fields of a type are located in memory.
 #[derive(PartialEq, Eq, Hash, Debug)]
 pub enum FieldPlacement {
@@ -688,7 +707,8 @@ pub enum FieldPlacement {
 
     /// Struct-like placement, with precomputed offsets.
     ///
-    /// Fields are guaranteed to not overlap, but note that gaps
+    /// Fields are guaranteed to not overlap, except for bit-fields sharing
+    /// bytes with each other, but note that gaps
     /// before, between and after all the fields are NOT always
     /// padding, and as such their contents may not be discarded.
     /// For example, enum variants leave a gap at the start,
@@ -703,7 +723,11 @@ pub enum FieldPlacement {
         /// Maps source order field indices to memory order indices,
         /// depending how fields were permuted.
         // FIXME(camlorn) also consider small vector  optimization here.
-        memory_index: Vec<u32>
+        memory_index: Vec<u32>,
+
+        /// Where the bits of each field are if it's a bit-field, in source
+        /// order. Empty unless there are bit-fields.
+        bit_fields: Vec<Option<BitField>>
     }
 }
 
@@ -744,6 +768,25 @@ impl FieldPlacement {
         }
     }
 
+    /// Where the bits of field `i` are, if it's a bit-field.
+    pub fn bit_field(&self, i: usize) -> Option<BitField> {
+        match *self {
+            FieldPlacement::Arbitrary { ref bit_fields, .. } => {
+                bit_fields.get(i).cloned().and_then(|bit_field| bit_field)
+            }
+            _ => None
+        }
+    }
+
+    /// Whether any of the fields are bit-fields, in which case fields can
+    /// share bytes and have to be accessed by their byte offsets.
+    pub fn has_bit_fields(&self) -> bool {
+        match *self {
+            FieldPlacement::Arbitrary { ref bit_fields, .. } => !bit_fields.is_empty(),
+            _ => false
+        }
+    }
+
     /// Get source indices of the fields by increasing offsets.
     #[inline]
     pub fn index_by_increasing_offset<'a>(&'a self) -> impl Iterator<Item=usize>+'a {
@@ -931,6 +974,43 @@ impl<'a, Ty> TyLayout<'a, Ty> {
     where Ty: TyLayoutMethods<'a, C>, C: LayoutOf<Ty = Ty> {
         Ty::field(self, cx, i)
     }
+
+    /// Returns false if an all-zero bit pattern is known to be invalid for
+    /// this layout, e.g. because the type is uninhabited or contains a
+    /// reference. Only the scalars reachable through structs, tuples and
+    /// arrays are inspected, so `true` does not guarantee validity.
+    pub fn might_permit_zero_init<C>(self, cx: &C) -> bool
+    where Ty: TyLayoutMethods<'a, C>, C: LayoutOf<Ty = Ty, TyLayout = Self> {
+        let scalar_allows_zero = |s: &Scalar| {
+            // The valid range wraps around, so it contains zero if it
+            // starts at zero or wraps past the maximum value.
+            *s.valid_range.start() == 0 || s.valid_range.start() > s.valid_range.end()
+        };
+        match self.abi {
+            Abi::Uninhabited => false,
+            Abi::Scalar(ref s) => scalar_allows_zero(s),
+            Abi::ScalarPair(ref a, ref b) => scalar_allows_zero(a) && scalar_allows_zero(b),
+            Abi::Vector { ref element, .. } => scalar_allows_zero(element),
+            Abi::Aggregate { .. } => {
+                match self.variants {
+                    Variants::Single { .. } => {}
+                    // A zeroed tag or niche selects some variant, whose
+                    // fields we don't look into.
+                    Variants::Tagged { .. } | Variants::NicheFilling { .. } => return true,
+                }
+                match self.fields {
+                    // None of the fields of a union has to be valid.
+                    FieldPlacement::Union(_) => true,
+                    FieldPlacement::Array { count, .. } => {
+                        count == 0 || self.field(cx, 0).might_permit_zero_init(cx)
+                    }
+                    FieldPlacement::Arbitrary { ref offsets, .. } => {
+                        (0..offsets.len()).all(|i| self.field(cx, i).might_permit_zero_init(cx))
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl<'a, Ty> TyLayout<'a, Ty> {
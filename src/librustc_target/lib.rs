@@ -43,3 +43,4 @@ extern crate rustc_data_structures;
 
 pub mod abi;
 pub mod spec;
+pub mod target_features;
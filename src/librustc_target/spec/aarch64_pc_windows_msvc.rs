@@ -12,7 +12,7 @@ use spec::{LinkerFlavor, Target, TargetResult, PanicStrategy};
 
 pub fn target() -> TargetResult {
     let mut base = super::windows_msvc_base::opts();
-    base.max_atomic_width = Some(64);
+    base.max_atomic_width = Some(128); // ldxp/stxp are part of the base ISA
     base.has_elf_tls = true;
 
     // FIXME: this shouldn't be panic=abort, it should be panic=unwind
@@ -0,0 +1,40 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use spec::{LinkerFlavor, Target, TargetResult};
+
+pub fn target() -> TargetResult {
+    let mut base = super::apple_base::opts();
+    base.cpu = "cyclone".to_string();
+    // arm64e is ARMv8.3, which adds the pointer authentication instructions.
+    base.features = "+neon,+fp-armv8,+v8.3a".to_string();
+    base.max_atomic_width = Some(128);
+    base.eliminate_frame_pointer = false;
+    base.abi_blacklist = super::arm_base::abi_blacklist();
+    base.ptrauth_returns = true;
+    // Makes the linker mark the Mach-O image with the arm64e CPU subtype, which
+    // the loader requires before it will run pointer-authenticated code.
+    base.pre_link_args.insert(LinkerFlavor::Gcc, vec!["-arch".to_string(),
+                                                      "arm64e".to_string()]);
+
+    Ok(Target {
+        llvm_target: "arm64e-apple-macosx".to_string(),
+        target_endian: "little".to_string(),
+        target_pointer_width: "64".to_string(),
+        target_c_int_width: "32".to_string(),
+        data_layout: "e-m:o-i64:64-i128:128-n32:64-S128".to_string(),
+        arch: "aarch64".to_string(),
+        target_os: "macos".to_string(),
+        target_env: String::new(),
+        target_vendor: "apple".to_string(),
+        linker_flavor: LinkerFlavor::Gcc,
+        options: base,
+    })
+}
@@ -226,6 +226,45 @@ impl ToJson for RelroLevel {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Hash, RustcEncodable, RustcDecodable)]
+pub enum AbortStrategy {
+    /// `llvm.trap`, an illegal instruction on most targets.
+    Trap,
+    /// `llvm.debugtrap`, a breakpoint (e.g. `brk #0xf000` on AArch64).
+    DebugTrap,
+    /// A call to the C library's `abort` function.
+    Call,
+}
+
+impl AbortStrategy {
+    pub fn desc(&self) -> &str {
+        match *self {
+            AbortStrategy::Trap => "trap",
+            AbortStrategy::DebugTrap => "debug-trap",
+            AbortStrategy::Call => "call",
+        }
+    }
+}
+
+impl FromStr for AbortStrategy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<AbortStrategy, ()> {
+        match s {
+            "trap" => Ok(AbortStrategy::Trap),
+            "debug-trap" => Ok(AbortStrategy::DebugTrap),
+            "call" => Ok(AbortStrategy::Call),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ToJson for AbortStrategy {
+    fn to_json(&self) -> Json {
+        self.desc().to_json()
+    }
+}
+
 pub type LinkArgs = BTreeMap<LinkerFlavor, Vec<String>>;
 pub type TargetResult = Result<Target, String>;
 
@@ -359,6 +398,7 @@ supported_targets! {
 
     ("x86_64-apple-darwin", x86_64_apple_darwin),
     ("i686-apple-darwin", i686_apple_darwin),
+    ("arm64e-apple-darwin", arm64e_apple_darwin),
 
     ("aarch64-fuchsia", aarch64_fuchsia),
     ("x86_64-fuchsia", x86_64_fuchsia),
@@ -627,6 +667,10 @@ pub struct TargetOptions {
     /// Panic strategy: "unwind" or "abort"
     pub panic_strategy: PanicStrategy,
 
+    /// How `intrinsics::abort` and compiler-inserted aborts are lowered:
+    /// "trap", "debug-trap" or "call". Defaults to "trap".
+    pub abort_strategy: AbortStrategy,
+
     /// A blacklist of ABIs unsupported by the current target. Note that generic
     /// ABIs are considered to be supported on all platforms and cannot be blacklisted.
     pub abi_blacklist: Vec<Abi>,
@@ -692,7 +736,12 @@ pub struct TargetOptions {
 
     /// If set, have the linker export exactly these symbols, instead of using
     /// the usual logic to figure this out from the crate itself.
-    pub override_export_symbols: Option<Vec<String>>
+    pub override_export_symbols: Option<Vec<String>>,
+
+    /// Whether functions sign and authenticate their return addresses using
+    /// the ARMv8.3 pointer authentication instructions, as required by the
+    /// arm64e ABI.
+    pub ptrauth_returns: bool,
 }
 
 impl Default for TargetOptions {
@@ -756,6 +805,7 @@ impl Default for TargetOptions {
             max_atomic_width: None,
             atomic_cas: true,
             panic_strategy: PanicStrategy::Unwind,
+            abort_strategy: AbortStrategy::Trap,
             abi_blacklist: vec![],
             crt_static_allows_dylibs: false,
             crt_static_default: false,
@@ -775,10 +825,23 @@ impl Default for TargetOptions {
             requires_uwtable: false,
             simd_types_indirect: true,
             override_export_symbols: None,
+            ptrauth_returns: false,
         }
     }
 }
 
+/// Inline assembly entering and leaving a section of code that interrupts
+/// can't preempt.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CriticalSection {
+    /// Disables interrupts, writing the previous interrupt state to `$0`.
+    pub enter: &'static str,
+    /// Restores the interrupt state passed in `$0`.
+    pub exit: &'static str,
+    /// The size of the interrupt state in bits.
+    pub state_bits: u64,
+}
+
 impl Target {
     /// Given a function ABI, turn it into the correct ABI for this target.
     pub fn adjust_abi(&self, abi: Abi) -> Abi {
@@ -816,6 +879,26 @@ impl Target {
         self.options.max_atomic_width.unwrap_or_else(|| self.target_pointer_width.parse().unwrap())
     }
 
+    /// How to mask interrupts around a critical section on this target, if
+    /// known. Used to implement atomics on single-core targets without them.
+    ///
+    /// Masking interrupts only makes a section atomic on a single core, so
+    /// this is limited to bare-metal M-profile (Cortex-M) targets.
+    pub fn critical_section(&self) -> Option<CriticalSection> {
+        let m_profile = ["thumbv6m", "thumbv7m", "thumbv7em", "thumbv8m"]
+            .iter()
+            .any(|arch| self.llvm_target.starts_with(arch));
+        if self.arch == "arm" && m_profile && self.target_os == "none" {
+            Some(CriticalSection {
+                enter: "mrs $0, PRIMASK\n\tcpsid i",
+                exit: "msr PRIMASK, $0",
+                state_bits: 32,
+            })
+        } else {
+            None
+        }
+    }
+
     pub fn is_abi_supported(&self, abi: Abi) -> bool {
         abi.generic() || !self.options.abi_blacklist.contains(&abi)
     }
@@ -890,6 +973,19 @@ impl Target {
                 Some(Ok(()))
             })).unwrap_or(Ok(()))
             } );
+            ($key_name:ident, AbortStrategy) => ( {
+                let name = (stringify!($key_name)).replace("_", "-");
+                obj.find(&name[..]).and_then(|o| o.as_string().and_then(|s| {
+                    match s.parse::<AbortStrategy>() {
+                        Ok(strategy) => base.options.$key_name = strategy,
+                        _ => return Some(Err(format!("'{}' is not a valid value for \
+                                                      abort-strategy. Use 'trap', \
+                                                      'debug-trap' or 'call'.",
+                                                      s))),
+                    }
+                    Some(Ok(()))
+                })).unwrap_or(Ok(()))
+            } );
             ($key_name:ident, RelroLevel) => ( {
                 let name = (stringify!($key_name)).replace("_", "-");
                 obj.find(&name[..]).and_then(|o| o.as_string().and_then(|s| {
@@ -1049,6 +1145,7 @@ impl Target {
         key!(min_atomic_width, Option<u64>);
         key!(atomic_cas, bool);
         try!(key!(panic_strategy, PanicStrategy));
+        try!(key!(abort_strategy, AbortStrategy));
         key!(crt_static_allows_dylibs, bool);
         key!(crt_static_default, bool);
         key!(crt_static_respected, bool);
@@ -1066,6 +1163,7 @@ impl Target {
         key!(requires_uwtable, bool);
         key!(simd_types_indirect, bool);
         key!(override_export_symbols, opt_list);
+        key!(ptrauth_returns, bool);
 
         if let Some(array) = obj.find("abi-blacklist").and_then(Json::as_array) {
             for name in array.iter().filter_map(|abi| abi.as_string()) {
@@ -1260,6 +1358,7 @@ impl ToJson for Target {
         target_option_val!(max_atomic_width);
         target_option_val!(atomic_cas);
         target_option_val!(panic_strategy);
+        target_option_val!(abort_strategy);
         target_option_val!(crt_static_allows_dylibs);
         target_option_val!(crt_static_default);
         target_option_val!(crt_static_respected);
@@ -1277,6 +1376,7 @@ impl ToJson for Target {
         target_option_val!(requires_uwtable);
         target_option_val!(simd_types_indirect);
         target_option_val!(override_export_symbols);
+        target_option_val!(ptrauth_returns);
 
         if default.abi_blacklist != self.options.abi_blacklist {
             d.insert("abi-blacklist".to_string(), self.options.abi_blacklist.iter()
@@ -0,0 +1,147 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Relations between the target features accepted by `#[target_feature]`.
+//!
+//! Features are named the way Rust names them, not the way LLVM does; see
+//! `to_llvm_feature` in the LLVM backend for the mapping.
+
+/// Each feature along with the features enabling it directly implies.
+type Implications = &'static [(&'static str, &'static [&'static str])];
+
+/// Pairs of features that can't be enabled together.
+type Conflicts = &'static [(&'static str, &'static str)];
+
+const ARM_IMPLIED: Implications = &[
+    ("neon", &["vfp3"]),
+    ("vfp3", &["vfp2"]),
+    ("vfp4", &["vfp3"]),
+    ("v6k", &["v5te"]),
+    ("v6t2", &["v5te"]),
+    ("v7", &["v6k", "v6t2"]),
+];
+
+const ARM_CONFLICTS: Conflicts = &[
+    ("aclass", "mclass"),
+    ("aclass", "rclass"),
+    ("mclass", "rclass"),
+];
+
+const AARCH64_IMPLIED: Implications = &[
+    ("neon", &["fp"]),
+    ("crypto", &["neon"]),
+    ("dotprod", &["neon"]),
+    ("fp16", &["neon"]),
+    ("sve", &["fp16"]),
+    ("rdm", &["neon"]),
+    ("v8.1a", &["crc", "lse", "rdm"]),
+    ("v8.2a", &["v8.1a", "ras"]),
+    ("v8.3a", &["v8.2a", "rcpc"]),
+];
+
+const X86_IMPLIED: Implications = &[
+    ("sse2", &["sse"]),
+    ("sse3", &["sse2"]),
+    ("ssse3", &["sse3"]),
+    ("sse4.1", &["ssse3"]),
+    ("sse4.2", &["sse4.1"]),
+    ("sse4a", &["sse3"]),
+    ("avx", &["sse4.2"]),
+    ("avx2", &["avx"]),
+    ("fma", &["avx"]),
+    ("aes", &["sse2"]),
+    ("pclmulqdq", &["sse2"]),
+    ("sha", &["sse2"]),
+    ("avx512f", &["avx2", "fma"]),
+    ("avx512bw", &["avx512f"]),
+    ("avx512cd", &["avx512f"]),
+    ("avx512dq", &["avx512f"]),
+    ("avx512er", &["avx512f"]),
+    ("avx512ifma", &["avx512f"]),
+    ("avx512pf", &["avx512f"]),
+    ("avx512vbmi", &["avx512bw"]),
+    ("avx512vl", &["avx512f"]),
+    ("avx512vpopcntdq", &["avx512f"]),
+    ("xsavec", &["xsave"]),
+    ("xsaveopt", &["xsave"]),
+    ("xsaves", &["xsave"]),
+];
+
+const HEXAGON_IMPLIED: Implications = &[
+    ("hvx-double", &["hvx"]),
+    ("hvx-length64b", &["hvx"]),
+    ("hvx-length128b", &["hvx"]),
+    ("hvxv62", &["hvxv60"]),
+    ("hvxv65", &["hvxv62"]),
+];
+
+const HEXAGON_CONFLICTS: Conflicts = &[
+    ("hvx-length64b", "hvx-length128b"),
+    ("hvx-length64b", "hvx-double"),
+];
+
+const POWERPC_IMPLIED: Implications = &[
+    ("vsx", &["altivec"]),
+    ("power8-altivec", &["altivec"]),
+    ("power9-altivec", &["power8-altivec"]),
+    ("power8-vector", &["vsx", "power8-altivec"]),
+    ("power9-vector", &["power8-vector", "power9-altivec"]),
+];
+
+fn implications(arch: &str) -> Implications {
+    match arch {
+        "arm" => ARM_IMPLIED,
+        "aarch64" => AARCH64_IMPLIED,
+        "x86" | "x86_64" => X86_IMPLIED,
+        "hexagon" => HEXAGON_IMPLIED,
+        "powerpc" | "powerpc64" => POWERPC_IMPLIED,
+        _ => &[],
+    }
+}
+
+fn conflicts(arch: &str) -> Conflicts {
+    match arch {
+        "arm" => ARM_CONFLICTS,
+        "hexagon" => HEXAGON_CONFLICTS,
+        _ => &[],
+    }
+}
+
+/// Returns the features that enabling `feature` directly implies on `arch`.
+pub fn implied_target_features(arch: &str, feature: &str) -> &'static [&'static str] {
+    implications(arch).iter()
+        .find(|&&(name, _)| name == feature)
+        .map(|&(_, implied)| implied)
+        .unwrap_or(&[])
+}
+
+/// Adds everything the given features imply, transitively, to `features`.
+pub fn add_implied_target_features(arch: &str, features: &mut Vec<String>) {
+    let mut i = 0;
+    while i < features.len() {
+        for &implied in implied_target_features(arch, &features[i]) {
+            if !features.iter().any(|f| f == implied) {
+                features.push(implied.to_string());
+            }
+        }
+        i += 1;
+    }
+}
+
+/// Returns the first pair of features in `features` that can't be enabled
+/// together on `arch`, if any.
+pub fn find_conflicting_target_features<'a>(arch: &str, features: &'a [String])
+                                            -> Option<(&'a str, &'a str)> {
+    conflicts(arch).iter().filter_map(|&(a, b)| {
+        let a = features.iter().find(|f| *f == a)?;
+        let b = features.iter().find(|f| *f == b)?;
+        Some((&a[..], &b[..]))
+    }).next()
+}
@@ -70,7 +70,7 @@ use rustc::traits::{self, ObligationCause, ObligationCauseCode};
 use rustc::ty::adjustment::{Adjustment, Adjust, AllowTwoPhase, AutoBorrow, AutoBorrowMutability};
 use rustc::ty::{self, TypeAndMut, Ty, ClosureSubsts};
 use rustc::ty::fold::TypeFoldable;
-use rustc::ty::error::TypeError;
+use rustc::ty::error::{ExpectedFound, TypeError};
 use rustc::ty::relate::RelateResult;
 use smallvec::{smallvec, SmallVec};
 use std::ops::Deref;
@@ -658,7 +658,20 @@ impl<'f, 'gcx, 'tcx> Coerce<'f, 'gcx, 'tcx> {
         debug!("coerce_from_fn_item(a={:?}, b={:?})", a, b);
 
         match b.sty {
-            ty::FnPtr(_) => {
+            ty::FnPtr(b_sig) => {
+                // Safe `#[target_feature]` functions are only safe to call
+                // where their features are enabled, so they only coerce to
+                // `unsafe` function pointers.
+                if let ty::FnDef(def_id, _) = a.sty {
+                    if b_sig.unsafety() == hir::Unsafety::Normal &&
+                       !self.tcx.codegen_fn_attrs(def_id).target_features.is_empty() {
+                        return Err(TypeError::UnsafetyMismatch(ExpectedFound {
+                            expected: hir::Unsafety::Normal,
+                            found: hir::Unsafety::Unsafe,
+                        }));
+                    }
+                }
+
                 let a_sig = a.fn_sig(self.tcx);
                 let InferOk { value: a_sig, mut obligations } =
                     self.normalize_associated_types_in_as_infer_ok(self.cause.span, &a_sig);
@@ -12,6 +12,7 @@
 //! intrinsics that the compiler exposes.
 
 use intrinsics;
+use rustc::middle::atomic_intrinsic::{AtomicIntrinsic, AtomicIntrinsicError, AtomicOp};
 use rustc::traits::{ObligationCause, ObligationCauseCode};
 use rustc::ty::{self, TyCtxt, Ty};
 use rustc::util::nodemap::FxHashMap;
@@ -75,6 +76,18 @@ fn equate_intrinsic_type<'a, 'tcx>(
     require_same_types(tcx, &cause, tcx.mk_fn_ptr(tcx.fn_sig(def_id)), fty);
 }
 
+/// Returns whether the given intrinsic is unsafe to call. Both the check of
+/// its type and the signature collect gives its declaration use this.
+pub fn intrinsic_operation_unsafety(intrinsic: &str) -> hir::Unsafety {
+    match intrinsic {
+        "size_of" | "min_align_of" | "needs_drop" |
+        "has_niche" | "niche_offset" | "niche_range_start" | "niche_range_end" |
+        "current_stack_pointer" | "frame_address" | "spin_loop" |
+        "wasm_memory_size" | "wasm_memory_grow" => hir::Unsafety::Normal,
+        _ => hir::Unsafety::Unsafe,
+    }
+}
+
 /// Remember to add all intrinsics here, in librustc_codegen_llvm/intrinsic.rs,
 /// and in libcore/intrinsics.rs
 pub fn check_intrinsic_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
@@ -82,46 +95,66 @@ pub fn check_intrinsic_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     let param = |n| tcx.mk_ty_param(n, Symbol::intern(&format!("P{}", n)).as_interned_str());
     let name = it.name.as_str();
     let (n_tps, inputs, output, unsafety) = if name.starts_with("atomic_") {
-        let split : Vec<&str> = name.split('_').collect();
-        assert!(split.len() >= 2, "Atomic intrinsic in an incorrect format");
-
-        //We only care about the operation here
-        let (n_tps, inputs, output) = match split[1] {
-            "cxchg" | "cxchgweak" => (1, vec![tcx.mk_mut_ptr(param(0)),
-                                              param(0),
-                                              param(0)],
-                                      tcx.intern_tup(&[param(0), tcx.types.bool])),
-            "load" => (1, vec![tcx.mk_imm_ptr(param(0))],
-                       param(0)),
-            "store" => (1, vec![tcx.mk_mut_ptr(param(0)), param(0)],
-                        tcx.mk_unit()),
-
-            "xchg" | "xadd" | "xsub" | "and"  | "nand" | "or" | "xor" | "max" |
-            "min"  | "umax" | "umin" => {
-                (1, vec![tcx.mk_mut_ptr(param(0)), param(0)],
-                 param(0))
-            }
-            "fence" | "singlethreadfence" => {
-                (0, Vec::new(), tcx.mk_unit())
-            }
-            op => {
+        let op = match AtomicIntrinsic::parse(&name) {
+            Ok(atomic) => atomic.op,
+            Err(AtomicIntrinsicError::UnknownOp(op)) => {
                 struct_span_err!(tcx.sess, it.span, E0092,
                       "unrecognized atomic operation function: `{}`", op)
                   .span_label(it.span, "unrecognized atomic operation")
                   .emit();
                 return;
             }
+            Err(AtomicIntrinsicError::UnknownOrdering(op, ordering)) => {
+                struct_span_err!(tcx.sess, it.span, E0092,
+                      "unrecognized ordering `{}` for atomic operation `{}`", ordering, op.name())
+                  .span_label(it.span, "unrecognized atomic ordering")
+                  .emit();
+                return;
+            }
+            Err(AtomicIntrinsicError::InvalidOrdering(op, ordering)) => {
+                struct_span_err!(tcx.sess, it.span, E0092,
+                      "invalid ordering `{}` for atomic operation `{}`", ordering, op.name())
+                  .span_label(it.span, "invalid atomic ordering")
+                  .emit();
+                return;
+            }
+        };
+
+        let (n_tps, inputs, output) = match op {
+            AtomicOp::Cxchg | AtomicOp::CxchgWeak => (1, vec![tcx.mk_mut_ptr(param(0)),
+                                                              param(0),
+                                                              param(0)],
+                                                      tcx.intern_tup(&[param(0),
+                                                                       tcx.types.bool])),
+            AtomicOp::Load => (1, vec![tcx.mk_imm_ptr(param(0))],
+                               param(0)),
+            AtomicOp::Store => (1, vec![tcx.mk_mut_ptr(param(0)), param(0)],
+                                tcx.mk_unit()),
+            AtomicOp::Fence | AtomicOp::SingleThreadFence => {
+                (0, Vec::new(), tcx.mk_unit())
+            }
+            _ => {
+                // The read-modify-write operations.
+                (1, vec![tcx.mk_mut_ptr(param(0)), param(0)],
+                 param(0))
+            }
         };
         (n_tps, inputs, output, hir::Unsafety::Unsafe)
     } else if &name[..] == "abort" || &name[..] == "unreachable" {
         (0, Vec::new(), tcx.types.never, hir::Unsafety::Unsafe)
     } else {
-        let unsafety = match &name[..] {
-            "size_of" | "min_align_of" | "needs_drop" => hir::Unsafety::Normal,
-            _ => hir::Unsafety::Unsafe,
-        };
+        let unsafety = intrinsic_operation_unsafety(&name[..]);
         let (n_tps, inputs, output) = match &name[..] {
             "breakpoint" => (0, Vec::new(), tcx.mk_unit()),
+            "spin_loop" => (0, Vec::new(), tcx.mk_unit()),
+            "current_stack_pointer" => (0, Vec::new(), tcx.mk_mut_ptr(tcx.types.u8)),
+            "frame_address" => (0, Vec::new(), tcx.mk_mut_ptr(tcx.types.u8)),
+            "swap_context" => {
+                let context = tcx.mk_array(tcx.mk_mut_ptr(tcx.types.u8), 5);
+                (0, vec![tcx.mk_mut_ptr(context), tcx.mk_imm_ptr(context)], tcx.mk_unit())
+            }
+            "wasm_memory_size" => (0, vec![tcx.types.u32], tcx.types.usize),
+            "wasm_memory_grow" => (0, vec![tcx.types.u32, tcx.types.usize], tcx.types.usize),
             "size_of" |
             "pref_align_of" | "min_align_of" => (1, Vec::new(), tcx.types.usize),
             "size_of_val" |  "min_align_of_val" => {
@@ -134,6 +167,7 @@ pub fn check_intrinsic_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
             "rustc_peek" => (1, vec![param(0)], param(0)),
             "init" => (1, Vec::new(), param(0)),
             "uninit" => (1, Vec::new(), param(0)),
+            "assert_inhabited" | "assert_zero_valid" => (1, Vec::new(), tcx.mk_unit()),
             "forget" => (1, vec![param(0)], tcx.mk_unit()),
             "transmute" => (2, vec![ param(0) ], param(1)),
             "move_val_init" => {
@@ -152,13 +186,31 @@ pub fn check_intrinsic_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                          }), tcx.types.i32],
                     tcx.mk_unit())
             }
+            "prefetch" => {
+                (1, vec![tcx.mk_ptr(ty::TypeAndMut {
+                          ty: param(0),
+                          mutbl: hir::MutImmutable
+                         }), tcx.types.i32, tcx.types.i32, tcx.types.i32],
+                    tcx.mk_unit())
+            }
             "drop_in_place" => {
                 (1, vec![tcx.mk_mut_ptr(param(0))], tcx.mk_unit())
             }
             "needs_drop" => (1, Vec::new(), tcx.types.bool),
+            "has_niche" => (1, Vec::new(), tcx.types.bool),
+            "niche_offset" => (1, Vec::new(), tcx.types.usize),
+            "niche_range_start" | "niche_range_end" => (1, Vec::new(), tcx.types.u128),
 
             "type_name" => (1, Vec::new(), tcx.mk_static_str()),
             "type_id" => (1, Vec::new(), tcx.types.u64),
+            "type_id128" => (1, Vec::new(), tcx.types.u128),
+            "const_allocate" => {
+                (0, vec![tcx.types.usize, tcx.types.usize], tcx.mk_mut_ptr(tcx.types.u8))
+            }
+            "const_deallocate" => {
+                (0, vec![tcx.mk_mut_ptr(tcx.types.u8), tcx.types.usize, tcx.types.usize],
+                 tcx.mk_unit())
+            }
             "offset" | "arith_offset" => {
               (1,
                vec![
@@ -173,7 +225,20 @@ pub fn check_intrinsic_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                    mutbl: hir::MutImmutable
                }))
             }
-            "copy" | "copy_nonoverlapping" => {
+            "fenv_rounding_mode" | "fenv_exceptions" => (0, vec![], tcx.types.u32),
+            "fenv_set_rounding_mode" | "fenv_clear_exceptions" => {
+                (0, vec![tcx.types.u32], tcx.mk_unit())
+            }
+            "cpuid" => {
+                let u32 = tcx.types.u32;
+                (0, vec![u32, u32], tcx.intern_tup(&[u32, u32, u32, u32]))
+            }
+            "cpu_hwcap" => (0, vec![], tcx.types.usize),
+            "offset_with_overflow" => {
+                (1, vec![tcx.mk_imm_ptr(param(0)), tcx.types.isize],
+                 tcx.intern_tup(&[tcx.mk_imm_ptr(param(0)), tcx.types.bool]))
+            }
+            "copy" | "copy_nonoverlapping" | "copy_nonoverlapping_atomic_unordered" => {
               (1,
                vec![
                   tcx.mk_ptr(ty::TypeAndMut {
@@ -277,11 +342,22 @@ pub fn check_intrinsic_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
             "nearbyintf64" => (0, vec![ tcx.types.f64 ], tcx.types.f64),
             "roundf32"     => (0, vec![ tcx.types.f32 ], tcx.types.f32),
             "roundf64"     => (0, vec![ tcx.types.f64 ], tcx.types.f64),
+            "canonicalizef32" => (0, vec![ tcx.types.f32 ], tcx.types.f32),
+            "canonicalizef64" => (0, vec![ tcx.types.f64 ], tcx.types.f64),
+            "minimumf32" | "maximumf32" =>
+                (0, vec![ tcx.types.f32, tcx.types.f32 ], tcx.types.f32),
+            "minimumf64" | "maximumf64" =>
+                (0, vec![ tcx.types.f64, tcx.types.f64 ], tcx.types.f64),
+
+            "read_via_copy" => (1, vec![ tcx.mk_imm_ptr(param(0)) ], param(0)),
+            "write_via_move" => (1, vec![ tcx.mk_mut_ptr(param(0)), param(0) ], tcx.mk_unit()),
 
             "volatile_load" | "unaligned_volatile_load" =>
                 (1, vec![ tcx.mk_imm_ptr(param(0)) ], param(0)),
             "volatile_store" | "unaligned_volatile_store" =>
                 (1, vec![ tcx.mk_mut_ptr(param(0)), param(0) ], tcx.mk_unit()),
+            "mmio_load" => (1, vec![ tcx.mk_imm_ptr(param(0)) ], param(0)),
+            "mmio_store" => (1, vec![ tcx.mk_mut_ptr(param(0)), param(0) ], tcx.mk_unit()),
 
             "ctpop" | "ctlz" | "ctlz_nonzero" | "cttz" | "cttz_nonzero" |
             "bswap" | "bitreverse" =>
@@ -301,15 +377,25 @@ pub fn check_intrinsic_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
                 (1, vec![param(0), param(0)], param(0)),
             "fadd_fast" | "fsub_fast" | "fmul_fast" | "fdiv_fast" | "frem_fast" =>
                 (1, vec![param(0), param(0)], param(0)),
+            "fadd_flags" | "fsub_flags" | "fmul_flags" | "fdiv_flags" | "frem_flags" =>
+                (1, vec![param(0), param(0), tcx.types.u32], param(0)),
 
             "assume" => (0, vec![tcx.types.bool], tcx.mk_unit()),
             "likely" => (0, vec![tcx.types.bool], tcx.types.bool),
             "unlikely" => (0, vec![tcx.types.bool], tcx.types.bool),
+            "is_val_statically_known" => (1, vec![param(0)], tcx.types.bool),
+            "cold_path" => (0, Vec::new(), tcx.mk_unit()),
 
             "discriminant_value" => (1, vec![
                     tcx.mk_imm_ref(tcx.mk_region(ty::ReLateBound(ty::INNERMOST,
                                                                  ty::BrAnon(0))),
                                    param(0))], tcx.types.u64),
+            "raw_discriminant" => (1, vec![
+                    tcx.mk_imm_ref(tcx.mk_region(ty::ReLateBound(ty::INNERMOST,
+                                                                 ty::BrAnon(0))),
+                                   param(0))], tcx.types.u128),
+            "set_discriminant" => (1, vec![tcx.mk_mut_ptr(param(0)), tcx.types.u128],
+                                   tcx.mk_unit()),
 
             "try" => {
                 let mut_u8 = tcx.mk_mut_ptr(tcx.types.u8);
@@ -357,15 +443,22 @@ pub fn check_platform_intrinsic_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         "simd_eq" | "simd_ne" | "simd_lt" | "simd_le" | "simd_gt" | "simd_ge" => {
             (2, vec![param(0), param(0)], param(1))
         }
-        "simd_add" | "simd_sub" | "simd_mul" | "simd_rem" |
+        "simd_add" | "simd_sub" | "simd_mul" | "simd_mul_high" | "simd_rem" |
+        "simd_avg_round" | "simd_avg_floor" |
         "simd_div" | "simd_shl" | "simd_shr" |
+        "simd_rotate_left" | "simd_rotate_right" |
         "simd_and" | "simd_or" | "simd_xor" |
         "simd_fmin" | "simd_fmax" | "simd_fpow" => {
             (1, vec![param(0), param(0)], param(0))
         }
+        "simd_funnel_shl" | "simd_funnel_shr" => {
+            (1, vec![param(0), param(0), param(0)], param(0))
+        }
+        "simd_abs" |
         "simd_fsqrt" | "simd_fsin" | "simd_fcos" | "simd_fexp" | "simd_fexp2" |
         "simd_flog2" | "simd_flog10" | "simd_flog" |
-        "simd_fabs" | "simd_floor" | "simd_ceil" => {
+        "simd_fabs" | "simd_floor" | "simd_ceil" | "simd_round" | "simd_trunc" |
+        "simd_rint" | "simd_nearbyint" => {
             (1, vec![param(0)], param(0))
         }
         "simd_fpowi" => {
@@ -380,18 +473,37 @@ pub fn check_platform_intrinsic_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
         "simd_scatter" => {
             (3, vec![param(0), param(1), param(2)], tcx.mk_unit())
         }
+        "simd_gather_with_align" => {
+            (3, vec![param(0), param(1), param(2), tcx.types.u32], param(0))
+        }
+        "simd_scatter_with_align" => {
+            (3, vec![param(0), param(1), param(2), tcx.types.u32], tcx.mk_unit())
+        }
+        "simd_masked_load" | "simd_expand_load" => {
+            (3, vec![param(0), param(1), param(2)], param(2))
+        }
+        "simd_masked_store" | "simd_compress_store" => {
+            (3, vec![param(0), param(1), param(2)], tcx.mk_unit())
+        }
         "simd_insert" => (2, vec![param(0), tcx.types.u32, param(1)], param(0)),
         "simd_extract" => (2, vec![param(0), tcx.types.u32], param(1)),
-        "simd_cast" => (2, vec![param(0)], param(1)),
+        "simd_cast" | "simd_as" => (2, vec![param(0)], param(1)),
+        "simd_cast_ptr" | "simd_expose_addr" | "simd_from_exposed_addr"
+            => (2, vec![param(0)], param(1)),
+        "simd_arith_offset" => (2, vec![param(0), param(1)], param(0)),
+        "simd_pack_sat" => (2, vec![param(0)], param(1)),
         "simd_select" => (2, vec![param(0), param(1), param(1)], param(1)),
+        "simd_bitmask" => (2, vec![param(0)], param(1)),
         "simd_reduce_all" | "simd_reduce_any" => (1, vec![param(0)], tcx.types.bool),
         "simd_reduce_add_ordered" | "simd_reduce_mul_ordered"
             => (2, vec![param(0), param(1)], param(1)),
         "simd_reduce_add_unordered" | "simd_reduce_mul_unordered" |
+        "simd_reduce_add_pairwise" | "simd_reduce_mul_pairwise" |
         "simd_reduce_and" | "simd_reduce_or"  | "simd_reduce_xor" |
         "simd_reduce_min" | "simd_reduce_max" |
         "simd_reduce_min_nanless" | "simd_reduce_max_nanless"
             => (2, vec![param(0)], param(1)),
+        "simd_shuffle" => (3, vec![param(0), param(0), param(1)], param(2)),
         name if name.starts_with("simd_shuffle") => {
             match name["simd_shuffle".len()..].parse() {
                 Ok(n) => {
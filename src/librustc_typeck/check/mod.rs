@@ -103,7 +103,7 @@ use rustc::ty::adjustment::{Adjust, Adjustment, AllowTwoPhase, AutoBorrow, AutoB
 use rustc::ty::fold::TypeFoldable;
 use rustc::ty::query::Providers;
 use rustc::ty::util::{Representability, IntTypeExt, Discr};
-use rustc::ty::layout::VariantIdx;
+use rustc::ty::layout::{self, VariantIdx};
 use rustc_data_structures::indexed_vec::Idx;
 use errors::{Applicability, DiagnosticBuilder, DiagnosticId};
 
@@ -154,7 +154,7 @@ mod closure;
 mod callee;
 mod compare_method;
 mod generator_interior;
-mod intrinsic;
+pub mod intrinsic;
 mod op;
 
 /// The type of a local binding, including the revealed type for anon types.
@@ -1302,6 +1302,9 @@ fn check_struct<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
 
     check_transparent(tcx, span, def_id);
     check_packed(tcx, span, def_id);
+    check_bit_fields(tcx, def_id);
+    check_scalable_fields(tcx, def_id);
+    check_pointer_niche(tcx, span, def_id);
 }
 
 fn check_union<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
@@ -1313,6 +1316,8 @@ fn check_union<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     check_representable(tcx, span, def_id);
 
     check_packed(tcx, span, def_id);
+    check_bit_fields(tcx, def_id);
+    check_scalable_fields(tcx, def_id);
 }
 
 pub fn check_item_type<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, it: &'tcx hir::Item) {
@@ -1710,6 +1715,7 @@ pub fn check_simd<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, sp: Span, def_id: DefId
             match e.sty {
                 ty::Param(_) => { /* struct<T>(T, T, T, T) is ok */ }
                 _ if e.is_machine() => { /* struct(u8, u8, u8, u8) is ok */ }
+                ty::Bool => { /* struct(bool, bool, bool, bool) is a mask */ }
                 _ => {
                     span_err!(tcx.sess, sp, E0077,
                               "SIMD vector element type should be machine type");
@@ -1774,6 +1780,113 @@ fn check_packed_inner<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     false
 }
 
+/// Checks the `#[bits(N)]` attributes on the fields of an ADT.
+/// Scalable vectors have a size that is only known at runtime, so they can't
+/// be given an offset in a field, not even inside of an array or tuple.
+fn check_scalable_fields<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId) {
+    fn stored_scalable_simd<'tcx>(ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        match ty.sty {
+            _ if ty.is_scalable_simd() => Some(ty),
+            ty::Array(element, _) => stored_scalable_simd(element),
+            ty::Tuple(tys) => tys.iter().filter_map(|&ty| stored_scalable_simd(ty)).next(),
+            _ => None,
+        }
+    }
+
+    let def = tcx.adt_def(def_id);
+    for field in def.all_fields() {
+        if let Some(vector) = stored_scalable_simd(tcx.type_of(field.did)) {
+            tcx.sess.span_err(tcx.def_span(field.did),
+                              &format!("scalable vector type `{}` can't be stored in `{}`, \
+                                        only passed by value or used as a local",
+                                       vector, tcx.item_path_str(def_id)));
+        }
+    }
+}
+
+fn check_bit_fields<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId) {
+    let def = tcx.adt_def(def_id);
+    for field in def.all_fields() {
+        if attr::find_by_name(&tcx.get_attrs(field.did), "bits").is_none() {
+            continue;
+        }
+        let span = tcx.def_span(field.did);
+        if !def.is_struct() {
+            tcx.sess.span_err(span, "only fields of structs can be bit-fields");
+            continue;
+        }
+        if !def.repr.c() || def.repr.packed() {
+            tcx.sess.span_err(span, "structs with bit-fields must be `#[repr(C)]` and not packed");
+            continue;
+        }
+        let width = match field.bit_width(tcx) {
+            Some(width) => width,
+            None => {
+                tcx.sess.span_err(span, "expected `#[bits(N)]` with an integer `N`");
+                continue;
+            }
+        };
+        let field_ty = tcx.type_of(field.did);
+        let max_width = match field_ty.sty {
+            ty::Bool => 1,
+            ty::Int(_) | ty::Uint(_) => {
+                tcx.layout_of(ty::ParamEnv::reveal_all().and(field_ty))
+                    .map(|layout| layout.size.bits())
+                    .unwrap_or(0)
+            }
+            _ => {
+                tcx.sess.span_err(span, &format!(
+                    "bit-field `{}` has type `{}`, which isn't an integer or `bool`",
+                    field.ident, field_ty));
+                continue;
+            }
+        };
+        if width == 0 || width > max_width {
+            tcx.sess.span_err(span, &format!(
+                "bit-field `{}` of type `{}` can't be {} bits wide",
+                field.ident, field_ty, width));
+        }
+    }
+}
+
+/// Checks that `#[pointer_niche]` is well-formed and that the struct it's on
+/// is laid out as a single pointer or integer.
+fn check_pointer_niche<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, sp: Span, def_id: DefId) {
+    let attr = match attr::find_by_name(&tcx.get_attrs(def_id), "pointer_niche") {
+        Some(attr) => attr,
+        None => return,
+    };
+    let (low_bits, high_bits) = match tcx.adt_def(def_id).pointer_niche(tcx) {
+        Some(bits) => bits,
+        None => {
+            tcx.sess.span_err(attr.span,
+                              "expected `#[pointer_niche(low_bits = N, high_bits = M)]` \
+                               with integers `N` and `M`");
+            return;
+        }
+    };
+    let ty = tcx.type_of(def_id);
+    let ty_layout = match tcx.layout_of(tcx.param_env(def_id).and(ty)) {
+        Ok(ty_layout) => ty_layout,
+        Err(_) => return,
+    };
+    match ty_layout.abi {
+        layout::Abi::Scalar(layout::Scalar { value: layout::Int(..), .. }) |
+        layout::Abi::Scalar(layout::Scalar { value: layout::Pointer, .. }) => {}
+        _ => {
+            tcx.sess.span_err(sp, "`#[pointer_niche]` can only be used on a struct whose only \
+                                   non-zero-sized field is a pointer or an integer");
+            return;
+        }
+    }
+    let bits = ty_layout.size.bits();
+    if u64::from(low_bits) + u64::from(high_bits) >= bits {
+        tcx.sess.span_err(attr.span, &format!(
+            "`#[pointer_niche]` leaves no valid values of `{}`, which is {} bits wide",
+            ty, bits));
+    }
+}
+
 fn check_transparent<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, sp: Span, def_id: DefId) {
     let adt = tcx.adt_def(def_id);
     if !adt.repr.transparent() {
@@ -1818,6 +1931,8 @@ pub fn check_enum<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>,
     let def_id = tcx.hir.local_def_id(id);
     let def = tcx.adt_def(def_id);
     def.destructor(tcx); // force the destructor to be evaluated
+    check_bit_fields(tcx, def_id);
+    check_scalable_fields(tcx, def_id);
 
     if vs.is_empty() {
         let attributes = tcx.get_attrs(def_id);
@@ -25,6 +25,7 @@
 //! crate as a kind of pass. This should eventually be factored away.
 
 use astconv::{AstConv, Bounds};
+use check::intrinsic::intrinsic_operation_unsafety;
 use constrained_type_params as ctp;
 use lint;
 use middle::lang_items::SizedTraitLangItem;
@@ -41,6 +42,7 @@ use rustc::util::captures::Captures;
 use rustc::util::nodemap::FxHashMap;
 use rustc_data_structures::sync::Lrc;
 use rustc_target::spec::abi;
+use rustc_target::target_features;
 
 use syntax::ast;
 use syntax::ast::MetaItemKind;
@@ -2085,10 +2087,7 @@ fn compute_sig_of_foreign_fn_decl<'a, 'tcx>(
     abi: abi::Abi,
 ) -> ty::PolyFnSig<'tcx> {
     let unsafety = if abi == abi::Abi::RustIntrinsic {
-        match &*tcx.item_name(def_id).as_str() {
-            "size_of" | "min_align_of" | "needs_drop" => hir::Unsafety::Normal,
-            _ => hir::Unsafety::Unsafe,
-        }
+        intrinsic_operation_unsafety(&*tcx.item_name(def_id).as_str())
     } else {
         hir::Unsafety::Unsafe
     };
@@ -2206,6 +2205,7 @@ fn from_target_feature(
                 Some("mmx_target_feature") => rust_features.mmx_target_feature,
                 Some("sse4a_target_feature") => rust_features.sse4a_target_feature,
                 Some("tbm_target_feature") => rust_features.tbm_target_feature,
+                Some("cmpxchg16b_target_feature") => rust_features.cmpxchg16b_target_feature,
                 Some("wasm_target_feature") => rust_features.wasm_target_feature,
                 Some(name) => bug!("unknown target feature gate {}", name),
                 None => true,
@@ -2225,6 +2225,72 @@ fn from_target_feature(
     }
 }
 
+fn from_multiversion(
+    tcx: TyCtxt,
+    attr: &ast::Attribute,
+    whitelist: &FxHashMap<String, Option<String>>,
+    targets: &mut Vec<Symbol>,
+) {
+    let msg = "#[multiversion] attribute must be of the form \
+               #[multiversion(targets(\"..\", \"default\"))]";
+    let list = match attr.meta_item_list() {
+        Some(ref list) if list.len() == 1 && list[0].check_name("targets") => {
+            match list[0].meta_item_list() {
+                Some(list) => list.to_vec(),
+                None => {
+                    tcx.sess.span_err(attr.span, msg);
+                    return;
+                }
+            }
+        }
+        _ => {
+            tcx.sess.span_err(attr.span, msg);
+            return;
+        }
+    };
+
+    for item in list {
+        let target = match item.literal().map(|lit| &lit.node) {
+            Some(&ast::LitKind::Str(target, _)) => target,
+            _ => {
+                tcx.sess.span_err(item.span, msg);
+                continue;
+            }
+        };
+        if targets.contains(&target) {
+            let msg = format!("target `{}` is listed more than once", target);
+            tcx.sess.span_err(item.span, &msg);
+            continue;
+        }
+        // Variants are only generated for features that are stable to
+        // enable, the unstable ones still need `#[target_feature]`.
+        if target != "default" && whitelist.get(&*target.as_str()) != Some(&None) {
+            let msg = format!("the feature named `{}` is not valid for this target", target);
+            tcx.sess.span_err(item.span, &msg);
+            continue;
+        }
+        targets.push(target);
+    }
+
+    if !targets.iter().any(|t| *t == "default") {
+        let msg = "#[multiversion] requires a `\"default\"` target to fall back to";
+        tcx.sess.span_err(attr.span, msg);
+    }
+}
+
+// Makes `#[target_feature(enable = "avx2")]` also enable the features `avx2`
+// builds upon, and rejects combinations the target can't have at once.
+fn complete_target_features(tcx: TyCtxt, span: Span, target_features: &mut Vec<Symbol>) {
+    let arch = &tcx.sess.target.target.arch;
+    let mut features = target_features.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+    target_features::add_implied_target_features(arch, &mut features);
+    if let Some((a, b)) = target_features::find_conflicting_target_features(arch, &features) {
+        let msg = format!("the target features `{}` and `{}` cannot be enabled together", a, b);
+        tcx.sess.span_err(span, &msg);
+    }
+    *target_features = features.iter().map(|f| Symbol::intern(f)).collect();
+}
+
 fn linkage_by_name<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, def_id: DefId, name: &str) -> Linkage {
     use rustc::mir::mono::Linkage::*;
 
@@ -2268,6 +2334,7 @@ fn codegen_fn_attrs<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, id: DefId) -> Codegen
     let whitelist = tcx.target_features_whitelist(LOCAL_CRATE);
 
     let mut inline_span = None;
+    let mut target_feature_span = None;
     for attr in attrs.iter() {
         if attr.check_name("cold") {
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::COLD;
@@ -2277,6 +2344,12 @@ fn codegen_fn_attrs<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, id: DefId) -> Codegen
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::UNWIND;
         } else if attr.check_name("rustc_allocator_nounwind") {
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::RUSTC_ALLOCATOR_NOUNWIND;
+        } else if attr.check_name("rustc_deallocator") {
+            codegen_fn_attrs.flags |= CodegenFnAttrFlags::DEALLOCATOR;
+        } else if attr.check_name("rustc_reallocator") {
+            codegen_fn_attrs.flags |= CodegenFnAttrFlags::REALLOCATOR;
+        } else if attr.check_name("rustc_allocator_zeroed") {
+            codegen_fn_attrs.flags |= CodegenFnAttrFlags::ALLOCATOR_ZEROED;
         } else if attr.check_name("naked") {
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::NAKED;
         } else if attr.check_name("no_mangle") {
@@ -2287,6 +2360,19 @@ fn codegen_fn_attrs<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, id: DefId) -> Codegen
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_DEBUG;
         } else if attr.check_name("used") {
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::USED;
+        } else if attr.check_name("offload") {
+            codegen_fn_attrs.flags |= CodegenFnAttrFlags::OFFLOAD;
+        } else if attr.check_name("vectorize") {
+            match attr.meta_item_list() {
+                Some(ref items) if items.len() == 1 && items[0].check_name("expect") => {
+                    codegen_fn_attrs.flags |= CodegenFnAttrFlags::EXPECT_VECTORIZED;
+                }
+                _ => {
+                    tcx.sess.span_err(attr.span, "expected `#[vectorize(expect)]`");
+                }
+            }
+        } else if attr.check_name("no_alloc") {
+            codegen_fn_attrs.flags |= CodegenFnAttrFlags::NO_ALLOC;
         } else if attr.check_name("thread_local") {
             codegen_fn_attrs.flags |= CodegenFnAttrFlags::THREAD_LOCAL;
         } else if attr.check_name("inline") {
@@ -2355,7 +2441,9 @@ fn codegen_fn_attrs<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, id: DefId) -> Codegen
                  .emit();
             }
         } else if attr.check_name("target_feature") {
-            if tcx.fn_sig(id).unsafety() == Unsafety::Normal {
+            target_feature_span = Some(attr.span);
+            if tcx.fn_sig(id).unsafety() == Unsafety::Normal &&
+               !tcx.features().target_feature_11 {
                 let msg = "#[target_feature(..)] can only be applied to \
                            `unsafe` function";
                 tcx.sess.span_err(attr.span, msg);
@@ -2367,6 +2455,8 @@ fn codegen_fn_attrs<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, id: DefId) -> Codegen
                 &whitelist,
                 &mut codegen_fn_attrs.target_features,
             );
+        } else if attr.check_name("multiversion") {
+            from_multiversion(tcx, attr, &whitelist, &mut codegen_fn_attrs.multiversion);
         } else if attr.check_name("linkage") {
             if let Some(val) = attr.value_str() {
                 codegen_fn_attrs.linkage = Some(linkage_by_name(tcx, id, &val.as_str()));
@@ -2386,9 +2476,24 @@ fn codegen_fn_attrs<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, id: DefId) -> Codegen
             }
         } else if attr.check_name("link_name") {
             codegen_fn_attrs.link_name = attr.value_str();
+        } else if attr.check_name("address_space") {
+            // Where the attribute is allowed and its form are checked in
+            // `hir::check_attr`, which doesn't see foreign statics.
+            let value = attr.value_str().and_then(|val| val.as_str().parse::<u32>().ok());
+            if tcx.is_foreign_item(id) && value.is_none() {
+                tcx.sess.span_err(
+                    attr.span,
+                    "#[address_space] attribute must be of the form #[address_space = \"N\"]",
+                );
+            }
+            codegen_fn_attrs.address_space = value;
         }
     }
 
+    if let Some(span) = target_feature_span {
+        complete_target_features(tcx, span, &mut codegen_fn_attrs.target_features);
+    }
+
     // If a function uses #[target_feature] it can't be inlined into general
     // purpose functions as they wouldn't have the right target features
     // enabled. For that reason we also forbid #[inline(always)] as it can't be
@@ -2405,6 +2510,12 @@ fn codegen_fn_attrs<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, id: DefId) -> Codegen
         }
     }
 
+    if !codegen_fn_attrs.multiversion.is_empty() &&
+       codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NAKED) {
+        let span = tcx.def_span(id);
+        tcx.sess.span_err(span, "#[multiversion] cannot be used on #[naked] functions");
+    }
+
     // Weak lang items have the same semantics as "std internal" symbols in the
     // sense that they're preserved through all our LTO passes and only
     // strippable by the linker.
@@ -1178,8 +1178,8 @@ type Foo2<A> = Box<A>; // ok!
 "##,
 
 E0092: r##"
-You tried to declare an undefined atomic operation function.
-Erroneous code example:
+You tried to declare an undefined atomic operation function, or one with an
+ordering that the operation doesn't support. Erroneous code example:
 
 ```compile_fail,E0092
 #![feature(intrinsics)]
@@ -1192,7 +1192,8 @@ extern "rust-intrinsic" {
 
 Please check you didn't make a mistake in the function's name. All intrinsic
 functions are defined in librustc_codegen_llvm/intrinsic.rs and in
-libcore/intrinsics.rs in the Rust source code. Example:
+libcore/intrinsics.rs in the Rust source code, and the orderings of the atomic
+ones in librustc/middle/atomic_intrinsic.rs. Example:
 
 ```
 #![feature(intrinsics)]
@@ -3355,26 +3356,28 @@ fn one_up_competitor(competitor_frequency: PublicationFrequency)
 "##,
 
 E0439: r##"
-The length of the platform-intrinsic function `simd_shuffle`
-wasn't specified. Erroneous code example:
+The length in the name of the platform-intrinsic function `simd_shuffle`
+isn't a number. Erroneous code example:
 
 ```compile_fail,E0439
 #![feature(platform_intrinsics)]
 
 extern "platform-intrinsic" {
-    fn simd_shuffle<A,B>(a: A, b: A, c: [u32; 8]) -> B;
-    // error: invalid `simd_shuffle`, needs length: `simd_shuffle`
+    fn simd_shuffle_eight<A,B>(a: A, b: A, c: [u32; 8]) -> B;
+    // error: invalid `simd_shuffle`, needs length: `simd_shuffle_eight`
 }
 ```
 
-The `simd_shuffle` function needs the length of the array passed as
-last parameter in its name. Example:
+The `simd_shuffle` function either takes the length of the array passed as
+last parameter in its name, or is generic over the type of that array.
+Example:
 
 ```
 #![feature(platform_intrinsics)]
 
 extern "platform-intrinsic" {
     fn simd_shuffle8<A,B>(a: A, b: A, c: [u32; 8]) -> B;
+    fn simd_shuffle<A,I,B>(a: A, b: A, c: I) -> B;
 }
 ```
 "##,
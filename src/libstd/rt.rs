@@ -74,6 +74,120 @@ fn lang_start<T: ::process::Termination + 'static>
     lang_start_internal(&move || main().report(), argc, argv)
 }
 
+// Called by the dispatcher of `#[multiversion]` functions to find out whether
+// one of its targets, named as in `#[target_feature]`, is available.
+#[cfg(all(not(stage0), not(test)))]
+#[lang = "multiversion_cpu_supports"]
+fn multiversion_cpu_supports(feature: *const u8, len: usize) -> bool {
+    let feature = unsafe {
+        ::str::from_utf8_unchecked(::slice::from_raw_parts(feature, len))
+    };
+    cpu_supports(feature)
+}
+
+#[cfg(all(not(stage0), not(test), any(target_arch = "x86", target_arch = "x86_64")))]
+fn cpu_supports(feature: &str) -> bool {
+    match feature {
+        "aes" => is_x86_feature_detected!("aes"),
+        "avx" => is_x86_feature_detected!("avx"),
+        "avx2" => is_x86_feature_detected!("avx2"),
+        "bmi1" => is_x86_feature_detected!("bmi1"),
+        "bmi2" => is_x86_feature_detected!("bmi2"),
+        "fma" => is_x86_feature_detected!("fma"),
+        "fxsr" => is_x86_feature_detected!("fxsr"),
+        "lzcnt" => is_x86_feature_detected!("lzcnt"),
+        "pclmulqdq" => is_x86_feature_detected!("pclmulqdq"),
+        "popcnt" => is_x86_feature_detected!("popcnt"),
+        "rdrand" => is_x86_feature_detected!("rdrand"),
+        "rdseed" => is_x86_feature_detected!("rdseed"),
+        "sha" => is_x86_feature_detected!("sha"),
+        "sse" => is_x86_feature_detected!("sse"),
+        "sse2" => is_x86_feature_detected!("sse2"),
+        "sse3" => is_x86_feature_detected!("sse3"),
+        "sse4.1" => is_x86_feature_detected!("sse4.1"),
+        "sse4.2" => is_x86_feature_detected!("sse4.2"),
+        "ssse3" => is_x86_feature_detected!("ssse3"),
+        "xsave" => is_x86_feature_detected!("xsave"),
+        "xsavec" => is_x86_feature_detected!("xsavec"),
+        "xsaveopt" => is_x86_feature_detected!("xsaveopt"),
+        "xsaves" => is_x86_feature_detected!("xsaves"),
+        _ => false,
+    }
+}
+
+// Without runtime detection only the `"default"` variant is ever picked.
+#[cfg(all(not(stage0), not(test), not(any(target_arch = "x86", target_arch = "x86_64"))))]
+fn cpu_supports(_feature: &str) -> bool {
+    false
+}
+
+/// A call site calling a trait method through a vtable, counted when compiled
+/// with `-Z count-virtual-calls`. The compiler emits one of these for each
+/// such call site, so its layout has to stay in sync with `rustc_codegen_ssa`.
+#[cfg(all(not(stage0), not(test)))]
+#[repr(C)]
+pub struct VirtualCallSite {
+    count: ::sync::atomic::AtomicUsize,
+    next: ::sync::atomic::AtomicPtr<VirtualCallSite>,
+    method: &'static str,
+    location: &'static str,
+}
+
+#[cfg(all(not(stage0), not(test)))]
+static VIRTUAL_CALL_SITES: ::sync::atomic::AtomicPtr<VirtualCallSite> =
+    ::sync::atomic::AtomicPtr::new(::ptr::null_mut());
+
+// Called before every call through a vtable in code compiled with
+// `-Z count-virtual-calls`. A site is added to `VIRTUAL_CALL_SITES` the first
+// time it's called, and the counts of all of them are printed at exit.
+#[cfg(all(not(stage0), not(test)))]
+#[lang = "count_virtual_call"]
+fn count_virtual_call(site: &'static VirtualCallSite) {
+    use sync::Once;
+    use sync::atomic::Ordering;
+
+    static REPORT: Once = Once::new();
+
+    if site.count.fetch_add(1, Ordering::Relaxed) != 0 {
+        return;
+    }
+    REPORT.call_once(|| {
+        let _ = ::sys_common::at_exit(report_virtual_calls);
+    });
+    let site_ptr = site as *const VirtualCallSite as *mut VirtualCallSite;
+    let mut head = VIRTUAL_CALL_SITES.load(Ordering::Acquire);
+    loop {
+        site.next.store(head, Ordering::Relaxed);
+        match VIRTUAL_CALL_SITES.compare_exchange_weak(head, site_ptr,
+                                                       Ordering::Release,
+                                                       Ordering::Acquire) {
+            Ok(_) => break,
+            Err(current) => head = current,
+        }
+    }
+}
+
+#[cfg(all(not(stage0), not(test)))]
+fn report_virtual_calls() {
+    use io::Write;
+    use sync::atomic::Ordering;
+
+    let mut sites = Vec::new();
+    let mut site = VIRTUAL_CALL_SITES.load(Ordering::Acquire);
+    while let Some(s) = unsafe { site.as_ref() } {
+        sites.push((s.count.load(Ordering::Relaxed), s.method, s.location));
+        site = s.next.load(Ordering::Relaxed);
+    }
+    sites.sort_by(|a, b| b.cmp(a));
+
+    let stderr = ::io::stderr();
+    let mut stderr = stderr.lock();
+    let _ = writeln!(stderr, "virtual calls, most frequent first:");
+    for (count, method, location) in sites {
+        let _ = writeln!(stderr, "{:>12} {} at {}", count, method, location);
+    }
+}
+
 /// Function used for reverting changes to the main stack before setrlimit().
 /// This is POSIX (non-Linux) specific and unlikely to be directly stabilized.
 #[unstable(feature = "rustc_stack_internals", issue = "0")]
@@ -425,6 +425,15 @@ declare_features! (
     (active, sse4a_target_feature, "1.27.0", Some(44839), None),
     (active, tbm_target_feature, "1.27.0", Some(44839), None),
     (active, wasm_target_feature, "1.30.0", Some(44839), None),
+    (active, cmpxchg16b_target_feature, "1.32.0", Some(44839), None),
+
+    // Allows `#[target_feature(...)]` on safe functions, which can then be
+    // called without `unsafe` from functions enabling the same features.
+    (active, target_feature_11, "1.32.0", None, None),
+
+    // Allows `#[multiversion(targets(...))]` to codegen a function once per
+    // target feature and pick a variant at runtime.
+    (active, multiversion, "1.32.0", None, None),
 
     // Allows macro invocations on modules expressions and statements and
     // procedural macros to expand to non-items.
@@ -498,6 +507,34 @@ declare_features! (
 
     // `reason = ` in lint attributes and `expect` lint attribute
     (active, lint_reasons, "1.31.0", Some(54503), None),
+
+    // Allows `#[address_space = "..."]` on statics and types.
+    (active, address_spaces, "1.32.0", None, None),
+
+    // Allows `#[bits(N)]` on fields of `#[repr(C)]` structs to make them bit-fields.
+    (active, bitfields, "1.32.0", None, None),
+
+    // Allows `#[pointer_niche(low_bits = N, high_bits = M)]` on structs to make
+    // the unused bits of a (tagged) pointer available for enum layout.
+    (active, pointer_niche, "1.32.0", None, None),
+
+    // Allows `#[inline(always)]` and `#[inline(never)]` on call expressions to
+    // override the callee's `#[inline]` at that call site.
+    (active, call_site_inline, "1.32.0", None, None),
+
+    // Allows `#[vectorize(expect)]` to report loops in a function that LLVM
+    // fails to vectorize.
+    (active, vectorize, "1.32.0", None, None),
+
+    // Allows the `#[unroll(N)]`, `#[no_unroll]` and `#[pipeline]` hints on loops.
+    (active, loop_hints, "1.32.0", None, None),
+
+    // Allows `#[no_alloc]`, which `-Z verify-no-alloc` checks.
+    (active, no_alloc, "1.32.0", None, None),
+
+    // Allows `#[offload]` on functions and statics to compile them for an
+    // accelerator with `-Z offload-device` and map them from the host.
+    (active, offload, "1.32.0", None, None),
 );
 
 declare_features! (
@@ -1060,6 +1097,49 @@ pub const BUILTIN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeG
                                   is an experimental feature",
                                  cfg_fn!(naked_functions))),
     ("target_feature", Whitelisted, Ungated),
+    ("multiversion", Whitelisted, Gated(Stability::Unstable,
+                                        "multiversion",
+                                        "the `#[multiversion]` attribute \
+                                         is an experimental feature",
+                                        cfg_fn!(multiversion))),
+    ("pointer_niche", Whitelisted, Gated(Stability::Unstable,
+                                    "pointer_niche",
+                                    "the `#[pointer_niche]` attribute \
+                                     is an experimental feature",
+                                    cfg_fn!(pointer_niche))),
+    ("offload", Whitelisted, Gated(Stability::Unstable,
+                                   "offload",
+                                   "the `#[offload]` attribute is an experimental feature",
+                                   cfg_fn!(offload))),
+    ("vectorize", Whitelisted, Gated(Stability::Unstable,
+                                     "vectorize",
+                                     "the `#[vectorize]` attribute is an experimental feature",
+                                     cfg_fn!(vectorize))),
+    ("no_alloc", Whitelisted, Gated(Stability::Unstable,
+                                    "no_alloc",
+                                    "the `#[no_alloc]` attribute is an experimental feature",
+                                    cfg_fn!(no_alloc))),
+    ("unroll", Whitelisted, Gated(Stability::Unstable,
+                                  "loop_hints",
+                                  "the `#[unroll]` attribute is an experimental feature",
+                                  cfg_fn!(loop_hints))),
+    ("no_unroll", Whitelisted, Gated(Stability::Unstable,
+                                     "loop_hints",
+                                     "the `#[no_unroll]` attribute is an experimental feature",
+                                     cfg_fn!(loop_hints))),
+    ("pipeline", Whitelisted, Gated(Stability::Unstable,
+                                    "loop_hints",
+                                    "the `#[pipeline]` attribute is an experimental feature",
+                                    cfg_fn!(loop_hints))),
+    ("address_space", Whitelisted, Gated(Stability::Unstable,
+                                         "address_spaces",
+                                         "the `#[address_space]` attribute \
+                                          is an experimental feature",
+                                         cfg_fn!(address_spaces))),
+    ("bits", Whitelisted, Gated(Stability::Unstable,
+                                "bitfields",
+                                "the `#[bits]` attribute is an experimental feature",
+                                cfg_fn!(bitfields))),
     ("export_name", Whitelisted, Ungated),
     ("inline", Whitelisted, Ungated),
     ("link", Whitelisted, Ungated),
@@ -1150,6 +1230,10 @@ pub const BUILTIN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeG
                                  "rustc_attrs",
                                  "never will be stable",
                                  cfg_fn!(rustc_attrs))),
+    ("rustc_scalable_vector", Whitelisted, Gated(Stability::Unstable,
+                             "rustc_attrs",
+                             "internal implementation detail",
+                             cfg_fn!(rustc_attrs))),
 
     // RFC #2093
     ("infer_static_outlives_requirements", Normal, Gated(Stability::Unstable,
@@ -1891,6 +1891,7 @@ mod tests {
             raw_identifier_spans: Lock::new(Vec::new()),
             registered_diagnostics: Lock::new(ErrorMap::new()),
             buffered_lints: Lock::new(vec![]),
+            known_target_features: Lock::new(FxHashSet::default()),
         }
     }
 
@@ -56,6 +56,10 @@ pub struct ParseSess {
     included_mod_stack: Lock<Vec<PathBuf>>,
     source_map: Lrc<SourceMap>,
     pub buffered_lints: Lock<Vec<BufferedEarlyLint>>,
+    /// The target features of all targets, which `cfg!(target_feature = "...")`
+    /// checks its argument against. Empty, and not checked, when no codegen
+    /// backend was asked for them.
+    pub known_target_features: Lock<FxHashSet<Symbol>>,
 }
 
 impl ParseSess {
@@ -79,6 +83,7 @@ impl ParseSess {
             included_mod_stack: Lock::new(vec![]),
             source_map,
             buffered_lints: Lock::new(vec![]),
+            known_target_features: Lock::new(FxHashSet::default()),
         }
     }
 
@@ -12,6 +12,7 @@
 /// a literal `true` or `false` based on whether the given cfg matches the
 /// current compilation environment.
 
+use syntax::ast;
 use syntax::ext::base::*;
 use syntax::ext::base;
 use syntax::ext::build::AstBuilder;
@@ -35,6 +36,32 @@ pub fn expand_cfg<'cx>(cx: &mut ExtCtxt,
         return DummyResult::expr(sp);
     }
 
+    check_target_features(cx, &cfg);
     let matches_cfg = attr::cfg_matches(&cfg, cx.parse_sess, cx.ecfg.features);
     MacEager::expr(cx.expr_bool(sp, matches_cfg))
 }
+
+/// Warns about `target_feature = "..."` predicates naming a feature which no
+/// target has, e.g. because of a typo, as they can never be true. Features of
+/// other targets are fine.
+fn check_target_features(cx: &ExtCtxt, cfg: &ast::MetaItem) {
+    if let Some(items) = cfg.meta_item_list() {
+        for item in items {
+            if let Some(item) = item.meta_item() {
+                check_target_features(cx, item);
+            }
+        }
+        return;
+    }
+    if cfg.name() != "target_feature" {
+        return;
+    }
+    let feature = match cfg.value_str() {
+        Some(feature) => feature,
+        None => return,
+    };
+    let known = cx.parse_sess.known_target_features.borrow();
+    if !known.is_empty() && !known.contains(&feature) {
+        cx.span_warn(cfg.span, &format!("unknown target feature `{}`", feature));
+    }
+}
@@ -12,6 +12,8 @@
 
 #include "llvm/Object/Archive.h"
 #include "llvm/Object/ArchiveWriter.h"
+#include "llvm/Object/COFFImportFile.h"
+#include "llvm/Object/ObjectFile.h"
 #include "llvm/Support/Path.h"
 
 using namespace llvm;
@@ -229,3 +231,57 @@ LLVMRustWriteArchive(char *Dst, size_t NumMembers,
 
   return LLVMRustResult::Failure;
 }
+
+typedef void (*LLVMRustSymbolCallback)(void *, const char *, size_t);
+
+// Calls `Callback` with the name of every symbol defined by the object file
+// (or linked executable or library) at `Path`.
+extern "C" LLVMRustResult
+LLVMRustForEachDefinedSymbol(const char *Path, void *State,
+                             LLVMRustSymbolCallback Callback) {
+  Expected<OwningBinary<Binary>> BinOrErr = createBinary(Path);
+  if (!BinOrErr) {
+    LLVMRustSetLastError(toString(BinOrErr.takeError()).c_str());
+    return LLVMRustResult::Failure;
+  }
+  auto *Obj = dyn_cast<ObjectFile>(BinOrErr->getBinary());
+  if (!Obj) {
+    LLVMRustSetLastError("not an object file");
+    return LLVMRustResult::Failure;
+  }
+  for (const SymbolRef &Sym : Obj->symbols()) {
+    if (Sym.getFlags() & SymbolRef::SF_Undefined)
+      continue;
+    Expected<StringRef> NameOrErr = Sym.getName();
+    if (!NameOrErr) {
+      consumeError(NameOrErr.takeError());
+      continue;
+    }
+    Callback(State, NameOrErr->data(), NameOrErr->size());
+  }
+  return LLVMRustResult::Success;
+}
+
+extern "C" LLVMRustResult
+LLVMRustWriteImportLibrary(const char *ImportName, const char *Path,
+                           const char **Exports, size_t NumExports,
+                           uint16_t Machine, bool MinGW) {
+#if LLVM_VERSION_GE(7, 0)
+  std::vector<COFFShortExport> ConvertedExports;
+  ConvertedExports.reserve(NumExports);
+  for (size_t I = 0; I < NumExports; I++) {
+    COFFShortExport Export;
+    Export.Name = Exports[I];
+    ConvertedExports.push_back(Export);
+  }
+  auto Error = writeImportLibrary(ImportName, Path, ConvertedExports,
+                                  static_cast<COFF::MachineTypes>(Machine),
+                                  MinGW);
+  if (!Error)
+    return LLVMRustResult::Success;
+  LLVMRustSetLastError(toString(std::move(Error)).c_str());
+#else
+  LLVMRustSetLastError("writing import libraries requires LLVM 7 or later");
+#endif
+  return LLVMRustResult::Failure;
+}
@@ -57,3 +57,85 @@ LLVMRustLinkerAdd(RustLinker *L, char *BC, size_t Len) {
   }
   return true;
 }
+
+// Links the module of a bitcode library into `DstRef`. Unless `Define`, the
+// library's definitions are only made available for inlining, and the ones
+// that aren't needed aren't linked at all, so that a single module of the
+// crate defines them. Fails if the library defines a symbol that the module
+// defines as well.
+extern "C" bool
+LLVMRustLinkBitcodeLibrary(LLVMModuleRef DstRef, char *BC, size_t Len,
+                           bool Define) {
+  Module *Dst = unwrap(DstRef);
+  std::unique_ptr<MemoryBuffer> Buf =
+      MemoryBuffer::getMemBufferCopy(StringRef(BC, Len));
+
+  Expected<std::unique_ptr<Module>> SrcOrError =
+      llvm::parseBitcodeFile(Buf->getMemBufferRef(), Dst->getContext());
+  if (!SrcOrError) {
+    LLVMRustSetLastError(toString(SrcOrError.takeError()).c_str());
+    return false;
+  }
+  std::unique_ptr<Module> Src = std::move(*SrcOrError);
+
+  for (GlobalValue &GV : Src->global_values()) {
+    if (GV.isDeclaration() || GV.hasLocalLinkage())
+      continue;
+    GlobalValue *Existing = Dst->getNamedValue(GV.getName());
+    if (Existing && !Existing->isDeclaration()) {
+      std::string Msg =
+          ("symbol `" + GV.getName() + "` is also defined by the crate").str();
+      LLVMRustSetLastError(Msg.c_str());
+      return false;
+    }
+  }
+
+  unsigned Flags = Linker::Flags::None;
+  if (!Define) {
+    for (Function &F : *Src) {
+      if (F.isDeclaration() || F.hasLocalLinkage())
+        continue;
+      F.setLinkage(GlobalValue::AvailableExternallyLinkage);
+      F.setComdat(nullptr);
+    }
+    for (GlobalVariable &GV : Src->globals()) {
+      if (GV.isDeclaration() || GV.hasLocalLinkage())
+        continue;
+      if (GV.isConstant()) {
+        GV.setLinkage(GlobalValue::AvailableExternallyLinkage);
+      } else {
+        GV.setInitializer(nullptr);
+        GV.setLinkage(GlobalValue::ExternalLinkage);
+      }
+      GV.setComdat(nullptr);
+    }
+    // Aliases can't be `available_externally`, so refer to the symbols of
+    // the module that defines them instead.
+    for (auto I = Src->alias_begin(), E = Src->alias_end(); I != E;) {
+      GlobalAlias &GA = *I++;
+      if (GA.hasLocalLinkage())
+        continue;
+      GlobalValue *Decl;
+      if (auto *FTy = dyn_cast<FunctionType>(GA.getValueType())) {
+        Decl = Function::Create(FTy, GlobalValue::ExternalLinkage, "",
+                                Src.get());
+      } else {
+        Decl = new GlobalVariable(*Src, GA.getValueType(), false,
+                                  GlobalValue::ExternalLinkage, nullptr, "",
+                                  nullptr, GlobalValue::NotThreadLocal,
+                                  GA.getType()->getAddressSpace());
+      }
+      Decl->takeName(&GA);
+      GA.replaceAllUsesWith(
+          ConstantExpr::getPointerBitCastOrAddrSpaceCast(Decl, GA.getType()));
+      GA.eraseFromParent();
+    }
+    Flags = Linker::Flags::LinkOnlyNeeded;
+  }
+
+  if (Linker::linkModules(*Dst, std::move(Src), Flags)) {
+    LLVMRustSetLastError("");
+    return false;
+  }
+  return true;
+}
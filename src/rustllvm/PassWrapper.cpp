@@ -22,6 +22,7 @@
 #include "llvm/Support/CBindingWrapping.h"
 #include "llvm/Support/FileSystem.h"
 #include "llvm/Support/Host.h"
+#include "llvm/Support/xxhash.h"
 #include "llvm/Target/TargetMachine.h"
 #include "llvm/Transforms/IPO/PassManagerBuilder.h"
 
@@ -760,6 +761,24 @@ extern "C" void LLVMRustSetModulePIELevel(LLVMModuleRef M) {
   unwrap(M)->setPIELevel(PIELevel::Level::Large);
 }
 
+// Sorts the functions and global variables of a module by a stable hash of
+// their names, so that the order of symbols in the object file doesn't depend
+// on the order in which they happened to be created. Unnamed globals and the
+// functions placed in `.text.hot.*` sections by `-Z symbol-ordering-file` keep
+// their relative order, in front of everything else.
+extern "C" void LLVMRustSortModuleSymbols(LLVMModuleRef M) {
+  auto Key = [](const GlobalValue &GV) -> std::pair<bool, uint64_t> {
+    if (!GV.hasName() || GV.getSection().startswith(".text.hot."))
+      return std::make_pair(false, 0);
+    return std::make_pair(true, xxHash64(GV.getName()));
+  };
+  auto Less = [&](const GlobalValue &A, const GlobalValue &B) {
+    return Key(A) < Key(B);
+  };
+  unwrap(M)->getFunctionList().sort(Less);
+  unwrap(M)->getGlobalList().sort(Less);
+}
+
 // Here you'll find an implementation of ThinLTO as used by the Rust compiler
 // right now. This ThinLTO support is only enabled on "recent ish" versions of
 // LLVM, and otherwise it's just blanket rejected from other compilers.
@@ -10,9 +10,11 @@
 
 #include "rustllvm.h"
 #include "llvm/IR/CallSite.h"
+#include "llvm/IR/CFG.h"
 #include "llvm/IR/DebugInfoMetadata.h"
 #include "llvm/IR/DiagnosticInfo.h"
 #include "llvm/IR/DiagnosticPrinter.h"
+#include "llvm/IR/Dominators.h"
 #include "llvm/IR/GlobalVariable.h"
 #include "llvm/IR/Instructions.h"
 #include "llvm/Object/Archive.h"
@@ -130,6 +132,20 @@ extern "C" LLVMValueRef LLVMRustGetOrInsertFunction(LLVMModuleRef M,
       unwrap(M)->getOrInsertFunction(Name, unwrap<FunctionType>(FunctionTy)));
 }
 
+// Declares the LLVM intrinsic `Name` with the type LLVM gives it, or returns
+// null if there's no such intrinsic or it's overloaded on its types.
+extern "C" LLVMValueRef LLVMRustGetOrInsertIntrinsic(LLVMModuleRef M,
+                                                     const char *Name) {
+#if LLVM_VERSION_GE(7, 0)
+  Intrinsic::ID ID = Function::lookupIntrinsicID(Name);
+  if (ID == Intrinsic::not_intrinsic || Intrinsic::isOverloaded(ID))
+    return nullptr;
+  return wrap(Intrinsic::getDeclaration(unwrap(M), ID));
+#else
+  return nullptr;
+#endif
+}
+
 extern "C" LLVMValueRef
 LLVMRustGetOrInsertGlobal(LLVMModuleRef M, const char *Name, LLVMTypeRef Ty) {
   return wrap(unwrap(M)->getOrInsertGlobal(Name, unwrap(Ty)));
@@ -198,6 +214,10 @@ static Attribute::AttrKind fromRust(LLVMRustAttribute Kind) {
     return Attribute::SanitizeMemory;
   case NonLazyBind:
     return Attribute::NonLazyBind;
+  case StrictFP:
+    return Attribute::StrictFP;
+  case InaccessibleMemOnly:
+    return Attribute::InaccessibleMemOnly;
   }
   report_fatal_error("bad AttributeKind");
 }
@@ -273,6 +293,40 @@ extern "C" void LLVMRustAddDereferenceableOrNullAttr(LLVMValueRef Fn,
   A->addAttributes(Index, B);
 }
 
+// The allocator attributes other than `allocsize` only exist since LLVM 15,
+// rustc doesn't ask for them before.
+extern "C" void LLVMRustAddAllocKindAttr(LLVMValueRef Fn, uint64_t Kind) {
+#if LLVM_VERSION_GE(15, 0)
+  Function *F = unwrap<Function>(Fn);
+  F->addFnAttr(Attribute::get(F->getContext(), Attribute::AllocKind, Kind));
+#else
+  report_fatal_error("allockind requires LLVM 15 or later");
+#endif
+}
+
+extern "C" void LLVMRustAddAllocSizeAttr(LLVMValueRef Fn, unsigned SizeArg) {
+  Function *F = unwrap<Function>(Fn);
+  AttrBuilder B;
+  B.addAllocSizeAttr(SizeArg, Optional<unsigned>());
+  F->addAttributes(AttributeList::FunctionIndex, B);
+}
+
+extern "C" void LLVMRustAddAllocAlignAttr(LLVMValueRef Fn, unsigned ArgNo) {
+#if LLVM_VERSION_GE(15, 0)
+  unwrap<Function>(Fn)->addParamAttr(ArgNo, Attribute::AllocAlign);
+#else
+  report_fatal_error("allocalign requires LLVM 15 or later");
+#endif
+}
+
+extern "C" void LLVMRustAddAllocPtrAttr(LLVMValueRef Fn, unsigned ArgNo) {
+#if LLVM_VERSION_GE(15, 0)
+  unwrap<Function>(Fn)->addParamAttr(ArgNo, Attribute::AllocatedPointer);
+#else
+  report_fatal_error("allocptr requires LLVM 15 or later");
+#endif
+}
+
 extern "C" void LLVMRustAddFunctionAttrStringValue(LLVMValueRef Fn,
                                                    unsigned Index,
                                                    const char *Name,
@@ -305,6 +359,44 @@ extern "C" void LLVMRustSetHasUnsafeAlgebra(LLVMValueRef V) {
   }
 }
 
+// Mirrors the `FAST_MATH_*` constants in libcore/intrinsics.rs.
+enum class LLVMRustFastMathFlags {
+  Reassoc = 1 << 0,
+  NoNaNs = 1 << 1,
+  NoInfs = 1 << 2,
+  NoSignedZeros = 1 << 3,
+  AllowReciprocal = 1 << 4,
+  AllowContract = 1 << 5,
+  ApproxFunc = 1 << 6,
+};
+
+static bool hasFlag(unsigned Flags, LLVMRustFastMathFlags Flag) {
+  return Flags & static_cast<unsigned>(Flag);
+}
+
+// enable an individual subset of the fpmath flags
+extern "C" void LLVMRustSetFastMathFlags(LLVMValueRef V, unsigned Flags) {
+  if (auto I = dyn_cast<Instruction>(unwrap<Value>(V))) {
+#if LLVM_VERSION_GE(6, 0)
+    I->setHasAllowReassoc(hasFlag(Flags, LLVMRustFastMathFlags::Reassoc));
+    I->setHasApproxFunc(hasFlag(Flags, LLVMRustFastMathFlags::ApproxFunc));
+#else
+    // Older LLVM only knows reassociation as part of "unsafe algebra", which
+    // implies all the other flags as well.
+    if (hasFlag(Flags, LLVMRustFastMathFlags::Reassoc) ||
+        hasFlag(Flags, LLVMRustFastMathFlags::ApproxFunc)) {
+      I->setHasUnsafeAlgebra(true);
+      return;
+    }
+#endif
+    I->setHasNoNaNs(hasFlag(Flags, LLVMRustFastMathFlags::NoNaNs));
+    I->setHasNoInfs(hasFlag(Flags, LLVMRustFastMathFlags::NoInfs));
+    I->setHasNoSignedZeros(hasFlag(Flags, LLVMRustFastMathFlags::NoSignedZeros));
+    I->setHasAllowReciprocal(hasFlag(Flags, LLVMRustFastMathFlags::AllowReciprocal));
+    I->setHasAllowContract(hasFlag(Flags, LLVMRustFastMathFlags::AllowContract));
+  }
+}
+
 extern "C" LLVMValueRef
 LLVMRustBuildAtomicLoad(LLVMBuilderRef B, LLVMValueRef Source, const char *Name,
                         LLVMAtomicOrdering Order) {
@@ -651,6 +743,17 @@ extern "C" LLVMMetadataRef LLVMRustDIBuilderCreateMemberType(
                                         fromRust(Flags), unwrapDI<DIType>(Ty)));
 }
 
+extern "C" LLVMMetadataRef LLVMRustDIBuilderCreateBitFieldMemberType(
+    LLVMRustDIBuilderRef Builder, LLVMMetadataRef Scope, const char *Name,
+    LLVMMetadataRef File, unsigned LineNo, uint64_t SizeInBits,
+    uint64_t OffsetInBits, uint64_t StorageOffsetInBits, LLVMRustDIFlags Flags,
+    LLVMMetadataRef Ty) {
+  return wrap(Builder->createBitFieldMemberType(
+      unwrapDI<DIScope>(Scope), Name, unwrapDI<DIFile>(File), LineNo,
+      SizeInBits, OffsetInBits, StorageOffsetInBits, fromRust(Flags),
+      unwrapDI<DIType>(Ty)));
+}
+
 extern "C" LLVMMetadataRef LLVMRustDIBuilderCreateVariantMemberType(
     LLVMRustDIBuilderRef Builder, LLVMMetadataRef Scope,
     const char *Name, LLVMMetadataRef File, unsigned LineNo, uint64_t SizeInBits,
@@ -900,6 +1003,19 @@ extern "C" LLVMTypeRef LLVMRustArrayType(LLVMTypeRef ElementTy,
   return wrap(ArrayType::get(unwrap(ElementTy), ElementCount));
 }
 
+// `<vscale x ElementCount x ElementTy>`, a vector whose length is a
+// runtime multiple of `ElementCount`.
+extern "C" LLVMTypeRef LLVMRustScalableVectorType(LLVMTypeRef ElementTy,
+                                                  unsigned ElementCount) {
+#if LLVM_VERSION_GE(11, 0)
+  return wrap(ScalableVectorType::get(unwrap(ElementTy), ElementCount));
+#elif LLVM_VERSION_GE(9, 0)
+  return wrap(VectorType::get(unwrap(ElementTy), ElementCount, true));
+#else
+  report_fatal_error("scalable vectors require LLVM 9 or later");
+#endif
+}
+
 DEFINE_SIMPLE_CONVERSION_FUNCTIONS(Twine, LLVMTwineRef)
 
 extern "C" void LLVMRustWriteTwineToString(LLVMTwineRef T, RustStringRef Str) {
@@ -1053,6 +1169,49 @@ extern "C" void LLVMRustSetInlineAsmDiagnosticHandler(
   unwrap(C)->setInlineAsmDiagnosticHandler(H, CX);
 }
 
+#if LLVM_VERSION_GE(6, 0)
+// A diagnostic handler that additionally enables all the remarks of one pass,
+// which LLVM doesn't produce at all otherwise.
+class RustDiagnosticHandler final : public DiagnosticHandler {
+public:
+  RustDiagnosticHandler(void *DiagContext, const char *RemarkPass)
+      : DiagnosticHandler(DiagContext), RemarkPass(RemarkPass) {}
+
+  bool isAnalysisRemarkEnabled(StringRef PassName) const override {
+    return PassName == RemarkPass ||
+           DiagnosticHandler::isAnalysisRemarkEnabled(PassName);
+  }
+
+  bool isMissedOptRemarkEnabled(StringRef PassName) const override {
+    return PassName == RemarkPass ||
+           DiagnosticHandler::isMissedOptRemarkEnabled(PassName);
+  }
+
+  bool isPassedOptRemarkEnabled(StringRef PassName) const override {
+    return PassName == RemarkPass ||
+           DiagnosticHandler::isPassedOptRemarkEnabled(PassName);
+  }
+
+private:
+  std::string RemarkPass;
+};
+#endif
+
+extern "C" void LLVMRustContextSetDiagnosticHandler(
+    LLVMContextRef C, LLVMDiagnosticHandler H, void *CX,
+    const char *RemarkPass) {
+#if LLVM_VERSION_GE(6, 0)
+  auto Handler = llvm::make_unique<RustDiagnosticHandler>(CX, RemarkPass);
+  Handler->DiagHandlerCallback =
+      reinterpret_cast<DiagnosticHandler::DiagnosticHandlerTy>(H);
+  unwrap(C)->setDiagnosticHandler(std::move(Handler));
+#else
+  // Older LLVMs only consult `-pass-remarks-missed` and friends.
+  unwrap(C)->setDiagnosticHandler(
+      reinterpret_cast<LLVMContext::DiagnosticHandlerTy>(H), CX);
+#endif
+}
+
 extern "C" void LLVMRustWriteSMDiagnosticToString(LLVMSMDiagnosticRef D,
                                                   RustStringRef Str) {
   RawRustStringOstream OS(Str);
@@ -1446,3 +1605,52 @@ LLVMRustBuildMaxNum(LLVMBuilderRef, LLVMValueRef, LLVMValueRef) {
    return nullptr;
 }
 #endif
+
+// Attaches the `llvm.loop` metadata for the given hints to every branch back
+// to `HeaderRef`, i.e. from a block that the header dominates. A zero
+// `UnrollCount` or `PipelineII` means that hint isn't given.
+extern "C" void LLVMRustSetLoopHints(LLVMBasicBlockRef HeaderRef,
+                                     unsigned UnrollCount, bool NoUnroll,
+                                     bool PipelineDisable, unsigned PipelineII) {
+  BasicBlock *Header = unwrap(HeaderRef);
+  Function *F = Header->getParent();
+  LLVMContext &Ctx = F->getContext();
+  Type *I32 = Type::getInt32Ty(Ctx);
+
+  // The first operand of a loop id is the loop id itself.
+  SmallVector<Metadata *, 4> MDs;
+  MDs.push_back(nullptr);
+  if (UnrollCount) {
+    MDs.push_back(MDNode::get(
+        Ctx, {MDString::get(Ctx, "llvm.loop.unroll.count"),
+              ConstantAsMetadata::get(ConstantInt::get(I32, UnrollCount))}));
+  }
+  if (NoUnroll) {
+    MDs.push_back(
+        MDNode::get(Ctx, MDString::get(Ctx, "llvm.loop.unroll.disable")));
+  }
+  if (PipelineDisable) {
+    MDs.push_back(MDNode::get(
+        Ctx, {MDString::get(Ctx, "llvm.loop.pipeline.disable"),
+              ConstantAsMetadata::get(ConstantInt::getTrue(Ctx))}));
+  }
+  if (PipelineII) {
+    MDs.push_back(MDNode::get(
+        Ctx, {MDString::get(Ctx, "llvm.loop.pipeline.initiationinterval"),
+              ConstantAsMetadata::get(ConstantInt::get(I32, PipelineII))}));
+  }
+  MDNode *LoopID = MDNode::getDistinct(Ctx, MDs);
+  LoopID->replaceOperandWith(0, LoopID);
+
+  DominatorTree DT(*F);
+  for (BasicBlock &BB : *F) {
+    if (!DT.dominates(Header, &BB))
+      continue;
+    for (BasicBlock *Succ : successors(&BB)) {
+      if (Succ == Header) {
+        BB.getTerminator()->setMetadata(LLVMContext::MD_loop, LoopID);
+        break;
+      }
+    }
+  }
+}
@@ -94,6 +94,8 @@ enum LLVMRustAttribute {
   SanitizeAddress = 21,
   SanitizeMemory = 22,
   NonLazyBind = 23,
+  StrictFP = 24,
+  InaccessibleMemOnly = 25,
 };
 
 typedef struct OpaqueRustString *RustStringRef;
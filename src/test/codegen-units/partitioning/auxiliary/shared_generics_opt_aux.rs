@@ -0,0 +1,32 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags:-C opt-level=2
+
+#![crate_type="rlib"]
+
+pub fn tiny_fn<T>(x: T, y: T) -> (T, T) {
+    (x, y)
+}
+
+pub fn big_fn<T: Clone + PartialOrd>(xs: &[T]) -> Vec<T> {
+    let mut out = Vec::new();
+    for x in xs {
+        if out.last().map_or(true, |last| last < x) {
+            out.push(x.clone());
+        }
+    }
+    out.reverse();
+    out
+}
+
+pub fn use_generic_fns() -> ((u32, u32), Vec<u32>) {
+    (tiny_fn(0, 1), big_fn(&[1, 2, 3]))
+}
@@ -0,0 +1,28 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// compile-flags:-Zprint-mono-items=eager -C opt-level=2 -Zincremental=tmp/partitioning-tests/shared-generics-opt
+
+#![crate_type="rlib"]
+
+// aux-build:shared_generics_opt_aux.rs
+extern crate shared_generics_opt_aux;
+
+//~ MONO_ITEM fn shared_generics_opt::foo[0]
+pub fn foo() {
+    // Tiny generic functions aren't shared in optimized builds, so this is
+    // instantiated again where it can be inlined.
+    //~ MONO_ITEM fn shared_generics_opt_aux::tiny_fn[0]<u32> @@ shared_generics_opt_aux-in-shared_generics_opt.volatile[External]
+    let _ = shared_generics_opt_aux::tiny_fn(0u32, 1u32);
+
+    // This is reused from `shared_generics_opt_aux` even at opt-level 2.
+    let _ = shared_generics_opt_aux::big_fn(&[1u32, 2, 3]);
+}
@@ -0,0 +1,101 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks the SysV classification of structs containing vectors and mixed
+// INTEGER/SSE eightbytes, with and without vector target features enabled.
+
+// only-x86_64
+// ignore-windows
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(repr_simd)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct f32x4(f32, f32, f32, f32);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct f32x8(f32, f32, f32, f32, f32, f32, f32, f32);
+
+#[repr(C)]
+pub struct Wrap128(f32x4);
+
+#[repr(C)]
+pub struct Wrap256(f32x8);
+
+#[repr(C)]
+pub struct Two128(f32x4, f32x4);
+
+#[repr(C)]
+pub struct Mixed {
+    a: f64,
+    b: i64,
+}
+
+#[repr(C)]
+pub struct FloatInt {
+    a: f32,
+    b: i32,
+    c: f64,
+}
+
+// A single SSE eightbyte followed by SSEUP is passed in one XMM register.
+// CHECK: <16 x i8> @wrap_128(<16 x i8>
+#[no_mangle]
+pub extern "C" fn wrap_128(a: Wrap128) -> Wrap128 {
+    a
+}
+
+// Without AVX there is no register wide enough, so this goes in memory.
+// CHECK: void @wrap_256(%Wrap256* {{.*}}sret{{.*}}, %Wrap256* {{.*}}byval
+#[no_mangle]
+pub extern "C" fn wrap_256(a: Wrap256) -> Wrap256 {
+    a
+}
+
+// More than two eightbytes, but not a single vector.
+// CHECK: void @two_128(%Two128* {{.*}}sret{{.*}}, %Two128* {{.*}}byval
+#[no_mangle]
+pub extern "C" fn two_128(a: Two128) -> Two128 {
+    a
+}
+
+// CHECK: { double, i64 } @mixed({ double, i64 }
+#[no_mangle]
+pub extern "C" fn mixed(a: Mixed) -> Mixed {
+    a
+}
+
+// INTEGER wins over SSE when both share an eightbyte.
+// CHECK: { i64, double } @float_int({ i64, double }
+#[no_mangle]
+pub extern "C" fn float_int(a: FloatInt) -> FloatInt {
+    a
+}
+
+// With AVX enabled for the function, it goes in a single YMM register.
+// CHECK: <32 x i8> @wrap_256_avx(<32 x i8>
+#[no_mangle]
+#[target_feature(enable = "avx")]
+pub unsafe extern "C" fn wrap_256_avx(a: Wrap256) -> Wrap256 {
+    a
+}
+
+// Direct calls use the ABI of the callee.
+// CHECK-LABEL: @call_wrap_256_avx
+// CHECK: call <32 x i8> @wrap_256_avx(<32 x i8>
+#[no_mangle]
+pub unsafe fn call_wrap_256_avx(a: Wrap256) -> Wrap256 {
+    wrap_256_avx(a)
+}
@@ -0,0 +1,38 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O
+// min-llvm-version 15.0
+
+#![crate_type = "lib"]
+
+// Newer LLVMs are also told what kind of allocation function each of the
+// global allocator functions is, and which arguments are the alignment and
+// the pointer.
+
+// CHECK-LABEL: @boxed
+#[no_mangle]
+pub fn boxed(x: u32) -> Box<u32> {
+    // CHECK: call {{.*}}@__rust_alloc(
+    Box::new(x)
+}
+
+// CHECK-LABEL: @unboxed
+#[no_mangle]
+pub fn unboxed(x: Box<u32>) -> u32 {
+    // CHECK: call {{.*}}@__rust_dealloc(
+    *x
+}
+
+// CHECK: declare noalias {{.*}}@__rust_alloc({{.*}}allocalign{{.*}}) unnamed_addr [[ALLOC:#[0-9]+]]
+// CHECK: declare void @__rust_dealloc({{.*}}allocptr{{.*}}) unnamed_addr [[DEALLOC:#[0-9]+]]
+
+// CHECK: attributes [[ALLOC]] = { {{.*}}allockind("alloc,uninitialized,aligned"){{.*}}
+// CHECK: attributes [[DEALLOC]] = { {{.*}}allockind("free"){{.*}}
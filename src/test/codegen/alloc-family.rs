@@ -0,0 +1,35 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O
+
+#![crate_type = "lib"]
+
+// The global allocator functions are declared as one allocation family.
+
+// CHECK-LABEL: @boxed
+#[no_mangle]
+pub fn boxed(x: u32) -> Box<u32> {
+    // CHECK: call {{.*}}@__rust_alloc(
+    Box::new(x)
+}
+
+// CHECK-LABEL: @unboxed
+#[no_mangle]
+pub fn unboxed(x: Box<u32>) -> u32 {
+    // CHECK: call {{.*}}@__rust_dealloc(
+    *x
+}
+
+// CHECK: declare noalias i8* @__rust_alloc({{.*}}) unnamed_addr [[ALLOC:#[0-9]+]]
+// CHECK: declare void @__rust_dealloc({{.*}}) unnamed_addr [[DEALLOC:#[0-9]+]]
+
+// CHECK: attributes [[ALLOC]] = { {{.*}}allocsize(0){{.*}}"alloc-family"="__rust_alloc"
+// CHECK: attributes [[DEALLOC]] = { {{.*}}"alloc-family"="__rust_alloc"
@@ -0,0 +1,39 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-x86_64
+// compile-flags: -C no-prepopulate-passes -C target-cpu=x86-64
+
+// Without `cmpxchg16b` for the whole crate, 128-bit atomics are still
+// emitted. LLVM lowers them per function: to `cmpxchg16b` where a function
+// enables it, and to libatomic's `__atomic_*` functions elsewhere.
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics, cmpxchg16b_target_feature)]
+
+use std::intrinsics::atomic_xadd;
+
+// CHECK-LABEL: @libcall
+#[no_mangle]
+pub unsafe fn libcall(p: *mut u128, x: u128) -> u128 {
+    // CHECK: atomicrmw add i128* %{{.*}}, i128 %{{.*}} seq_cst
+    atomic_xadd(p, x)
+}
+
+// CHECK-LABEL: @native
+// CHECK-SAME: #[[ATTRS:[0-9]+]]
+#[no_mangle]
+#[target_feature(enable = "cmpxchg16b")]
+pub unsafe fn native(p: *mut u128, x: u128) -> u128 {
+    // CHECK: atomicrmw add i128* %{{.*}}, i128 %{{.*}} seq_cst
+    atomic_xadd(p, x)
+}
+
+// CHECK: attributes #[[ATTRS]] = {{.*}}"target-features"="{{[^"]*}}+cmpxchg16b
@@ -0,0 +1,45 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-x86_64
+// compile-flags: -C no-prepopulate-passes -C target-feature=+cmpxchg16b
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{atomic_cxchg, atomic_load, atomic_store, atomic_xadd};
+
+// CHECK-LABEL: @load
+#[no_mangle]
+pub unsafe fn load(p: *mut u128) -> u128 {
+    // CHECK: load atomic i128, i128* %{{.*}} seq_cst, align 16
+    atomic_load(p)
+}
+
+// CHECK-LABEL: @store
+#[no_mangle]
+pub unsafe fn store(p: *mut i128, x: i128) {
+    // CHECK: store atomic i128 %{{.*}}, i128* %{{.*}} seq_cst, align 16
+    atomic_store(p, x)
+}
+
+// CHECK-LABEL: @cxchg
+#[no_mangle]
+pub unsafe fn cxchg(p: *mut u128, old: u128, new: u128) -> (u128, bool) {
+    // CHECK: cmpxchg i128* %{{.*}}, i128 %{{.*}}, i128 %{{.*}} seq_cst seq_cst
+    atomic_cxchg(p, old, new)
+}
+
+// CHECK-LABEL: @xadd
+#[no_mangle]
+pub unsafe fn xadd(p: *mut u128, x: u128) -> u128 {
+    // CHECK: atomicrmw add i128* %{{.*}}, i128 %{{.*}} seq_cst
+    atomic_xadd(p, x)
+}
@@ -0,0 +1,52 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O
+// ignore-emscripten
+
+// Checks that `AtomicPtr` accesses the pointer as a pointer rather than
+// through `ptrtoint`/`inttoptr` casts, except for the exchange.
+
+#![crate_type = "lib"]
+
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+// CHECK-LABEL: @load
+#[no_mangle]
+pub fn load(a: &AtomicPtr<u32>) -> *mut u32 {
+    // CHECK-NOT: ptrtoint
+    // CHECK: load atomic i32*, i32** %{{.*}} acquire
+    // CHECK-NOT: inttoptr
+    a.load(Ordering::Acquire)
+}
+
+// CHECK-LABEL: @store
+#[no_mangle]
+pub fn store(a: &AtomicPtr<u32>, p: *mut u32) {
+    // CHECK-NOT: ptrtoint
+    // CHECK: store atomic i32* %p, i32** %{{.*}} release
+    a.store(p, Ordering::Release)
+}
+
+// CHECK-LABEL: @compare_exchange
+#[no_mangle]
+pub fn compare_exchange(a: &AtomicPtr<u32>, old: *mut u32, new: *mut u32) -> bool {
+    // CHECK-NOT: ptrtoint
+    // CHECK: cmpxchg i32** %{{.*}}, i32* %old, i32* %new seq_cst seq_cst
+    a.compare_exchange(old, new, Ordering::SeqCst, Ordering::SeqCst).is_ok()
+}
+
+// CHECK-LABEL: @swap
+#[no_mangle]
+pub fn swap(a: &AtomicPtr<u32>, p: *mut u32) -> *mut u32 {
+    // CHECK: atomicrmw xchg
+    // CHECK: inttoptr
+    a.swap(p, Ordering::SeqCst)
+}
@@ -0,0 +1,61 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes --target=thumbv6m-none-eabi --crate-type=rlib
+// compile-flags: -Z atomics-critical-sections
+
+#![feature(no_core, lang_items, intrinsics)]
+#![no_core]
+
+#[lang="sized"]
+trait Sized { }
+#[lang="freeze"]
+trait Freeze { }
+#[lang="copy"]
+trait Copy { }
+
+impl Copy for u32 { }
+impl Copy for bool { }
+
+extern "rust-intrinsic" {
+    fn atomic_xadd<T>(dst: *mut T, src: T) -> T;
+    fn atomic_cxchg<T>(dst: *mut T, old: T, src: T) -> (T, bool);
+    fn atomic_fence();
+}
+
+// CHECK-LABEL: @fetch_add
+#[no_mangle]
+pub unsafe fn fetch_add(p: *mut u32, v: u32) -> u32 {
+// CHECK-NOT: atomicrmw
+// CHECK: [[STATE:%.*]] = call i32 asm sideeffect "mrs $0, PRIMASK\0A\09cpsid i", "=r,~{memory}"()
+// CHECK: load volatile i32
+// CHECK: add i32
+// CHECK: store volatile i32
+// CHECK: call void asm sideeffect "msr PRIMASK, $0", "r,~{memory}"(i32 [[STATE]])
+    atomic_xadd(p, v)
+}
+
+// CHECK-LABEL: @compare_exchange
+#[no_mangle]
+pub unsafe fn compare_exchange(p: *mut u32, old: u32, new: u32) -> (u32, bool) {
+// CHECK-NOT: cmpxchg
+// CHECK: cpsid i
+// CHECK: icmp eq i32
+// CHECK: select i1
+// CHECK: msr PRIMASK
+    atomic_cxchg(p, old, new)
+}
+
+// CHECK-LABEL: @fence
+#[no_mangle]
+pub unsafe fn fence() {
+// CHECK: fence seq_cst
+    atomic_fence()
+}
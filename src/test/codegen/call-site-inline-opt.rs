@@ -0,0 +1,43 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O
+
+#![crate_type = "lib"]
+#![feature(call_site_inline, stmt_expr_attributes)]
+
+#[no_mangle]
+#[inline]
+pub fn hinted(x: &mut u32) {
+    *x += 1;
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn never(x: &mut u32) {
+    *x *= 3;
+}
+
+// CHECK-LABEL: @keeps_call
+#[no_mangle]
+pub fn keeps_call(x: &mut u32) {
+    // CHECK: call {{.*}}@hinted(
+    #[inline(never)] hinted(x);
+}
+
+// CHECK-LABEL: @inlines_call
+#[no_mangle]
+pub fn inlines_call(x: &mut u32) {
+    // CHECK-NOT: call
+    // CHECK: mul i32 {{.*}}3
+    // CHECK-NOT: call
+    // CHECK: ret void
+    #[inline(always)] never(x);
+}
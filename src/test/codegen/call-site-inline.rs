@@ -0,0 +1,44 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(call_site_inline, stmt_expr_attributes)]
+
+#[no_mangle]
+#[inline]
+pub fn hinted() {}
+
+#[no_mangle]
+#[inline(never)]
+pub fn never() {}
+
+pub struct S;
+
+impl S {
+    #[no_mangle]
+    #[inline(never)]
+    pub fn method(&self) {}
+}
+
+// CHECK-LABEL: @caller
+#[no_mangle]
+pub fn caller(s: &S) {
+    // CHECK: call {{.*}}@hinted() #[[NOINLINE:[0-9]+]]
+    #[inline(never)] hinted();
+    // CHECK: call {{.*}}@never() #[[ALWAYS:[0-9]+]]
+    #[inline(always)] never();
+    // CHECK: call {{.*}}@method({{.*}}) #[[ALWAYS]]
+    #[inline(always)] s.method();
+}
+
+// CHECK: attributes #[[NOINLINE]] = { {{.*}}noinline{{.*}} }
+// CHECK: attributes #[[ALWAYS]] = { {{.*}}alwaysinline{{.*}} }
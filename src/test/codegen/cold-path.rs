@@ -0,0 +1,46 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::cold_path;
+
+extern {
+    fn hot();
+    fn cold();
+}
+
+// CHECK-LABEL: @cold_then
+#[no_mangle]
+pub fn cold_then(x: bool) {
+    // CHECK: br i1 %{{.*}}, label %{{.*}}, label %{{.*}}, !prof ![[COLD_THEN:[0-9]+]]
+    if x {
+        unsafe { cold_path(); cold(); }
+    } else {
+        unsafe { hot(); }
+    }
+}
+
+// CHECK-LABEL: @cold_else
+#[no_mangle]
+pub fn cold_else(x: bool) {
+    // CHECK: br i1 %{{.*}}, label %{{.*}}, label %{{.*}}, !prof ![[COLD_ELSE:[0-9]+]]
+    if x {
+        unsafe { hot(); }
+    } else {
+        unsafe { cold_path(); cold(); }
+    }
+}
+
+// CHECK: ![[COLD_THEN]] = !{!"branch_weights", i32 1, i32 2000}
+// CHECK: ![[COLD_ELSE]] = !{!"branch_weights", i32 2000, i32 1}
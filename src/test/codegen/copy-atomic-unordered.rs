@@ -0,0 +1,32 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// compile-flags: -C no-prepopulate-passes
+// only-x86_64
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::copy_nonoverlapping_atomic_unordered;
+
+// CHECK-LABEL: @copy_u32
+#[no_mangle]
+pub unsafe fn copy_u32(src: *const u32, dst: *mut u32, count: usize) {
+    // CHECK: call void @llvm.memcpy.element.unordered.atomic.p0i8.p0i8.i64(i8* align 4 %{{.*}}, i8* align 4 %{{.*}}, i64 %{{.*}}, i32 4)
+    copy_nonoverlapping_atomic_unordered(src, dst, count)
+}
+
+// CHECK-LABEL: @copy_u64
+#[no_mangle]
+pub unsafe fn copy_u64(src: *const u64, dst: *mut u64, count: usize) {
+    // CHECK: call void @llvm.memcpy.element.unordered.atomic.p0i8.p0i8.i64(i8* align 8 %{{.*}}, i8* align 8 %{{.*}}, i64 %{{.*}}, i32 8)
+    copy_nonoverlapping_atomic_unordered(src, dst, count)
+}
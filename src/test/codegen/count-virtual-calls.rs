@@ -0,0 +1,25 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes -Z count-virtual-calls
+
+#![crate_type = "lib"]
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+// CHECK-LABEL: @total_area
+#[no_mangle]
+pub fn total_area(shape: &dyn Shape) -> f64 {
+    // CHECK: call void @{{.*}}count_virtual_call{{.*}}@virtual_call_site
+    // CHECK: load {{.*}} !invariant.load
+    shape.area()
+}
@@ -0,0 +1,29 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-linux
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::cpu_hwcap;
+
+// `getauxval` only reads memory the program has no other access to, so
+// repeated queries can be merged.
+
+// CHECK-LABEL: @hwcap
+#[no_mangle]
+pub fn hwcap() -> usize {
+    // CHECK: call {{i64|i32}} @getauxval({{i64|i32}} 16) #[[ATTR:[0-9]+]]
+    unsafe { cpu_hwcap() }
+}
+
+// CHECK: attributes #[[ATTR]] = { {{.*}}inaccessiblememonly{{.*}}readonly{{.*}} }
@@ -0,0 +1,27 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// only-x86_64
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::cpuid;
+
+// CHECK-LABEL: @max_leaf
+#[no_mangle]
+pub fn max_leaf() -> u32 {
+    // CHECK: call { i32, i32, i32, i32 } asm "movq %rbx, %rsi{{.*}}cpuid{{.*}}", "={ax},={si},={cx},={dx},{ax},{cx}"(i32 0, i32 0) #[[ATTR:[0-9]+]]
+    unsafe { cpuid(0, 0).0 }
+}
+
+// CHECK: attributes #[[ATTR]] = { nounwind }
@@ -0,0 +1,29 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// compile-flags: -C no-prepopulate-passes
+
+// Drop glue that just drops a few fields in a row is hinted to be inlined.
+
+#![crate_type = "lib"]
+
+pub struct TwoVecs {
+    a: Vec<u8>,
+    b: Vec<u32>,
+}
+
+// CHECK: define internal void @_ZN4core3ptr13drop_in_place{{.*}}(%TwoVecs*{{.*}} unnamed_addr #[[ATTRS:[0-9]+]]
+
+#[no_mangle]
+pub fn drop_two_vecs(_x: TwoVecs) {
+}
+
+// CHECK: attributes #[[ATTRS]] = { {{.*}}inlinehint
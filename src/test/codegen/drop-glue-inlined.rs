@@ -0,0 +1,27 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O
+
+// The hinted drop glue of a struct of two `Vec`s is inlined into its caller.
+
+#![crate_type = "lib"]
+
+pub struct TwoVecs {
+    a: Vec<u8>,
+    b: Vec<u32>,
+}
+
+// CHECK-LABEL: @drop_two_vecs
+// CHECK-NOT: call {{.*}}drop_in_place{{.*}}(%TwoVecs*
+// CHECK: ret void
+#[no_mangle]
+pub fn drop_two_vecs(_x: TwoVecs) {
+}
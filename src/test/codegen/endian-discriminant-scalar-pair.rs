@@ -0,0 +1,52 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// revisions: little big
+// [little] compile-flags: --target powerpc64le-unknown-linux-gnu
+// [big] compile-flags: --target powerpc64-unknown-linux-gnu
+// compile-flags: -C no-prepopulate-passes --crate-type=rlib
+
+// Discriminant reads and scalar pairs don't depend on the byte order: the tag
+// is loaded through its field at the offset from the layout, and the halves
+// of a pair are kept as separate struct members. Both revisions must produce
+// the same IR.
+
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang="sized"]
+trait Sized { }
+#[lang="copy"]
+trait Copy { }
+
+#[repr(u8)]
+pub enum Tagged {
+    A(u32),
+    B(u16),
+}
+
+// CHECK-LABEL: @is_a
+#[no_mangle]
+pub fn is_a(t: &Tagged) -> bool {
+// CHECK: [[TAG_PTR:%[0-9]+]] = getelementptr inbounds {{.*}}, i32 0, i32 0
+// CHECK: load i8, i8* [[TAG_PTR]]
+    match *t {
+        Tagged::A(_) => true,
+        Tagged::B(_) => false,
+    }
+}
+
+// CHECK-LABEL: define { i32, i16 } @pair(i32{{.*}} %a, i16{{.*}} %b)
+#[no_mangle]
+pub fn pair(a: u32, b: u16) -> (u32, u16) {
+// CHECK: insertvalue { i32, i16 } undef, i32 %a, 0
+// CHECK: insertvalue { i32, i16 } {{.*}}, i16 %b, 1
+    (a, b)
+}
@@ -0,0 +1,52 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// only-x86_64
+// compile-flags: -C no-prepopulate-passes
+
+// Checks that a function accessing the floating-point environment constrains
+// its floating-point operations, isn't inlined and doesn't inline its callees.
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics, repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+use std::intrinsics::{fenv_set_rounding_mode, sqrtf64};
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct f64x2(f64, f64);
+
+extern "platform-intrinsic" {
+    fn simd_add<T>(x: T, y: T) -> T;
+}
+
+#[inline]
+pub fn double(x: f64) -> f64 {
+    x * 2.0
+}
+
+// CHECK-LABEL: define void @rounded({{.*}} #[[FN_ATTRS:[0-9]+]]
+#[no_mangle]
+pub unsafe fn rounded(a: f64, v: f64x2, out: &mut f64, vout: &mut f64x2) {
+    fenv_set_rounding_mode(1);
+// CHECK: call double @llvm.experimental.constrained.sqrt.f64(double {{.*}}, metadata !"round.dynamic", metadata !"fpexcept.strict")
+    *out = sqrtf64(a);
+// CHECK: call <2 x double> @llvm.experimental.constrained.fadd.v2f64(<2 x double> {{.*}}, <2 x double> {{.*}}, metadata !"round.dynamic", metadata !"fpexcept.strict")
+    *vout = simd_add(v, v);
+// CHECK: call double @{{.*}}double{{.*}}(double {{.*}}) #[[CALL_ATTRS:[0-9]+]]
+// CHECK: call double @llvm.experimental.constrained.fadd.f64(
+    *out += double(a);
+    fenv_set_rounding_mode(0);
+}
+
+// CHECK-DAG: attributes #[[FN_ATTRS]] = {{.*}}noinline{{.*}}strictfp
+// CHECK-DAG: attributes #[[CALL_ATTRS]] = {{.*}}noinline
@@ -0,0 +1,30 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{canonicalizef32, canonicalizef64};
+
+// CHECK-LABEL: @canonicalize32
+#[no_mangle]
+pub fn canonicalize32(x: f32) -> f32 {
+    // CHECK: call float @llvm.canonicalize.f32(float %x)
+    unsafe { canonicalizef32(x) }
+}
+
+// CHECK-LABEL: @canonicalize64
+#[no_mangle]
+pub fn canonicalize64(x: f64) -> f64 {
+    // CHECK: call double @llvm.canonicalize.f64(double %x)
+    unsafe { canonicalizef64(x) }
+}
@@ -0,0 +1,56 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes
+// min-llvm-version 6.0
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{fadd_flags, fmul_flags, fdiv_flags};
+use std::intrinsics::{FAST_MATH_CONTRACT, FAST_MATH_REASSOC, FAST_MATH_NO_NANS};
+use std::intrinsics::{FAST_MATH_NO_INFS, FAST_MATH_ALLOW_RECIPROCAL};
+
+// CHECK-LABEL: @contract
+#[no_mangle]
+pub fn contract(x: f32, y: f32) -> f32 {
+// CHECK: fmul contract float
+    unsafe {
+        fmul_flags(x, y, FAST_MATH_CONTRACT)
+    }
+}
+
+// CHECK-LABEL: @reassoc
+#[no_mangle]
+pub fn reassoc(x: f64, y: f64) -> f64 {
+// CHECK: fadd reassoc double
+    unsafe {
+        fadd_flags(x, y, FAST_MATH_REASSOC)
+    }
+}
+
+// CHECK-LABEL: @finite_reciprocal
+#[no_mangle]
+pub fn finite_reciprocal(x: f32, y: f32) -> f32 {
+// CHECK: fdiv nnan ninf arcp float
+    unsafe {
+        fdiv_flags(x, y, FAST_MATH_NO_NANS | FAST_MATH_NO_INFS | FAST_MATH_ALLOW_RECIPROCAL)
+    }
+}
+
+// CHECK-LABEL: @no_flags
+#[no_mangle]
+pub fn no_flags(x: f32, y: f32) -> f32 {
+// CHECK: fadd float
+// CHECK-NOT: fast
+    unsafe {
+        fadd_flags(x, y, 0)
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --target hexagon-unknown-linux-musl -C no-prepopulate-passes --crate-type=rlib
+
+// Vectors that fit an HVX register or register pair are passed and returned
+// directly, other vectors wider than 64 bits in memory.
+
+#![feature(no_core, lang_items, repr_simd)]
+#![no_core]
+#![allow(non_camel_case_types)]
+
+#[lang="sized"]
+trait Sized { }
+#[lang="copy"]
+trait Copy { }
+
+#[repr(simd)]
+pub struct i32x2(i32, i32);
+
+#[repr(simd)]
+pub struct i32x4(i32, i32, i32, i32);
+
+#[repr(simd)]
+pub struct i32x16(i32, i32, i32, i32, i32, i32, i32, i32,
+                  i32, i32, i32, i32, i32, i32, i32, i32);
+
+#[repr(simd)]
+pub struct i32x32(i32, i32, i32, i32, i32, i32, i32, i32,
+                  i32, i32, i32, i32, i32, i32, i32, i32,
+                  i32, i32, i32, i32, i32, i32, i32, i32,
+                  i32, i32, i32, i32, i32, i32, i32, i32);
+
+impl Copy for i32x2 {}
+impl Copy for i32x4 {}
+impl Copy for i32x16 {}
+impl Copy for i32x32 {}
+
+// CHECK: define <2 x i32> @small(<2 x i32> %x)
+#[no_mangle]
+pub extern "C" fn small(x: i32x2) -> i32x2 {
+    x
+}
+
+// CHECK: define void @not_hvx(<4 x i32>* {{.*}}sret{{.*}}, <4 x i32>* {{.*}}%x)
+#[no_mangle]
+pub extern "C" fn not_hvx(x: i32x4) -> i32x4 {
+    x
+}
+
+// CHECK: define <16 x i32> @hvx_64b(<16 x i32> %x)
+#[no_mangle]
+pub extern "C" fn hvx_64b(x: i32x16) -> i32x16 {
+    x
+}
+
+// CHECK: define <32 x i32> @hvx_128b(<32 x i32> %x)
+#[no_mangle]
+pub extern "C" fn hvx_128b(x: i32x32) -> i32x32 {
+    x
+}
@@ -0,0 +1,47 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// compile-flags: --target hexagon-unknown-linux-musl -C no-prepopulate-passes --crate-type=rlib
+// min-llvm-version 7.0
+
+// HVX intrinsics are declared with the vectors of `i32` LLVM gives them, and
+// vectors of other lanes are bitcast around the call.
+
+#![feature(no_core, lang_items, repr_simd, platform_intrinsics)]
+#![no_core]
+#![allow(non_camel_case_types)]
+
+#[lang="sized"]
+trait Sized { }
+#[lang="copy"]
+trait Copy { }
+
+#[repr(simd)]
+pub struct i8x64(i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8,
+                 i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8,
+                 i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8,
+                 i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8);
+
+impl Copy for i8x64 {}
+
+extern "platform-intrinsic" {
+    fn Q6_Vb_vadd_VbVb_sat64(x: i8x64, y: i8x64) -> i8x64;
+}
+
+// CHECK-LABEL: @add_sat
+#[no_mangle]
+pub unsafe fn add_sat(x: i8x64, y: i8x64) -> i8x64 {
+// CHECK: [[X:%[0-9]+]] = bitcast <64 x i8> {{.*}} to <16 x i32>
+// CHECK: [[Y:%[0-9]+]] = bitcast <64 x i8> {{.*}} to <16 x i32>
+// CHECK: [[R:%[0-9]+]] = call <16 x i32> @llvm.hexagon.V6.vaddbsat(<16 x i32> [[X]], <16 x i32> [[Y]])
+// CHECK: bitcast <16 x i32> [[R]] to <64 x i8>
+    Q6_Vb_vadd_VbVb_sat64(x, y)
+}
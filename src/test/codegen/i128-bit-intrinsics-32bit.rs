@@ -0,0 +1,49 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks that 128-bit bswap, bitreverse and ctpop are split into 64-bit
+// halves on 32-bit targets.
+
+// only-x86
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{bitreverse, bswap, ctpop};
+
+// CHECK-LABEL: @swap
+#[no_mangle]
+pub fn swap(x: u128) -> u128 {
+// CHECK-NOT: @llvm.bswap.i128
+// CHECK: call i64 @llvm.bswap.i64
+// CHECK: call i64 @llvm.bswap.i64
+// CHECK: shl i128 %{{.*}}, 64
+    unsafe { bswap(x) }
+}
+
+// CHECK-LABEL: @reverse
+#[no_mangle]
+pub fn reverse(x: u128) -> u128 {
+// CHECK-NOT: @llvm.bitreverse.i128
+// CHECK: call i64 @llvm.bitreverse.i64
+// CHECK: call i64 @llvm.bitreverse.i64
+    unsafe { bitreverse(x) }
+}
+
+// CHECK-LABEL: @popcount
+#[no_mangle]
+pub fn popcount(x: u128) -> u128 {
+// CHECK-NOT: @llvm.ctpop.i128
+// CHECK: call i64 @llvm.ctpop.i64
+// CHECK: call i64 @llvm.ctpop.i64
+// CHECK: zext i64 %{{.*}} to i128
+    unsafe { ctpop(x) }
+}
@@ -0,0 +1,44 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// revisions: linux windows
+// [linux] compile-flags: --target x86_64-unknown-linux-gnu
+// [windows] compile-flags: --target x86_64-pc-windows-gnu
+// compile-flags: -C no-prepopulate-passes --crate-type=rlib
+
+// 128-bit integers are passed like C's `__int128`: on Linux they are never
+// split between the last register and the stack, where they are 16-byte
+// aligned, and on Windows they are returned in `xmm0` instead of through a
+// hidden pointer.
+
+#![feature(no_core, lang_items)]
+#![no_core]
+
+#[lang="sized"]
+trait Sized { }
+#[lang="copy"]
+trait Copy { }
+
+// CHECK-LABEL: @identity
+#[no_mangle]
+pub extern "C" fn identity(x: i128) -> i128 {
+// linux-SAME: (i128 %x)
+// windows-SAME: (i128* {{.*}}%x)
+// windows: ret <16 x i8>
+    x
+}
+
+// CHECK-LABEL: @on_stack
+#[no_mangle]
+pub extern "C" fn on_stack(_a: i64, _b: i64, _c: i64, _d: i64, _e: i64, _x: i128) {
+// linux-SAME: i64 %_e, i128* {{.*}}byval{{.*}}align 16{{.*}}%_x)
+// windows-SAME: i64 %_e, i128* {{.*}}%_x)
+}
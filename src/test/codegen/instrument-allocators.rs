@@ -0,0 +1,35 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes -Z instrument-allocators
+
+#![crate_type = "lib"]
+
+use std::alloc::{alloc, dealloc, realloc, Layout};
+
+// `alloc` and friends are `#[inline]`, so they're instantiated here and
+// their calls to the allocator are instrumented.
+
+// CHECK-DAG: call i8* @__rust_alloc_instrumented({{.*}}, i64 {{-?[0-9]+}})
+// CHECK-DAG: call i8* @__rust_realloc_instrumented({{.*}}, i64 {{-?[0-9]+}})
+// CHECK-DAG: call void @__rust_dealloc_instrumented({{.*}}, i64 {{-?[0-9]+}})
+#[no_mangle]
+pub unsafe fn roundtrip() {
+    let layout = Layout::from_size_align_unchecked(16, 8);
+    let p = alloc(layout);
+    let p = realloc(p, layout, 32);
+    dealloc(p, Layout::from_size_align_unchecked(32, 8));
+}
+
+// CHECK-DAG: define linkonce_odr hidden i8* @__rust_alloc_instrumented
+// CHECK-DAG: @__rust_alloc_event_hook = external global
+// CHECK-DAG: load atomic {{i32|i64}}, {{i32|i64}}* @__rust_alloc_event_hook acquire
+// CHECK-DAG: call void %{{.*}}(i32 0,
+// CHECK-DAG: call void %{{.*}}(i32 1,
@@ -0,0 +1,51 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O
+// min-llvm-version 8.0
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::is_val_statically_known;
+
+#[inline]
+pub fn known<T: Copy>(x: T) -> bool {
+    unsafe { is_val_statically_known(x) }
+}
+
+// CHECK-LABEL: @known_int
+#[no_mangle]
+pub fn known_int() -> bool {
+    // CHECK: ret i1 true
+    known(1u32)
+}
+
+// CHECK-LABEL: @unknown_int
+#[no_mangle]
+pub fn unknown_int(x: u32) -> bool {
+    // CHECK: ret i1 false
+    known(x)
+}
+
+// CHECK-LABEL: @known_float
+#[no_mangle]
+pub fn known_float() -> bool {
+    // CHECK: ret i1 true
+    known(1.5f64)
+}
+
+// CHECK-LABEL: @unknown_aggregate
+#[no_mangle]
+pub fn unknown_aggregate() -> bool {
+    // Only first-class values are supported; aggregates are never known.
+    // CHECK: ret i1 false
+    known((1u8, 2u64))
+}
@@ -19,6 +19,7 @@ use std::intrinsics::{likely,unlikely};
 pub fn check_likely(x: i32, y: i32) -> Option<i32> {
     unsafe {
         // CHECK: call i1 @llvm.expect.i1(i1 %{{.*}}, i1 true)
+        // CHECK: br i1 %{{.*}}, label %{{.*}}, label %{{.*}}, !prof ![[LIKELY:[0-9]+]]
         if likely(x == y) {
             None
         } else {
@@ -31,6 +32,7 @@ pub fn check_likely(x: i32, y: i32) -> Option<i32> {
 pub fn check_unlikely(x: i32, y: i32) -> Option<i32> {
     unsafe {
         // CHECK: call i1 @llvm.expect.i1(i1 %{{.*}}, i1 false)
+        // CHECK: br i1 %{{.*}}, label %{{.*}}, label %{{.*}}, !prof ![[UNLIKELY:[0-9]+]]
         if unlikely(x == y) {
             None
         } else {
@@ -39,3 +41,24 @@ pub fn check_unlikely(x: i32, y: i32) -> Option<i32> {
     }
 }
 
+// The result of `likely` may have changed by the time it is branched on.
+// CHECK-LABEL: @check_reassigned
+#[no_mangle]
+pub fn check_reassigned(x: i32, y: i32, flip: bool) -> Option<i32> {
+    unsafe {
+        let mut cond = likely(x == y);
+        if flip {
+            cond = !cond;
+        }
+        // CHECK-NOT: !prof
+        // CHECK: ret
+        if cond {
+            None
+        } else {
+            Some(x + y)
+        }
+    }
+}
+
+// CHECK: ![[LIKELY]] = !{!"branch_weights", i32 2000, i32 1}
+// CHECK: ![[UNLIKELY]] = !{!"branch_weights", i32 1, i32 2000}
@@ -0,0 +1,34 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// min-llvm-version 9.0
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(loop_hints)]
+
+// CHECK-LABEL: @pipelined
+#[no_mangle]
+pub fn pipelined(xs: &[u32]) -> u32 {
+    let mut i = 0;
+    let mut sum = 0u32;
+    // CHECK: br label %{{[a-z0-9]+}}, !llvm.loop ![[PIPELINED:[0-9]+]]
+    #[pipeline(initiation_interval = 2)]
+    loop {
+        if i == xs.len() {
+            break sum;
+        }
+        sum = sum.wrapping_add(xs[i]);
+        i += 1;
+    }
+}
+
+// CHECK: ![[PIPELINED]] = distinct !{![[PIPELINED]], ![[II:[0-9]+]]}
+// CHECK: ![[II]] = !{!"llvm.loop.pipeline.initiationinterval", i32 2}
@@ -0,0 +1,42 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(loop_hints)]
+
+// CHECK-LABEL: @unrolled
+#[no_mangle]
+pub fn unrolled(xs: &mut [u32]) {
+    // CHECK: br label %{{[a-z0-9]+}}, !llvm.loop ![[UNROLLED:[0-9]+]]
+    #[unroll(4)]
+    for x in xs {
+        *x += 1;
+    }
+}
+
+// CHECK-LABEL: @not_unrolled
+#[no_mangle]
+pub fn not_unrolled(mut n: u32) -> u32 {
+    let mut sum = 0;
+    // CHECK: br label %{{[a-z0-9]+}}, !llvm.loop ![[NOT_UNROLLED:[0-9]+]]
+    #[no_unroll]
+    while n > 0 {
+        sum += n;
+        n -= 1;
+    }
+    sum
+}
+
+// CHECK: ![[UNROLLED]] = distinct !{![[UNROLLED]], ![[COUNT:[0-9]+]]}
+// CHECK: ![[COUNT]] = !{!"llvm.loop.unroll.count", i32 4}
+// CHECK: ![[NOT_UNROLLED]] = distinct !{![[NOT_UNROLLED]], ![[DISABLE:[0-9]+]]}
+// CHECK: ![[DISABLE]] = !{!"llvm.loop.unroll.disable"}
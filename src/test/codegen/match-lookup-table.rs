@@ -0,0 +1,51 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+#[repr(u8)]
+pub enum State {
+    Start,
+    Ident,
+    Number,
+    Done,
+}
+
+// CHECK: @[[TABLE:[a-z_.0-9]*switch_table[a-z_.0-9]*]] = {{.*}}constant [4 x i32] [i32 10, i32 3, i32 7, i32 42]
+
+// CHECK-LABEL: @cost
+#[no_mangle]
+pub fn cost(state: State) -> u32 {
+    // CHECK: call void @llvm.assume
+    // CHECK: getelementptr inbounds [4 x i32], [4 x i32]* @[[TABLE]]
+    // CHECK-NOT: switch
+    match state {
+        State::Start => 10,
+        State::Ident => 3,
+        State::Number => 7,
+        State::Done => 42,
+    }
+}
+
+// Arms doing more than producing a constant keep the switch.
+// CHECK-LABEL: @next
+#[no_mangle]
+pub fn next(state: State, c: u32) -> u32 {
+    // CHECK: switch
+    match state {
+        State::Start => 10,
+        State::Ident => c,
+        State::Number => 7,
+        State::Done => 42,
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes -C codegen-units=1 -Z merge-shims -Z share-generics=no
+
+#![crate_type = "lib"]
+
+pub struct A(Box<u8>);
+pub struct B(Box<u8>);
+pub struct C(u32, A);
+pub struct D(u32, B);
+
+// The drop glue of `A` and `B` is identical, so one is emitted as an alias of
+// the other. That makes the glue of `C` and `D` identical too.
+
+// CHECK: @_ZN4core3ptr18drop_in_place{{[0-9a-zA-Z_]+}} = internal alias
+// CHECK: @_ZN4core3ptr18drop_in_place{{[0-9a-zA-Z_]+}} = internal alias
+// CHECK-NOT: = internal alias
+
+// CHECK-LABEL: @make
+#[no_mangle]
+pub fn make(a: bool) {
+    if a {
+        C(1, A(Box::new(1)));
+    } else {
+        D(2, B(Box::new(2)));
+    }
+}
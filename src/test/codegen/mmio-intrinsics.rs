@@ -0,0 +1,41 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{mmio_load, mmio_store};
+
+#[repr(transparent)]
+pub struct Reg(u32);
+
+// CHECK-LABEL: @read_reg
+#[no_mangle]
+pub unsafe fn read_reg(p: *const Reg) -> Reg {
+// CHECK: load volatile i32, i32* %{{.*}}, align 4
+    mmio_load(p)
+}
+
+// CHECK-LABEL: @write_reg
+#[no_mangle]
+pub unsafe fn write_reg(p: *mut Reg, v: Reg) {
+// CHECK: store volatile i32 %{{.*}}, i32* %{{.*}}, align 4
+    mmio_store(p, v)
+}
+
+// CHECK-LABEL: @write_f32
+#[no_mangle]
+pub unsafe fn write_f32(p: *mut f32, v: f32) {
+// CHECK-NOT: store volatile float
+// CHECK: store volatile i32 %{{.*}}, i32* %{{.*}}, align 4
+    mmio_store(p, v)
+}
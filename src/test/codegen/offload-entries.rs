@@ -0,0 +1,30 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// compile-flags: -C no-prepopulate-passes
+// only-linux
+
+#![crate_type = "lib"]
+#![feature(offload)]
+
+// CHECK-DAG: @__rust_offload_entry.{{.*}}5SCALE{{.*}} = internal constant { i8*, i8*, i64, i32, i32 } {{.*}}, i64 4, i32 0, i32 0 }, section "omp_offloading_entries"
+#[offload]
+static SCALE: f32 = 2.0;
+
+// CHECK-DAG: @__rust_offload_entry.{{.*}}5scale{{.*}} = internal constant { i8*, i8*, i64, i32, i32 } {{.*}}, i64 0, i32 0, i32 0 }, section "omp_offloading_entries"
+#[offload]
+fn scale(x: &mut [f32]) {
+    for x in x {
+        *x *= SCALE;
+    }
+}
+
+// CHECK: @llvm.used = {{.*}}__rust_offload_entry.{{.*}}__rust_offload_entry.
@@ -0,0 +1,26 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes -C panic=abort -Z panic-abort-minimal
+
+#![crate_type = "lib"]
+#![no_std]
+
+// CHECK-LABEL: @formatted
+#[no_mangle]
+pub fn formatted(x: u32) {
+// CHECK-NOT: panic_fmt
+// CHECK: store volatile i8* %{{.*}}, i8** %panic_payload
+// CHECK-NEXT: call void @llvm.trap()
+// CHECK-NEXT: unreachable
+    if x > 10 {
+        panic!("x is {}", x);
+    }
+}
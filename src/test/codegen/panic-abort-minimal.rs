@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes -C panic=abort -Z panic-abort-minimal -C overflow-checks
+
+#![crate_type = "lib"]
+
+// CHECK-LABEL: @index
+#[no_mangle]
+pub fn index(x: &[u8], i: usize) -> u8 {
+// CHECK-NOT: panic_bounds_check
+// CHECK: store volatile i8* %{{.*}}, i8** %panic_payload
+// CHECK-NEXT: call void @llvm.trap()
+// CHECK-NEXT: unreachable
+    x[i]
+}
+
+// CHECK-LABEL: @add
+#[no_mangle]
+pub fn add(a: u32, b: u32) -> u32 {
+// CHECK-NOT: core{{.*}}panicking
+// CHECK: call void @llvm.trap()
+    a + b
+}
@@ -0,0 +1,35 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// compile-flags: -C no-prepopulate-passes -C overflow-checks=on
+
+// The locations passed to the panic lang items are named after their contents
+// and put into COMDATs so the linker keeps one copy of each, and their strings
+// are NUL-terminated so they can go into mergeable sections.
+
+#![crate_type = "lib"]
+
+// CHECK-DAG: @{{.*}} = internal unnamed_addr constant [29 x i8] c"attempt to add with overflow\00"
+// CHECK-DAG: @__rust_panic_loc.{{[0-9a-f]+}} = linkonce_odr hidden unnamed_addr constant {{.*}}, comdat, align
+
+// CHECK-LABEL: @add
+#[no_mangle]
+pub fn add(a: u32, b: u32) -> u32 {
+// CHECK: call void @{{.*}}panic{{.*}}@__rust_panic_loc.
+    a + b
+}
+
+// CHECK-LABEL: @index
+#[no_mangle]
+pub fn index(x: &[u8], i: usize) -> u8 {
+// CHECK: call void @{{.*}}panic_bounds_check{{.*}}@__rust_panic_loc.
+    x[i]
+}
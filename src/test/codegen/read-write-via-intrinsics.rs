@@ -0,0 +1,43 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{read_via_copy, write_via_move};
+
+pub struct Big([u64; 8]);
+
+// CHECK-LABEL: @read_u32
+#[no_mangle]
+pub unsafe fn read_u32(p: *const u32) -> u32 {
+// CHECK-NOT: memcpy
+// CHECK: load i32, i32* %p, align 4
+    read_via_copy(p)
+}
+
+// CHECK-LABEL: @write_u32
+#[no_mangle]
+pub unsafe fn write_u32(p: *mut u32, v: u32) {
+// CHECK-NOT: memcpy
+// CHECK: store i32 %v, i32* %p, align 4
+    write_via_move(p, v)
+}
+
+// Aggregates are still copied, but straight into the destination.
+// CHECK-LABEL: @read_big
+#[no_mangle]
+pub unsafe fn read_big(p: *const Big) -> Big {
+// CHECK: call void @llvm.memcpy
+// CHECK-NOT: call void @llvm.memcpy
+    read_via_copy(p)
+}
@@ -0,0 +1,67 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// only-x86_64
+// compile-flags: -C no-prepopulate-passes -C target-feature=+avx512f
+
+// With AVX-512 enabled, masks of `bool`s stay vectors of `i1`s, which live
+// in `k` registers, instead of being widened to integer lanes. The vectors
+// themselves are still passed through memory, as the target spec's baseline
+// features don't include AVX-512.
+
+#![crate_type = "lib"]
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct f32x16(f32, f32, f32, f32, f32, f32, f32, f32,
+                  f32, f32, f32, f32, f32, f32, f32, f32);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct m32x16(bool, bool, bool, bool, bool, bool, bool, bool,
+                  bool, bool, bool, bool, bool, bool, bool, bool);
+
+extern "platform-intrinsic" {
+    fn simd_add<T>(x: T, y: T) -> T;
+    fn simd_lt<T, U>(x: T, y: T) -> U;
+    fn simd_select<M, T>(m: M, a: T, b: T) -> T;
+    fn simd_bitmask<M, U>(m: M) -> U;
+}
+
+// CHECK-LABEL: @add(
+#[no_mangle]
+pub fn add(a: f32x16, b: f32x16) -> f32x16 {
+    unsafe { simd_add(a, b) }
+}
+
+// CHECK-LABEL: @lt(
+#[no_mangle]
+pub fn lt(a: f32x16, b: f32x16) -> m32x16 {
+// CHECK: fcmp olt <16 x float>
+// CHECK-NOT: sext
+    unsafe { simd_lt(a, b) }
+}
+
+// CHECK-LABEL: @select(
+#[no_mangle]
+pub fn select(m: m32x16, a: f32x16, b: f32x16) -> f32x16 {
+// CHECK: select <16 x i1>
+    unsafe { simd_select(m, a, b) }
+}
+
+// CHECK-LABEL: @bitmask(
+#[no_mangle]
+pub fn bitmask(m: m32x16) -> u16 {
+// CHECK: bitcast <16 x i1> %{{.*}} to i16
+    unsafe { simd_bitmask(m) }
+}
@@ -0,0 +1,60 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// revisions: little big
+// [little] compile-flags: --target powerpc64le-unknown-linux-gnu
+// [big] compile-flags: --target powerpc64-unknown-linux-gnu
+// compile-flags: -C no-prepopulate-passes --crate-type=rlib
+
+// simd_bitmask puts lane 0 in the least significant bit regardless of the
+// byte order of the target, so on big-endian targets the lanes are reversed
+// before the vector of `i1`s is bitcast to an integer.
+
+#![feature(no_core, lang_items, repr_simd, platform_intrinsics)]
+#![no_core]
+#![allow(non_camel_case_types)]
+
+#[lang="sized"]
+trait Sized { }
+#[lang="copy"]
+trait Copy { }
+
+#[repr(simd)]
+pub struct i32x4(i32, i32, i32, i32);
+
+#[repr(simd)]
+pub struct i8x16(i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8, i8);
+
+extern "platform-intrinsic" {
+    fn simd_bitmask<T, U>(x: T) -> U;
+}
+
+// CHECK-LABEL: @bitmask_4
+#[no_mangle]
+pub unsafe fn bitmask_4(m: i32x4) -> u8 {
+// CHECK: [[MSBS:%[0-9]+]] = trunc <4 x i32> {{.*}} to <4 x i1>
+// little-NOT: shufflevector
+// little: bitcast <4 x i1> [[MSBS]] to i4
+// big: [[REV:%[0-9]+]] = shufflevector <4 x i1> [[MSBS]], <4 x i1> undef, <4 x i32> <i32 3, i32 2, i32 1, i32 0>
+// big: bitcast <4 x i1> [[REV]] to i4
+// CHECK: zext i4 {{.*}} to i8
+    simd_bitmask(m)
+}
+
+// CHECK-LABEL: @bitmask_16
+#[no_mangle]
+pub unsafe fn bitmask_16(m: i8x16) -> u16 {
+// CHECK: [[MSBS:%[0-9]+]] = trunc <16 x i8> {{.*}} to <16 x i1>
+// little-NOT: shufflevector
+// big: shufflevector <16 x i1> [[MSBS]], <16 x i1> undef, <16 x i32> <i32 15, i32 14,
+// CHECK: bitcast <16 x i1> {{.*}} to i16
+    simd_bitmask(m)
+}
@@ -0,0 +1,82 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-emscripten
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct f32x4(pub f32, pub f32, pub f32, pub f32);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct f64x2(pub f64, pub f64);
+
+extern "platform-intrinsic" {
+    fn simd_round<T>(x: T) -> T;
+    fn simd_trunc<T>(x: T) -> T;
+    fn simd_rint<T>(x: T) -> T;
+    fn simd_nearbyint<T>(x: T) -> T;
+}
+
+// CHECK-LABEL: @round_32x4
+#[no_mangle]
+pub unsafe fn round_32x4(a: f32x4) -> f32x4 {
+    // CHECK: call fast <4 x float> @llvm.round.v4f32
+    simd_round(a)
+}
+
+// CHECK-LABEL: @round_64x2
+#[no_mangle]
+pub unsafe fn round_64x2(a: f64x2) -> f64x2 {
+    // CHECK: call fast <2 x double> @llvm.round.v2f64
+    simd_round(a)
+}
+
+// CHECK-LABEL: @trunc_32x4
+#[no_mangle]
+pub unsafe fn trunc_32x4(a: f32x4) -> f32x4 {
+    // CHECK: call fast <4 x float> @llvm.trunc.v4f32
+    simd_trunc(a)
+}
+
+// CHECK-LABEL: @trunc_64x2
+#[no_mangle]
+pub unsafe fn trunc_64x2(a: f64x2) -> f64x2 {
+    // CHECK: call fast <2 x double> @llvm.trunc.v2f64
+    simd_trunc(a)
+}
+
+// CHECK-LABEL: @rint_32x4
+#[no_mangle]
+pub unsafe fn rint_32x4(a: f32x4) -> f32x4 {
+    // CHECK: call fast <4 x float> @llvm.rint.v4f32
+    simd_rint(a)
+}
+
+// CHECK-LABEL: @nearbyint_32x4
+#[no_mangle]
+pub unsafe fn nearbyint_32x4(a: f32x4) -> f32x4 {
+    // CHECK: call fast <4 x float> @llvm.nearbyint.v4f32
+    simd_nearbyint(a)
+}
+
+// CHECK-LABEL: @nearbyint_64x2
+#[no_mangle]
+pub unsafe fn nearbyint_64x2(a: f64x2) -> f64x2 {
+    // CHECK: call fast <2 x double> @llvm.nearbyint.v2f64
+    simd_nearbyint(a)
+}
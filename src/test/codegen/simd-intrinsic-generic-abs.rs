@@ -0,0 +1,46 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-emscripten
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct i16x8(i16, i16, i16, i16, i16, i16, i16, i16);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct f32x4(f32, f32, f32, f32);
+
+extern "platform-intrinsic" {
+    fn simd_abs<T>(x: T) -> T;
+}
+
+// CHECK-LABEL: @abs_i16x8
+#[no_mangle]
+pub unsafe fn abs_i16x8(a: i16x8) -> i16x8 {
+    // CHECK: [[NEG:%[0-9]+]] = sub <8 x i16> zeroinitializer, [[X:%[0-9]+]]
+    // CHECK: [[IS_NEG:%[0-9]+]] = icmp slt <8 x i16> [[X]], zeroinitializer
+    // CHECK: select <8 x i1> [[IS_NEG]], <8 x i16> [[NEG]], <8 x i16> [[X]]
+    simd_abs(a)
+}
+
+// CHECK-LABEL: @abs_f32x4
+#[no_mangle]
+pub unsafe fn abs_f32x4(a: f32x4) -> f32x4 {
+    // CHECK: call fast <4 x float> @llvm.fabs.v4f32
+    simd_abs(a)
+}
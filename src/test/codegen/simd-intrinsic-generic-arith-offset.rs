@@ -0,0 +1,36 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-emscripten
+// ignore-tidy-linelength
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Vec4<T>(pub T, pub T, pub T, pub T);
+
+extern "platform-intrinsic" {
+    fn simd_arith_offset<P, O>(pointers: P, offsets: O) -> P;
+}
+
+// CHECK-LABEL: @arith_offset_f32x4
+#[no_mangle]
+pub unsafe fn arith_offset_f32x4(pointers: Vec4<*const f32>,
+                                 offsets: Vec4<isize>) -> Vec4<*const f32> {
+    // CHECK: getelementptr float, <4 x float*> {{.*}}, <4 x i{{[0-9]+}}> {{.*}}
+    // CHECK-NOT: getelementptr inbounds
+    simd_arith_offset(pointers, offsets)
+}
@@ -0,0 +1,38 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-emscripten
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct u8x16(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+
+extern "platform-intrinsic" {
+    fn simd_avg_round<T>(x: T, y: T) -> T;
+}
+
+// CHECK-LABEL: @avg_round_u8x16
+#[no_mangle]
+pub unsafe fn avg_round_u8x16(a: u8x16, b: u8x16) -> u8x16 {
+    // CHECK: zext <16 x i8> {{.*}} to <16 x i16>
+    // CHECK: zext <16 x i8> {{.*}} to <16 x i16>
+    // CHECK: add <16 x i16>
+    // CHECK: add <16 x i16> {{.*}}, <i16 1,
+    // CHECK: lshr <16 x i16> {{.*}}, <i16 1,
+    // CHECK: trunc <16 x i16> {{.*}} to <16 x i8>
+    simd_avg_round(a, b)
+}
@@ -0,0 +1,72 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+#![feature(repr_simd, platform_intrinsics)]
+#[allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct f32x4(pub f32, pub f32, pub f32, pub f32);
+
+extern "platform-intrinsic" {
+    fn simd_insert<T, U>(x: T, idx: u32, val: U) -> T;
+    fn simd_extract<T, U>(x: T, idx: u32) -> U;
+}
+
+// CHECK-LABEL: @extract_const
+#[no_mangle]
+pub unsafe fn extract_const(a: f32x4) -> f32 {
+    // CHECK: extractelement <4 x float> %{{.*}}, i32 1
+    simd_extract(a, 1)
+}
+
+// CHECK-LABEL: @extract_dynamic
+#[no_mangle]
+pub unsafe fn extract_dynamic(a: f32x4, i: u32, j: u32) -> f32 {
+    // CHECK: %simd_index_slot = alloca <4 x float>
+    // CHECK-NOT: alloca <4 x float>
+    // CHECK: [[I_OK:%[0-9]+]] = icmp ult i32 %i, 4
+    // CHECK: [[I:%[0-9]+]] = select i1 [[I_OK]], i32 %i, i32 0
+    // CHECK: getelementptr inbounds float, float* %{{.*}}, i32 [[I]]
+    // CHECK: [[J_OK:%[0-9]+]] = icmp ult i32 %j, 4
+    // CHECK: [[J:%[0-9]+]] = select i1 [[J_OK]], i32 %j, i32 0
+    // CHECK: getelementptr inbounds float, float* %{{.*}}, i32 [[J]]
+    let x: f32 = simd_extract(a, i);
+    let y: f32 = simd_extract(a, j);
+    x + y
+}
+
+// Every function gets its own slot.
+// CHECK-LABEL: @extract_dynamic_again
+#[no_mangle]
+pub unsafe fn extract_dynamic_again(a: f32x4, i: u32) -> f32 {
+    // CHECK: %simd_index_slot = alloca <4 x float>
+    simd_extract(a, i)
+}
+
+// CHECK-LABEL: @insert_const
+#[no_mangle]
+pub unsafe fn insert_const(a: f32x4, x: f32) -> f32x4 {
+    // CHECK: insertelement <4 x float> %{{.*}}, float %x, i32 2
+    simd_insert(a, 2, x)
+}
+
+// CHECK-LABEL: @insert_dynamic
+#[no_mangle]
+pub unsafe fn insert_dynamic(a: f32x4, i: u32, x: f32) -> f32x4 {
+    // CHECK-NOT: alloca <4 x float>
+    // CHECK: [[MASK:%[0-9]+]] = icmp eq <4 x i32> %{{.*}}, <i32 0, i32 1, i32 2, i32 3>
+    // CHECK: select <4 x i1> [[MASK]], <4 x float> %{{.*}}, <4 x float> %{{.*}}
+    simd_insert(a, i, x)
+}
@@ -0,0 +1,52 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-emscripten
+// ignore-tidy-linelength
+// min-llvm-version 6.0
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Vec4<T>(pub T, pub T, pub T, pub T);
+
+extern "platform-intrinsic" {
+    fn simd_masked_load<M, P, T>(mask: M, pointer: P, values: T) -> T;
+    fn simd_masked_store<M, P, T>(mask: M, pointer: P, values: T);
+}
+
+// CHECK-LABEL: @load_f32x4
+#[no_mangle]
+pub unsafe fn load_f32x4(mask: Vec4<i32>, pointer: *const f32,
+                         values: Vec4<f32>) -> Vec4<f32> {
+    // CHECK: call <4 x float> @llvm.masked.load.v4f32.p0v4f32(<4 x float>* {{.*}}, i32 4, <4 x i1> {{.*}}, <4 x float> {{.*}})
+    simd_masked_load(mask, pointer, values)
+}
+
+// CHECK-LABEL: @load_u8x4
+#[no_mangle]
+pub unsafe fn load_u8x4(mask: Vec4<i8>, pointer: *const u8,
+                        values: Vec4<u8>) -> Vec4<u8> {
+    // CHECK: call <4 x i8> @llvm.masked.load.v4i8.p0v4i8(<4 x i8>* {{.*}}, i32 1, <4 x i1> {{.*}}, <4 x i8> {{.*}})
+    simd_masked_load(mask, pointer, values)
+}
+
+// CHECK-LABEL: @store_f64x4
+#[no_mangle]
+pub unsafe fn store_f64x4(mask: Vec4<i64>, pointer: *mut f64, values: Vec4<f64>) {
+    // CHECK: call void @llvm.masked.store.v4f64.p0v4f64(<4 x double> {{.*}}, <4 x double>* {{.*}}, i32 8, <4 x i1> {{.*}})
+    simd_masked_store(mask, pointer, values)
+}
@@ -0,0 +1,41 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-emscripten
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct i16x8(i16, i16, i16, i16, i16, i16, i16, i16);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct i8x8(i8, i8, i8, i8, i8, i8, i8, i8);
+
+extern "platform-intrinsic" {
+    fn simd_pack_sat<T, U>(x: T) -> U;
+}
+
+// CHECK-LABEL: @pack_sat_i16x8
+#[no_mangle]
+pub unsafe fn pack_sat_i16x8(a: i16x8) -> i8x8 {
+    // CHECK: icmp sgt <8 x i16> {{.*}}, <i16 127,
+    // CHECK: select
+    // CHECK: icmp slt <8 x i16> {{.*}}, <i16 -128,
+    // CHECK: select
+    // CHECK: trunc <8 x i16> {{.*}} to <8 x i8>
+    simd_pack_sat(a)
+}
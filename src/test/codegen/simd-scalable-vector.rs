@@ -0,0 +1,63 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// min-llvm-version 9.0
+// compile-flags: --target aarch64-unknown-linux-gnu -C target-feature=+sve
+// compile-flags: -C no-prepopulate-passes --crate-type=rlib
+
+// `#[rustc_scalable_vector]` types lower to `<vscale x N x T>` and are
+// passed directly, even where fixed-length vectors would go through memory.
+
+#![feature(no_core, lang_items, repr_simd, platform_intrinsics, rustc_attrs)]
+#![no_core]
+#![allow(non_camel_case_types)]
+
+#[lang="sized"]
+trait Sized { }
+#[lang="copy"]
+trait Copy { }
+
+#[repr(simd)]
+#[rustc_scalable_vector]
+pub struct svfloat32_t(f32, f32, f32, f32);
+
+#[repr(simd)]
+#[rustc_scalable_vector]
+pub struct svint32_t(i32, i32, i32, i32);
+
+impl Copy for svfloat32_t {}
+impl Copy for svint32_t {}
+
+extern "platform-intrinsic" {
+    fn simd_add<T>(x: T, y: T) -> T;
+    fn simd_lt<T, U>(x: T, y: T) -> U;
+    fn simd_select<M, T>(m: M, a: T, b: T) -> T;
+}
+
+// CHECK-LABEL: @add
+#[no_mangle]
+pub unsafe fn add(a: svfloat32_t, b: svfloat32_t) -> svfloat32_t {
+// CHECK-SAME: <vscale x 4 x float> %a, <vscale x 4 x float> %b
+// CHECK: fadd <vscale x 4 x float>
+    simd_add(a, b)
+}
+
+// CHECK-LABEL: @min
+#[no_mangle]
+pub extern "C" fn min(a: svfloat32_t, b: svfloat32_t) -> svfloat32_t {
+// CHECK-SAME: <vscale x 4 x float> %a, <vscale x 4 x float> %b
+// CHECK: fcmp olt <vscale x 4 x float>
+// CHECK: trunc <vscale x 4 x i32> {{.*}} to <vscale x 4 x i1>
+// CHECK: select <vscale x 4 x i1>
+    unsafe {
+        let m: svint32_t = simd_lt(a, b);
+        simd_select(m, a, b)
+    }
+}
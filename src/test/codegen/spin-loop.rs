@@ -0,0 +1,36 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O
+// min-llvm-version 8.0
+// only-x86_64
+
+#![crate_type = "lib"]
+#![feature(renamed_spin_loop)]
+
+use std::sync::atomic::spin_loop_hint;
+
+// CHECK-LABEL: @spin_forever
+#[no_mangle]
+pub fn spin_forever() -> ! {
+    // CHECK: call void @llvm.sideeffect()
+    // CHECK: call void asm sideeffect "pause"
+    loop {
+        std::hint::spin_loop();
+    }
+}
+
+// CHECK-LABEL: @spin_hint
+#[no_mangle]
+pub fn spin_hint() {
+    // CHECK: call void @llvm.sideeffect()
+    // CHECK: call void asm sideeffect "pause"
+    spin_loop_hint();
+}
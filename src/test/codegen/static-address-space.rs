@@ -0,0 +1,49 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+#![feature(address_spaces)]
+
+// CHECK: @FOO = {{.*}}addrspace(1) global
+#[no_mangle]
+#[address_space = "1"]
+pub static FOO: u32 = 42;
+
+// CHECK: @BAR = {{.*}}addrspacecast ({{.*}}addrspace(1)* @FOO
+#[no_mangle]
+pub static BAR: &u32 = &FOO;
+
+#[address_space = "2"]
+pub struct InSharedMemory(u32);
+
+// CHECK: @SHARED = {{.*}}addrspace(2) global
+#[no_mangle]
+pub static SHARED: InSharedMemory = InSharedMemory(0);
+
+// CHECK: @OVERRIDDEN = {{.*}}addrspace(1) global
+#[no_mangle]
+#[address_space = "1"]
+pub static OVERRIDDEN: InSharedMemory = InSharedMemory(0);
+
+// CHECK-LABEL: @read_foo
+#[no_mangle]
+pub fn read_foo() -> u32 {
+// CHECK: load i32, i32* addrspacecast (i32 addrspace(1)* @FOO to i32*)
+    FOO
+}
+
+// CHECK-LABEL: @shared_field
+#[no_mangle]
+pub fn shared_field() -> &'static u32 {
+// CHECK: addrspacecast ({{.*}} addrspace(2)* @SHARED to
+    &SHARED.0
+}
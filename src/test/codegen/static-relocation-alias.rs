@@ -0,0 +1,28 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes -C relocation-model=pic -C codegen-units=1
+// only-linux
+
+// Pointers to exported statics of the same codegen unit in the initializers
+// of other statics go through a private alias, so that the dynamic loader
+// doesn't need to look the symbol up.
+
+#![crate_type = "lib"]
+
+// CHECK: @EXPORTED = constant
+#[no_mangle]
+pub static EXPORTED: u32 = 1;
+
+// CHECK: @POINTER = constant <{ i8* }> <{ i8* bitcast ({{.*}}@static_alias.{{[0-9a-z]+}} to i8*) }>
+#[no_mangle]
+pub static POINTER: &u32 = &EXPORTED;
+
+// CHECK: @static_alias.{{[0-9a-z]+}} = private alias {{.*}}@EXPORTED
@@ -0,0 +1,33 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-macos
+// ignore-windows
+// ignore-wasm32
+// compile-flags: -Z symbol-ordering-file={{src-base}}/auxiliary/symbol-order.txt
+
+#![crate_type = "lib"]
+
+// The listed functions are defined first, in the listed order, and in
+// `.text.hot.*` sections.
+
+// CHECK: define void @hot_second(){{.*}} section ".text.hot.hot_second"
+// CHECK: define void @hot_first(){{.*}} section ".text.hot.hot_first"
+// CHECK: define void @cold()
+// CHECK-NOT: section
+
+#[no_mangle]
+pub fn cold() {}
+
+#[no_mangle]
+pub fn hot_first() {}
+
+#[no_mangle]
+pub fn hot_second() {}
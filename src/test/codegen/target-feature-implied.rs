@@ -0,0 +1,24 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// only-x86_64
+
+#![crate_type = "lib"]
+
+// CHECK-LABEL: @fma_and_aes
+// CHECK-SAME: #[[ATTRS:[0-9]+]]
+// Enabling a feature enables everything it builds upon.
+#[no_mangle]
+#[target_feature(enable = "fma,aes")]
+pub unsafe fn fma_and_aes() {
+}
+
+// CHECK: attributes #[[ATTRS]] = { {{.*}}"target-features"="+fma,+aes,+avx,+sse2,+sse4.2,+sse,+sse4.1,+ssse3,+sse3"{{.*}} }
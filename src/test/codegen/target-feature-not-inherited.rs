@@ -0,0 +1,43 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// only-x86_64
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+// CHECK: define {{.*}}closure{{.*}} #[[CLOSURE_ATTRS:[0-9]+]]
+// CHECK: define {{.*}}nested{{.*}} #[[NESTED_ATTRS:[0-9]+]]
+// CHECK: define {{.*}}method{{.*}} #[[METHOD_ATTRS:[0-9]+]]
+
+// Closures and items nested in a `#[target_feature]` function don't get its
+// features: they can escape it, e.g. as a returned closure or fn pointer, and
+// be called where the features aren't available.
+#[target_feature(enable = "sse4.2")]
+pub unsafe fn outer(xs: &[u32]) -> u32 {
+    #[inline(never)]
+    fn nested(x: u32) -> u32 {
+        x + 1
+    }
+
+    pub struct S;
+    impl S {
+        #[inline(never)]
+        pub fn method(&self) {}
+    }
+    S.method();
+
+    xs.iter().map(|&x| nested(x)).sum()
+}
+
+// CHECK-NOT: attributes #[[CLOSURE_ATTRS]] = { {{.*}}"target-features"{{.*}} }
+// CHECK-NOT: attributes #[[NESTED_ATTRS]] = { {{.*}}"target-features"{{.*}} }
+// CHECK-NOT: attributes #[[METHOD_ATTRS]] = { {{.*}}"target-features"{{.*}} }
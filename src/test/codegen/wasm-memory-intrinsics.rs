@@ -0,0 +1,40 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -C no-prepopulate-passes --target=wasm32-unknown-unknown --crate-type=rlib
+
+#![feature(no_core, lang_items, intrinsics)]
+#![no_core]
+
+#[lang="sized"]
+trait Sized { }
+#[lang="freeze"]
+trait Freeze { }
+#[lang="copy"]
+trait Copy { }
+
+extern "rust-intrinsic" {
+    fn wasm_memory_size(mem: u32) -> usize;
+    fn wasm_memory_grow(mem: u32, delta: usize) -> usize;
+}
+
+// CHECK-LABEL: @size
+#[no_mangle]
+pub fn size() -> usize {
+// CHECK: call i32 @llvm.wasm.memory.size.i32(i32 0)
+    wasm_memory_size(0)
+}
+
+// CHECK-LABEL: @grow
+#[no_mangle]
+pub fn grow(delta: usize) -> usize {
+// CHECK: call i32 @llvm.wasm.memory.grow.i32(i32 0, i32 %delta)
+    wasm_memory_grow(0, delta)
+}
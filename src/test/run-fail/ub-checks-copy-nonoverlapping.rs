@@ -0,0 +1,23 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// error-pattern:unsafe precondition violated: `copy_nonoverlapping` with overlapping ranges
+// compile-flags: -Z ub-checks
+
+use std::ptr;
+
+fn main() {
+    let mut xs = [1u32, 2, 3, 4];
+    unsafe {
+        let p = xs.as_mut_ptr();
+        ptr::copy_nonoverlapping(p, p.offset(2), 2);
+        ptr::copy_nonoverlapping(p, p.offset(1), 2);
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// error-pattern:unsafe precondition violated: `unchecked_shl` by 32 or more bits
+// compile-flags: -Z ub-checks
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics::unchecked_shl;
+
+#[inline(never)]
+fn shift(x: u32, by: u32) -> u32 {
+    unsafe { unchecked_shl(x, by) }
+}
+
+fn main() {
+    assert_eq!(shift(1, 31), 1 << 31);
+    shift(1, 32);
+}
@@ -0,0 +1,25 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(intrinsics, lang_items, no_core)]
+#![no_core]
+#![crate_type = "lib"]
+
+#[lang="sized"]
+trait Sized { }
+
+extern "rust-intrinsic" {
+    fn abort() -> !;
+}
+
+#[no_mangle]
+pub fn stop() -> ! {
+    unsafe { abort() }
+}
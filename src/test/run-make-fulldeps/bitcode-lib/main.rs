@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+extern "C" {
+    fn foo_mul_add(a: u32, b: u32, c: u32) -> u32;
+    static FOO_BIAS: u32;
+}
+
+mod a {
+    pub fn run(x: u32) -> u32 {
+        unsafe { super::foo_mul_add(x, 3, super::FOO_BIAS) }
+    }
+}
+
+mod b {
+    pub fn run(x: u32) -> u32 {
+        unsafe { super::foo_mul_add(x, x, 1) }
+    }
+}
+
+fn main() {
+    assert_eq!(a::run(5), 22);
+    assert_eq!(b::run(4), 17);
+}
@@ -0,0 +1,32 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "rlib"]
+
+pub struct Big {
+    pub a: [u64; 8],
+}
+
+#[no_mangle]
+pub extern "C" fn nothing() {}
+
+#[no_mangle]
+pub extern "C" fn add_u8(a: u8, b: u8) -> u8 {
+    a.wrapping_add(b)
+}
+
+#[no_mangle]
+pub extern "C" fn make_big() -> Big {
+    Big { a: [0; 8] }
+}
+
+pub fn sum(xs: &[u32]) -> u32 {
+    xs.iter().sum()
+}
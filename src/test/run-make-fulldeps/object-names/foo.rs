@@ -0,0 +1,39 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+pub mod a {
+    #[no_mangle]
+    pub extern "C" fn foo_a(x: u32) -> u32 {
+        x + 1
+    }
+}
+
+pub mod b {
+    #[no_mangle]
+    pub extern "C" fn foo_b(x: u32) -> u32 {
+        ::a::foo_a(x) * 2
+    }
+}
+
+pub mod c {
+    #[no_mangle]
+    pub extern "C" fn foo_c(x: u32) -> u32 {
+        ::b::foo_b(x) * 3
+    }
+}
+
+pub mod d {
+    #[no_mangle]
+    pub extern "C" fn foo_d(x: u32) -> u32 {
+        ::c::foo_c(x) * 4
+    }
+}
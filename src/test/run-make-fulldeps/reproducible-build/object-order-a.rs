@@ -0,0 +1,22 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The same items as object-order-b.rs, in another order.
+
+#![crate_type="rlib"]
+
+#[no_mangle]
+pub fn first() -> u32 { 1 }
+
+#[no_mangle]
+pub fn second() -> u32 { 2 }
+
+#[no_mangle]
+pub fn third() -> u32 { 3 }
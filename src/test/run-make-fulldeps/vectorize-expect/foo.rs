@@ -0,0 +1,36 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+#![feature(vectorize)]
+
+pub struct Node {
+    value: u32,
+    next: Option<Box<Node>>,
+}
+
+// Chasing a linked list has no trip count the vectorizer could work with.
+#[vectorize(expect)]
+pub fn sum_list(mut node: &Node) -> u32 {
+    let mut sum = 0;
+    loop {
+        sum += node.value;
+        match node.next {
+            Some(ref next) => node = next,
+            None => return sum,
+        }
+    }
+}
+
+// Vectorized, so never reported, whatever the vectorizer said on the way.
+#[vectorize(expect)]
+pub fn sum_slice(xs: &[u32]) -> u32 {
+    xs.iter().fold(0, |sum, &x| sum.wrapping_add(x))
+}
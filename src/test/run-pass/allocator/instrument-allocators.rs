@@ -0,0 +1,46 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z instrument-allocators
+
+#![feature(alloc_event_hook)]
+
+use std::alloc::{self, AllocEvent, Layout};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static FREED: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn hook(event: AllocEvent, _ptr: *mut u8, size: usize, _align: usize, _callsite: u64) {
+    match event {
+        AllocEvent::Alloc => ALLOCATED.fetch_add(size, Ordering::SeqCst),
+        AllocEvent::Dealloc => FREED.fetch_add(size, Ordering::SeqCst),
+    };
+}
+
+fn main() {
+    unsafe {
+        let layout = Layout::from_size_align(16, 8).unwrap();
+
+        // Nothing is reported until a hook is registered.
+        let p = alloc::alloc(layout);
+        alloc::dealloc(p, layout);
+        assert_eq!(ALLOCATED.load(Ordering::SeqCst), 0);
+
+        alloc::set_alloc_event_hook(hook);
+        let p = alloc::alloc(layout);
+        let p = alloc::realloc(p, layout, 32);
+        alloc::dealloc(p, Layout::from_size_align(32, 8).unwrap());
+        assert!(alloc::take_alloc_event_hook().is_some());
+
+        assert_eq!(ALLOCATED.load(Ordering::SeqCst), 16 + 32);
+        assert_eq!(FREED.load(Ordering::SeqCst), 16 + 32);
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-x86_64
+// compile-flags: -C target-feature=+cmpxchg16b
+
+#![feature(cfg_target_has_atomic, core_intrinsics)]
+
+use std::intrinsics::{atomic_cxchg, atomic_load, atomic_store, atomic_xadd};
+
+#[repr(align(16))]
+struct Aligned(u128);
+
+#[cfg(target_has_atomic = "128")]
+fn main() {
+    let mut x = Aligned(0);
+    let p = &mut x.0 as *mut u128;
+    unsafe {
+        atomic_store(p, 1 << 100);
+        assert_eq!(atomic_xadd(p, 1), 1 << 100);
+        assert_eq!(atomic_cxchg(p, 1, 2), ((1 << 100) + 1, false));
+        assert_eq!(atomic_cxchg(p, (1 << 100) + 1, u128::max_value()), ((1 << 100) + 1, true));
+        assert_eq!(atomic_load(p), u128::max_value());
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(bitfields)]
+
+use std::mem::size_of;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Header {
+    #[bits(4)]
+    version: u8,
+    #[bits(4)]
+    ihl: u8,
+    #[bits(6)]
+    dscp: u8,
+    #[bits(2)]
+    ecn: u8,
+    length: u16,
+}
+
+#[repr(C)]
+struct Mixed {
+    #[bits(1)]
+    flag: bool,
+    #[bits(3)]
+    delta: i32,
+    #[bits(20)]
+    big: u32,
+    tail: u8,
+}
+
+fn main() {
+    assert_eq!(size_of::<Header>(), 4);
+    // MSVC starts a new unit whenever the type of the bit-fields changes.
+    assert_eq!(size_of::<Mixed>(), if cfg!(target_env = "msvc") { 12 } else { 4 });
+
+    // Bit-fields can't be borrowed, so read them by value as with packed fields.
+    let mut h = Header { version: 4, ihl: 5, dscp: 0, ecn: 0, length: 20 };
+    h.dscp = 46;
+    h.ecn = 3;
+    assert_eq!({ h.version }, 4);
+    assert_eq!({ h.ihl }, 5);
+    assert_eq!({ h.dscp }, 46);
+    assert_eq!({ h.ecn }, 3);
+    assert_eq!(h.length, 20);
+
+    // Values are truncated to the width of the field.
+    h.version = 0x1f;
+    assert_eq!({ h.version }, 0xf);
+    assert_eq!({ h.ihl }, 5);
+
+    let mut m = Mixed { flag: true, delta: -3, big: 0xfffff, tail: 7 };
+    assert!(m.flag);
+    assert_eq!({ m.delta }, -3);
+    assert_eq!({ m.big }, 0xfffff);
+    assert_eq!(m.tail, 7);
+    m.delta = 3;
+    m.flag = false;
+    assert!(!m.flag);
+    assert_eq!({ m.delta }, 3);
+    assert_eq!({ m.big }, 0xfffff);
+}
@@ -0,0 +1,40 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that type-only intrinsics give the same results at compile time and
+// at runtime, including from generic `const fn`s.
+
+#![feature(const_needs_drop, core_intrinsics)]
+
+use std::intrinsics;
+use std::mem;
+
+struct NonTrivial(u8, String);
+
+const fn drop_cost<T>() -> usize {
+    mem::size_of::<T>() * mem::needs_drop::<T>() as usize
+}
+
+const COST_U64: usize = drop_cost::<u64>();
+const COST_NON_TRIVIAL: usize = drop_cost::<NonTrivial>();
+const ALIGN_U64: usize = mem::align_of::<u64>();
+
+#[inline(never)]
+fn runtime_drop_cost<T>() -> usize {
+    mem::size_of::<T>() * mem::needs_drop::<T>() as usize
+}
+
+fn main() {
+    assert_eq!(COST_U64, 0);
+    assert_eq!(COST_U64, runtime_drop_cost::<u64>());
+    assert_eq!(COST_NON_TRIVIAL, mem::size_of::<NonTrivial>());
+    assert_eq!(COST_NON_TRIVIAL, runtime_drop_cost::<NonTrivial>());
+    assert_eq!(ALIGN_U64, intrinsics::min_align_of::<u64>());
+}
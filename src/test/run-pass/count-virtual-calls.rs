@@ -0,0 +1,35 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z count-virtual-calls
+// ignore-emscripten no threads
+
+use std::thread;
+
+trait Counter {
+    fn bump(&self, n: u32) -> u32;
+}
+
+struct One;
+
+impl Counter for One {
+    fn bump(&self, n: u32) -> u32 { n + 1 }
+}
+
+fn bump_many(counter: &(dyn Counter + Sync), times: u32) -> u32 {
+    (0..times).fold(0, |n, _| counter.bump(n))
+}
+
+fn main() {
+    let threads: Vec<_> = (0..4).map(|_| thread::spawn(|| bump_many(&One, 1000))).collect();
+    for t in threads {
+        assert_eq!(t.join().unwrap(), 1000);
+    }
+}
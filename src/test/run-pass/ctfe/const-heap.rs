@@ -0,0 +1,32 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(core_intrinsics, const_let, const_raw_ptr_deref)]
+
+use std::intrinsics;
+
+const FOO: i32 = unsafe {
+    let ptr = intrinsics::const_allocate(4, 4) as *mut i32;
+    *ptr = 42;
+    let val = *ptr + 1;
+    intrinsics::const_deallocate(ptr as *mut u8, 4, 4);
+    val
+};
+
+fn main() {
+    assert_eq!(FOO, 43);
+
+    // The const heap does not exist at runtime.
+    unsafe {
+        let ptr = intrinsics::const_allocate(4, 4);
+        assert!(ptr.is_null());
+        intrinsics::const_deallocate(ptr, 4, 4);
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+#![feature(extern_types, extern_size)]
+
+// Test that size_of_val and align_of_val use the ExternSize impl of an extern type.
+
+use std::mem::{self, size_of_val, align_of_val, ExternSize};
+
+extern {
+    type Sized16;
+    type Unknown;
+}
+
+unsafe impl ExternSize for Sized16 {
+    fn size_of_val(&self) -> usize { 16 }
+    fn align_of_val(&self) -> usize { 8 }
+}
+
+#[repr(C)]
+struct Header {
+    len: usize,
+}
+
+extern {
+    type Buffer;
+}
+
+unsafe impl ExternSize for Buffer {
+    fn size_of_val(&self) -> usize {
+        let header = unsafe { &*(self as *const Buffer as *const Header) };
+        mem::size_of::<Header>() + header.len
+    }
+    fn align_of_val(&self) -> usize { mem::align_of::<Header>() }
+}
+
+fn generic_size<T: ?Sized>(x: &T) -> usize {
+    size_of_val(x)
+}
+
+fn main() {
+    let x: &Sized16 = unsafe { &*(8usize as *const Sized16) };
+    assert_eq!(size_of_val(x), 16);
+    assert_eq!(align_of_val(x), 8);
+    assert_eq!(generic_size(x), 16);
+
+    let y: &Unknown = unsafe { &*(8usize as *const Unknown) };
+    assert_eq!(size_of_val(y), 0);
+    assert_eq!(align_of_val(y), 1);
+
+    let header = Header { len: 5 };
+    let buf: &Buffer = unsafe { &*(&header as *const Header as *const Buffer) };
+    assert_eq!(generic_size(buf), mem::size_of::<Header>() + 5);
+}
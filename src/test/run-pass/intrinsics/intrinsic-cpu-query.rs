@@ -0,0 +1,39 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-x86_64
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics::cpuid;
+
+fn main() {
+    unsafe {
+        // Leaf 0 gives the highest basic leaf and the vendor string.
+        let (max_leaf, ebx, ecx, edx) = cpuid(0, 0);
+        assert!(max_leaf >= 1);
+        let mut vendor = Vec::new();
+        for reg in &[ebx, edx, ecx] {
+            vendor.extend_from_slice(&reg.to_le_bytes());
+        }
+        assert!(vendor.iter().all(|b| b.is_ascii_graphic()));
+
+        // Every x86_64 CPU has SSE2, bit 26 of `edx` in leaf 1.
+        let (_, _, _, edx) = cpuid(1, 0);
+        assert!(edx & (1 << 26) != 0);
+        assert_eq!(cpuid(1, 0), cpuid(1, 0));
+
+        #[cfg(target_os = "linux")]
+        {
+            // On x86 Linux reports leaf 1's `edx` as `AT_HWCAP`.
+            assert_eq!(std::intrinsics::cpu_hwcap() as u32, edx);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// only-x86_64
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{fenv_clear_exceptions, fenv_exceptions};
+use std::intrinsics::{fenv_rounding_mode, fenv_set_rounding_mode};
+use std::ptr;
+
+const DIVISION_BY_ZERO: u32 = 2;
+const NEAREST: u32 = 0;
+const DOWNWARD: u32 = 1;
+const UPWARD: u32 = 2;
+
+// The volatile reads keep the division from being constant-folded.
+#[inline(never)]
+fn divide(a: f64, b: f64) -> f64 {
+    unsafe { ptr::read_volatile(&a) / ptr::read_volatile(&b) }
+}
+
+pub fn main() {
+    unsafe {
+        assert_eq!(fenv_rounding_mode(), NEAREST);
+
+        fenv_clear_exceptions(!0);
+        assert_eq!(fenv_exceptions(), 0);
+        assert!(divide(1.0, 0.0).is_infinite());
+        assert_eq!(fenv_exceptions() & DIVISION_BY_ZERO, DIVISION_BY_ZERO);
+        fenv_clear_exceptions(DIVISION_BY_ZERO);
+        assert_eq!(fenv_exceptions() & DIVISION_BY_ZERO, 0);
+
+        fenv_set_rounding_mode(UPWARD);
+        assert_eq!(fenv_rounding_mode(), UPWARD);
+        let up = divide(1.0, 3.0);
+        fenv_set_rounding_mode(DOWNWARD);
+        assert_eq!(fenv_rounding_mode(), DOWNWARD);
+        let down = divide(1.0, 3.0);
+        fenv_set_rounding_mode(NEAREST);
+        assert!(up > down);
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics;
+use std::{f32, f64};
+
+// Keep the calls out of line so that the intrinsics aren't constant folded.
+#[inline(never)]
+fn min32(a: f32, b: f32) -> f32 { unsafe { intrinsics::minimumf32(a, b) } }
+#[inline(never)]
+fn max32(a: f32, b: f32) -> f32 { unsafe { intrinsics::maximumf32(a, b) } }
+#[inline(never)]
+fn min64(a: f64, b: f64) -> f64 { unsafe { intrinsics::minimumf64(a, b) } }
+#[inline(never)]
+fn max64(a: f64, b: f64) -> f64 { unsafe { intrinsics::maximumf64(a, b) } }
+
+fn main() {
+    assert_eq!(min32(1.0, 2.0), 1.0);
+    assert_eq!(max32(1.0, 2.0), 2.0);
+    assert_eq!(min64(-3.5, 2.0), -3.5);
+    assert_eq!(max64(-3.5, 2.0), 2.0);
+
+    // NaN is propagated, unlike with `min`/`max`.
+    assert!(min32(f32::NAN, 1.0).is_nan());
+    assert!(max32(1.0, f32::NAN).is_nan());
+    assert!(min64(1.0, f64::NAN).is_nan());
+    assert!(max64(f64::NAN, 1.0).is_nan());
+
+    // -0.0 orders before +0.0.
+    assert!(min32(0.0, -0.0).is_sign_negative());
+    assert!(max32(-0.0, 0.0).is_sign_positive());
+    assert!(min64(-0.0, 0.0).is_sign_negative());
+    assert!(max64(0.0, -0.0).is_sign_positive());
+}
@@ -0,0 +1,55 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{has_niche, niche_offset, niche_range_end, niche_range_start};
+use std::num::NonZeroU32;
+
+#[repr(C)]
+struct Pair(u32, &'static u8);
+
+enum Letter { A, B, C }
+
+const REF_HAS_NICHE: bool = has_niche::<&u8>();
+const NONZERO_START: u128 = niche_range_start::<NonZeroU32>();
+
+fn range<T>() -> (u128, u128) {
+    (niche_range_start::<T>(), niche_range_end::<T>())
+}
+
+pub fn main() {
+    assert!(REF_HAS_NICHE);
+    assert_eq!(NONZERO_START, 1);
+
+    assert!(has_niche::<bool>());
+    assert_eq!(range::<bool>(), (0, 1));
+    assert_eq!(niche_offset::<bool>(), 0);
+
+    assert!(has_niche::<char>());
+    assert_eq!(range::<char>(), (0, 0x10FFFF));
+
+    assert!(has_niche::<NonZeroU32>());
+    assert_eq!(range::<NonZeroU32>(), (1, 0xFFFF_FFFF));
+
+    assert!(has_niche::<Letter>());
+    assert_eq!(range::<Letter>(), (0, 2));
+
+    // The niche is in the reference, after the `u32`.
+    assert!(has_niche::<Pair>());
+    assert_eq!(niche_offset::<Pair>(), std::mem::size_of::<usize>().max(4));
+    assert_eq!(niche_range_start::<Pair>(), 1);
+
+    assert!(!has_niche::<u32>());
+    assert!(!has_niche::<()>());
+    assert_eq!(niche_offset::<u32>(), 0);
+    assert_eq!(range::<u32>(), (0, 0));
+}
@@ -0,0 +1,43 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+#![feature(core_intrinsics)]
+
+use std::intrinsics::offset_with_overflow;
+
+pub fn main() {
+    let xs = [1u32, 2, 3, 4];
+    let p = xs.as_ptr();
+    unsafe {
+        let (q, overflow) = offset_with_overflow(p, 2);
+        assert!(!overflow);
+        assert_eq!(*q, 3);
+
+        let (q, overflow) = offset_with_overflow(q, -1);
+        assert!(!overflow);
+        assert_eq!(*q, 2);
+
+        // The offset in bytes doesn't fit in an `isize`.
+        let (_, overflow) = offset_with_overflow(p, isize::max_value());
+        assert!(overflow);
+
+        // The address wraps around.
+        let low = 8 as *const u32;
+        let (q, overflow) = offset_with_overflow(low, -4);
+        assert!(overflow);
+        assert_eq!(q as usize, 8usize.wrapping_sub(16));
+
+        let high = usize::max_value() as *const u8;
+        let (q, overflow) = offset_with_overflow(high, 1);
+        assert!(overflow);
+        assert_eq!(q as usize, 0);
+    }
+}
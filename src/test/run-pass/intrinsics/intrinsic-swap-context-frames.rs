@@ -0,0 +1,63 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// only-x86_64
+// compile-flags: -C force-frame-pointers=yes
+
+// Test that both contexts find their frames and locals intact after
+// switching, including from functions nested a few frames deep.
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics::swap_context;
+use std::ptr;
+
+static mut MAIN: [*mut u8; 5] = [ptr::null_mut(); 5];
+static mut COROUTINE: [*mut u8; 5] = [ptr::null_mut(); 5];
+
+#[inline(never)]
+fn nested(depth: u32, value: u64) -> u64 {
+    let locals = [value, value * 2, value * 3];
+    if depth == 0 {
+        unsafe { swap_context(&mut COROUTINE, &MAIN) };
+    } else {
+        assert_eq!(nested(depth - 1, value + 1), value + depth as u64);
+    }
+    assert_eq!(locals, [value, value * 2, value * 3]);
+    value + depth as u64
+}
+
+extern "C" fn coroutine() -> ! {
+    let mut round = 0;
+    loop {
+        round += 1;
+        nested(round % 4, round as u64 * 100);
+    }
+}
+
+#[inline(never)]
+fn resume(round: u64) {
+    let locals = [round, !round];
+    unsafe { swap_context(&mut MAIN, &COROUTINE) };
+    assert_eq!(locals, [round, !round]);
+}
+
+fn main() {
+    let mut stack = vec![0u8; 64 * 1024];
+    unsafe {
+        let top = stack.as_mut_ptr().add(stack.len()) as usize & !15;
+        COROUTINE[1] = coroutine as *mut u8;
+        COROUTINE[2] = (top - 8) as *mut u8;
+    }
+    for round in 0..20 {
+        resume(round);
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// only-x86_64
+
+// Test switching back and forth between main and a coroutine with its own stack.
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{frame_address, swap_context};
+use std::ptr;
+
+static mut MAIN: [*mut u8; 5] = [ptr::null_mut(); 5];
+static mut COROUTINE: [*mut u8; 5] = [ptr::null_mut(); 5];
+static mut COUNTER: u32 = 0;
+
+extern "C" fn coroutine() -> ! {
+    let mut local = 0;
+    loop {
+        unsafe {
+            local += 1;
+            COUNTER += 1;
+            assert_eq!(local, COUNTER);
+            swap_context(&mut COROUTINE, &MAIN);
+        }
+    }
+}
+
+fn main() {
+    assert!(!frame_address().is_null());
+
+    let mut stack = vec![0u8; 64 * 1024];
+    unsafe {
+        // Enter as if called, i.e. with a return address slot on the stack.
+        let top = stack.as_mut_ptr().add(stack.len()) as usize & !15;
+        COROUTINE[1] = coroutine as *mut u8;
+        COROUTINE[2] = (top - 8) as *mut u8;
+
+        for i in 1..10 {
+            let before = COUNTER;
+            swap_context(&mut MAIN, &COROUTINE);
+            assert_eq!(COUNTER, before + 1);
+            assert_eq!(COUNTER, i);
+        }
+    }
+}
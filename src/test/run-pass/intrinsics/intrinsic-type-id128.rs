@@ -0,0 +1,40 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{type_id, type_id128};
+
+struct A;
+struct B<T>(T);
+
+fn check<T: ?Sized + 'static>() -> u128 {
+    let wide = unsafe { type_id128::<T>() };
+    // The 64-bit identifier is the lower half of the 128-bit one.
+    assert_eq!(wide as u64, unsafe { type_id::<T>() });
+    wide
+}
+
+pub fn main() {
+    let ids = [
+        check::<A>(),
+        check::<B<A>>(),
+        check::<B<u8>>(),
+        check::<str>(),
+        check::<fn(&'static A)>(),
+    ];
+    for (i, a) in ids.iter().enumerate() {
+        for b in &ids[i + 1..] {
+            assert!(a != b);
+        }
+    }
+    assert_eq!(check::<B<A>>(), ids[1]);
+}
@@ -0,0 +1,38 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-x86_64
+
+#![feature(multiversion)]
+
+#[multiversion(targets("avx2", "sse4.2", "default"))]
+fn sum(xs: &[u32]) -> u32 {
+    xs.iter().sum()
+}
+
+#[multiversion(targets("popcnt", "default"))]
+fn count_ones<T: Into<u64>>(x: T) -> u32 {
+    x.into().count_ones()
+}
+
+#[multiversion(targets("avx", "default"))]
+fn pair(a: u8, b: String) -> (u8, String) {
+    (a, b)
+}
+
+fn main() {
+    let xs = (0..100).collect::<Vec<u32>>();
+    assert_eq!(sum(&xs), 4950);
+    // The second call goes through the cached variant.
+    assert_eq!(sum(&xs[..10]), 45);
+    assert_eq!(count_ones(0xffu8), 8);
+    assert_eq!(count_ones(0xf0f0u16), 8);
+    assert_eq!(pair(1, "a".to_string()), (1, "a".to_string()));
+}
@@ -10,11 +10,12 @@
 
 // ignore-wasm32-bare always compiled as panic=abort right now and this requires unwinding
 // This test checks that instantiating an uninhabited type via `mem::{uninitialized,zeroed}` results
-// in a runtime panic.
+// in a runtime panic, as does zero-initializing a type for which zero is not a valid value.
 
 #![feature(never_type)]
 
 use std::{mem, panic};
+use std::ptr::NonNull;
 
 #[allow(dead_code)]
 struct Foo {
@@ -24,13 +25,19 @@ struct Foo {
 
 enum Bar {}
 
+#[allow(dead_code)]
+struct Baz {
+    x: usize,
+    y: &'static u8,
+}
+
 fn main() {
     unsafe {
         assert_eq!(
             panic::catch_unwind(|| {
                 mem::uninitialized::<!>()
             }).err().and_then(|a| a.downcast_ref::<String>().map(|s| {
-                s == "Attempted to instantiate uninhabited type ! using mem::uninitialized"
+                s == "attempted to instantiate uninhabited type `!`"
             })),
             Some(true)
         );
@@ -39,7 +46,7 @@ fn main() {
             panic::catch_unwind(|| {
                 mem::zeroed::<!>()
             }).err().and_then(|a| a.downcast_ref::<String>().map(|s| {
-                s == "Attempted to instantiate uninhabited type ! using mem::zeroed"
+                s == "attempted to instantiate uninhabited type `!`"
             })),
             Some(true)
         );
@@ -48,7 +55,7 @@ fn main() {
             panic::catch_unwind(|| {
                 mem::uninitialized::<Foo>()
             }).err().and_then(|a| a.downcast_ref::<String>().map(|s| {
-                s == "Attempted to instantiate uninhabited type Foo using mem::uninitialized"
+                s == "attempted to instantiate uninhabited type `Foo`"
             })),
             Some(true)
         );
@@ -57,7 +64,7 @@ fn main() {
             panic::catch_unwind(|| {
                 mem::zeroed::<Foo>()
             }).err().and_then(|a| a.downcast_ref::<String>().map(|s| {
-                s == "Attempted to instantiate uninhabited type Foo using mem::zeroed"
+                s == "attempted to instantiate uninhabited type `Foo`"
             })),
             Some(true)
         );
@@ -66,7 +73,7 @@ fn main() {
             panic::catch_unwind(|| {
                 mem::uninitialized::<Bar>()
             }).err().and_then(|a| a.downcast_ref::<String>().map(|s| {
-                s == "Attempted to instantiate uninhabited type Bar using mem::uninitialized"
+                s == "attempted to instantiate uninhabited type `Bar`"
             })),
             Some(true)
         );
@@ -75,9 +82,41 @@ fn main() {
             panic::catch_unwind(|| {
                 mem::zeroed::<Bar>()
             }).err().and_then(|a| a.downcast_ref::<String>().map(|s| {
-                s == "Attempted to instantiate uninhabited type Bar using mem::zeroed"
+                s == "attempted to instantiate uninhabited type `Bar`"
             })),
             Some(true)
         );
+
+        assert_eq!(
+            panic::catch_unwind(|| {
+                mem::zeroed::<&'static u8>()
+            }).err().and_then(|a| a.downcast_ref::<String>().map(|s| {
+                s == "attempted to zero-initialize type `&u8`, which is invalid"
+            })),
+            Some(true)
+        );
+
+        assert_eq!(
+            panic::catch_unwind(|| {
+                mem::zeroed::<NonNull<u8>>()
+            }).err().and_then(|a| a.downcast_ref::<String>().map(|s| {
+                s == "attempted to zero-initialize type `std::ptr::NonNull<u8>`, which is invalid"
+            })),
+            Some(true)
+        );
+
+        assert_eq!(
+            panic::catch_unwind(|| {
+                mem::zeroed::<Baz>()
+            }).err().and_then(|a| a.downcast_ref::<String>().map(|s| {
+                s == "attempted to zero-initialize type `Baz`, which is invalid"
+            })),
+            Some(true)
+        );
+
+        // Zero is a valid value for these, so no panic happens.
+        let _: Option<&'static u8> = mem::zeroed();
+        let _: [usize; 4] = mem::zeroed();
+        let _: *const u8 = mem::zeroed();
     }
 }
@@ -0,0 +1,59 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pointer_niche)]
+
+use std::mem::size_of;
+
+// A pointer to 8-aligned data with a 3-bit tag in its high bits.
+#[pointer_niche(low_bits = 3)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Tagged(usize);
+
+impl Tagged {
+    fn new(ptr: &u64, tag: usize) -> Tagged {
+        assert!(tag < 8);
+        unsafe { Tagged(ptr as *const u64 as usize | tag << (size_of::<usize>() * 8 - 3)) }
+    }
+}
+
+#[pointer_niche(high_bits = 16)]
+#[derive(Copy, Clone)]
+struct Address(*const u8);
+
+// `low_bits = 3` leaves room for eight dataless variants.
+#[allow(dead_code)]
+enum Fits { A(Tagged), B, C, D, E, F, G, H, I }
+
+#[allow(dead_code)]
+enum TooMany { A(Tagged), B, C, D, E, F, G, H, I, J }
+
+fn main() {
+    assert_eq!(size_of::<Option<Tagged>>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<Option<Option<Tagged>>>>(), size_of::<usize>());
+    assert_eq!(size_of::<Fits>(), size_of::<usize>());
+    assert!(size_of::<TooMany>() > size_of::<usize>());
+    assert_eq!(size_of::<Option<Address>>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<Option<Address>>>(), size_of::<usize>());
+
+    let x = 42u64;
+    let tagged = Some(Tagged::new(&x, 7));
+    assert_eq!(tagged, Some(Tagged::new(&x, 7)));
+    assert!(tagged.is_some());
+    let none: Option<Option<Tagged>> = Some(None);
+    assert!(none.unwrap().is_none());
+    match Fits::A(Tagged::new(&x, 0)) {
+        Fits::A(t) => assert_eq!(t, Tagged::new(&x, 0)),
+        _ => panic!(),
+    }
+
+    let address = Some(unsafe { Address(&x as *const u64 as *const u8) });
+    assert_eq!(address.map(|a| a.0), Some(&x as *const u64 as *const u8));
+}
@@ -0,0 +1,56 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{discriminant_value, raw_discriminant, set_discriminant};
+
+#[allow(dead_code)]
+enum Tagged {
+    A = 7,
+    B = 9,
+}
+
+#[derive(Debug, PartialEq)]
+enum Niche {
+    Data(bool),
+    Empty,
+    Other,
+}
+
+fn main() {
+    unsafe {
+        // Tags that aren't niches are stored as the discriminant.
+        assert_eq!(raw_discriminant(&Tagged::B), 9);
+
+        // Niche-encoded tags are returned as is.
+        assert_eq!(discriminant_value(&None::<bool>), 0);
+        assert_eq!(raw_discriminant(&None::<bool>), 2);
+        assert_eq!(raw_discriminant(&Some(true)), 1);
+        assert_eq!(raw_discriminant(&Niche::Other), 3);
+
+        let r = &5u8;
+        assert_eq!(raw_discriminant(&Some(r)), r as *const u8 as usize as u128);
+        assert_eq!(raw_discriminant(&None::<&u8>), 0);
+
+        // No tag at all.
+        assert_eq!(raw_discriminant(&5u32), 0);
+
+        let mut x = Niche::Data(false);
+        set_discriminant(&mut x, 2);
+        assert_eq!(x, Niche::Empty);
+        set_discriminant(&mut x, 1);
+        assert_eq!(x, Niche::Data(true));
+
+        let mut y = Some(false);
+        set_discriminant(&mut y, 2);
+        assert_eq!(y, None);
+    }
+}
@@ -40,6 +40,10 @@ extern "platform-intrinsic" {
     fn simd_flog2<T>(x: T) -> T;
     fn simd_fpow<T>(x: T, y: T) -> T;
     fn simd_fpowi<T>(x: T, y: i32) -> T;
+    fn simd_round<T>(x: T) -> T;
+    fn simd_trunc<T>(x: T) -> T;
+    fn simd_rint<T>(x: T) -> T;
+    fn simd_nearbyint<T>(x: T) -> T;
 }
 
 macro_rules! assert_approx_eq_f32 {
@@ -109,5 +113,18 @@ fn main() {
 
         let r = simd_fsin(z);
         assert_approx_eq!(z, r);
+
+        let r = simd_round(h);
+        assert_approx_eq!(x, r);
+
+        let r = simd_trunc(f32x4(1.7, -1.7, 0.5, -0.5));
+        assert_approx_eq!(f32x4(1.0, -1.0, 0.0, 0.0), r);
+
+        // Halfway cases round to even in the default rounding mode.
+        let r = simd_rint(f32x4(0.5, 1.5, 2.5, -0.5));
+        assert_approx_eq!(f32x4(0.0, 2.0, 2.0, 0.0), r);
+
+        let r = simd_nearbyint(f32x4(0.5, 1.5, 2.5, -0.5));
+        assert_approx_eq!(f32x4(0.0, 2.0, 2.0, 0.0), r);
     }
 }
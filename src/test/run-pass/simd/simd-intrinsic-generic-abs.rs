@@ -0,0 +1,47 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+
+// Test that simd_abs works on signed integer and float vectors.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i8x8(i8, i8, i8, i8, i8, i8, i8, i8);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i32x4(i32, i32, i32, i32);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct f32x4(f32, f32, f32, f32);
+
+extern "platform-intrinsic" {
+    fn simd_abs<T>(x: T) -> T;
+}
+
+fn main() {
+    unsafe {
+        let r = simd_abs(i8x8(-128, -127, -1, 0, 1, 127, -5, 5));
+        assert_eq!(r, i8x8(-128, 127, 1, 0, 1, 127, 5, 5));
+
+        let r = simd_abs(i32x4(i32::min_value(), -100_000, 0, 42));
+        assert_eq!(r, i32x4(i32::min_value(), 100_000, 0, 42));
+
+        let r = simd_abs(f32x4(-1.5, 0.0, -0.0, 3.25));
+        assert_eq!(r, f32x4(1.5, 0.0, 0.0, 3.25));
+        assert!(r.2.is_sign_positive());
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+
+// Test that vectors of pointers can be offset lane-wise and then gathered from.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct x4<T>(pub T, pub T, pub T, pub T);
+
+extern "platform-intrinsic" {
+    fn simd_arith_offset<P, O>(pointers: P, offsets: O) -> P;
+    fn simd_gather<T, U, V>(values: T, pointers: U, mask: V) -> T;
+}
+
+fn main() {
+    let x = [10_u64, 11, 12, 13, 14, 15, 16, 17];
+    let p = x.as_ptr();
+
+    unsafe {
+        let base = x4(p, p, p.offset(4), p.offset(7));
+        let ptrs: x4<*const u64> = simd_arith_offset(base, x4(3_isize, 1, 0, -2));
+        assert_eq!(ptrs, x4(p.offset(3), p.offset(1), p.offset(4), p.offset(5)));
+
+        let r = simd_gather(x4(0_u64, 0, 0, 0), ptrs, x4(-1_i64, -1, -1, -1));
+        assert_eq!(r, x4(13, 11, 14, 15));
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+
+// Test that simd_as has the saturating semantics of `as` for float to int casts.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct x4<T>(pub T, pub T, pub T, pub T);
+
+extern "platform-intrinsic" {
+    fn simd_as<T, U>(x: T) -> U;
+}
+
+fn main() {
+    use std::f32::{INFINITY, NAN, NEG_INFINITY};
+
+    unsafe {
+        let r: x4<i8> = simd_as(x4(1.9_f32, -1.9, 300.0, -300.0));
+        assert_eq!(r, x4(1, -1, i8::max_value(), i8::min_value()));
+
+        let r: x4<i32> = simd_as(x4(NAN, INFINITY, NEG_INFINITY, 0.0_f32));
+        assert_eq!(r, x4(0, i32::max_value(), i32::min_value(), 0));
+
+        let r: x4<u8> = simd_as(x4(-1.0_f64, 255.5, 1e10, NAN));
+        assert_eq!(r, x4(0, 255, u8::max_value(), 0));
+
+        let r: x4<u64> = simd_as(x4(1e30_f32, 2.0, -0.5, INFINITY));
+        assert_eq!(r, x4(u64::max_value(), 2, 0, u64::max_value()));
+
+        let r: x4<isize> = simd_as(x4(NAN, 1e30_f64, -1e30, -2.5));
+        assert_eq!(r, x4(0, isize::max_value(), isize::min_value(), -2));
+
+        let r: x4<usize> = simd_as(x4(7_u8, 0, 255, 1));
+        assert_eq!(r, x4(7, 0, 255, 1));
+
+        // Other casts behave like simd_cast.
+        let r: x4<f32> = simd_as(x4(1_i32, -2, 3, -4));
+        assert_eq!(r, x4(1.0, -2.0, 3.0, -4.0));
+
+        let r: x4<u8> = simd_as(x4(257_u32, 2, 3, 4));
+        assert_eq!(r, x4(1, 2, 3, 4));
+    }
+}
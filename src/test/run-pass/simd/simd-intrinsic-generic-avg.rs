@@ -0,0 +1,45 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+
+// Test that simd_avg_round and simd_avg_floor average lanes without overflowing.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u8x8(u8, u8, u8, u8, u8, u8, u8, u8);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i16x4(i16, i16, i16, i16);
+
+extern "platform-intrinsic" {
+    fn simd_avg_round<T>(x: T, y: T) -> T;
+    fn simd_avg_floor<T>(x: T, y: T) -> T;
+}
+
+fn main() {
+    let a = u8x8(255, 255, 0, 1, 100, 7, 200, 128);
+    let b = u8x8(255, 254, 0, 2, 50, 8, 255, 127);
+
+    unsafe {
+        assert_eq!(simd_avg_round(a, b), u8x8(255, 255, 0, 2, 75, 8, 228, 128));
+        assert_eq!(simd_avg_floor(a, b), u8x8(255, 254, 0, 1, 75, 7, 227, 127));
+
+        let x = i16x4(32767, -32768, -3, -5);
+        let y = i16x4(32767, -32768, 0, 2);
+        assert_eq!(simd_avg_round(x, y), i16x4(32767, -32768, -1, -1));
+        assert_eq!(simd_avg_floor(x, y), i16x4(32767, -32768, -2, -2));
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+#![allow(non_camel_case_types)]
+
+// ignore-emscripten
+
+// Test that the simd_bitmask intrinsic produces correct results.
+
+#![feature(repr_simd, platform_intrinsics)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u32x4(pub u32, pub u32, pub u32, pub u32);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u8x4(pub u8, pub u8, pub u8, pub u8);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i8x16(
+    pub i8, pub i8, pub i8, pub i8, pub i8, pub i8, pub i8, pub i8,
+    pub i8, pub i8, pub i8, pub i8, pub i8, pub i8, pub i8, pub i8,
+);
+
+extern "platform-intrinsic" {
+    fn simd_bitmask<T, U>(x: T) -> U;
+}
+
+fn main() {
+    let z = u32x4(0, 0, 0, 0);
+    let m = u32x4(!0, !0, 0, !0);
+
+    unsafe {
+        let r: u8 = simd_bitmask(z);
+        assert_eq!(r, 0);
+
+        let r: u8 = simd_bitmask(m);
+        assert_eq!(r, 0b1011);
+
+        let m = u8x4(0x80, 0, 0xff, 0x7f);
+        let r: u8 = simd_bitmask(m);
+        assert_eq!(r, 0b0101);
+
+        let m = i8x16(-1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, -1);
+        let r: u16 = simd_bitmask(m);
+        assert_eq!(r, 0x8001);
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+
+// Test that vectors of pointers can be cast to other pointer types and to and from usize.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct x4<T>(pub T, pub T, pub T, pub T);
+
+extern "platform-intrinsic" {
+    fn simd_cast_ptr<T, U>(values: T) -> U;
+    fn simd_expose_addr<T, U>(values: T) -> U;
+    fn simd_from_exposed_addr<T, U>(values: T) -> U;
+    fn simd_gather<T, U, V>(values: T, pointers: U, mask: V) -> T;
+}
+
+fn main() {
+    let x = [10_u32, 11, 12, 13];
+    let p = x.as_ptr();
+
+    unsafe {
+        let ptrs = x4(p, p.offset(1), p.offset(2), p.offset(3));
+
+        let bytes: x4<*const u8> = simd_cast_ptr(ptrs);
+        assert_eq!(bytes, x4(p as *const u8, p.offset(1) as *const u8,
+                             p.offset(2) as *const u8, p.offset(3) as *const u8));
+
+        let addrs: x4<usize> = simd_expose_addr(bytes);
+        assert_eq!(addrs, x4(p as usize, p as usize + 4, p as usize + 8, p as usize + 12));
+
+        let back: x4<*const u32> = simd_from_exposed_addr(x4(addrs.3, addrs.2, addrs.1, addrs.0));
+        let r = simd_gather(x4(0_u32, 0, 0, 0), back, x4(-1_i32, -1, -1, -1));
+        assert_eq!(r, x4(13, 12, 11, 10));
+    }
+}
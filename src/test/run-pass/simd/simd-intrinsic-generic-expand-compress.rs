@@ -0,0 +1,51 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+// min-llvm-version 6.0
+
+// Test that the simd_expand_load and simd_compress_store intrinsics produce the correct results.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct x4<T>(pub T, pub T, pub T, pub T);
+
+extern "platform-intrinsic" {
+    fn simd_expand_load<M, P, T>(mask: M, pointer: P, values: T) -> T;
+    fn simd_compress_store<M, P, T>(mask: M, pointer: P, values: T) -> ();
+}
+
+fn main() {
+    let mut x = [0_u32, 1, 2, 3, 4, 5];
+    let mask = x4(-1_i32, 0, -1, 0);
+    let default = x4(9_u32, 9, 9, 9);
+
+    unsafe {
+        // One consecutive element is read for each set lane.
+        let r = simd_expand_load(mask, x[4..].as_ptr(), default);
+        assert_eq!(r, x4(4, 9, 5, 9));
+
+        let none = x4(0_i32, 0, 0, 0);
+        let r = simd_expand_load(none, x[6..].as_ptr(), default);
+        assert_eq!(r, default);
+
+        // The set lanes are packed into consecutive elements.
+        simd_compress_store(mask, x[4..].as_mut_ptr(), x4(10_u32, 11, 12, 13));
+        assert_eq!(x, [0, 1, 2, 3, 10, 12]);
+
+        let last = x4(0_i32, 0, 0, -1);
+        simd_compress_store(last, x.as_mut_ptr(), x4(14_u32, 15, 16, 17));
+        assert_eq!(x, [17, 1, 2, 3, 10, 12]);
+    }
+}
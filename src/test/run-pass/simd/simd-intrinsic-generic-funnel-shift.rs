@@ -0,0 +1,62 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+
+// Test that simd_funnel_shl and simd_funnel_shr shift each pair of elements by its own amount.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u32x4(u32, u32, u32, u32);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u8x4(u8, u8, u8, u8);
+
+extern "platform-intrinsic" {
+    fn simd_funnel_shl<T>(hi: T, lo: T, shift: T) -> T;
+    fn simd_funnel_shr<T>(hi: T, lo: T, shift: T) -> T;
+}
+
+fn fshl(hi: u32, lo: u32, shift: u32) -> u32 {
+    ((((hi as u64) << 32 | lo as u64) << (shift % 32)) >> 32) as u32
+}
+
+fn fshr(hi: u32, lo: u32, shift: u32) -> u32 {
+    (((hi as u64) << 32 | lo as u64) >> (shift % 32)) as u32
+}
+
+fn main() {
+    let hi = u32x4(0x1234_5678, 0xdead_beef, 0xffff_ffff, 1);
+    let lo = u32x4(0x9abc_def0, 0x0bad_f00d, 0, 0x8000_0000);
+    let s = u32x4(4, 0, 31, 33);
+
+    unsafe {
+        let r = simd_funnel_shl(hi, lo, s);
+        assert_eq!(r, u32x4(fshl(hi.0, lo.0, s.0), fshl(hi.1, lo.1, s.1),
+                            fshl(hi.2, lo.2, s.2), fshl(hi.3, lo.3, s.3)));
+        assert_eq!(r.0, 0x2345_6789);
+        assert_eq!(r.1, 0xdead_beef);
+
+        let r = simd_funnel_shr(hi, lo, s);
+        assert_eq!(r, u32x4(fshr(hi.0, lo.0, s.0), fshr(hi.1, lo.1, s.1),
+                            fshr(hi.2, lo.2, s.2), fshr(hi.3, lo.3, s.3)));
+        assert_eq!(r.0, 0x89ab_cdef);
+        assert_eq!(r.1, 0x0bad_f00d);
+
+        let r = simd_funnel_shl(u8x4(0x0f, 0x80, 1, 2), u8x4(0xf0, 0x01, 0xff, 3),
+                                u8x4(4, 1, 8, 7));
+        assert_eq!(r, u8x4(0xff, 0x00, 1, 0x01));
+    }
+}
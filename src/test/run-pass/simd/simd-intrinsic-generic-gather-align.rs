@@ -0,0 +1,60 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+// min-llvm-version 6.0
+
+// Test that simd_{gather,scatter}_with_align can access unaligned elements.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct x4<T>(pub T, pub T, pub T, pub T);
+
+#[repr(C, packed)]
+#[derive(Copy, Clone)]
+struct Packed {
+    tag: u8,
+    value: u32,
+}
+
+extern "platform-intrinsic" {
+    fn simd_gather_with_align<T, U, V>(x: T, y: U, z: V, align: u32) -> T;
+    fn simd_scatter_with_align<T, U, V>(x: T, y: U, z: V, align: u32);
+}
+
+fn main() {
+    let mut xs = [
+        Packed { tag: 0, value: 10 },
+        Packed { tag: 1, value: 11 },
+        Packed { tag: 2, value: 12 },
+        Packed { tag: 3, value: 13 },
+    ];
+    let mask = x4(-1_i32, -1, 0, -1);
+
+    unsafe {
+        // The `value` fields are at offsets 1, 6, 11 and 16.
+        let base = xs.as_mut_ptr() as *mut u8;
+        let p = |i: isize| base.offset(1 + 5 * i) as *mut u32;
+        let pointers = x4(p(0), p(1), p(2), p(3));
+
+        let r = simd_gather_with_align(x4(0_u32, 0, 0, 0), pointers, mask, 1);
+        assert_eq!(r, x4(10, 11, 0, 13));
+
+        simd_scatter_with_align(x4(20_u32, 21, 22, 23), pointers, mask, 1);
+    }
+
+    let values: Vec<u32> = xs.iter().map(|x| x.value).collect();
+    assert_eq!(values, [20, 21, 12, 23]);
+    assert_eq!(xs[3].tag, 3);
+}
@@ -61,6 +61,12 @@ fn main() {
         let r_strided = simd_gather(default, pointers, mask);
 
         assert_eq!(r_strided, s_strided);
+
+        // with an unsigned mask
+        let umask = x4(!0_u32, !0, 0, !0);
+        let r_strided = simd_gather(default, pointers, umask);
+
+        assert_eq!(r_strided, s_strided);
     }
 
     // writing to *mut
@@ -0,0 +1,49 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+// min-llvm-version 6.0
+
+// Test that the simd_masked_{load,store} intrinsics produce the correct results.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct x4<T>(pub T, pub T, pub T, pub T);
+
+extern "platform-intrinsic" {
+    fn simd_masked_load<M, P, T>(mask: M, pointer: P, values: T) -> T;
+    fn simd_masked_store<M, P, T>(mask: M, pointer: P, values: T) -> ();
+}
+
+fn main() {
+    let mut x = [0_u32, 1, 2, 3, 4, 5];
+    let mask = x4(-1_i32, 0, -1, 0);
+    let default = x4(9_u32, 9, 9, 9);
+
+    unsafe {
+        // Only the selected elements are read, so the tail is never touched.
+        let r = simd_masked_load(mask, x[2..].as_ptr(), default);
+        assert_eq!(r, x4(2, 9, 4, 9));
+
+        let tail = x4(-1_i32, -1, 0, 0);
+        let r = simd_masked_load(tail, x[4..].as_ptr(), default);
+        assert_eq!(r, x4(4, 5, 9, 9));
+
+        simd_masked_store(mask, x.as_mut_ptr(), x4(10_u32, 11, 12, 13));
+        assert_eq!(x, [10, 1, 12, 3, 4, 5]);
+
+        simd_masked_store(tail, x[4..].as_mut_ptr(), x4(14_u32, 15, 16, 17));
+        assert_eq!(x, [10, 1, 12, 3, 14, 15]);
+    }
+}
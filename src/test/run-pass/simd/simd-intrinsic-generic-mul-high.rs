@@ -0,0 +1,71 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+
+// Test that simd_mul_high returns the high half of each full-width product.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i16x8(i16, i16, i16, i16, i16, i16, i16, i16);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u16x8(u16, u16, u16, u16, u16, u16, u16, u16);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u32x4(u32, u32, u32, u32);
+
+extern "platform-intrinsic" {
+    fn simd_mul<T>(x: T, y: T) -> T;
+    fn simd_mul_high<T>(x: T, y: T) -> T;
+}
+
+fn main() {
+    let a = i16x8(-32768, 1000, -1, -300, 0x7fff, 2, -2, 12345);
+    let b = i16x8(-32768, 1000, 1, 200, 0x7fff, 3, -3, -6789);
+    let high = |x: i16, y: i16| ((x as i32 * y as i32) >> 16) as i16;
+
+    unsafe {
+        let r = simd_mul_high(a, b);
+        assert_eq!(r, i16x8(high(a.0, b.0), high(a.1, b.1), high(a.2, b.2), high(a.3, b.3),
+                            high(a.4, b.4), high(a.5, b.5), high(a.6, b.6), high(a.7, b.7)));
+        assert_eq!(r, i16x8(16384, 15, -1, -1, 16383, 0, 0, -1279));
+
+        let x = u16x8(0xffff, 1000, 1, 0x8000, 0x8000, 2, 0, 0x1234);
+        let y = u16x8(0xffff, 1000, 1, 2, 0x8000, 3, 0xffff, 0x100);
+        let r = simd_mul_high(x, y);
+        assert_eq!(r, u16x8(0xfffe, 15, 0, 1, 0x4000, 0, 0, 0x12));
+
+        // Together with simd_mul, it gives the full product.
+        let x = u32x4(0xffff_ffff, 0x1234_5678, 7, 0x8000_0000);
+        let y = u32x4(2, 0x9abc_def0, 6, 0x8000_0000);
+        let lo = simd_mul(x, y);
+        let hi = simd_mul_high(x, y);
+        let full = |i: usize, a: u32, b: u32| {
+            let p = a as u64 * b as u64;
+            let (lo, hi) = (lane(lo, i), lane(hi, i));
+            assert_eq!((lo, hi), (p as u32, (p >> 32) as u32));
+        };
+        full(0, x.0, y.0);
+        full(1, x.1, y.1);
+        full(2, x.2, y.2);
+        full(3, x.3, y.3);
+    }
+}
+
+fn lane(v: u32x4, i: usize) -> u32 {
+    [v.0, v.1, v.2, v.3][i]
+}
@@ -0,0 +1,64 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+
+// Test that simd_pack_sat narrows lanes with saturation.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i16x8(i16, i16, i16, i16, i16, i16, i16, i16);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i8x8(i8, i8, i8, i8, i8, i8, i8, i8);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u8x8(u8, u8, u8, u8, u8, u8, u8, u8);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u32x4(u32, u32, u32, u32);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i16x4(i16, i16, i16, i16);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u16x4(u16, u16, u16, u16);
+
+extern "platform-intrinsic" {
+    fn simd_pack_sat<T, U>(x: T) -> U;
+}
+
+fn main() {
+    let x = i16x8(-32768, -129, -128, -1, 0, 127, 128, 32767);
+
+    unsafe {
+        let r: i8x8 = simd_pack_sat(x);
+        assert_eq!(r, i8x8(-128, -128, -128, -1, 0, 127, 127, 127));
+
+        let r: u8x8 = simd_pack_sat(x);
+        assert_eq!(r, u8x8(0, 0, 0, 0, 0, 127, 128, 255));
+
+        let y = u32x4(0, 0x7fff, 0x8000, 0xffff_ffff);
+        let r: i16x4 = simd_pack_sat(y);
+        assert_eq!(r, i16x4(0, 0x7fff, 0x7fff, 0x7fff));
+
+        let r: u16x4 = simd_pack_sat(y);
+        assert_eq!(r, u16x4(0, 0x7fff, 0x8000, 0xffff));
+    }
+}
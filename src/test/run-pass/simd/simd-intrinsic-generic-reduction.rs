@@ -48,6 +48,8 @@ extern "platform-intrinsic" {
     fn simd_reduce_mul_unordered<T, U>(x: T) -> U;
     fn simd_reduce_add_ordered<T, U>(x: T, acc: U) -> U;
     fn simd_reduce_mul_ordered<T, U>(x: T, acc: U) -> U;
+    fn simd_reduce_add_pairwise<T, U>(x: T) -> U;
+    fn simd_reduce_mul_pairwise<T, U>(x: T) -> U;
     fn simd_reduce_min<T, U>(x: T) -> U;
     fn simd_reduce_max<T, U>(x: T) -> U;
     fn simd_reduce_min_nanless<T, U>(x: T) -> U;
@@ -70,6 +72,10 @@ fn main() {
         assert_eq!(r, 5_i32);
         let r: i32 = simd_reduce_mul_ordered(x, -1);
         assert_eq!(r, 24_i32);
+        let r: i32 = simd_reduce_add_pairwise(x);
+        assert_eq!(r, 6_i32);
+        let r: i32 = simd_reduce_mul_pairwise(x);
+        assert_eq!(r, -24_i32);
 
         let r: i32 = simd_reduce_min(x);
         assert_eq!(r, -2_i32);
@@ -133,14 +139,27 @@ fn main() {
         assert_eq!(r, 6_f32);
         let r: f32 = simd_reduce_mul_unordered(x);
         assert_eq!(r, -24_f32);
-        // FIXME: only works correctly for accumulator, 0:
-        // https://bugs.llvm.org/show_bug.cgi?id=36734
         let r: f32 = simd_reduce_add_ordered(x, 0.);
         assert_eq!(r, 6_f32);
-        // FIXME: only works correctly for accumulator, 1:
-        // https://bugs.llvm.org/show_bug.cgi?id=36734
         let r: f32 = simd_reduce_mul_ordered(x, 1.);
         assert_eq!(r, -24_f32);
+        let r: f32 = simd_reduce_add_ordered(x, -1.);
+        assert_eq!(r, 5_f32);
+        let r: f32 = simd_reduce_mul_ordered(x, 2.);
+        assert_eq!(r, -48_f32);
+        assert_eq!(reduce_add_ordered(x, 10.), 16_f32);
+        assert_eq!(reduce_mul_ordered(x, -0.5), 12_f32);
+        let r: f32 = simd_reduce_add_pairwise(x);
+        assert_eq!(r, 6_f32);
+        let r: f32 = simd_reduce_mul_pairwise(x);
+        assert_eq!(r, -24_f32);
+
+        // (a + b) + (c + d) rather than ((a + b) + c) + d
+        let y = f32x4(1e8, 1., -1e8, 1.);
+        let r: f32 = simd_reduce_add_pairwise(y);
+        assert_eq!(r, 0_f32);
+        let r: f32 = simd_reduce_add_ordered(y, 0.);
+        assert_eq!(r, 1_f32);
 
         let r: f32 = simd_reduce_min(x);
         assert_eq!(r, -2_f32);
@@ -172,3 +191,14 @@ fn main() {
         assert_eq!(r, false);
     }
 }
+
+// Accumulators that are only known at run-time.
+#[inline(never)]
+unsafe fn reduce_add_ordered(x: f32x4, acc: f32) -> f32 {
+    simd_reduce_add_ordered(x, acc)
+}
+
+#[inline(never)]
+unsafe fn reduce_mul_ordered(x: f32x4, acc: f32) -> f32 {
+    simd_reduce_mul_ordered(x, acc)
+}
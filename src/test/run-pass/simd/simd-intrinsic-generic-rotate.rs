@@ -0,0 +1,58 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+
+// Test that simd_rotate_left and simd_rotate_right rotate each element by its own amount.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u32x4(u32, u32, u32, u32);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i8x4(i8, i8, i8, i8);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u64x2(u64, u64);
+
+extern "platform-intrinsic" {
+    fn simd_rotate_left<T>(x: T, shift: T) -> T;
+    fn simd_rotate_right<T>(x: T, shift: T) -> T;
+}
+
+fn main() {
+    let x = u32x4(0x8000_0001, 0x1234_5678, 1, 0xffff_0000);
+    let s = u32x4(1, 8, 0, 36);
+
+    unsafe {
+        let r = simd_rotate_left(x, s);
+        assert_eq!(r, u32x4(x.0.rotate_left(1), x.1.rotate_left(8),
+                            x.2.rotate_left(0), x.3.rotate_left(36)));
+        let r = simd_rotate_right(x, s);
+        assert_eq!(r, u32x4(x.0.rotate_right(1), x.1.rotate_right(8),
+                            x.2.rotate_right(0), x.3.rotate_right(36)));
+
+        let y = i8x4(-128, 1, -1, 0x12);
+        let r = simd_rotate_left(y, i8x4(1, 7, 3, 4));
+        assert_eq!(r, i8x4(1, -128, -1, 0x21));
+        let r = simd_rotate_right(y, i8x4(1, 1, 3, 4));
+        assert_eq!(r, i8x4(0x40, -128, -1, 0x21));
+
+        let z = u64x2(0x0123_4567_89ab_cdef, 1);
+        let r = simd_rotate_left(z, u64x2(32, 63));
+        assert_eq!(r, u64x2(0x89ab_cdef_0123_4567, 1 << 63));
+    }
+}
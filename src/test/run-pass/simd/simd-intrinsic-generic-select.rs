@@ -146,4 +146,15 @@ fn main() {
         let e = b8x4(t, f, t, t);
         assert_eq!(r, e);
     }
+
+    unsafe {
+        // unsigned masks select the same way as signed ones
+        let m = u32x4(!0, 0, !0, 0);
+        let a = i32x4(1, 2, 3, 4);
+        let b = i32x4(5, 6, 7, 8);
+
+        let r: i32x4 = simd_select(m, a, b);
+        let e = i32x4(1, 6, 3, 8);
+        assert_eq!(r, e);
+    }
 }
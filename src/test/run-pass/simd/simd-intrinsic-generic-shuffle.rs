@@ -0,0 +1,52 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten FIXME(#45351) hits an LLVM assert
+
+// Test that simd_shuffle takes the number of indices from the type of its index array.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct i32x2(i32, i32);
+#[repr(simd)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct i32x4(i32, i32, i32, i32);
+#[repr(simd)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct i32x8(i32, i32, i32, i32, i32, i32, i32, i32);
+
+extern "platform-intrinsic" {
+    fn simd_shuffle<T, I, U>(x: T, y: T, idx: I) -> U;
+    fn simd_shuffle4<T, U>(x: T, y: T, idx: [u32; 4]) -> U;
+}
+
+const REVERSE: [u32; 4] = [3, 2, 1, 0];
+
+fn main() {
+    let x = i32x4(0, 1, 2, 3);
+    let y = i32x4(4, 5, 6, 7);
+
+    unsafe {
+        let r: i32x2 = simd_shuffle(x, y, [1, 6]);
+        assert_eq!(r, i32x2(1, 6));
+
+        let r: i32x4 = simd_shuffle(x, y, REVERSE);
+        assert_eq!(r, i32x4(3, 2, 1, 0));
+        let r: i32x4 = simd_shuffle4(x, y, REVERSE);
+        assert_eq!(r, i32x4(3, 2, 1, 0));
+
+        let r: i32x8 = simd_shuffle(x, y, [7, 6, 5, 4, 3, 2, 1, 0]);
+        assert_eq!(r, i32x8(7, 6, 5, 4, 3, 2, 1, 0));
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// run-pass
+// ignore-emscripten
+
+// Vectors of `bool` are masks, which are `<N x i1>` while in registers.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct f32x4(f32, f32, f32, f32);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct m32x4(bool, bool, bool, bool);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct m8x16(bool, bool, bool, bool, bool, bool, bool, bool,
+             bool, bool, bool, bool, bool, bool, bool, bool);
+
+extern "platform-intrinsic" {
+    fn simd_lt<T, U>(x: T, y: T) -> U;
+    fn simd_eq<T, U>(x: T, y: T) -> U;
+    fn simd_select<M, T>(m: M, a: T, b: T) -> T;
+    fn simd_bitmask<M, U>(m: M) -> U;
+    fn simd_reduce_all<M>(m: M) -> bool;
+    fn simd_reduce_any<M>(m: M) -> bool;
+}
+
+#[inline(never)]
+fn invert(m: m32x4) -> m32x4 {
+    m32x4(!m.0, !m.1, !m.2, !m.3)
+}
+
+fn main() {
+    let a = f32x4(1.0, 5.0, 3.0, 8.0);
+    let b = f32x4(2.0, 4.0, 3.0, 9.0);
+    unsafe {
+        let lt: m32x4 = simd_lt(a, b);
+        assert_eq!(lt, m32x4(true, false, false, true));
+        assert_eq!(invert(lt), m32x4(false, true, true, false));
+
+        assert_eq!(simd_select(lt, a, b), f32x4(1.0, 4.0, 3.0, 8.0));
+        assert_eq!(simd_bitmask::<_, u8>(lt), 0b1001);
+
+        assert!(simd_reduce_any(lt));
+        assert!(!simd_reduce_all(lt));
+        let eq: m32x4 = simd_eq(a, a);
+        assert!(simd_reduce_all(eq));
+
+        // Masks are stored as one byte per lane.
+        let bytes: [u8; 4] = std::mem::transmute(lt);
+        assert_eq!(bytes, [1, 0, 0, 1]);
+
+        let wide = m8x16(true, false, false, false, false, false, false, false,
+                         false, false, false, false, false, false, false, true);
+        assert_eq!(simd_bitmask::<_, u16>(wide), 0x8001);
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test calling boxed closures by value, which moves the unsized closure out
+// of the box.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+struct DropCounter(Rc<Cell<u32>>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn call_once(f: Box<dyn FnOnce(u32) -> String>) -> String {
+    f(7)
+}
+
+fn call_twice(mut f: Box<dyn FnMut() -> u32>) -> u32 {
+    f();
+    f()
+}
+
+fn call_generic<F: FnOnce() -> u32>(f: F) -> u32 {
+    f()
+}
+
+fn main() {
+    let s = String::from("hello");
+    assert_eq!(call_once(Box::new(move |n| format!("{} {}", s, n))), "hello 7");
+
+    let mut count = 0;
+    assert_eq!(call_twice(Box::new(move || { count += 1; count })), 2);
+
+    let f: Box<dyn Fn() -> u32> = Box::new(|| 3);
+    assert_eq!(f() + f(), 6);
+
+    let f: Box<dyn FnOnce() -> u32 + Send> = Box::new(|| 4);
+    assert_eq!(call_generic(f), 4);
+
+    // The captured state is dropped exactly once, whether or not it's called.
+    let drops = Rc::new(Cell::new(0));
+    let counter = DropCounter(drops.clone());
+    let f: Box<dyn FnOnce()> = Box::new(move || drop(counter));
+    f();
+    assert_eq!(drops.get(), 1);
+
+    let counter = DropCounter(drops.clone());
+    let f: Box<dyn FnOnce()> = Box::new(move || drop(counter));
+    drop(f);
+    assert_eq!(drops.get(), 2);
+}
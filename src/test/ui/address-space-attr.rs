@@ -0,0 +1,25 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(address_spaces)]
+
+#[address_space = "1"] //~ ERROR attribute should be applied to a static, struct, enum or union
+fn foo() {}
+
+#[address_space = "global"] //~ ERROR must be of the form
+static BAR: u32 = 0;
+
+#[address_space = "1"]
+struct Baz;
+
+#[address_space = "1"]
+static BAZ: Baz = Baz;
+
+fn main() {}
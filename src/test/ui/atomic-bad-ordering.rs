@@ -0,0 +1,33 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(intrinsics)]
+
+extern "rust-intrinsic" {
+    fn atomic_load_acqrel_failacq<T>(src: *const T) -> T;
+    //~^ ERROR unrecognized ordering `acqrel_failacq` for atomic operation `load`
+    fn atomic_xadd_failrelaxed<T>(dst: *mut T, src: T) -> T;
+    //~^ ERROR unrecognized ordering `failrelaxed` for atomic operation `xadd`
+    fn atomic_cxchg_acq_failrelaxed<T>(dst: *mut T, old: T, src: T) -> (T, bool);
+
+    fn atomic_load_rel<T>(src: *const T) -> T;
+    //~^ ERROR invalid ordering `rel` for atomic operation `load`
+    fn atomic_store_acq<T>(dst: *mut T, val: T);
+    //~^ ERROR invalid ordering `acq` for atomic operation `store`
+    fn atomic_fence_relaxed();
+    //~^ ERROR invalid ordering `relaxed` for atomic operation `fence`
+    fn atomic_xadd_unordered<T>(dst: *mut T, src: T) -> T;
+    //~^ ERROR invalid ordering `unordered` for atomic operation `xadd`
+    fn atomic_load_unordered<T>(src: *const T) -> T;
+    fn atomic_store_rel<T>(dst: *mut T, val: T);
+    fn atomic_fence_acq();
+}
+
+fn main() {}
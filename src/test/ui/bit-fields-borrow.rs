@@ -0,0 +1,25 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(bitfields)]
+
+#[repr(C)]
+struct Flags {
+    #[bits(4)]
+    low: u8,
+    #[bits(4)]
+    high: u8,
+}
+
+fn main() {
+    let flags = Flags { low: 1, high: 2 };
+    let _high = flags.high;
+    let _low = &flags.low; //~ ERROR cannot borrow a bit-field
+}
@@ -0,0 +1,30 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(bitfields)]
+
+struct NotC {
+    #[bits(3)]
+    a: u8, //~ ERROR structs with bit-fields must be `#[repr(C)]` and not packed
+}
+
+#[repr(C)]
+struct Invalid {
+    #[bits(3)]
+    a: f32, //~ ERROR bit-field `a` has type `f32`, which isn't an integer or `bool`
+    #[bits(9)]
+    b: u8, //~ ERROR bit-field `b` of type `u8` can't be 9 bits wide
+    #[bits(0)]
+    c: u32, //~ ERROR bit-field `c` of type `u32` can't be 0 bits wide
+    #[bits(x)]
+    d: u32, //~ ERROR expected `#[bits(N)]` with an integer `N`
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+#![feature(call_site_inline, stmt_expr_attributes)]
+
+#[inline(always)]
+pub fn always() {}
+
+pub fn plain() {}
+
+pub fn caller() {
+    #[inline(never)] always();
+    //~^ ERROR `#[inline(never)]` can't override the `#[inline(always)]` of the function it calls
+    #[inline(always)] plain();
+    //~^ WARN `#[inline(always)]` on a call expression has no effect without optimizations
+}
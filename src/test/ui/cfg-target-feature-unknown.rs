@@ -0,0 +1,21 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+
+// `cfg!` warns about target features no target has.
+
+fn main() {
+    let _ = cfg!(target_feature = "avx9000"); //~ WARN unknown target feature `avx9000`
+    let _ = cfg!(any(unix, not(target_feature = "sse5"))); //~ WARN unknown target feature `sse5`
+    // Features of other targets are fine.
+    let _ = cfg!(any(target_feature = "neon", target_feature = "avx2"));
+    let _ = cfg!(target_feature = "crt-static");
+}
@@ -11,7 +11,7 @@
 #![feature(platform_intrinsics)]
 
 extern "platform-intrinsic" {
-    fn simd_shuffle<A,B>(a: A, b: A, c: [u32; 8]) -> B; //~ ERROR E0439
+    fn simd_shuffle_eight<A,B>(a: A, b: A, c: [u32; 8]) -> B; //~ ERROR E0439
 }
 
 fn main () {
@@ -0,0 +1,18 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    #[unroll(4)] //~ ERROR the `#[unroll]` attribute is an experimental feature
+    for _ in 0..8 {}
+    #[no_unroll] //~ ERROR the `#[no_unroll]` attribute is an experimental feature
+    loop { break }
+    #[pipeline(disable)] //~ ERROR the `#[pipeline]` attribute is an experimental feature
+    while false {}
+}
@@ -0,0 +1,17 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[offload] //~ ERROR the `#[offload]` attribute is an experimental feature
+static SCALE: f32 = 2.0;
+
+#[offload] //~ ERROR the `#[offload]` attribute is an experimental feature
+fn scale(x: f32) -> f32 { x * SCALE }
+
+fn main() {}
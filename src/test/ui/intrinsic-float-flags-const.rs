@@ -0,0 +1,29 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the flags passed to the f*_flags intrinsics must be a constant.
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{fadd_flags, fmul_flags, FAST_MATH_CONTRACT, FAST_MATH_NO_NANS};
+
+fn main() {
+    let (x, y) = (1.0_f32, 2.0_f32);
+
+    unsafe {
+        let _ = fadd_flags(x, y, FAST_MATH_CONTRACT | FAST_MATH_NO_NANS);
+
+        let flags = std::env::args().count() as u32;
+        let _ = fadd_flags(x, y, flags);
+        //~^ ERROR argument 3 is required to be a constant
+        let _ = fmul_flags(x, y, flags & FAST_MATH_CONTRACT);
+        //~^ ERROR argument 3 is required to be a constant
+    }
+}
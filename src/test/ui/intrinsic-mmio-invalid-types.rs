@@ -0,0 +1,26 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-x86_64
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{mmio_load, mmio_store};
+
+#[repr(C)]
+struct Three(u8, u8, u8);
+
+fn main() {
+    unsafe {
+        mmio_load(0x1000 as *const Three); //~ ERROR E0511
+        mmio_store(0x1000 as *mut [u8; 4], [0; 4]); //~ ERROR E0511
+        mmio_load(0x1000 as *const u128); //~ ERROR E0511
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the prefetch intrinsics reject arguments LLVM can't take.
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{prefetch, prefetch_read_data};
+
+fn main() {
+    let x = 0u8;
+    let locality = std::env::args().count() as i32;
+    unsafe {
+        prefetch_read_data(&x, 3);
+        prefetch(&x, 1, 0, 1);
+
+        prefetch_read_data(&x, 4);
+        //~^ ERROR locality must be between 0 and 3
+        prefetch_read_data(&x, locality);
+        //~^ ERROR locality must be a constant
+        prefetch(&x, 2, 0, 1);
+        //~^ ERROR rw must be between 0 and 1
+    }
+}
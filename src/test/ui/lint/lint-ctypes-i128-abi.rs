@@ -0,0 +1,35 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-x86_64
+
+// 128-bit integers match C's `__int128` on x86_64, but code built by older
+// versions of rustc laid them out and passed them differently.
+
+#![deny(improper_ctypes, i128_ffi_abi_change)]
+
+#[repr(C)]
+pub struct Wide {
+    pub tag: u8,
+    pub value: u128,
+}
+
+#[repr(transparent)]
+pub struct TransparentI128(i128);
+
+extern {
+    pub fn i128_type(p: i128); //~ ERROR uses type `i128`
+    pub fn u128_ret() -> u128; //~ ERROR uses type `u128`
+    pub fn wide(p: *const Wide); //~ ERROR uses type `u128`
+    pub fn transparent_i128(p: TransparentI128); //~ ERROR uses type `i128`
+    pub fn narrow(p: i64);
+}
+
+fn main() {}
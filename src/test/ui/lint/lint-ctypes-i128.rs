@@ -0,0 +1,27 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-x86_64
+// ignore-aarch64
+
+// 128-bit integers are only FFI-safe where they match C's `__int128`.
+
+#![deny(improper_ctypes)]
+
+#[repr(transparent)]
+pub struct TransparentI128(i128);
+
+extern {
+    pub fn i128_type(p: i128); //~ ERROR uses type `i128`
+    pub fn u128_type(p: u128); //~ ERROR uses type `u128`
+    pub fn transparent_i128(p: TransparentI128); //~ ERROR: uses type `i128`
+}
+
+fn main() {}
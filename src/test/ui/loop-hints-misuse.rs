@@ -0,0 +1,24 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(loop_hints)]
+
+#[unroll(4)] //~ ERROR attribute should be applied to a loop
+fn f() {}
+
+fn main() {
+    #[unroll(0)] //~ ERROR malformed loop hint
+    for _ in 0..8 {}
+    #[pipeline(initiation_interval)] //~ ERROR malformed loop hint
+    loop { break }
+    #[unroll(2)]
+    #[no_unroll]
+    while false {} //~ ERROR conflicting `#[unroll]` and `#[no_unroll]` hints
+}
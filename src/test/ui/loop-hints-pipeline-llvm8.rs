@@ -0,0 +1,23 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-pass
+// no-system-llvm
+
+// The in-tree LLVM doesn't know the `llvm.loop.pipeline.*` metadata yet, so
+// `#[pipeline]` is ignored with a warning.
+
+#![feature(loop_hints)]
+
+fn main() {
+    let mut i = 0;
+    #[pipeline(initiation_interval = 2)]
+    while i < 4 { i += 1; }
+}
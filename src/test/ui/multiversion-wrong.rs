@@ -0,0 +1,41 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-x86_64
+
+#![feature(multiversion)]
+
+#[multiversion(avx2)]
+//~^ ERROR: must be of the form
+fn a() {}
+
+#[multiversion(targets(avx2, "default"))]
+//~^ ERROR: must be of the form
+fn b() {}
+
+#[multiversion(targets("foo", "default"))]
+//~^ ERROR: not valid for this target
+fn c() {}
+
+#[multiversion(targets("avx2", "avx2", "default"))]
+//~^ ERROR: listed more than once
+fn d() {}
+
+#[multiversion(targets("avx2", "sse4.2"))]
+//~^ ERROR: requires a `"default"` target
+fn e() {}
+
+fn main() {
+    a();
+    b();
+    c();
+    d();
+    e();
+}
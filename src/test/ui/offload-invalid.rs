@@ -0,0 +1,23 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(offload)]
+
+#[offload] //~ ERROR attribute should be applied to a function or static
+struct Buffer([f32; 4]);
+
+#[offload] //~ ERROR attribute can't be applied to a generic function
+fn fill<T: Copy>(x: &mut [T], v: T) {
+    for x in x {
+        *x = v;
+    }
+}
+
+fn main() {}
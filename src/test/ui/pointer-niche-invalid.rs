@@ -0,0 +1,32 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pointer_niche)]
+#![allow(dead_code)]
+
+#[pointer_niche(low_bits = 2)] //~ ERROR attribute can only be applied to a struct
+enum NotAStruct { A, B }
+
+#[pointer_niche(low_bits = "2")] //~ ERROR expected `#[pointer_niche(
+struct NotAnInteger(usize);
+
+#[pointer_niche(middle_bits = 2)] //~ ERROR expected `#[pointer_niche(
+struct UnknownKey(usize);
+
+#[pointer_niche(low_bits = 2)]
+struct TwoFields(usize, usize); //~ ERROR can only be used on a struct whose only non-zero-sized
+
+#[pointer_niche(low_bits = 2)]
+struct Float(f64); //~ ERROR can only be used on a struct whose only non-zero-sized
+
+#[pointer_niche(low_bits = 4, high_bits = 4)] //~ ERROR leaves no valid values of `Full`
+struct Full(u8);
+
+fn main() {}
@@ -0,0 +1,28 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pointer_niche)]
+
+#[pointer_niche(low_bits = 3)]
+pub struct Tagged(usize);
+
+pub fn new(addr: usize) -> Tagged {
+    Tagged(addr) //~ ERROR initializing type with `#[pointer_niche]` is unsafe
+}
+
+pub fn set(tagged: &mut Tagged, addr: usize) {
+    tagged.0 = addr; //~ ERROR mutation of field of type with `#[pointer_niche]` is unsafe
+}
+
+pub unsafe fn new_unchecked(addr: usize) -> Tagged {
+    Tagged(addr)
+}
+
+fn main() {}
@@ -0,0 +1,40 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z relro-report -C relocation-model=pic -C codegen-units=1
+// compile-pass
+// only-linux
+
+// Statics whose initializers contain pointers are reported with the number
+// of relocations the dynamic loader has to apply to them. Exported statics of
+// the same codegen unit are pointed to through private aliases, so only
+// exported functions need a symbol lookup.
+
+#![crate_type = "lib"]
+
+static LOCAL: u32 = 1;
+
+fn callback() {}
+
+pub fn exported_callback() {}
+
+pub static NO_POINTERS: [u32; 2] = [1, 2];
+
+pub static NAMES: &[&str] = &["a", "b"];
+
+pub static TO_LOCAL: &u32 = &LOCAL;
+
+pub static TO_EXPORTED: &[u32; 2] = &NO_POINTERS;
+
+pub static CALLBACK: fn() = callback;
+
+pub static EXPORTED_CALLBACK: fn() = exported_callback;
+
+pub static mut CURRENT: &u32 = &LOCAL;
@@ -0,0 +1,35 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![deny(dynamic_simd_index)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+#[allow(non_camel_case_types)]
+struct i32x4(i32, i32, i32, i32);
+
+extern "platform-intrinsic" {
+    fn simd_insert<T, U>(x: T, idx: u32, val: U) -> T;
+    fn simd_extract<T, U>(x: T, idx: u32) -> U;
+}
+
+fn main() {
+    let x = i32x4(1, 2, 3, 4);
+    let i = std::env::args().count() as u32;
+    unsafe {
+        let _: i32 = simd_extract(x, 0);
+        let _ = simd_insert(x, 3, 0i32);
+        let _: i32 = simd_extract(x, i);
+        //~^ ERROR `simd_extract` with a non-constant lane index
+        // Stays in registers.
+        let _ = simd_insert(x, i, 0i32);
+    }
+}
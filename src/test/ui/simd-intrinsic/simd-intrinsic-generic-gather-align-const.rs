@@ -0,0 +1,44 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the alignment passed to simd_{gather,scatter}_with_align must be
+// a constant, like the indices of simd_shuffle.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct x4<T>(pub T, pub T, pub T, pub T);
+
+extern "platform-intrinsic" {
+    fn simd_gather_with_align<T, U, V>(x: T, y: U, z: V, align: u32) -> T;
+    fn simd_scatter_with_align<T, U, V>(x: T, y: U, z: V, align: u32);
+}
+
+const ALIGN: u32 = 2;
+
+fn main() {
+    let mut x = [0_f32; 4];
+    let p = &mut x[0] as *mut f32;
+    let ps = x4(p, p, p, p);
+    let mask = x4(-1_i32, -1, -1, -1);
+
+    unsafe {
+        let _ = simd_gather_with_align(x4(0_f32, 0., 0., 0.), ps, mask, ALIGN * 2);
+        simd_scatter_with_align(x4(1_f32, 2., 3., 4.), ps, mask, ALIGN);
+
+        let align = std::env::args().count() as u32;
+        let _ = simd_gather_with_align(x4(0_f32, 0., 0., 0.), ps, mask, align);
+        //~^ ERROR argument 4 is required to be a constant
+        simd_scatter_with_align(x4(1_f32, 2., 3., 4.), ps, mask, align);
+        //~^ ERROR argument 4 is required to be a constant
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that the alignment passed to simd_{gather,scatter}_with_align must be
+// a power of two that LLVM supports.
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct x4<T>(pub T, pub T, pub T, pub T);
+
+extern "platform-intrinsic" {
+    fn simd_gather_with_align<T, U, V>(x: T, y: U, z: V, align: u32) -> T;
+    fn simd_scatter_with_align<T, U, V>(x: T, y: U, z: V, align: u32);
+}
+
+fn main() {
+    let mut x = [0_f32; 4];
+    let p = &mut x[0] as *mut f32;
+    let ps = x4(p, p, p, p);
+    let mask = x4(-1_i32, -1, -1, -1);
+
+    unsafe {
+        let _ = simd_gather_with_align(x4(0_f32, 0., 0., 0.), ps, mask, 1);
+        simd_scatter_with_align(x4(1_f32, 2., 3., 4.), ps, mask, 2);
+
+        let _ = simd_gather_with_align(x4(0_f32, 0., 0., 0.), ps, mask, 3);
+        //~^ ERROR alignment `3` is not a power of two
+        simd_scatter_with_align(x4(1_f32, 2., 3., 4.), ps, mask, 0);
+        //~^ ERROR alignment `0` is not a power of two
+        let _ = simd_gather_with_align(x4(0_f32, 0., 0., 0.), ps, mask, 1 << 30);
+        //~^ ERROR alignment `1073741824` is larger than the maximum of `536870912`
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-emscripten FIXME(#45351) hits an LLVM assert
+
+#![feature(repr_simd, platform_intrinsics)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct i32x2(i32, i32);
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct i32x4(i32, i32, i32, i32);
+
+extern "platform-intrinsic" {
+    fn simd_shuffle<T, I, U>(x: T, y: T, idx: I) -> U;
+}
+
+fn main() {
+    let x = i32x4(0, 1, 2, 3);
+
+    unsafe {
+        let _: i32x2 = simd_shuffle(x, x, [0_i32, 1]);
+        //~^ ERROR simd_shuffle index must be an array of `u32`, got `[i32; 2]`
+        let _: i32x4 = simd_shuffle(x, x, [0_u32, 1]);
+        //~^ ERROR expected return type of length 2, found `i32x4` with length 4
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Out-of-bounds shuffle indices are reported before codegen.
+// compile-flags: -Z no-codegen
+
+#![feature(repr_simd, platform_intrinsics)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+#[allow(non_camel_case_types)]
+struct i32x4(i32, i32, i32, i32);
+
+extern "platform-intrinsic" {
+    fn simd_shuffle4<T, U>(x: T, y: T, idx: [u32; 4]) -> U;
+}
+
+fn main() {
+    let x = i32x4(1, 2, 3, 4);
+    unsafe {
+        let _: i32x4 = simd_shuffle4(x, x, [0, 7, 1, 6]);
+        let _: i32x4 = simd_shuffle4(x, x, [0, 8, 1, 20]);
+        //~^ ERROR shuffle index #1 is out of bounds (limit 8)
+        //~| ERROR shuffle index #3 is out of bounds (limit 8)
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(repr_simd, rustc_attrs)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[rustc_scalable_vector]
+#[derive(Copy, Clone)]
+pub struct svfloat32_t(f32, f32, f32, f32);
+
+pub struct Generic<T> {
+//~^ ERROR scalable vector type `svfloat32_t` can't be stored in `Generic<svfloat32_t>`
+    v: T,
+}
+
+fn main() {
+    let _ = std::mem::size_of::<Generic<svfloat32_t>>();
+}
@@ -18,6 +18,7 @@
 // gate-test-powerpc_target_feature
 // gate-test-avx512_target_feature
 // gate-test-tbm_target_feature
+// gate-test-cmpxchg16b_target_feature
 // gate-test-arm_target_feature
 // gate-test-aarch64_target_feature
 // gate-test-hexagon_target_feature
@@ -0,0 +1,36 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// only-x86_64
+
+#![feature(target_feature_11)]
+
+#[target_feature(enable = "avx")]
+fn avx() {}
+
+#[target_feature(enable = "avx2")]
+fn avx2() {
+    avx(); // `avx2` implies `avx`
+}
+
+fn plain() {
+    avx();
+    //~^ ERROR call to function with `#[target_feature]` is unsafe
+}
+
+fn main() {
+    let _: fn() = avx;
+    //~^ ERROR mismatched types
+    let _: unsafe fn() = avx;
+    unsafe {
+        avx2();
+    }
+    plain();
+}
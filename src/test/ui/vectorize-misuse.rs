@@ -0,0 +1,21 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(vectorize)]
+
+#[vectorize(expect)] //~ ERROR attribute should be applied to a function
+struct Foo;
+
+#[vectorize(expect)] //~ ERROR attribute can't be applied to a generic function
+fn sum<T: Copy + Into<u64>>(xs: &[T]) -> u64 {
+    xs.iter().map(|&x| x.into()).sum()
+}
+
+fn main() {}
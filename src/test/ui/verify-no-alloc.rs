@@ -0,0 +1,50 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z verify-no-alloc
+
+#![feature(no_alloc, box_syntax)]
+
+fn make() -> Box<u32> {
+    box 1
+}
+
+#[no_alloc]
+fn uses_box() -> u32 { //~ ERROR `#[no_alloc]` function `uses_box` may allocate
+    *make()
+}
+
+#[no_alloc]
+fn overflow(a: u32, b: u32) -> u32 {
+    //~^ ERROR `#[no_alloc]` function `overflow` may format a panic message
+    a + b
+}
+
+#[no_alloc]
+fn mix(a: u32, b: u32) -> u32 {
+    a ^ b
+}
+
+#[no_alloc]
+fn indirect(f: fn() -> u32) -> u32 {
+    //~^ ERROR cannot verify call to a function pointer from `#[no_alloc]` function `indirect`
+    f()
+}
+
+fn zero() -> u32 {
+    0
+}
+
+fn main() {
+    uses_box();
+    overflow(1, 2);
+    mix(1, 2);
+    indirect(zero);
+}
@@ -2084,12 +2084,17 @@ impl<'test> TestCx<'test> {
             .arg("--input-file")
             .arg(irfile)
             .arg(&self.testpaths.file);
+        // With revisions, e.g. one per target, lines prefixed with the name of
+        // the revision are checked in addition to the `CHECK` lines.
+        if let Some(revision) = self.revision {
+            filecheck
+                .arg("--check-prefix=CHECK")
+                .arg(format!("--check-prefix={}", revision));
+        }
         self.compose_and_run(filecheck, "", None, None)
     }
 
     fn run_codegen_test(&self) {
-        assert!(self.revision.is_none(), "revisions not relevant here");
-
         if self.config.llvm_filecheck.is_none() {
             self.fatal("missing --llvm-filecheck");
         }